@@ -6,9 +6,29 @@ pub enum ConfigKey {
     AssetRootPath,
     SketchRootPath,
     SketchFileName,
+    /// Default log level, or a full set of `EnvFilter` directives like `warn,processing=debug`.
     LogLevel,
+    /// Comma separated `target=level` directives applied on top of [`ConfigKey::LogLevel`].
+    LogTargets,
+    /// Who owns the window. Unset (the default) means windows are created externally (e.g. GLFW)
+    /// and handed to us as raw handles; [`WINDOWING_WINIT`] lets Bevy create and drive the window.
+    Windowing,
+    /// Comma separated wgpu backends to pick an adapter from, e.g. `vulkan` or `metal,gl`. Unset
+    /// falls back to the `WGPU_BACKEND` environment variable, then to every primary backend.
+    Backends,
+    /// Use the GPU with this name, as reported by `list_adapters`, instead of the default one.
+    AdapterName,
+    /// `true` to draw 3D geometry that has no material in magenta, so a forgotten material stands
+    /// out instead of silently falling back to an unlit fill.
+    RequireMaterial,
+    /// Name of the Bevy asset source that relative asset paths load from, such as `embedded` for
+    /// assets compiled into the binary. Takes precedence over [`ConfigKey::AssetRootPath`].
+    AssetSource,
 }
 
+/// [`ConfigKey::Windowing`] value for Bevy/winit managed windows.
+pub const WINDOWING_WINIT: &str = "winit";
+
 // TODO: Consider Box<dyn Any> instead of String
 #[derive(Resource)]
 pub struct Config {
@@ -38,6 +58,33 @@ impl Config {
     pub fn set(&mut self, k: ConfigKey, v: String) {
         self.map.insert(k, v);
     }
+
+    /// Only log messages at `level` or above from targets without a level of their own.
+    pub fn log_level(&mut self, level: tracing::Level) {
+        self.set(ConfigKey::LogLevel, level.to_string());
+    }
+
+    /// Log messages from `target` and the modules under it at `level` or above, whatever the
+    /// default level is. `wgpu` and `naga` only log errors and warnings unless set here.
+    pub fn log_target(&mut self, target: &str, level: tracing::Level) {
+        let directive = format!("{target}={level}");
+        let targets = match self.map.remove(&ConfigKey::LogTargets) {
+            Some(targets) => format!("{targets},{directive}"),
+            None => directive,
+        };
+        self.set(ConfigKey::LogTargets, targets);
+    }
+
+    /// Load images, fonts, gltf files and shaders from the asset source named `source`. Use
+    /// `embedded` to ship a sketch as a single binary with its assets compiled in.
+    pub fn asset_source(&mut self, source: &str) {
+        self.set(ConfigKey::AssetSource, source.to_string());
+    }
+
+    /// See [`ConfigKey::RequireMaterial`].
+    pub fn require_material(&mut self, required: bool) {
+        self.set(ConfigKey::RequireMaterial, required.to_string());
+    }
 }
 
 impl Default for Config {
@@ -1,3 +1,4 @@
+use bevy::ecs::system::{RegisteredSystemError, SystemInput};
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, ProcessingError>;
@@ -6,6 +7,8 @@ pub type Result<T> = std::result::Result<T, ProcessingError>;
 pub enum ProcessingError {
     #[error("App was accessed from multiple threads")]
     AppAccess,
+    #[error("App not initialized, call init() first")]
+    NotInitialized,
     #[error("Error initializing tracing: {0}")]
     Tracing(#[from] tracing::subscriber::SetGlobalDefaultError),
     #[error("Surface not found")]
@@ -32,10 +35,20 @@ pub enum ProcessingError {
     TransformNotFound,
     #[error("Material not found")]
     MaterialNotFound,
+    #[error("Light not found")]
+    LightNotFound,
     #[error("Unknown shader property: {0}")]
     UnknownShaderProperty(String),
     #[error("GLTF load error: {0}")]
     GltfLoadError(String),
+    #[error("GLTF save error: {0}")]
+    GltfSaveError(String),
+    #[error("OBJ load error: {0}")]
+    ObjLoadError(String),
+    #[error("Table error: {0}")]
+    TableError(String),
+    #[error("Image save error: {0}")]
+    ImageSaveError(String),
     #[error("Webcam not connected")]
     WebcamNotConnected,
     #[error("Shader compilation error: {0}")]
@@ -62,4 +75,47 @@ pub enum ProcessingError {
     FontNotFound,
     #[error("Font load error: {0}")]
     FontLoadError(String),
+    #[error("Clipboard error: {0}")]
+    ClipboardError(String),
+    #[error("System error: {0}")]
+    SystemError(String),
+    #[error("Render thread error: {0}")]
+    RenderThread(String),
+}
+
+// systems run via `run_system_cached_with` can fail to run at all (e.g. invalid params); surface
+// that as an error rather than panicking across the FFI boundary
+impl<I: SystemInput, O> From<RegisteredSystemError<I, O>> for ProcessingError
+where
+    RegisteredSystemError<I, O>: std::fmt::Display,
+{
+    fn from(err: RegisteredSystemError<I, O>) -> Self {
+        ProcessingError::SystemError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::prelude::{Res, Resource, World};
+
+    #[derive(Resource)]
+    struct Missing;
+
+    fn needs_missing(_: Res<Missing>) -> Result<()> {
+        Ok(())
+    }
+
+    fn run(world: &mut World) -> Result<()> {
+        world.run_system_cached(needs_missing)?
+    }
+
+    #[test]
+    fn failing_system_is_err() {
+        let mut world = World::new();
+        assert!(matches!(
+            run(&mut world),
+            Err(ProcessingError::SystemError(_))
+        ));
+    }
 }
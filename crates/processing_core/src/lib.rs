@@ -1,13 +1,20 @@
 pub mod config;
 pub mod error;
 
+use std::any::Any;
 use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::OnceLock;
+use std::sync::mpsc::{self, Sender};
 
 use bevy::app::App;
+use bevy::ecs::world::World;
 use tracing::debug;
 
 static IS_INIT: OnceLock<()> = OnceLock::new();
+static RENDER_THREAD: OnceLock<Sender<Job>> = OnceLock::new();
+
+type Job = Box<dyn FnOnce() + Send>;
 
 thread_local! {
     static APP: RefCell<Option<App>> = const { RefCell::new(None) };
@@ -20,9 +27,7 @@ pub fn app_mut<T>(cb: impl FnOnce(&mut App) -> error::Result<T>) -> error::Resul
         let mut app_borrow = app_cell
             .try_borrow_mut()
             .map_err(|_| error::ProcessingError::AppAccess)?;
-        let app = app_borrow
-            .as_mut()
-            .ok_or(error::ProcessingError::AppAccess)?;
+        let app = app_borrow.as_mut().ok_or_else(missing_app_error)?;
         cb(app)
     });
     match res {
@@ -31,6 +36,21 @@ pub fn app_mut<T>(cb: impl FnOnce(&mut App) -> error::Result<T>) -> error::Resul
     }
 }
 
+// no app on this thread either means `init` was never called, or it was
+// called on another thread
+fn missing_app_error() -> error::ProcessingError {
+    if RENDER_THREAD.get().is_some() {
+        error::ProcessingError::RenderThread(
+            "the app lives on the render thread, call this through run_on_render_thread"
+                .to_string(),
+        )
+    } else if IS_INIT.get().is_some() {
+        error::ProcessingError::AppAccess
+    } else {
+        error::ProcessingError::NotInitialized
+    }
+}
+
 pub fn is_already_init() -> error::Result<bool> {
     let is_init = IS_INIT.get().is_some();
     let thread_has_app = APP.with(|app_cell| app_cell.borrow().is_some());
@@ -54,3 +74,134 @@ pub fn set_app(app: App) {
 pub fn take_app() -> Option<App> {
     APP.with(|app_cell| app_cell.borrow_mut().take())
 }
+
+/// Lend `world` to the thread-local app for the duration of `cb`, so the public API can be used
+/// from inside a running schedule (i.e. when Bevy's own runner owns the [`App`]). Anything that
+/// needs to drive the app itself, like `app.update()`, isn't available while the world is lent.
+pub fn with_lent_world<T>(world: &mut World, cb: impl FnOnce() -> T) -> T {
+    let mut lent = App::empty();
+    std::mem::swap(lent.world_mut(), world);
+    let prev = APP.with(|app_cell| app_cell.borrow_mut().replace(lent));
+    let _guard = LentWorld { world, prev };
+    cb()
+}
+
+struct LentWorld<'w> {
+    world: &'w mut World,
+    prev: Option<App>,
+}
+
+impl Drop for LentWorld<'_> {
+    // swap back even if the callback panics, otherwise the running app is left with an empty world
+    fn drop(&mut self) {
+        let lent =
+            APP.with(|app_cell| std::mem::replace(&mut *app_cell.borrow_mut(), self.prev.take()));
+        if let Some(mut lent) = lent {
+            std::mem::swap(lent.world_mut(), self.world);
+        }
+    }
+}
+
+/// Start a dedicated thread that owns the app. `init` runs first on the new thread and should
+/// create the app there (e.g. with [`set_app`]); once it succeeds, [`run_on_render_thread`] hands
+/// work to that thread from anywhere. Jobs run one at a time in the order they were sent. Calls
+/// that reach for the app from any other thread fail with a [`error::ProcessingError::RenderThread`]
+/// error rather than running.
+pub fn spawn_render_thread(
+    init: impl FnOnce() -> error::Result<()> + Send + 'static,
+) -> error::Result<()> {
+    if IS_INIT.get().is_some() {
+        return Err(error::ProcessingError::AppAccess);
+    }
+    let (jobs, queue) = mpsc::channel::<Job>();
+    let (ready, wait_ready) = mpsc::sync_channel(1);
+    std::thread::Builder::new()
+        .name("processing-render".to_string())
+        .spawn(move || {
+            let res = init();
+            let ok = res.is_ok();
+            let _ = ready.send(res);
+            if ok {
+                // a panicking job mustn't take the thread down, or every later call would hang
+                for job in queue {
+                    if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                        tracing::error!("render thread job panicked: {}", panic_message(&*panic));
+                    }
+                }
+            }
+        })
+        .map_err(|e| error::ProcessingError::RenderThread(e.to_string()))?;
+
+    wait_ready
+        .recv()
+        .map_err(|e| error::ProcessingError::RenderThread(e.to_string()))??;
+    RENDER_THREAD
+        .set(jobs)
+        .map_err(|_| error::ProcessingError::RenderThread("already running".to_string()))
+}
+
+/// Whether calls from this thread have to go through the render thread to reach the app.
+pub fn is_off_render_thread() -> bool {
+    // a borrowed cell means the app is in use on this thread
+    RENDER_THREAD.get().is_some()
+        && APP.with(|app_cell| app_cell.try_borrow().is_ok_and(|app| app.is_none()))
+}
+
+/// Queue `job` on the render thread without waiting for it. Without a render thread, or when
+/// called on it, `job` runs right away.
+fn submit(job: impl FnOnce() + Send + 'static) -> error::Result<()> {
+    match RENDER_THREAD.get() {
+        Some(jobs) if is_off_render_thread() => jobs.send(Box::new(job)).map_err(|_| {
+            error::ProcessingError::RenderThread("render thread has exited".to_string())
+        }),
+        _ => {
+            job();
+            Ok(())
+        }
+    }
+}
+
+/// Run `f` on the render thread and wait for its result, so any of the public API can be called
+/// from a thread that doesn't own the app. Without a render thread, or when called on it, `f` runs
+/// right away.
+pub fn run_on_render_thread<T: Send + 'static>(
+    f: impl FnOnce() -> error::Result<T> + Send + 'static,
+) -> error::Result<T> {
+    if !is_off_render_thread() {
+        return f();
+    }
+    let (result, wait_result) = mpsc::sync_channel(1);
+    submit(move || {
+        let res = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+            Err(error::ProcessingError::RenderThread(format!(
+                "call panicked: {}",
+                panic_message(&*panic)
+            )))
+        });
+        let _ = result.send(res);
+    })?;
+    wait_result.recv().map_err(|_| {
+        error::ProcessingError::RenderThread("render thread dropped the call".to_string())
+    })?
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(s) = panic.downcast_ref::<String>() {
+        s.clone()
+    } else if let Some(s) = panic.downcast_ref::<&'static str>() {
+        s.to_string()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_mut_before_init_is_not_initialized() {
+        let res = app_mut(|_| Ok(()));
+        assert!(matches!(res, Err(error::ProcessingError::NotInitialized)));
+    }
+}
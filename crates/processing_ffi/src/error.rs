@@ -63,3 +63,51 @@ where
         None
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn last_error() -> Option<String> {
+        let ptr = processing_check_error();
+        (!ptr.is_null()).then(|| {
+            unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    #[test]
+    fn check_catches_panic() {
+        clear_error();
+        let res: Option<()> = check(|| panic!("boom"));
+        assert!(res.is_none());
+        assert_eq!(last_error().as_deref(), Some("Panic occurred: boom"));
+    }
+
+    #[test]
+    fn ffi_call_before_init_sets_error() {
+        assert_eq!(crate::processing_geometry_attribute_position(), 0);
+        assert_eq!(
+            last_error(),
+            Some(ProcessingError::NotInitialized.to_string())
+        );
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn ffi_panic_returns_an_error() {
+        crate::processing_init();
+        assert_eq!(last_error(), None);
+        // one pixel of data for a 2x2 image trips the pixel size assertion inside the core
+        let data = [255u8; 4];
+        let id = unsafe { crate::processing_image_create(2, 2, data.as_ptr(), data.len()) };
+        assert_eq!(id, 0);
+        assert!(last_error().is_some_and(|e| e.starts_with("Panic occurred: ")));
+
+        // the app is still usable after the panic
+        assert_ne!(crate::processing_geometry_attribute_position(), 0);
+        assert_eq!(last_error(), None);
+    }
+}
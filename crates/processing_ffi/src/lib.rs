@@ -1,5 +1,6 @@
 use bevy::{
-    math::{Vec2, Vec3, Vec4},
+    color::LinearRgba,
+    math::{Affine3A, Mat4, Vec2, Vec3, Vec4},
     prelude::Entity,
     render::render_resource::{Extent3d, TextureFormat},
 };
@@ -265,6 +266,24 @@ pub extern "C" fn processing_end_draw(graphics_id: u64) {
     error::check(|| graphics_end_draw(graphics_entity));
 }
 
+/// Saves the graphics context to `path`, in the format its extension names (`.png`, `.jpg`,
+/// `.tga` or `.bmp`). A run of `#`s in the file name is replaced with the zero-padded frame
+/// count, as in `saveFrame("frame-####.png")`.
+///
+/// # Safety
+/// - graphics_id is a valid ID returned from graphics_create.
+/// - `path` is a valid pointer to a null-terminated string.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_save(graphics_id: u64, path: *const std::ffi::c_char) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        let path = unsafe { cstr_to_str(path) }?;
+        graphics_save(graphics_entity, path)
+    });
+}
+
 /// Shuts down internal resources with given exit code, but does *not* terminate the process.
 ///
 /// SAFETY:
@@ -318,6 +337,48 @@ pub extern "C" fn processing_set_fill(graphics_id: u64, color: Color) {
     });
 }
 
+/// Set the tint multiplied into images drawn with `processing_image`.
+///
+/// SAFETY:
+/// - graphics_id is a valid ID returned from graphics_create.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_set_tint(graphics_id: u64, color: Color) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        let mode = graphics_get_color_mode(graphics_entity)?;
+        graphics_record_command(graphics_entity, DrawCommand::Tint(color.resolve(&mode)))
+    });
+}
+
+/// Draw images without a tint.
+///
+/// SAFETY:
+/// - graphics_id is a valid ID returned from graphics_create.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_no_tint(graphics_id: u64) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| graphics_record_command(graphics_entity, DrawCommand::NoTint));
+}
+
+/// Set the fill color from a packed `0xAARRGGBB` value. A zero alpha byte is read as opaque.
+///
+/// SAFETY:
+/// - graphics_id is a valid ID returned from graphics_create.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_set_fill_argb(graphics_id: u64, argb: u32) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        let color = processing::prelude::color::from_argb(argb);
+        graphics_record_command(graphics_entity, DrawCommand::Fill(color))
+    });
+}
+
 /// Set the stroke color.
 ///
 /// SAFETY:
@@ -336,6 +397,21 @@ pub extern "C" fn processing_set_stroke_color(graphics_id: u64, color: Color) {
     });
 }
 
+/// Set the stroke color from a packed `0xAARRGGBB` value. A zero alpha byte is read as opaque.
+///
+/// SAFETY:
+/// - graphics_id is a valid ID returned from graphics_create.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_set_stroke_argb(graphics_id: u64, argb: u32) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        let color = processing::prelude::color::from_argb(argb);
+        graphics_record_command(graphics_entity, DrawCommand::StrokeColor(color))
+    });
+}
+
 /// Set the stroke weight.
 ///
 /// SAFETY:
@@ -374,6 +450,19 @@ pub extern "C" fn processing_set_stroke_join(graphics_id: u64, join: u8) {
     });
 }
 
+/// Set where the stroke of closed shapes sits relative to their edge.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_set_stroke_align(graphics_id: u64, align: u8) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        graphics_record_command(
+            graphics_entity,
+            DrawCommand::StrokeAlign(processing::prelude::StrokeAlignMode::from(align)),
+        )
+    });
+}
+
 /// Set the rect mode.
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_rect_mode(graphics_id: u64, mode: u8) {
@@ -400,6 +489,69 @@ pub extern "C" fn processing_ellipse_mode(graphics_id: u64, mode: u8) {
     });
 }
 
+/// Set the shape mode used to place `processing_shape` draws.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_shape_mode(graphics_id: u64, mode: u8) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        graphics_record_command(
+            graphics_entity,
+            DrawCommand::ShapeMode(processing::prelude::ShapeMode::from(mode)),
+        )
+    });
+}
+
+/// Set the image mode.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_image_mode(graphics_id: u64, mode: u8) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        graphics_record_command(
+            graphics_entity,
+            DrawCommand::ImageMode(processing::prelude::ShapeMode::from(mode)),
+        )
+    });
+}
+
+/// Draw an image as a `w` x `h` quad at `x`, `y`, positioned by the image mode and tinted by the
+/// current tint.
+///
+/// SAFETY:
+/// - graphics_id is a valid ID returned from graphics_create.
+/// - image_id is a valid ID returned from processing_image_create.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_image(
+    graphics_id: u64,
+    image_id: u64,
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let image_entity = Entity::from_bits(image_id);
+    error::check(|| {
+        graphics_record_command(
+            graphics_entity,
+            DrawCommand::Image {
+                entity: image_entity,
+                dx: x,
+                dy: y,
+                d_width: Some(w),
+                d_height: Some(h),
+                sx: None,
+                sy: None,
+                s_width: None,
+                s_height: None,
+            },
+        )
+    });
+}
+
 /// Disable fill for subsequent shapes.
 ///
 /// SAFETY:
@@ -460,6 +612,50 @@ pub extern "C" fn processing_reset_matrix(graphics_id: u64) {
     error::check(|| graphics_record_command(graphics_entity, DrawCommand::ResetMatrix));
 }
 
+/// Multiply the transformation matrix by a column-major 4x4 matrix.
+///
+/// # Safety
+/// - matrix is a valid pointer to 16 floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_apply_matrix(graphics_id: u64, matrix: *const [f32; 16]) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let matrix = unsafe { *matrix };
+    error::check(|| graphics_record_command(graphics_entity, DrawCommand::ApplyMatrix(matrix)));
+}
+
+/// Write the current transformation matrix to `out` as 16 column-major floats.
+///
+/// # Safety
+/// - `out` must be valid for a write of 16 floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_get_matrix(graphics_id: u64, out: *mut [f32; 16]) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    if let Some(matrix) = error::check(|| graphics_get_matrix(graphics_entity)) {
+        unsafe { *out = matrix };
+    }
+}
+
+/// Write where the point `(x, y, z)`, drawn at the current transform, lands on screen to `out`.
+///
+/// # Safety
+/// - `out` must be valid for a write of 2 floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_screen_xy(
+    graphics_id: u64,
+    x: f32,
+    y: f32,
+    z: f32,
+    out: *mut [f32; 2],
+) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    if let Some((sx, sy)) = error::check(|| graphics_screen_xy(graphics_entity, x, y, z)) {
+        unsafe { *out = [sx, sy] };
+    }
+}
+
 /// Translate the coordinate system.
 ///
 /// SAFETY:
@@ -613,6 +809,30 @@ pub extern "C" fn processing_line(graphics_id: u64, x1: f32, y1: f32, x2: f32, y
     error::check(|| graphics_record_command(graphics_entity, DrawCommand::Line { x1, y1, x2, y2 }));
 }
 
+/// Draw a line between two points in 3d.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_line_3d(
+    graphics_id: u64,
+    x1: f32,
+    y1: f32,
+    z1: f32,
+    x2: f32,
+    y2: f32,
+    z2: f32,
+) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        graphics_record_command(
+            graphics_entity,
+            DrawCommand::Line3d {
+                start: Vec3::new(x1, y1, z1),
+                end: Vec3::new(x2, y2, z2),
+            },
+        )
+    });
+}
+
 /// Draw a triangle.
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_triangle(
@@ -681,6 +901,34 @@ pub extern "C" fn processing_point(graphics_id: u64, x: f32, y: f32) {
     error::check(|| graphics_record_command(graphics_entity, DrawCommand::Point { x, y }));
 }
 
+/// Draw a point in 3d.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_point_3d(graphics_id: u64, x: f32, y: f32, z: f32) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        graphics_record_command(graphics_entity, DrawCommand::Point3d(Vec3::new(x, y, z)))
+    });
+}
+
+/// Draw many points at once.
+/// points_ptr points to an array of (x, y) float pairs.
+///
+/// # Safety
+/// - points_ptr is a valid pointer to count * 2 readable floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_points(graphics_id: u64, points_ptr: *const f32, count: u32) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let points: Vec<[f32; 2]> = (0..count as usize)
+        .map(|i| unsafe {
+            let base = points_ptr.add(i * 2);
+            [*base, *base.add(1)]
+        })
+        .collect();
+    error::check(|| graphics_record_command(graphics_entity, DrawCommand::Points(points)));
+}
+
 /// Draw a square.
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_square(graphics_id: u64, x: f32, y: f32, s: f32) {
@@ -1371,8 +1619,8 @@ pub extern "C" fn processing_text_size(graphics_id: u64, size: f32) {
 }
 
 /// Set the text alignment.
-/// h: 0=LEFT, 1=CENTER, 2=RIGHT
-/// v: 0=BASELINE, 1=TOP, 2=CENTER, 3=BOTTOM
+/// h: PROCESSING_TEXT_ALIGN_LEFT, _CENTER or _RIGHT
+/// v: PROCESSING_TEXT_ALIGN_BASELINE, _TOP, _MIDDLE or _BOTTOM
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_text_align(graphics_id: u64, h: u8, v: u8) {
     error::clear_error();
@@ -1396,6 +1644,14 @@ pub extern "C" fn processing_text_wrap(graphics_id: u64, mode: u8) {
     error::check(|| graphics_text_wrap(graphics_entity, mode));
 }
 
+/// Set the text mode. 0=VECTOR, 1=SDF
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_text_mode(graphics_id: u64, mode: u8) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| graphics_text_mode(graphics_entity, mode));
+}
+
 /// Measure the width of text.
 ///
 /// # Safety
@@ -1495,6 +1751,18 @@ pub extern "C" fn processing_image_resize(image_id: u64, new_width: u32, new_hei
     error::check(|| image_resize(image_entity, new_size));
 }
 
+/// Apply a filter to an image. `kind` is 0 gray, 1 invert, 2 threshold, 3 blur or 4 posterize,
+/// and `param` is the threshold level, blur radius or number of posterize levels.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_image_filter(image_id: u64, kind: u8, param: f32) {
+    error::clear_error();
+    let image_entity = Entity::from_bits(image_id);
+    error::check(|| {
+        let filter = processing::prelude::image::Filter::from_parts(kind, param)?;
+        image_filter(image_entity, filter)
+    });
+}
+
 /// Load pixels from an image into a caller-provided buffer.
 ///
 /// # Safety
@@ -1537,6 +1805,48 @@ pub unsafe extern "C" fn processing_image_readback(
     });
 }
 
+/// Read an image's pixels straight into a caller-provided buffer, without the intermediate
+/// allocation `processing_image_readback` makes.
+///
+/// # Safety
+/// - Init and image_create have been called.
+/// - image_id is a valid ID returned from image_create.
+/// - buffer is a valid pointer to at least buffer_len Color elements.
+/// - buffer_len must equal width * height of the image.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_image_readback_into(
+    image_id: u64,
+    buffer: *mut Color,
+    buffer_len: usize,
+) {
+    error::clear_error();
+    let image_entity = Entity::from_bits(image_id);
+    // Color is wider than LinearRgba, so the pixels are read into the front of the buffer and
+    // then widened in place, back to front so nothing is overwritten before it's read
+    const _: () = assert!(
+        size_of::<Color>() >= size_of::<LinearRgba>()
+            && align_of::<Color>() >= align_of::<LinearRgba>()
+    );
+    error::check(|| {
+        let pixels = buffer.cast::<LinearRgba>();
+        // SAFETY: Caller guarantees buffer is valid for buffer_len Colors, which covers
+        // buffer_len LinearRgbas
+        image_readback_into(image_entity, unsafe {
+            std::slice::from_raw_parts_mut(pixels, buffer_len)
+        })?;
+        for i in (0..buffer_len).rev() {
+            // SAFETY: Color i starts at or after the end of LinearRgba i - 1, so writing it only
+            // clobbers pixels that were already widened
+            unsafe {
+                let pixel = pixels.add(i).read();
+                buffer.add(i).write(Color::from_linear(pixel));
+            }
+        }
+        Ok(())
+    });
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_mode_3d(graphics_id: u64) {
     error::clear_error();
@@ -1551,6 +1861,33 @@ pub extern "C" fn processing_mode_2d(graphics_id: u64) {
     error::check(|| graphics_mode_2d(graphics_entity));
 }
 
+/// Place the camera at the eye point looking at the center point, with the given up direction.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn processing_camera(
+    graphics_id: u64,
+    eye_x: f32,
+    eye_y: f32,
+    eye_z: f32,
+    center_x: f32,
+    center_y: f32,
+    center_z: f32,
+    up_x: f32,
+    up_y: f32,
+    up_z: f32,
+) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        graphics_camera(
+            graphics_entity,
+            Vec3::new(eye_x, eye_y, eye_z),
+            Vec3::new(center_x, center_y, center_z),
+            Vec3::new(up_x, up_y, up_z),
+        )
+    });
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_perspective(
     graphics_id: u64,
@@ -1695,6 +2032,18 @@ pub const PROCESSING_STROKE_JOIN_ROUND: u8 = 0;
 pub const PROCESSING_STROKE_JOIN_MITER: u8 = 1;
 pub const PROCESSING_STROKE_JOIN_BEVEL: u8 = 2;
 
+pub const PROCESSING_STROKE_ALIGN_INNER: u8 = 0;
+pub const PROCESSING_STROKE_ALIGN_CENTER: u8 = 1;
+pub const PROCESSING_STROKE_ALIGN_OUTER: u8 = 2;
+
+pub const PROCESSING_TEXT_ALIGN_LEFT: u8 = 0;
+pub const PROCESSING_TEXT_ALIGN_CENTER: u8 = 1;
+pub const PROCESSING_TEXT_ALIGN_RIGHT: u8 = 2;
+pub const PROCESSING_TEXT_ALIGN_BASELINE: u8 = 0;
+pub const PROCESSING_TEXT_ALIGN_TOP: u8 = 1;
+pub const PROCESSING_TEXT_ALIGN_MIDDLE: u8 = 2;
+pub const PROCESSING_TEXT_ALIGN_BOTTOM: u8 = 3;
+
 pub const PROCESSING_BLEND_MODE_BLEND: u8 = 0;
 pub const PROCESSING_BLEND_MODE_ADD: u8 = 1;
 pub const PROCESSING_BLEND_MODE_SUBTRACT: u8 = 2;
@@ -1768,6 +2117,14 @@ pub extern "C" fn processing_geometry_layout_add_attribute(layout_id: u64, attr_
     error::check(|| geometry_layout_add_attribute(layout_entity, attr_entity));
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_layout_remove_attribute(layout_id: u64, attr_id: u64) {
+    error::clear_error();
+    let layout_entity = Entity::from_bits(layout_id);
+    let attr_entity = Entity::from_bits(attr_id);
+    error::check(|| geometry_layout_remove_attribute(layout_entity, attr_entity));
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_layout_destroy(layout_id: u64) {
     error::clear_error();
@@ -1775,6 +2132,14 @@ pub extern "C" fn processing_geometry_layout_destroy(layout_id: u64) {
     error::check(|| geometry_layout_destroy(entity));
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_rebuild_layout(geo_id: u64, layout_id: u64) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    let layout_entity = Entity::from_bits(layout_id);
+    error::check(|| geometry_rebuild_layout(entity, layout_entity));
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_create_with_layout(layout_id: u64, topology: u8) -> u64 {
     error::clear_error();
@@ -1861,22 +2226,34 @@ pub extern "C" fn processing_geometry_attribute_destroy(attr_id: u64) {
 
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_attribute_position() -> u64 {
-    geometry_attribute_position().to_bits()
+    error::clear_error();
+    error::check(geometry_attribute_position)
+        .map(|e| e.to_bits())
+        .unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_attribute_normal() -> u64 {
-    geometry_attribute_normal().to_bits()
+    error::clear_error();
+    error::check(geometry_attribute_normal)
+        .map(|e| e.to_bits())
+        .unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_attribute_color() -> u64 {
-    geometry_attribute_color().to_bits()
+    error::clear_error();
+    error::check(geometry_attribute_color)
+        .map(|e| e.to_bits())
+        .unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_attribute_uv() -> u64 {
-    geometry_attribute_uv().to_bits()
+    error::clear_error();
+    error::check(geometry_attribute_uv)
+        .map(|e| e.to_bits())
+        .unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
@@ -2108,6 +2485,168 @@ pub extern "C" fn processing_geometry_set_uv(geo_id: u64, index: u32, u: f32, v:
     error::check(|| geometry_set_uv(entity, index, Vec2::new(u, v)));
 }
 
+/// Empty the geometry's vertices and indices, keeping its layout, for refilling.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_clear(geo_id: u64) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    error::check(|| geometry_clear(entity));
+}
+
+/// # Safety
+/// - `data` must be valid for reads of `len` elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_set_positions(
+    geo_id: u64,
+    data: *const [f32; 3],
+    len: u32,
+) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    let values = unsafe { std::slice::from_raw_parts(data, len as usize) };
+    error::check(|| geometry_set_positions(entity, values));
+}
+
+/// # Safety
+/// - `data` must be valid for reads of `len` elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_set_normals(
+    geo_id: u64,
+    data: *const [f32; 3],
+    len: u32,
+) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    let values = unsafe { std::slice::from_raw_parts(data, len as usize) };
+    error::check(|| geometry_set_normals(entity, values));
+}
+
+/// # Safety
+/// - `data` must be valid for reads of `len` elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_set_colors(
+    geo_id: u64,
+    data: *const [f32; 4],
+    len: u32,
+) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    let values = unsafe { std::slice::from_raw_parts(data, len as usize) };
+    error::check(|| geometry_set_colors(entity, values));
+}
+
+/// # Safety
+/// - `data` must be valid for reads of `len` elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_set_uvs(geo_id: u64, data: *const [f32; 2], len: u32) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    let values = unsafe { std::slice::from_raw_parts(data, len as usize) };
+    error::check(|| geometry_set_uvs(entity, values));
+}
+
+/// # Safety
+/// - `data` must be valid for reads of `len` elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_set_indices(geo_id: u64, data: *const u32, len: u32) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    let values = unsafe { std::slice::from_raw_parts(data, len as usize) };
+    error::check(|| geometry_set_indices(entity, values));
+}
+
+/// Recompute smooth, area weighted normals from the geometry's triangles.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_compute_normals(geo_id: u64) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    error::check(|| geometry_compute_normals(entity));
+}
+
+/// Laplacian smoothing: move each vertex factor of the way toward its neighbors' average,
+/// iterations times. Normals are kept; call processing_geometry_compute_normals to update them.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_smooth(geo_id: u64, iterations: u32, factor: f32) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    error::check(|| geometry_smooth(entity, iterations, factor));
+}
+
+/// Compute the geometry's bounding box. Writes [x, y, z] of its corners to out_min and out_max.
+///
+/// # Safety
+/// - out_min and out_max are valid pointers to writable float arrays of at least 3 elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_bounds(
+    geo_id: u64,
+    out_min: *mut f32,
+    out_max: *mut f32,
+) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    if let Some((min, max)) = error::check(|| geometry_bounds(entity)) {
+        unsafe {
+            std::slice::from_raw_parts_mut(out_min, 3).copy_from_slice(&min.to_array());
+            std::slice::from_raw_parts_mut(out_max, 3).copy_from_slice(&max.to_array());
+        }
+    }
+}
+
+/// Bake a column-major 4x4 matrix into the geometry's positions and normals.
+///
+/// # Safety
+/// - matrix is a valid pointer to 16 floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_apply_transform(
+    geo_id: u64,
+    matrix: *const [f32; 16],
+) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    let transform = Affine3A::from_mat4(Mat4::from_cols_array(unsafe { &*matrix }));
+    error::check(|| geometry_apply_transform(entity, transform));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_to_indexed(geo_id: u64) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    error::check(|| geometry_to_indexed(entity));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_to_nonindexed(geo_id: u64) {
+    error::clear_error();
+    let entity = Entity::from_bits(geo_id);
+    error::check(|| geometry_to_nonindexed(entity));
+}
+
+/// Merge geo_count geometries into a new geometry and return its id, or 0 on error. transforms
+/// is either null or holds one column-major 4x4 matrix per geometry to move it by first.
+///
+/// # Safety
+/// - geo_ids is a valid pointer to geo_count geometry ids.
+/// - transforms is null or a valid pointer to geo_count * 16 floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_geometry_merge(
+    geo_ids: *const u64,
+    geo_count: u32,
+    transforms: *const [f32; 16],
+) -> u64 {
+    error::clear_error();
+    let ids = unsafe { std::slice::from_raw_parts(geo_ids, geo_count as usize) };
+    let entities: Vec<Entity> = ids.iter().map(|&id| Entity::from_bits(id)).collect();
+    let transforms: Option<Vec<Affine3A>> = (!transforms.is_null()).then(|| {
+        unsafe { std::slice::from_raw_parts(transforms, geo_count as usize) }
+            .iter()
+            .map(|m| Affine3A::from_mat4(Mat4::from_cols_array(m)))
+            .collect()
+    });
+    error::check(|| geometry_merge(&entities, transforms.as_deref()))
+        .map(|e| e.to_bits())
+        .unwrap_or(0)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_destroy(geo_id: u64) {
     error::clear_error();
@@ -2115,6 +2654,31 @@ pub extern "C" fn processing_geometry_destroy(geo_id: u64) {
     error::check(|| geometry_destroy(entity));
 }
 
+/// Load a Wavefront `.obj` model from a file path as a geometry.
+///
+/// # Safety
+/// - Init has been called.
+/// - path is a valid null-terminated C string.
+/// - This is called from the same thread as init.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_obj_load(path: *const std::ffi::c_char) -> u64 {
+    error::clear_error();
+
+    // SAFETY: Caller guarantees path is a valid C string
+    let c_str = unsafe { std::ffi::CStr::from_ptr(path) };
+    let path_str = match c_str.to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_error("Invalid UTF-8 in obj path");
+            return 0;
+        }
+    };
+
+    error::check(|| obj_load(path_str))
+        .map(|entity| entity.to_bits())
+        .unwrap_or(0)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_model(graphics_id: u64, geo_id: u64) {
     error::clear_error();
@@ -2123,6 +2687,43 @@ pub extern "C" fn processing_model(graphics_id: u64, geo_id: u64) {
     error::check(|| graphics_record_command(graphics_entity, DrawCommand::Geometry(geo_entity)));
 }
 
+/// Draw a geometry modeled in the unit square stretched over the rect at `x`, `y`, `w`, `h`,
+/// placed by the shape mode.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_shape(graphics_id: u64, geo_id: u64, x: f32, y: f32, w: f32, h: f32) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let geometry = Entity::from_bits(geo_id);
+    error::check(|| {
+        graphics_record_command(
+            graphics_entity,
+            DrawCommand::Shape {
+                geometry,
+                x,
+                y,
+                w,
+                h,
+                material: None,
+            },
+        )
+    });
+}
+
+/// Draw a geometry with `mat_id` in place of the current material for this draw only.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_model_with_material(graphics_id: u64, geo_id: u64, mat_id: u64) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let geometry = Entity::from_bits(geo_id);
+    let material = Entity::from_bits(mat_id);
+    error::check(|| {
+        graphics_record_command(
+            graphics_entity,
+            DrawCommand::GeometryWithMaterial { geometry, material },
+        )
+    });
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_geometry_box(width: f32, height: f32, depth: f32) -> u64 {
     error::clear_error();
@@ -2139,6 +2740,24 @@ pub extern "C" fn processing_geometry_sphere(radius: f32, sectors: u32, stacks:
         .unwrap_or(0)
 }
 
+/// Start capturing the graphics' 3D shapes into a geometry instead of drawing them.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_begin(graphics_id: u64) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| geometry_begin(graphics_entity));
+}
+
+/// Bake the shapes drawn since `processing_geometry_begin` into a new geometry.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_geometry_end(graphics_id: u64) -> u64 {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| geometry_end(graphics_entity))
+        .map(|e| e.to_bits())
+        .unwrap_or(0)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_light_create_directional(
     graphics_id: u64,
@@ -2155,6 +2774,44 @@ pub extern "C" fn processing_light_create_directional(
     .unwrap_or(0)
 }
 
+/// Switch a light off (false) or back on (true).
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_light_set_enabled(light_id: u64, enabled: bool) {
+    error::clear_error();
+    let entity = Entity::from_bits(light_id);
+    error::check(|| light_set_enabled(entity, enabled));
+}
+
+/// Remove a light. Lights last until destroyed, so create them once rather than every frame.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_light_destroy(light_id: u64) {
+    error::clear_error();
+    let entity = Entity::from_bits(light_id);
+    error::check(|| light_destroy(entity));
+}
+
+/// Give the graphics an ambient light, replacing any it had.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_light_create_ambient(graphics_id: u64, color: Color, brightness: f32) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        let mode = graphics_get_color_mode(graphics_entity)?;
+        light_create_ambient(graphics_entity, color.resolve(&mode), brightness)
+    });
+}
+
+/// Change the color and brightness of the graphics' ambient light.
+#[unsafe(no_mangle)]
+pub extern "C" fn processing_light_set_ambient(graphics_id: u64, color: Color, brightness: f32) {
+    error::clear_error();
+    let graphics_entity = Entity::from_bits(graphics_id);
+    error::check(|| {
+        let mode = graphics_get_color_mode(graphics_entity)?;
+        light_set_ambient(graphics_entity, color.resolve(&mode), brightness)
+    });
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_light_create_point(
     graphics_id: u64,
@@ -2260,6 +2917,23 @@ pub unsafe extern "C" fn processing_material_set_float4(
     });
 }
 
+/// Set texture `name` on Material to an image.
+///
+/// # Safety
+/// - `name` must be non-null
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn processing_material_set_texture(
+    mat_id: u64,
+    name: *const std::ffi::c_char,
+    image_id: u64,
+) {
+    error::clear_error();
+    error::check(|| {
+        let name = unsafe { cstr_to_str(name) }?;
+        material_set_texture(Entity::from_bits(mat_id), name, Entity::from_bits(image_id))
+    });
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn processing_material_destroy(mat_id: u64) {
     error::clear_error();
@@ -2931,3 +3605,20 @@ fn key_code_to_u32(kc: KeyCode) -> u32 {
         _ => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_align_constants_match_render() {
+        // cbindgen only sees literals, so these are spelled out rather than re-exported
+        assert_eq!(PROCESSING_TEXT_ALIGN_LEFT, TEXT_ALIGN_LEFT);
+        assert_eq!(PROCESSING_TEXT_ALIGN_CENTER, TEXT_ALIGN_CENTER);
+        assert_eq!(PROCESSING_TEXT_ALIGN_RIGHT, TEXT_ALIGN_RIGHT);
+        assert_eq!(PROCESSING_TEXT_ALIGN_BASELINE, TEXT_ALIGN_BASELINE);
+        assert_eq!(PROCESSING_TEXT_ALIGN_TOP, TEXT_ALIGN_TOP);
+        assert_eq!(PROCESSING_TEXT_ALIGN_MIDDLE, TEXT_ALIGN_MIDDLE);
+        assert_eq!(PROCESSING_TEXT_ALIGN_BOTTOM, TEXT_ALIGN_BOTTOM);
+    }
+}
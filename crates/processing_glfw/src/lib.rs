@@ -12,8 +12,9 @@ use processing_core::app_mut;
 use processing_core::error::Result;
 use processing_input::{
     input_cursor_grab_mode, input_cursor_visible, input_flush, input_set_char,
-    input_set_cursor_enter, input_set_cursor_leave, input_set_focus, input_set_key,
-    input_set_mouse_button, input_set_mouse_move, input_set_scroll, input_window_resize,
+    input_set_cursor_enter, input_set_cursor_leave, input_set_file_drop, input_set_focus,
+    input_set_key, input_set_mouse_button, input_set_mouse_move, input_set_scroll,
+    input_window_resize,
 };
 use processing_render::surface::{MonitorWorkarea, WindowControls};
 
@@ -301,6 +302,11 @@ impl GlfwContext {
                 WindowEvent::Focus(focused) => {
                     input_set_focus(surface, focused).unwrap();
                 }
+                WindowEvent::FileDrop(paths) => {
+                    for path in paths {
+                        input_set_file_drop(surface, path).unwrap();
+                    }
+                }
                 WindowEvent::Size(width, height) => {
                     // A drag delivers many Size events per poll; keep only the last and apply it once after the loop.
                     pending_resize = Some((width, height));
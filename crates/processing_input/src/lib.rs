@@ -8,19 +8,22 @@ use bevy::input::mouse::{
 };
 use bevy::input::touch::TouchPhase;
 use bevy::prelude::*;
-use bevy::window::{CursorMoved, WindowResized};
+use bevy::window::{CursorMoved, FileDragAndDrop, WindowResized};
 
 use processing_core::app_mut;
 use processing_core::error;
 
-pub use state::{CursorPosition, LastKey, LastMouseButton};
+pub use state::{CharInput, CursorPosition, DroppedFiles, LastKey, LastMouseButton};
 
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<LastKey>()
+        app.add_message::<CharInput>()
+            .add_message::<FileDragAndDrop>()
+            .init_resource::<LastKey>()
             .init_resource::<LastMouseButton>()
+            .init_resource::<DroppedFiles>()
             .add_systems(
                 PreUpdate,
                 (
@@ -29,6 +32,7 @@ impl Plugin for InputPlugin {
                         state::track_cursor_position,
                         state::track_last_key,
                         state::track_last_mouse_button,
+                        state::track_dropped_files,
                     )
                         .after(state::snapshot_cursor),
                 ),
@@ -116,14 +120,10 @@ pub fn input_set_key(surface: Entity, key_code: KeyCode, pressed: bool) -> error
 
 pub fn input_set_char(surface: Entity, key_code: KeyCode, character: char) -> error::Result<()> {
     app_mut(|app| {
-        let text = String::from(character);
-        app.world_mut().write_message(KeyboardInput {
-            key_code,
-            logical_key: Key::Character(text.clone().into()),
-            state: ButtonState::Pressed,
-            text: Some(text.into()),
-            repeat: false,
+        app.world_mut().write_message(CharInput {
             window: surface,
+            key_code,
+            character,
         });
         Ok(())
     })
@@ -155,6 +155,17 @@ pub fn input_set_focus(surface: Entity, focused: bool) -> error::Result<()> {
     })
 }
 
+/// Report a file dropped onto the surface, as when dragged in from the file manager.
+pub fn input_set_file_drop(surface: Entity, path: std::path::PathBuf) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut().write_message(FileDragAndDrop::DroppedFile {
+            window: surface,
+            path_buf: path,
+        });
+        Ok(())
+    })
+}
+
 pub fn input_cursor_grab_mode(surface: Entity) -> error::Result<bevy::window::CursorGrabMode> {
     app_mut(|app| {
         let cursor = app
@@ -321,6 +332,11 @@ pub fn input_mouse_any_just_released() -> error::Result<bool> {
     })
 }
 
+/// Whether a mouse button was released this frame without the mouse moving since it was pressed.
+pub fn input_mouse_clicked() -> error::Result<bool> {
+    app_mut(|app| Ok(app.world().resource::<LastMouseButton>().just_clicked))
+}
+
 pub fn input_key_any_just_pressed() -> error::Result<bool> {
     app_mut(|app| Ok(app.world().resource::<LastKey>().just_pressed))
 }
@@ -329,6 +345,16 @@ pub fn input_key_any_just_released() -> error::Result<bool> {
     app_mut(|app| Ok(app.world().resource::<LastKey>().just_released))
 }
 
+/// Whether a character was typed this frame, as opposed to a physical key being pressed.
+pub fn input_key_just_typed() -> error::Result<bool> {
+    app_mut(|app| Ok(app.world().resource::<LastKey>().just_typed))
+}
+
+/// The files dropped onto any surface this frame, in the order they were dropped.
+pub fn input_dropped_files() -> error::Result<Vec<std::path::PathBuf>> {
+    app_mut(|app| Ok(app.world().resource::<DroppedFiles>().paths.clone()))
+}
+
 pub fn input_mouse_moved() -> error::Result<bool> {
     app_mut(|app| {
         let d = app.world().resource::<AccumulatedMouseMotion>().delta;
@@ -343,6 +369,42 @@ pub fn input_mouse_scrolled() -> error::Result<bool> {
     })
 }
 
+/// A mouse event with a Processing callback, like `mousePressed()` for [`MouseEvent::Pressed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseEvent {
+    Pressed,
+    Dragged,
+    Moved,
+    Released,
+    Clicked,
+    Wheel,
+}
+
+/// The mouse events that happened this frame, in the order their callbacks should fire.
+pub fn input_mouse_events() -> error::Result<Vec<MouseEvent>> {
+    let mut events = Vec::new();
+    if input_mouse_any_just_pressed()? {
+        events.push(MouseEvent::Pressed);
+    }
+    if input_mouse_moved()? {
+        events.push(if input_mouse_is_pressed()? {
+            MouseEvent::Dragged
+        } else {
+            MouseEvent::Moved
+        });
+    }
+    if input_mouse_any_just_released()? {
+        events.push(MouseEvent::Released);
+    }
+    if input_mouse_clicked()? {
+        events.push(MouseEvent::Clicked);
+    }
+    if input_mouse_scrolled()? {
+        events.push(MouseEvent::Wheel);
+    }
+    Ok(events)
+}
+
 pub fn input_window_resize(surface: Entity, width: f32, height: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut().write_message(WindowResized {
@@ -353,3 +415,67 @@ pub fn input_window_resize(surface: Entity, width: f32, height: f32) -> error::R
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_keys_are_all_down() {
+        let mut app = App::new();
+        app.add_plugins((bevy::input::InputPlugin, InputPlugin))
+            .add_message::<CursorMoved>();
+        let surface = app.world_mut().spawn_empty().id();
+        processing_core::set_app(app);
+
+        input_set_key(surface, KeyCode::KeyW, true).unwrap();
+        input_set_key(surface, KeyCode::KeyD, true).unwrap();
+        input_set_mouse_button(surface, MouseButton::Right, true).unwrap();
+        input_flush().unwrap();
+        assert!(input_key_is_down(KeyCode::KeyW).unwrap());
+        assert!(input_key_is_down(KeyCode::KeyD).unwrap());
+        assert!(!input_key_is_down(KeyCode::KeyA).unwrap());
+        assert_eq!(input_mouse_button().unwrap(), Some(MouseButton::Right));
+
+        input_set_key(surface, KeyCode::KeyW, false).unwrap();
+        input_flush().unwrap();
+        assert!(!input_key_is_down(KeyCode::KeyW).unwrap());
+        assert!(input_key_is_down(KeyCode::KeyD).unwrap());
+    }
+
+    #[test]
+    fn press_drag_release_fires_in_order() {
+        let mut app = App::new();
+        app.add_plugins((bevy::input::InputPlugin, InputPlugin))
+            .add_message::<CursorMoved>();
+        let surface = app.world_mut().spawn_empty().id();
+        processing_core::set_app(app);
+
+        input_set_mouse_move(surface, 10.0, 10.0).unwrap();
+        input_flush().unwrap();
+        input_set_mouse_button(surface, MouseButton::Left, true).unwrap();
+        input_flush().unwrap();
+        assert_eq!(input_mouse_events().unwrap(), [MouseEvent::Pressed]);
+
+        input_set_mouse_move(surface, 20.0, 15.0).unwrap();
+        input_flush().unwrap();
+        assert_eq!(input_mouse_events().unwrap(), [MouseEvent::Dragged]);
+        assert_eq!(input_mouse_x(surface).unwrap(), 20.0);
+        assert_eq!(input_mouse_y(surface).unwrap(), 15.0);
+        assert_eq!(input_pmouse_x(surface).unwrap(), 10.0);
+        assert_eq!(input_pmouse_y(surface).unwrap(), 10.0);
+
+        input_set_mouse_button(surface, MouseButton::Left, false).unwrap();
+        input_flush().unwrap();
+        assert_eq!(input_mouse_events().unwrap(), [MouseEvent::Released]);
+
+        input_set_mouse_button(surface, MouseButton::Left, true).unwrap();
+        input_flush().unwrap();
+        input_set_mouse_button(surface, MouseButton::Left, false).unwrap();
+        input_flush().unwrap();
+        assert_eq!(
+            input_mouse_events().unwrap(),
+            [MouseEvent::Released, MouseEvent::Clicked]
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use bevy::input::ButtonState;
 use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::{MouseButton, MouseButtonInput};
+use bevy::input::mouse::{MouseButton, MouseButtonInput, MouseMotion};
+use std::path::PathBuf;
+
 use bevy::prelude::*;
-use bevy::window::CursorMoved;
+use bevy::window::{CursorMoved, FileDragAndDrop};
 
 use bevy::input::keyboard::KeyCode;
 
@@ -22,17 +24,37 @@ impl CursorPosition {
     }
 }
 
+/// A typed character, as opposed to a physical key press. Sent by backends that report text
+/// separately from keys (e.g. GLFW's char callback).
+#[derive(Message, Debug, Clone)]
+pub struct CharInput {
+    pub window: Entity,
+    pub key_code: KeyCode,
+    pub character: char,
+}
+
 #[derive(Resource, Default)]
 pub struct LastKey {
     pub code: Option<KeyCode>,
     pub character: Option<char>,
     pub just_pressed: bool,
     pub just_released: bool,
+    pub just_typed: bool,
 }
 
 #[derive(Resource, Default)]
 pub struct LastMouseButton {
     pub button: Option<MouseButton>,
+    /// A button was released this frame without the mouse moving since it was pressed.
+    pub just_clicked: bool,
+    held: bool,
+    dragged: bool,
+}
+
+/// Files dropped onto a window this frame, like Processing's `dropped()`.
+#[derive(Resource, Default)]
+pub struct DroppedFiles {
+    pub paths: Vec<PathBuf>,
 }
 
 pub fn snapshot_cursor(mut query: Query<&mut CursorPosition>) {
@@ -52,34 +74,239 @@ pub fn track_cursor_position(
     }
 }
 
-pub fn track_last_key(mut reader: MessageReader<KeyboardInput>, mut last: ResMut<LastKey>) {
+pub fn track_last_key(
+    mut reader: MessageReader<KeyboardInput>,
+    mut chars: MessageReader<CharInput>,
+    mut last: ResMut<LastKey>,
+) {
     // our cbs fire on key auto repeats but bevy just_pressed only fires on the initial press
     // we track edge state off of the raw input stream
     last.just_pressed = false;
     last.just_released = false;
+    last.just_typed = false;
     for event in reader.read() {
         match event.state {
             ButtonState::Pressed => {
                 last.code = Some(event.key_code);
+                // winit reports the typed text along with the key press
                 last.character = event.text.as_ref().and_then(|t| t.chars().next());
                 last.just_pressed = true;
+                last.just_typed |= last.character.is_some();
             }
             ButtonState::Released => {
                 last.just_released = true;
             }
         }
     }
+    // read after the key presses so the character survives a press in the same frame
+    for event in chars.read() {
+        if !matches!(event.key_code, KeyCode::Unidentified(_)) {
+            last.code = Some(event.key_code);
+        }
+        last.character = Some(event.character);
+        last.just_typed = true;
+    }
 }
 
 pub fn track_last_mouse_button(
     mut reader: MessageReader<MouseButtonInput>,
+    mut motion: MessageReader<MouseMotion>,
     mut last: ResMut<LastMouseButton>,
 ) {
-    if let Some(event) = reader
-        .read()
-        .filter(|e| e.state == ButtonState::Pressed)
-        .last()
-    {
-        last.button = Some(event.button);
+    last.just_clicked = false;
+    // motion and button messages aren't ordered relative to each other, so any motion this frame
+    // counts as happening while the button was still held
+    if motion.read().count() > 0 && last.held {
+        last.dragged = true;
+    }
+    for event in reader.read() {
+        match event.state {
+            ButtonState::Pressed => {
+                last.button = Some(event.button);
+                last.held = true;
+                last.dragged = false;
+            }
+            ButtonState::Released => {
+                last.held = false;
+                last.just_clicked = !last.dragged;
+            }
+        }
+    }
+}
+
+pub fn track_dropped_files(
+    mut reader: MessageReader<FileDragAndDrop>,
+    mut dropped: ResMut<DroppedFiles>,
+) {
+    dropped.paths.clear();
+    for event in reader.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            dropped.paths.push(path_buf.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::keyboard::{Key, NativeKey, NativeKeyCode};
+
+    fn app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_message::<MouseButtonInput>()
+            .add_message::<MouseMotion>()
+            .add_message::<CursorMoved>()
+            .init_resource::<LastMouseButton>()
+            .add_systems(
+                Update,
+                (
+                    snapshot_cursor,
+                    (track_cursor_position, track_last_mouse_button),
+                )
+                    .chain(),
+            );
+        let window = app.world_mut().spawn(CursorPosition::default()).id();
+        (app, window)
+    }
+
+    fn button(app: &mut App, window: Entity, state: ButtonState) {
+        app.world_mut().write_message(MouseButtonInput {
+            button: MouseButton::Left,
+            state,
+            window,
+        });
+    }
+
+    fn move_to(app: &mut App, window: Entity, position: Vec2) {
+        let delta = position - app.world().get::<CursorPosition>(window).unwrap().current();
+        app.world_mut().write_message(CursorMoved {
+            window,
+            position,
+            delta: Some(delta),
+        });
+        app.world_mut().write_message(MouseMotion { delta });
+    }
+
+    #[test]
+    fn char_input_is_typed_not_pressed() {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<CharInput>()
+            .init_resource::<LastKey>()
+            .add_systems(Update, track_last_key);
+        let window = app.world_mut().spawn_empty().id();
+
+        // glfw sends the physical key, then the character with shift/layout applied
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::KeyA,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window,
+        });
+        app.world_mut().write_message(CharInput {
+            window,
+            key_code: KeyCode::Unidentified(NativeKeyCode::Unidentified),
+            character: 'A',
+        });
+        app.update();
+        let last = app.world().resource::<LastKey>();
+        assert!(last.just_pressed);
+        assert!(last.just_typed);
+        assert_eq!(last.code, Some(KeyCode::KeyA));
+        assert_eq!(last.character, Some('A'));
+
+        app.update();
+        let last = app.world().resource::<LastKey>();
+        assert!(!last.just_pressed);
+        assert!(!last.just_typed);
+    }
+
+    #[test]
+    fn held_key_repeats_press() {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<CharInput>()
+            .init_resource::<LastKey>()
+            .add_systems(Update, track_last_key);
+        let window = app.world_mut().spawn_empty().id();
+
+        // glfw reports auto repeat as another press, and key_pressed fires for each of them
+        for repeat in [false, true, true] {
+            app.world_mut().write_message(KeyboardInput {
+                key_code: KeyCode::ArrowLeft,
+                logical_key: Key::Unidentified(NativeKey::Unidentified),
+                state: ButtonState::Pressed,
+                text: None,
+                repeat,
+                window,
+            });
+            app.update();
+            let last = app.world().resource::<LastKey>();
+            assert!(last.just_pressed);
+            assert!(!last.just_released);
+            assert_eq!(last.code, Some(KeyCode::ArrowLeft));
+        }
+    }
+
+    #[test]
+    fn dropped_files_last_one_frame() {
+        let mut app = App::new();
+        app.add_message::<FileDragAndDrop>()
+            .init_resource::<DroppedFiles>()
+            .add_systems(Update, track_dropped_files);
+        let window = app.world_mut().spawn_empty().id();
+
+        app.world_mut().write_message(FileDragAndDrop::HoveredFile {
+            window,
+            path_buf: PathBuf::from("/tmp/hovered.png"),
+        });
+        app.world_mut().write_message(FileDragAndDrop::DroppedFile {
+            window,
+            path_buf: PathBuf::from("/tmp/dropped.png"),
+        });
+        app.update();
+        assert_eq!(
+            app.world().resource::<DroppedFiles>().paths,
+            [PathBuf::from("/tmp/dropped.png")]
+        );
+
+        app.update();
+        assert!(app.world().resource::<DroppedFiles>().paths.is_empty());
+    }
+
+    #[test]
+    fn press_drag_release_is_not_a_click() {
+        let (mut app, window) = app();
+        move_to(&mut app, window, Vec2::new(10.0, 10.0));
+        button(&mut app, window, ButtonState::Pressed);
+        app.update();
+        assert!(!app.world().resource::<LastMouseButton>().just_clicked);
+
+        move_to(&mut app, window, Vec2::new(20.0, 15.0));
+        app.update();
+        let cursor = app.world().get::<CursorPosition>(window).unwrap();
+        assert_eq!(cursor.current(), Vec2::new(20.0, 15.0));
+        assert_eq!(cursor.previous(), Vec2::new(10.0, 10.0));
+
+        button(&mut app, window, ButtonState::Released);
+        app.update();
+        assert!(!app.world().resource::<LastMouseButton>().just_clicked);
+    }
+
+    #[test]
+    fn press_release_in_place_is_a_click() {
+        let (mut app, window) = app();
+        button(&mut app, window, ButtonState::Pressed);
+        app.update();
+        button(&mut app, window, ButtonState::Released);
+        app.update();
+        let last = app.world().resource::<LastMouseButton>();
+        assert!(last.just_clicked);
+        assert_eq!(last.button, Some(MouseButton::Left));
+
+        app.update();
+        assert!(!app.world().resource::<LastMouseButton>().just_clicked);
     }
 }
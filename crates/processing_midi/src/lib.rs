@@ -90,9 +90,7 @@ pub fn send_message(In(msg): In<MidiMessage>, world: &mut World) -> Result<()> {
 pub fn midi_refresh_ports() -> error::Result<()> {
     app_mut(|app| {
         let world = app.world_mut();
-        world
-            .run_system_cached(nannou_midi::native::enumerate_midi_ports)
-            .unwrap();
+        world.run_system_cached(nannou_midi::native::enumerate_midi_ports)?;
         Ok(())
     })
 }
@@ -101,7 +99,7 @@ pub fn midi_refresh_ports() -> error::Result<()> {
 pub fn midi_list_ports() -> error::Result<Vec<String>> {
     app_mut(|app| {
         let world = app.world_mut();
-        world.run_system_cached(list_ports).unwrap()
+        world.run_system_cached(list_ports)?
     })
 }
 
@@ -109,11 +107,9 @@ pub fn midi_list_ports() -> error::Result<Vec<String>> {
 pub fn midi_connect(port: usize) -> error::Result<()> {
     app_mut(|app| {
         let world = app.world_mut();
-        world.run_system_cached_with(connect, port).unwrap()?;
+        world.run_system_cached_with(connect, port)??;
         // Materialize the MidiOutputStream component before the caller sends.
-        world
-            .run_system_cached(nannou_midi::native::open_midi_outputs)
-            .unwrap();
+        world.run_system_cached(nannou_midi::native::open_midi_outputs)?;
         Ok(())
     })
 }
@@ -122,7 +118,7 @@ pub fn midi_connect(port: usize) -> error::Result<()> {
 pub fn midi_disconnect() -> error::Result<()> {
     app_mut(|app| {
         let world = app.world_mut();
-        world.run_system_cached(disconnect).unwrap()
+        world.run_system_cached(disconnect)?
     })
 }
 
@@ -131,11 +127,8 @@ pub fn midi_note_on(note: u8, velocity: u8) -> error::Result<()> {
     app_mut(|app| {
         let world = app.world_mut();
         world
-            .run_system_cached_with(send_message, MidiMessage::from([NOTE_ON, note, velocity]))
-            .unwrap()?;
-        world
-            .run_system_cached(nannou_midi::native::send_midi_messages)
-            .unwrap();
+            .run_system_cached_with(send_message, MidiMessage::from([NOTE_ON, note, velocity]))??;
+        world.run_system_cached(nannou_midi::native::send_midi_messages)?;
         Ok(())
     })
 }
@@ -144,12 +137,8 @@ pub fn midi_note_on(note: u8, velocity: u8) -> error::Result<()> {
 pub fn midi_note_off(note: u8) -> error::Result<()> {
     app_mut(|app| {
         let world = app.world_mut();
-        world
-            .run_system_cached_with(send_message, MidiMessage::from([NOTE_OFF, note, 0]))
-            .unwrap()?;
-        world
-            .run_system_cached(nannou_midi::native::send_midi_messages)
-            .unwrap();
+        world.run_system_cached_with(send_message, MidiMessage::from([NOTE_OFF, note, 0]))??;
+        world.run_system_cached(nannou_midi::native::send_midi_messages)?;
         Ok(())
     })
 }
@@ -158,12 +147,8 @@ pub fn midi_note_off(note: u8) -> error::Result<()> {
 pub fn midi_play_notes(note: u8, duration: u64) -> error::Result<()> {
     app_mut(|app| {
         let world = app.world_mut();
-        world
-            .run_system_cached_with(send_message, MidiMessage::from([NOTE_ON, note, 127]))
-            .unwrap()?;
-        world
-            .run_system_cached(nannou_midi::native::send_midi_messages)
-            .unwrap();
+        world.run_system_cached_with(send_message, MidiMessage::from([NOTE_ON, note, 127]))??;
+        world.run_system_cached(nannou_midi::native::send_midi_messages)?;
         Ok(())
     })?;
 
@@ -171,12 +156,8 @@ pub fn midi_play_notes(note: u8, duration: u64) -> error::Result<()> {
 
     app_mut(|app| {
         let world = app.world_mut();
-        world
-            .run_system_cached_with(send_message, MidiMessage::from([NOTE_OFF, note, 127]))
-            .unwrap()?;
-        world
-            .run_system_cached(nannou_midi::native::send_midi_messages)
-            .unwrap();
+        world.run_system_cached_with(send_message, MidiMessage::from([NOTE_OFF, note, 127]))??;
+        world.run_system_cached(nannou_midi::native::send_midi_messages)?;
         Ok(())
     })
 }
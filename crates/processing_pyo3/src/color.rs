@@ -6,6 +6,7 @@ use pyo3::{exceptions::PyTypeError, prelude::*, types::PyTuple};
 
 use crate::math::{PyVec3, PyVec4, PyVecIter, hash_f32};
 
+use processing::prelude::color;
 pub use processing::prelude::color::{ColorMode, ColorSpace};
 
 fn int_maxes(space: &ColorSpace) -> [f32; 4] {
@@ -20,6 +21,35 @@ fn int_maxes(space: &ColorSpace) -> [f32; 4] {
     }
 }
 
+/// The color mode of the sketch's graphics, or the default mode before there is one, so color
+/// methods read channels in the same ranges as the module-level `red()` or `hue()`.
+fn active_color_mode(py: Python<'_>) -> PyResult<ColorMode> {
+    let module = PyModule::import(py, "mewnala.mewnala")?;
+    match crate::graphics::get_graphics(&module)? {
+        Some(graphics) => graphics.color_mode(),
+        None => Ok(ColorMode::default()),
+    }
+}
+
+/// Whether a lone int is a packed `0xAARRGGBB` color rather than a gray level, like the values
+/// `color()` returns. Gray runs from 0 to the current mode's first channel max, in int units.
+fn is_packed_color(v: i64, mode: &ColorMode) -> bool {
+    let gray_max =
+        (mode.max[0] / mode.space.default_maxes()[0] * int_maxes(&mode.space)[0]).round();
+    v < 0 || v as f32 > gray_max
+}
+
+/// A packed int as a color. Ints that fit in 24 bits are bare `0xRRGGBB` hex literals written
+/// without an alpha byte and read as opaque; anything wider carries its own alpha.
+fn packed_color(v: i64) -> Color {
+    let argb = v as u32;
+    if (0..=0xFFFFFF).contains(&v) {
+        color::from_argb(0xFF000000 | argb)
+    } else {
+        color::from_argb(argb)
+    }
+}
+
 /// Parse a Python int or float into an f32 for a given channel.
 pub(crate) fn parse_numeric(
     space: &ColorSpace,
@@ -65,6 +95,11 @@ pub(crate) fn extract_color_with_mode(
             if let Ok(v) = first.extract::<PyRef<PyVec3>>() {
                 return Ok(space.color(v.0.x, v.0.y, v.0.z, native[3]));
             }
+            if let Ok(v) = first.extract::<i64>()
+                && is_packed_color(v, mode)
+            {
+                return Ok(packed_color(v));
+            }
             let v = convert_channel(mode, &first, 0)?;
             Ok(space.gray(v, native[3]))
         }
@@ -213,6 +248,17 @@ impl PyColor {
         Self(Color::Xyza(Xyza::new(x, y, z, a)))
     }
 
+    /// A color from a packed `0xAARRGGBB` int, as passed to `fill`.
+    #[staticmethod]
+    pub fn from_argb(argb: u32) -> Self {
+        Self(color::from_argb(argb))
+    }
+
+    /// This color packed into a `0xAARRGGBB` int.
+    fn to_argb(&self) -> u32 {
+        color::to_argb(self.0)
+    }
+
     #[staticmethod]
     pub fn hex(s: &str) -> PyResult<Self> {
         parse_hex(s).map(Self)
@@ -332,8 +378,25 @@ impl PyColor {
         Self(self.0.with_luminance(value))
     }
 
-    fn hue(&self) -> f32 {
-        self.0.hue()
+    /// Channels in the ranges set by `color_mode`, like the module-level `red()` and `hue()`.
+    fn red(&self, py: Python<'_>) -> PyResult<f32> {
+        Ok(active_color_mode(py)?.rgba(self.0)[0])
+    }
+
+    fn green(&self, py: Python<'_>) -> PyResult<f32> {
+        Ok(active_color_mode(py)?.rgba(self.0)[1])
+    }
+
+    fn blue(&self, py: Python<'_>) -> PyResult<f32> {
+        Ok(active_color_mode(py)?.rgba(self.0)[2])
+    }
+
+    fn alpha(&self, py: Python<'_>) -> PyResult<f32> {
+        Ok(active_color_mode(py)?.rgba(self.0)[3])
+    }
+
+    fn hue(&self, py: Python<'_>) -> PyResult<f32> {
+        Ok(active_color_mode(py)?.hsba(self.0)[0])
     }
 
     fn with_hue(&self, hue: f32) -> Self {
@@ -344,8 +407,12 @@ impl PyColor {
         Self(self.0.rotate_hue(degrees))
     }
 
-    fn saturation(&self) -> f32 {
-        self.0.saturation()
+    fn saturation(&self, py: Python<'_>) -> PyResult<f32> {
+        Ok(active_color_mode(py)?.hsba(self.0)[1])
+    }
+
+    fn brightness(&self, py: Python<'_>) -> PyResult<f32> {
+        Ok(active_color_mode(py)?.hsba(self.0)[2])
     }
 
     fn with_saturation(&self, saturation: f32) -> Self {
@@ -445,6 +512,7 @@ pub enum ColorLike {
     Vec4(PyVec4),
     Tuple4((f32, f32, f32, f32)),
     Tuple3((f32, f32, f32)),
+    Packed(i64),
 }
 
 impl ColorLike {
@@ -455,6 +523,7 @@ impl ColorLike {
             ColorLike::Vec4(v) => Ok(Color::srgba(v.0.x, v.0.y, v.0.z, v.0.w)),
             ColorLike::Tuple4((r, g, b, a)) => Ok(Color::srgba(r, g, b, a)),
             ColorLike::Tuple3((r, g, b)) => Ok(Color::srgba(r, g, b, 1.0)),
+            ColorLike::Packed(argb) => Ok(packed_color(argb)),
         }
     }
 }
@@ -479,6 +548,31 @@ mod tests {
         assert!((s.alpha - 1.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn packed_color_follows_color_mode_max() {
+        let rgb = ColorMode::default();
+        assert!(!is_packed_color(255, &rgb));
+        assert!(is_packed_color(256, &rgb));
+        assert!(is_packed_color(0xFFFF8800, &rgb));
+        assert!(is_packed_color(-1, &rgb));
+
+        // color_mode(RGB, 1000) stores its max in native units, as set_color_mode parses it
+        let wide = ColorMode::with_uniform_max(ColorSpace::Srgb, 1000.0 / 255.0);
+        assert!(!is_packed_color(500, &wide));
+        assert!(is_packed_color(0xFFFF8800, &wide));
+
+        let narrow = ColorMode::with_uniform_max(ColorSpace::Srgb, 100.0 / 255.0);
+        assert!(!is_packed_color(100, &narrow));
+        assert!(is_packed_color(200, &narrow));
+    }
+
+    #[test]
+    fn bare_rgb_ints_are_opaque() {
+        assert_eq!(color::to_argb(packed_color(0xFF8800)), 0xFFFF8800);
+        assert_eq!(color::to_argb(packed_color(0x80FF8800)), 0x80FF8800);
+        assert_eq!(color::to_argb(packed_color(-1)), 0xFFFFFFFF);
+    }
+
     #[test]
     fn test_hex_roundtrip() {
         let c = parse_hex("#FF00FF").unwrap();
@@ -42,6 +42,11 @@ impl Gltf {
         gltf_camera(self.entity, index).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Frees the loaded scene. Geometry and materials already taken from it keep working.
+    pub fn unload(&self) -> PyResult<()> {
+        gltf_unload(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn light(&self, index: usize) -> PyResult<Light> {
         let entity =
             gltf_light(self.entity, index).map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
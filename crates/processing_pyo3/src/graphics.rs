@@ -3,16 +3,16 @@ use crate::glfw::GlfwContext;
 use crate::input;
 use crate::math::{extract_vec2, extract_vec3, extract_vec4};
 use bevy::{
-    color::{ColorToPacked, Srgba},
-    math::Vec4,
+    color::{Color, LinearRgba},
+    math::{Affine3A, Mat4, Vec3, Vec4},
     prelude::Entity,
     render::render_resource::{Extent3d, TextureFormat},
 };
 use processing::prelude::*;
 use pyo3::{
-    exceptions::PyRuntimeError,
+    exceptions::{PyRuntimeError, PyTypeError},
     prelude::*,
-    types::{PyDict, PyTuple},
+    types::{PyBytes, PyDict, PyTuple},
 };
 
 #[cfg(feature = "cuda")]
@@ -56,16 +56,22 @@ fn cuda_import_from_interface(
 #[derive(Clone)]
 pub struct PyBlendMode {
     pub(crate) blend_state: Option<bevy::render::render_resource::BlendState>,
-    name: Option<&'static str>,
+    preset: Option<BlendMode>,
 }
 
 impl PyBlendMode {
     pub(crate) fn from_preset(mode: BlendMode) -> Self {
         Self {
             blend_state: mode.to_blend_state(),
-            name: Some(mode.name()),
+            preset: Some(mode),
         }
     }
+
+    /// The preset this mode was made from. Pixel compositing only knows the presets.
+    pub(crate) fn preset(&self) -> PyResult<BlendMode> {
+        self.preset
+            .ok_or_else(|| PyRuntimeError::new_err("blend() needs one of the preset blend modes"))
+    }
 }
 
 #[pymethods]
@@ -86,13 +92,13 @@ impl PyBlendMode {
         .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
         Ok(Self {
             blend_state: Some(blend_state),
-            name: None,
+            preset: None,
         })
     }
 
     fn __repr__(&self) -> String {
-        match self.name {
-            Some(name) => format!("BlendMode.{name}"),
+        match self.preset {
+            Some(mode) => format!("BlendMode.{}", mode.name()),
             None => "BlendMode(custom)".to_string(),
         }
     }
@@ -213,14 +219,18 @@ impl Light {
         let v = extract_vec3(args)?;
         transform_look_at(self.entity, v).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
-}
 
-// TODO: implement `light_destroy`
-// impl Drop for Light {
-//     fn drop(&mut self) {
-//         let _ = light_destroy(self.entity);
-//     }
-// }
+    /// Switches the light off or back on, keeping its settings.
+    pub fn set_enabled(&self, enabled: bool) -> PyResult<()> {
+        light_set_enabled(self.entity, enabled).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Removes the light from the scene. Lights aren't removed when the `Light` object is
+    /// dropped, since sketches often create them in `setup` without keeping a reference.
+    pub fn destroy(&self) -> PyResult<()> {
+        light_destroy(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+}
 
 #[pyclass]
 #[derive(Debug)]
@@ -291,6 +301,59 @@ fn path_commands_to_py(
         .collect()
 }
 
+/// Extract one color argument, which may itself be a tuple of components in the color mode.
+fn extract_color_arg(obj: &Bound<'_, PyAny>, mode: &ColorMode) -> PyResult<Color> {
+    match obj.cast::<PyTuple>() {
+        Ok(components) => extract_color_with_mode(components, mode),
+        Err(_) => extract_color_with_mode(&PyTuple::new(obj.py(), [obj])?, mode),
+    }
+}
+
+/// Run a Python callback over `pixels`, either once per pixel as `callback(x, y, color)` or, when
+/// `batch` is set, once with the list of all colors. The returned colors replace the pixels.
+fn map_pixels_with_callback(
+    callback: &Bound<'_, PyAny>,
+    batch: bool,
+    pixels: &mut [LinearRgba],
+    width: u32,
+) -> PyResult<()> {
+    let to_py = |pixel: LinearRgba| crate::color::PyColor::from(Color::from(pixel));
+    let from_py = |ob: Bound<'_, PyAny>| -> PyResult<LinearRgba> {
+        Ok(ob
+            .extract::<crate::color::ColorLike>()?
+            .into_color()?
+            .to_linear())
+    };
+
+    let mapped: Vec<LinearRgba> = if batch {
+        let colors: Vec<_> = pixels.iter().map(|p| to_py(*p)).collect();
+        callback
+            .call1((colors,))?
+            .try_iter()?
+            .map(|ob| from_py(ob?))
+            .collect::<PyResult<_>>()?
+    } else {
+        pixels
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let (x, y) = (i as u32 % width, i as u32 / width);
+                from_py(callback.call1((x, y, to_py(*p)))?)
+            })
+            .collect::<PyResult<_>>()?
+    };
+
+    if mapped.len() != pixels.len() {
+        return Err(PyRuntimeError::new_err(format!(
+            "map_pixels callback returned {} colors for {} pixels",
+            mapped.len(),
+            pixels.len()
+        )));
+    }
+    pixels.copy_from_slice(&mapped);
+    Ok(())
+}
+
 #[pyclass]
 #[derive(Debug)]
 pub struct Image {
@@ -308,6 +371,14 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ImageRef {
         if let Ok(img) = ob.extract::<PyRef<Image>>() {
             return Ok(ImageRef { entity: img.entity });
         }
+        // an offscreen graphics draws into an image, so it can be drawn like one
+        if let Ok(graphics) = ob.extract::<PyRef<Graphics>>()
+            && graphics.surface.glfw_ctx.is_none()
+        {
+            return Ok(ImageRef {
+                entity: graphics.surface.entity,
+            });
+        }
         #[cfg(feature = "webcam")]
         if let Ok(cam) = ob.extract::<PyRef<crate::webcam::Webcam>>() {
             return Ok(ImageRef {
@@ -315,11 +386,32 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ImageRef {
             });
         }
         Err(pyo3::exceptions::PyTypeError::new_err(
-            "expected an Image or Webcam",
+            "expected an Image, Webcam or offscreen Graphics",
         ))
     }
 }
 
+/// Where `copy()` and `blend()` read pixels from: anything [`ImageRef`] accepts, or any graphics,
+/// including on-screen ones, read back from its surface.
+pub(crate) struct PixelSource {
+    pub entity: Entity,
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for PixelSource {
+    type Error = PyErr;
+
+    fn extract(ob: pyo3::Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(graphics) = ob.extract::<PyRef<Graphics>>() {
+            return Ok(PixelSource {
+                entity: graphics.entity,
+            });
+        }
+        ob.extract::<ImageRef>().map(|image| PixelSource {
+            entity: image.entity,
+        })
+    }
+}
+
 #[pymethods]
 impl Image {
     /// Applies a `Sampler` to this image, controlling filtering and wrapping.
@@ -332,6 +424,132 @@ impl Image {
         image_set_sampler(self.entity, sampler.filter, sampler.wrap_x, sampler.wrap_y)
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
+
+    /// Replaces every pixel with the color returned by `callback(x, y, color)`, computed on the
+    /// CPU. Calling into Python per pixel is slow, so with `batch=True` the callback is instead
+    /// called once with the list of all colors and returns the new list.
+    ///
+    /// ```python
+    /// img.map_pixels(lambda x, y, c: (1 - c.r, 1 - c.g, 1 - c.b, c.a))
+    /// ```
+    #[pyo3(signature = (callback, batch=false))]
+    fn map_pixels(&self, callback: &Bound<'_, PyAny>, batch: bool) -> PyResult<()> {
+        image_map_batch(self.entity, |pixels, width| {
+            map_pixels_with_callback(callback, batch, pixels, width)
+        })
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?
+    }
+
+    /// The color of the pixel at `x`, `y`. The first call reads the image back from the GPU and
+    /// later ones use that copy.
+    fn get(&self, x: u32, y: u32) -> PyResult<crate::color::PyColor> {
+        image_get_pixel(self.entity, x, y)
+            .map(|c| crate::color::PyColor::from(Color::from(c)))
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Sets the pixel at `x`, `y`. Pixels set between draws are uploaded together.
+    fn set(&self, x: u32, y: u32, color: crate::color::ColorLike) -> PyResult<()> {
+        let color = color.into_color()?.to_linear();
+        image_set_pixel(self.entity, x, y, color)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Applies a filter to every pixel: `GRAY`, `INVERT`, `THRESHOLD` (level from 0 to 1, default
+    /// 0.5), `BLUR` (radius in pixels, default 1) or `POSTERIZE` (number of levels, required).
+    ///
+    /// ```python
+    /// img.filter(BLUR, 4)
+    /// ```
+    #[pyo3(signature = (kind, param=None))]
+    fn filter(&self, kind: u8, param: Option<f32>) -> PyResult<()> {
+        let param = match (kind, param) {
+            (_, Some(param)) => param,
+            (2, None) => 0.5,
+            (3, None) => 1.0,
+            (4, None) => {
+                return Err(PyRuntimeError::new_err(
+                    "POSTERIZE needs the number of levels",
+                ));
+            }
+            _ => 0.0,
+        };
+        let filter = processing::prelude::image::Filter::from_parts(kind, param)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        image_filter(self.entity, filter).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Per-channel histograms as four lists, red, green, blue then alpha, each counting pixels
+    /// into `bins` equal steps from 0 to 1.
+    #[pyo3(signature = (bins=256))]
+    fn histogram(&self, bins: usize) -> PyResult<Vec<Vec<u32>>> {
+        image_histogram(self.entity, bins)
+            .map(Vec::from)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Copies the `sw` x `sh` region at `sx`, `sy` of `source` into the `dw` x `dh` region at `dx`,
+    /// `dy` of this image, scaling it to fit. `source` may be this image, or a graphics to grab
+    /// part of a frame. Regions reaching past either edge are clipped.
+    #[allow(clippy::too_many_arguments)]
+    fn copy(
+        &self,
+        source: PixelSource,
+        sx: u32,
+        sy: u32,
+        sw: u32,
+        sh: u32,
+        dx: u32,
+        dy: u32,
+        dw: u32,
+        dh: u32,
+    ) -> PyResult<()> {
+        image_copy(source.entity, sx, sy, sw, sh, self.entity, dx, dy, dw, dh)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Like `copy()`, blending the region into this image with one of the preset blend modes.
+    ///
+    /// ```python
+    /// img.blend(overlay, 0, 0, 64, 64, 0, 0, 128, 128, MULTIPLY)
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    fn blend(
+        &self,
+        source: PixelSource,
+        sx: u32,
+        sy: u32,
+        sw: u32,
+        sh: u32,
+        dx: u32,
+        dy: u32,
+        dw: u32,
+        dh: u32,
+        mode: &PyBlendMode,
+    ) -> PyResult<()> {
+        image_blend(
+            source.entity,
+            sx,
+            sy,
+            sw,
+            sh,
+            self.entity,
+            dx,
+            dy,
+            dw,
+            dh,
+            mode.preset()?,
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Saves the image in the format named by `path`'s extension: `.png`, `.jpg`, `.tga` or
+    /// `.bmp`. `quality`, from 1 to 100, only applies to JPEG.
+    #[pyo3(signature = (path, quality=90))]
+    fn save(&self, path: &str, quality: u8) -> PyResult<()> {
+        image_save_with_quality(self.entity, path, quality)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
 }
 
 impl Drop for Image {
@@ -396,8 +614,19 @@ impl Geometry {
             .and_then(|t| geometry::Topology::from_u8(t.borrow().as_u8()))
             .unwrap_or(geometry::Topology::TriangleList);
 
+        // `dynamic=True` for geometry whose vertices are rewritten every frame
+        let dynamic = kwargs
+            .and_then(|k| k.get_item("dynamic").ok().flatten())
+            .map(|d| d.extract::<bool>())
+            .transpose()?
+            .unwrap_or(false);
+
         let geometry =
             geometry_create(topology).map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        if dynamic {
+            geometry_set_dynamic(geometry, true)
+                .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        }
         Ok(Self { entity: geometry })
     }
 
@@ -435,10 +664,204 @@ impl Geometry {
         geometry_set_vertex(self.entity, i, v).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Remove every vertex and index so the geometry can be refilled, keeping its layout.
+    pub fn clear(&self) -> PyResult<()> {
+        geometry_clear(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Replace every position at once with a list of `(x, y, z)` tuples. If the vertex count
+    /// changes, the other attributes are refilled with the current normal, color and uv. To
+    /// shrink an indexed geometry, call `set_indices` first so no index names a dropped vertex.
+    pub fn set_positions(&self, positions: Vec<[f32; 3]>) -> PyResult<()> {
+        geometry_set_positions(self.entity, &positions)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Replace every normal at once with a list of `(x, y, z)` tuples, one per vertex.
+    pub fn set_normals(&self, normals: Vec<[f32; 3]>) -> PyResult<()> {
+        geometry_set_normals(self.entity, &normals)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Replace every color at once with a list of `(r, g, b, a)` tuples, one per vertex.
+    pub fn set_colors(&self, colors: Vec<[f32; 4]>) -> PyResult<()> {
+        geometry_set_colors(self.entity, &colors)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Replace every uv at once with a list of `(u, v)` tuples, one per vertex.
+    pub fn set_uvs(&self, uvs: Vec<[f32; 2]>) -> PyResult<()> {
+        geometry_set_uvs(self.entity, &uvs).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn set_indices(&self, indices: Vec<u32>) -> PyResult<()> {
+        geometry_set_indices(self.entity, &indices)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn vertex_count(&self) -> PyResult<u32> {
         geometry_vertex_count(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Rewind triangles so they're all counter-clockwise relative to their normals. Returns the
+    /// number of triangles flipped.
+    pub fn fix_winding(&self) -> PyResult<u32> {
+        geometry_fix_winding(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Replace the normals with smooth ones averaged from the triangles around each vertex, for
+    /// meshes built with `vertex` that were never given normals.
+    pub fn compute_normals(&self) -> PyResult<()> {
+        geometry_compute_normals(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Bakes a 4x4 matrix, given as 16 floats in column-major order, into the vertices, moving
+    /// every position and turning every normal to match.
+    pub fn apply_transform(&self, matrix: [f32; 16]) -> PyResult<()> {
+        let transform = Affine3A::from_mat4(Mat4::from_cols_array(&matrix));
+        geometry_apply_transform(self.entity, transform)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Shares identical vertices between triangles through an index buffer.
+    pub fn to_indexed(&self) -> PyResult<()> {
+        geometry_to_indexed(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Gives every triangle corner its own vertex, e.g. before flat shading.
+    pub fn to_nonindexed(&self) -> PyResult<()> {
+        geometry_to_nonindexed(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Laplacian smoothing: move each vertex `factor` of the way toward the average of its
+    /// neighbors, `iterations` times. Call `compute_normals` afterwards to match the normals.
+    #[pyo3(signature = (iterations=1, factor=0.5))]
+    pub fn smooth(&self, iterations: u32, factor: f32) -> PyResult<()> {
+        geometry_smooth(self.entity, iterations, factor)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Drop an attribute and its per-vertex data from this geometry, e.g.
+    /// `geometry.remove_attribute(Attribute.uv())`.
+    pub fn remove_attribute(&self, attribute: &crate::particles::Attribute) -> PyResult<()> {
+        geometry_remove_attribute(self.entity, attribute.entity)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Check this geometry for degenerate triangles, unreferenced vertices and non-manifold edges.
+    /// Returns a dict with those lists plus `is_manifold` and `is_valid` flags.
+    pub fn validate<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let report =
+            geometry_validate(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        let dict = PyDict::new(py);
+        dict.set_item("is_manifold", report.is_manifold())?;
+        dict.set_item("is_valid", report.is_valid())?;
+        dict.set_item("degenerate_triangles", report.degenerate_triangles)?;
+        dict.set_item("unreferenced_vertices", report.unreferenced_vertices)?;
+        dict.set_item("non_manifold_edges", report.non_manifold_edges)?;
+        Ok(dict)
+    }
+
+    /// The sphere enclosing this geometry as `((x, y, z), radius)`, for manual culling or framing
+    /// the camera.
+    pub fn bounding_sphere(&self) -> PyResult<((f32, f32, f32), f32)> {
+        let (center, radius) = geometry_bounding_sphere(self.entity)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(((center.x, center.y, center.z), radius))
+    }
+
+    /// The box around this geometry as `((min_x, min_y, min_z), (max_x, max_y, max_z))`.
+    pub fn bounds(&self) -> PyResult<((f32, f32, f32), (f32, f32, f32))> {
+        let (min, max) =
+            geometry_bounds(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok((min.into(), max.into()))
+    }
+
+    /// The center of this geometry's bounding box as `(x, y, z)`.
+    pub fn center(&self) -> PyResult<(f32, f32, f32)> {
+        geometry_center(self.entity)
+            .map(Into::into)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Start a 2D path, built with `path_vertex`, `bezier_vertex`, `quadratic_vertex`,
+    /// `curve_vertex` and `arc`, that's tessellated into this geometry by `end_path`.
+    pub fn begin_path(&self) -> PyResult<()> {
+        geometry_begin_path(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn path_vertex(&self, x: f32, y: f32) -> PyResult<()> {
+        geometry_path_vertex(self.entity, x, y).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn bezier_vertex(
+        &self,
+        cx1: f32,
+        cy1: f32,
+        cx2: f32,
+        cy2: f32,
+        x: f32,
+        y: f32,
+    ) -> PyResult<()> {
+        geometry_path_bezier_vertex(self.entity, cx1, cy1, cx2, cy2, x, y)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn quadratic_vertex(&self, cx: f32, cy: f32, x: f32, y: f32) -> PyResult<()> {
+        geometry_path_quadratic_vertex(self.entity, cx, cy, x, y)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn curve_vertex(&self, x: f32, y: f32) -> PyResult<()> {
+        geometry_path_curve_vertex(self.entity, x, y)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Add an elliptical arc around `(cx, cy)` with radii `rx` and `ry`, from angle `start` to
+    /// `stop` in radians, to the path.
+    pub fn arc(&self, cx: f32, cy: f32, rx: f32, ry: f32, start: f32, stop: f32) -> PyResult<()> {
+        geometry_path_arc(self.entity, cx, cy, rx, ry, start, stop)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn begin_contour(&self) -> PyResult<()> {
+        geometry_path_begin_contour(self.entity)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn end_contour(&self) -> PyResult<()> {
+        geometry_path_end_contour(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    #[pyo3(signature = (close=false))]
+    pub fn end_path(&self, close: bool) -> PyResult<()> {
+        geometry_end_path(self.entity, close).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Save this geometry to a glTF binary (`.glb`) file, with `material` if given. Both are named
+    /// after the file, so `cube.glb` loads back as `gltf.geometry("cube")`.
+    #[pyo3(signature = (path, material=None))]
+    pub fn save_gltf(
+        &self,
+        path: &str,
+        material: Option<PyRef<'_, crate::material::Material>>,
+    ) -> PyResult<()> {
+        geometry_save_gltf(self.entity, path, material.map(|m| m.entity))
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Draw lower detail geometries in place of this one when it's far from the camera, as a
+    /// list of `(distance, geometry)` levels. An empty list clears it.
+    pub fn set_lod(&self, levels: Vec<(f32, PyRef<'_, Geometry>)>) -> PyResult<()> {
+        let levels = levels.iter().map(|(d, g)| (*d, g.entity)).collect();
+        geometry_set_lod(self.entity, levels).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Draw this line geometry's lines `width` units wide instead of 1px. 0 restores thin lines.
+    pub fn line_width(&self, width: f32) -> PyResult<()> {
+        geometry_line_width(self.entity, width).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     #[staticmethod]
     #[pyo3(signature = (radius, sectors=32, stacks=18))]
     pub fn sphere(radius: f32, sectors: u32, stacks: u32) -> PyResult<Self> {
@@ -481,6 +904,37 @@ impl Drop for Graphics {
     }
 }
 
+impl Graphics {
+    /// A graphics drawing to an offscreen image in the already initialized app.
+    pub(crate) fn offscreen(width: u32, height: u32) -> PyResult<Self> {
+        // todo: allow caller to specify texture format? we use an sRGB format by default since
+        // it plays well with converting to PNG
+        let texture_format = TextureFormat::Rgba8UnormSrgb;
+
+        let surface_entity = surface_create_offscreen(width, height, 1.0, texture_format)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+
+        let surface = Surface {
+            entity: surface_entity,
+            glfw_ctx: None,
+        };
+
+        let graphics = graphics_create(surface.entity, width, height, texture_format)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+
+        Ok(Self {
+            entity: graphics,
+            surface,
+            width,
+            height,
+        })
+    }
+
+    pub(crate) fn color_mode(&self) -> PyResult<ColorMode> {
+        graphics_get_color_mode(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+}
+
 #[pymethods]
 impl Graphics {
     #[new]
@@ -537,28 +991,7 @@ impl Graphics {
             config.set(ConfigKey::LogLevel, level.to_string());
         }
         init(config).map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
-
-        // todo: allow caller to specify texture format? we use an sRGB format by default since
-        // it plays well with converting to PNG
-        let texture_format = TextureFormat::Rgba8UnormSrgb;
-
-        let surface_entity = surface_create_offscreen(width, height, 1.0, texture_format)
-            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
-
-        let surface = Surface {
-            entity: surface_entity,
-            glfw_ctx: None,
-        };
-
-        let graphics = graphics_create(surface.entity, width, height, texture_format)
-            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
-
-        Ok(Self {
-            entity: graphics,
-            surface,
-            width,
-            height,
-        })
+        Self::offscreen(width, height)
     }
 
     #[getter]
@@ -571,6 +1004,10 @@ impl Graphics {
         self.surface.pixel_density()
     }
 
+    pub fn copy_to_clipboard(&self) -> PyResult<()> {
+        self.surface.copy_to_clipboard()
+    }
+
     #[getter]
     pub fn pixel_width(&self) -> PyResult<u32> {
         self.surface.pixel_width()
@@ -581,39 +1018,80 @@ impl Graphics {
         self.surface.pixel_height()
     }
 
+    /// Replaces every pixel of the surface with the color returned by `callback(x, y, color)`,
+    /// computed on the CPU. With `batch=True` the callback gets the list of all colors at once.
+    #[pyo3(signature = (callback, batch=false))]
+    pub fn map_pixels(&self, callback: &Bound<'_, PyAny>, batch: bool) -> PyResult<()> {
+        graphics_map_batch(self.entity, |pixels, width| {
+            map_pixels_with_callback(callback, batch, pixels, width)
+        })
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?
+    }
+
+    /// Shows the frame rate and frame count in the top left corner, drawn over everything else.
+    #[pyo3(signature = (show=true))]
+    pub fn show_fps(&self, show: bool) -> PyResult<()> {
+        graphics_show_fps(self.entity, show).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Seconds spent in each stage of the last frame, keyed by `command_flush`, `tessellation`,
+    /// `gpu_submit` and `readback`.
+    pub fn frame_timings<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let timings = graphics_frame_timings(self.entity)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        let dict = PyDict::new(py);
+        dict.set_item("command_flush", timings.command_flush.as_secs_f64())?;
+        dict.set_item("tessellation", timings.tessellation.as_secs_f64())?;
+        dict.set_item("gpu_submit", timings.gpu_submit.as_secs_f64())?;
+        dict.set_item("readback", timings.readback.as_secs_f64())?;
+        Ok(dict)
+    }
+
     pub fn readback_png(&self) -> PyResult<Vec<u8>> {
-        let raw = graphics_readback_raw(self.entity)
+        graphics_readback_raw(self.entity)
+            .and_then(|raw| raw.to_png())
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// The surface's pixels as `bytes` in its own texture format, e.g. 4 bytes per pixel for
+    /// 8-bit RGBA, without converting to colors.
+    pub fn load_pixels_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let bytes = graphics_load_pixels_bytes(self.entity)
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(PyBytes::new(py, &bytes))
+    }
 
-        // png-ify our raw data, for srgb formats we're already good
-        let rgba_bytes = match raw.format {
-            TextureFormat::Rgba8UnormSrgb => raw.bytes,
-            _ => {
-                let pixels = graphics_readback(self.entity)
-                    .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
-                pixels
-                    .iter()
-                    .flat_map(|pixel| Srgba::from(*pixel).to_u8_array())
-                    .collect()
-            }
-        };
+    /// Overwrites the surface with bytes laid out as `load_pixels_bytes` returns them.
+    pub fn update_pixels_bytes(&self, data: &[u8]) -> PyResult<()> {
+        graphics_update_pixels_bytes(self.entity, data)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
 
-        let mut png_buf: Vec<u8> = Vec::new();
-        {
-            let mut encoder = png::Encoder::new(&mut png_buf, raw.width, raw.height);
-            // todo: infer these from the texture format instead of hardcoding
-            encoder.set_color(png::ColorType::Rgba);
-            encoder.set_depth(png::BitDepth::Eight);
-            encoder.set_source_srgb(png::SrgbRenderingIntent::Perceptual);
-            let mut writer = encoder
-                .write_header()
-                .map_err(|e| PyRuntimeError::new_err(format!("PNG header: {e}")))?;
-            writer
-                .write_image_data(&rgba_bytes)
-                .map_err(|e| PyRuntimeError::new_err(format!("PNG write: {e}")))?;
-        }
+    /// Per-channel histograms of the surface as four lists, red, green, blue then alpha, each
+    /// counting pixels into `bins` equal steps from 0 to 1.
+    #[pyo3(signature = (bins=256))]
+    pub fn histogram(&self, bins: usize) -> PyResult<Vec<Vec<u32>>> {
+        image_histogram(self.entity, bins)
+            .map(Vec::from)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// The depth of every pixel of the current frame as an `array.array('f')`, row by row. Nearer
+    /// surfaces have larger values, and pixels where nothing was drawn are 0.
+    pub fn readback_depth<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let depth = graphics_readback_depth(self.entity)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        crate::math::float_array(py, &depth)
+    }
 
-        Ok(png_buf)
+    /// Saves the current frame in the format named by `path`'s extension: `.png`, `.jpg`, `.tga`
+    /// or `.bmp`. A run of `#`s in `path` becomes the zero-padded frame count, so
+    /// `save("frame-####.png")` writes a numbered sequence. `quality`, from 1 to 100, only
+    /// applies to JPEG. Values outside 0 to 1 on HDR surfaces are clipped.
+    #[pyo3(signature = (path, quality=90))]
+    pub fn save(&self, path: &str, quality: u8) -> PyResult<()> {
+        graphics_save_with_quality(self.entity, path, quality)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
     pub fn poll_for_sketch_update(&self) -> PyResult<Sketch> {
@@ -637,6 +1115,50 @@ impl Graphics {
         .map(crate::color::PyColor::from)
     }
 
+    /// Blend from `a` to `b` by `t` in the current color mode, going around the hue circle the
+    /// short way in HSB.
+    pub fn lerp_color(
+        &self,
+        a: crate::color::ColorLike,
+        b: crate::color::ColorLike,
+        t: f32,
+    ) -> PyResult<crate::color::PyColor> {
+        graphics_lerp_color(self.entity, a.into_color()?, b.into_color()?, t)
+            .map(crate::color::PyColor::from)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Channels of `color` in the ranges set by `color_mode`, like Processing's `red()`. Red,
+    /// green and blue are 0 to 1 unless the mode is RGB, and hue, saturation and brightness use
+    /// HSB's own ranges unless the mode is HSB.
+    pub fn red(&self, color: crate::color::ColorLike) -> PyResult<f32> {
+        Ok(self.color_mode()?.rgba(color.into_color()?)[0])
+    }
+
+    pub fn green(&self, color: crate::color::ColorLike) -> PyResult<f32> {
+        Ok(self.color_mode()?.rgba(color.into_color()?)[1])
+    }
+
+    pub fn blue(&self, color: crate::color::ColorLike) -> PyResult<f32> {
+        Ok(self.color_mode()?.rgba(color.into_color()?)[2])
+    }
+
+    pub fn alpha(&self, color: crate::color::ColorLike) -> PyResult<f32> {
+        Ok(self.color_mode()?.rgba(color.into_color()?)[3])
+    }
+
+    pub fn hue(&self, color: crate::color::ColorLike) -> PyResult<f32> {
+        Ok(self.color_mode()?.hsba(color.into_color()?)[0])
+    }
+
+    pub fn saturation(&self, color: crate::color::ColorLike) -> PyResult<f32> {
+        Ok(self.color_mode()?.hsba(color.into_color()?)[1])
+    }
+
+    pub fn brightness(&self, color: crate::color::ColorLike) -> PyResult<f32> {
+        Ok(self.color_mode()?.hsba(color.into_color()?)[2])
+    }
+
     #[pyo3(signature = (*args))]
     pub fn background(&self, args: &Bound<'_, PyTuple>) -> PyResult<()> {
         let color = extract_color_with_mode(
@@ -648,11 +1170,39 @@ impl Graphics {
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Fills the surface with a gradient from `c1` to `c2`, running top to bottom (`VERTICAL`),
+    /// left to right (`HORIZONTAL`) or top left to bottom right (`DIAGONAL`).
+    #[pyo3(signature = (c1, c2, direction=0))]
+    pub fn background_gradient(
+        &self,
+        c1: &Bound<'_, PyAny>,
+        c2: &Bound<'_, PyAny>,
+        direction: u8,
+    ) -> PyResult<()> {
+        let mode = graphics_get_color_mode(self.entity)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        graphics_record_command(
+            self.entity,
+            DrawCommand::BackgroundGradient {
+                from: extract_color_arg(c1, &mode)?,
+                to: extract_color_arg(c2, &mode)?,
+                direction: processing::prelude::GradientDirection::from(direction),
+            },
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn background_image(&self, image: &Image) -> PyResult<()> {
         graphics_record_command(self.entity, DrawCommand::BackgroundImage(image.entity))
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Covers the surface with copies of `image`, each `tile_w` x `tile_h` pixels.
+    pub fn background_tiled(&self, image: &Image, tile_w: f32, tile_h: f32) -> PyResult<()> {
+        graphics_background_tiled(self.entity, image.entity, tile_w, tile_h)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     #[pyo3(signature = (*args))]
     pub fn fill(&self, args: &Bound<'_, PyTuple>) -> PyResult<()> {
         if args.len() == 1
@@ -704,6 +1254,16 @@ impl Graphics {
         .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// How `shape` reads its `x`, `y`, `w`, `h`, with the same modes as `rect_mode`. `CORNER` by
+    /// default.
+    pub fn shape_mode(&self, mode: u8) -> PyResult<()> {
+        graphics_record_command(
+            self.entity,
+            DrawCommand::ShapeMode(processing::prelude::ShapeMode::from(mode)),
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn ellipse_mode(&self, mode: u8) -> PyResult<()> {
         graphics_record_command(
             self.entity,
@@ -728,6 +1288,14 @@ impl Graphics {
         .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    pub fn stroke_align(&self, align: u8) -> PyResult<()> {
+        graphics_record_command(
+            self.entity,
+            DrawCommand::StrokeAlign(processing::prelude::StrokeAlignMode::from(align)),
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn rect(
         &self,
         x: f32,
@@ -762,8 +1330,22 @@ impl Graphics {
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
-    pub fn line(&self, x1: f32, y1: f32, x2: f32, y2: f32) -> PyResult<()> {
-        graphics_record_command(self.entity, DrawCommand::Line { x1, y1, x2, y2 })
+    /// `line(x1, y1, x2, y2)` in 2d, or `line(x1, y1, z1, x2, y2, z2)` in 3d.
+    #[pyo3(signature = (*args))]
+    pub fn line(&self, args: &Bound<'_, PyTuple>) -> PyResult<()> {
+        let cmd = if let Ok((x1, y1, x2, y2)) = args.extract() {
+            DrawCommand::Line { x1, y1, x2, y2 }
+        } else if let Ok((x1, y1, z1, x2, y2, z2)) = args.extract() {
+            DrawCommand::Line3d {
+                start: Vec3::new(x1, y1, z1),
+                end: Vec3::new(x2, y2, z2),
+            }
+        } else {
+            return Err(PyTypeError::new_err(
+                "line takes 4 coordinates in 2d or 6 in 3d",
+            ));
+        };
+        graphics_record_command(self.entity, cmd)
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
@@ -809,8 +1391,21 @@ impl Graphics {
         .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
-    pub fn point(&self, x: f32, y: f32) -> PyResult<()> {
-        graphics_record_command(self.entity, DrawCommand::Point { x, y })
+    /// `point(x, y)` in 2d, or `point(x, y, z)` in 3d.
+    #[pyo3(signature = (x, y, z=None))]
+    pub fn point(&self, x: f32, y: f32, z: Option<f32>) -> PyResult<()> {
+        let cmd = match z {
+            Some(z) => DrawCommand::Point3d(Vec3::new(x, y, z)),
+            None => DrawCommand::Point { x, y },
+        };
+        graphics_record_command(self.entity, cmd)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Draw a list of `(x, y)` points in one go.
+    pub fn points(&self, points: Vec<(f32, f32)>) -> PyResult<()> {
+        let points = points.into_iter().map(|(x, y)| [x, y]).collect();
+        graphics_record_command(self.entity, DrawCommand::Points(points))
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
@@ -924,6 +1519,19 @@ impl Graphics {
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Starts capturing the 3d shapes drawn until `end_geometry()` instead of drawing them.
+    pub fn begin_geometry(&self) -> PyResult<()> {
+        geometry_begin(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Bakes the shapes drawn since `begin_geometry()` into one `Geometry`, at the transforms
+    /// they were drawn with.
+    pub fn end_geometry(&self) -> PyResult<Geometry> {
+        let entity =
+            geometry_end(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Geometry { entity })
+    }
+
     pub fn vertex(&self, x: f32, y: f32) -> PyResult<()> {
         graphics_record_command(self.entity, DrawCommand::ShapeVertex { x, y })
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
@@ -1173,17 +1781,18 @@ impl Graphics {
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Align text horizontally with `LEFT`, `CENTER` or `RIGHT` and vertically with `BASELINE`,
+    /// `TOP`, `CENTER` or `BOTTOM`, so `text_align(CENTER, CENTER)` centers on both axes.
     #[pyo3(signature = (h, v=None))]
     pub fn text_align(&self, h: u8, v: Option<u8>) -> PyResult<()> {
-        use processing::prelude::{TextAlignH, TextAlignV};
-        graphics_record_command(
-            self.entity,
-            DrawCommand::TextAlign {
-                h: TextAlignH::from(h),
-                v: TextAlignV::from(v.unwrap_or(0)),
-            },
-        )
-        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+        // the module's CENTER is the horizontal one, and TOP and BOTTOM are numbered around it
+        let v = match v {
+            Some(TEXT_ALIGN_CENTER) => TEXT_ALIGN_MIDDLE,
+            Some(101) => TEXT_ALIGN_TOP,
+            Some(102) => TEXT_ALIGN_BOTTOM,
+            _ => TEXT_ALIGN_BASELINE,
+        };
+        graphics_text_align(self.entity, h, v).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
     pub fn text_leading(&self, leading: f32) -> PyResult<()> {
@@ -1197,6 +1806,10 @@ impl Graphics {
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    pub fn text_mode(&self, mode: u8) -> PyResult<()> {
+        graphics_text_mode(self.entity, mode).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     /// Number of lines `content` wraps to.
     pub fn text_line_count(&self, content: &str) -> PyResult<usize> {
         graphics_text_line_count(self.entity, content)
@@ -1258,9 +1871,11 @@ impl Graphics {
 
     /// Loads an image from a file and returns an Image object.
     ///
-    /// The path is relative to the sketch's assets directory.
-    pub fn load_image(&self, file: &str) -> PyResult<Image> {
-        match image_load(file) {
+    /// The path is relative to the sketch's assets directory. Photos with an EXIF orientation
+    /// tag are turned upright unless `auto_orient` is false.
+    #[pyo3(signature = (file, auto_orient=true))]
+    pub fn load_image(&self, file: &str, auto_orient: bool) -> PyResult<Image> {
+        match image_load_with_orientation(file, auto_orient) {
             Ok(image) => Ok(Image { entity: image }),
             Err(e) => Err(PyRuntimeError::new_err(format!("{e}"))),
         }
@@ -1305,6 +1920,75 @@ impl Graphics {
         .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Copies the `sw` x `sh` region at `sx`, `sy` of `source` into the `dw` x `dh` rectangle at
+    /// `dx`, `dy`, scaling it to fit.
+    ///
+    /// Unlike `image()` this ignores `image_mode()` and `tint()`, but is still affected by the
+    /// current transform.
+    #[allow(clippy::too_many_arguments)]
+    pub fn copy(
+        &self,
+        source: ImageRef,
+        sx: f32,
+        sy: f32,
+        sw: f32,
+        sh: f32,
+        dx: f32,
+        dy: f32,
+        dw: f32,
+        dh: f32,
+    ) -> PyResult<()> {
+        graphics_record_command(
+            self.entity,
+            DrawCommand::DrawImageRegion {
+                image: source.entity,
+                sx,
+                sy,
+                sw,
+                sh,
+                dx,
+                dy,
+                dw,
+                dh,
+            },
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Blends the `sw` x `sh` region at `sx`, `sy` of `source` into the `dw` x `dh` rectangle at
+    /// `dx`, `dy` of the surface's pixels with one of the preset blend modes.
+    ///
+    /// Unlike `copy()` this works on pixels directly, so the transform doesn't apply.
+    #[allow(clippy::too_many_arguments)]
+    pub fn blend(
+        &self,
+        source: PixelSource,
+        sx: u32,
+        sy: u32,
+        sw: u32,
+        sh: u32,
+        dx: u32,
+        dy: u32,
+        dw: u32,
+        dh: u32,
+        mode: &PyBlendMode,
+    ) -> PyResult<()> {
+        image_blend(
+            source.entity,
+            sx,
+            sy,
+            sw,
+            sh,
+            self.entity,
+            dx,
+            dy,
+            dw,
+            dh,
+            mode.preset()?,
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     /// Sets a tint color applied when drawing images.
     ///
     /// Accepts the same color arguments as `fill()`. The tint is multiplied
@@ -1339,6 +2023,35 @@ impl Graphics {
         .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Reads back part of the surface. With `w` and `h`, returns that region as a new `Image`,
+    /// otherwise returns the `Color` of the pixel at `x`, `y`.
+    #[pyo3(signature = (x, y, w=None, h=None))]
+    pub fn get(
+        &self,
+        py: Python<'_>,
+        x: u32,
+        y: u32,
+        w: Option<u32>,
+        h: Option<u32>,
+    ) -> PyResult<Py<PyAny>> {
+        match (w, h) {
+            (Some(w), Some(h)) => {
+                let entity = graphics_get_image(self.entity, x, y, w, h)
+                    .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+                Ok(Image { entity }.into_pyobject(py)?.into_any().unbind())
+            }
+            (None, None) => {
+                let pixel = graphics_get_pixel(self.entity, x, y)
+                    .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+                let color = crate::color::PyColor::from(Color::from(pixel));
+                Ok(color.into_pyobject(py)?.into_any().unbind())
+            }
+            _ => Err(PyRuntimeError::new_err(
+                "get() needs both w and h, or neither",
+            )),
+        }
+    }
+
     pub fn create_image(&self, width: u32, height: u32) -> PyResult<Image> {
         let size = Extent3d {
             width,
@@ -1366,6 +2079,34 @@ impl Graphics {
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Multiplies the current transform by a 4x4 matrix, given as 16 floats in column-major order.
+    pub fn apply_matrix(&self, matrix: [f32; 16]) -> PyResult<()> {
+        graphics_record_command(self.entity, DrawCommand::ApplyMatrix(matrix))
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// The current transform as 16 floats in column-major order.
+    pub fn get_matrix(&self) -> PyResult<[f32; 16]> {
+        graphics_get_matrix(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Where a point drawn at the current transform lands on screen, as `(x, y)` in pixels.
+    #[pyo3(signature = (x, y, z=0.0))]
+    pub fn screen_xy(&self, x: f32, y: f32, z: f32) -> PyResult<(f32, f32)> {
+        graphics_screen_xy(self.entity, x, y, z)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    #[pyo3(signature = (x, y, z=0.0))]
+    pub fn screen_x(&self, x: f32, y: f32, z: f32) -> PyResult<f32> {
+        Ok(self.screen_xy(x, y, z)?.0)
+    }
+
+    #[pyo3(signature = (x, y, z=0.0))]
+    pub fn screen_y(&self, x: f32, y: f32, z: f32) -> PyResult<f32> {
+        Ok(self.screen_xy(x, y, z)?.1)
+    }
+
     #[pyo3(signature = (*args))]
     pub fn translate(&self, args: &Bound<'_, PyTuple>) -> PyResult<()> {
         let v = extract_vec2(args)?;
@@ -1527,11 +2268,61 @@ impl Graphics {
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
-    pub fn draw_geometry(&self, geometry: &Geometry) -> PyResult<()> {
-        graphics_record_command(self.entity, DrawCommand::Geometry(geometry.entity))
+    /// Draw a geometry, with `material` in place of the current material for just this draw.
+    #[pyo3(signature = (geometry, material=None))]
+    pub fn draw_geometry(
+        &self,
+        geometry: &Geometry,
+        material: Option<&crate::material::Material>,
+    ) -> PyResult<()> {
+        let cmd = match material {
+            Some(material) => DrawCommand::GeometryWithMaterial {
+                geometry: geometry.entity,
+                material: material.entity,
+            },
+            None => DrawCommand::Geometry(geometry.entity),
+        };
+        graphics_record_command(self.entity, cmd)
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Draw a geometry. With `x` and `y` it's taken as modeled in the unit square, `(0, 0)` to
+    /// `(1, 1)`, and stretched over the rect at `x`, `y`, `w`, `h` as placed by `shape_mode`;
+    /// without a size it's drawn at unit size. `material` stands in for the current material for
+    /// just this draw.
+    #[pyo3(signature = (geometry, x=None, y=None, w=1.0, h=1.0, material=None))]
+    pub fn shape(
+        &self,
+        geometry: &Geometry,
+        x: Option<f32>,
+        y: Option<f32>,
+        w: f32,
+        h: f32,
+        material: Option<&crate::material::Material>,
+    ) -> PyResult<()> {
+        let (x, y) = match (x, y) {
+            (Some(x), Some(y)) => (x, y),
+            (None, None) => return self.draw_geometry(geometry, material),
+            _ => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(
+                    "shape() needs both x and y, or neither",
+                ));
+            }
+        };
+        graphics_record_command(
+            self.entity,
+            DrawCommand::Shape {
+                geometry: geometry.entity,
+                x,
+                y,
+                w,
+                h,
+                material: material.map(|m| m.entity),
+            },
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn particles(
         &self,
         particles: &crate::particles::Particles,
@@ -1658,6 +2449,30 @@ impl Graphics {
         transform_look_at(self.entity, v).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
 
+    /// Processing's `camera()`: the eye position, the point it looks at and the up direction.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (eye_x, eye_y, eye_z, center_x, center_y, center_z, up_x=0.0, up_y=1.0, up_z=0.0))]
+    pub fn camera(
+        &self,
+        eye_x: f32,
+        eye_y: f32,
+        eye_z: f32,
+        center_x: f32,
+        center_y: f32,
+        center_z: f32,
+        up_x: f32,
+        up_y: f32,
+        up_z: f32,
+    ) -> PyResult<()> {
+        graphics_camera(
+            self.entity,
+            Vec3::new(eye_x, eye_y, eye_z),
+            Vec3::new(center_x, center_y, center_z),
+            Vec3::new(up_x, up_y, up_z),
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn perspective(&self, fov: f32, aspect: f32, near: f32, far: f32) -> PyResult<()> {
         graphics_perspective(
             self.entity,
@@ -1742,6 +2557,13 @@ impl Graphics {
         }
     }
 
+    /// Lights the whole scene evenly with `color`. Calling it again replaces the ambient light.
+    pub fn light_ambient(&self, color: crate::color::ColorLike, brightness: f32) -> PyResult<()> {
+        let color = color.into_color()?;
+        light_create_ambient(self.entity, color, brightness)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     pub fn light_point(
         &self,
         color: crate::color::ColorLike,
@@ -86,6 +86,17 @@ pub fn key_code() -> PyResult<Option<u32>> {
         .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
 }
 
+pub fn dropped_files() -> PyResult<Vec<String>> {
+    processing::prelude::input_dropped_files()
+        .map(|paths| {
+            paths
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect()
+        })
+        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+}
+
 pub fn sync_globals(
     globals: &Bound<'_, PyAny>,
     surface: Entity,
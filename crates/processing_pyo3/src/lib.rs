@@ -23,6 +23,7 @@ mod monitor;
 pub(crate) mod particles;
 pub(crate) mod shader;
 mod surface;
+mod table;
 mod time;
 #[cfg(feature = "webcam")]
 mod webcam;
@@ -42,6 +43,7 @@ use pyo3::{
 };
 use shader::Shader;
 use std::ffi::{CStr, CString};
+use table::Table;
 
 use bevy::log::warn;
 use gltf::Gltf;
@@ -145,28 +147,34 @@ fn dispatch_event_callbacks(locals: &Bound<'_, PyAny>) -> PyResult<()> {
     let err =
         |e: processing::prelude::error::ProcessingError| PyRuntimeError::new_err(format!("{e}"));
 
-    if input_mouse_any_just_pressed().map_err(err)? {
-        try_call(locals, "mouse_pressed")?;
-    }
-    if input_mouse_any_just_released().map_err(err)? {
-        try_call(locals, "mouse_released")?;
-    }
-    if input_mouse_moved().map_err(err)? {
-        if input_mouse_is_pressed().map_err(err)? {
-            try_call(locals, "mouse_dragged")?;
-        } else {
-            try_call(locals, "mouse_moved")?;
-        }
-    }
-    if input_mouse_scrolled().map_err(err)? {
-        try_call(locals, "mouse_wheel")?;
+    for event in input_mouse_events().map_err(err)? {
+        let name = match event {
+            MouseEvent::Pressed => "mouse_pressed",
+            MouseEvent::Dragged => "mouse_dragged",
+            MouseEvent::Moved => "mouse_moved",
+            MouseEvent::Released => "mouse_released",
+            MouseEvent::Clicked => "mouse_clicked",
+            MouseEvent::Wheel => "mouse_wheel",
+        };
+        try_call(locals, name)?;
     }
     if input_key_any_just_pressed().map_err(err)? {
         try_call(locals, "key_pressed")?;
     }
+    if input_key_just_typed().map_err(err)? {
+        try_call(locals, "key_typed")?;
+    }
     if input_key_any_just_released().map_err(err)? {
         try_call(locals, "key_released")?;
     }
+    if let Ok(cb) = locals.get_item("file_dropped")
+        && cb.is_callable()
+    {
+        for path in input::dropped_files()? {
+            cb.call1((path,))
+                .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        }
+    }
     Ok(())
 }
 
@@ -239,7 +247,7 @@ fn create_graphics_context(module: &Bound<'_, PyModule>, width: u32, height: u32
         }
     }
 
-    Ok(())
+    math::apply_pending_seed()
 }
 
 const DEFAULT_WIDTH: u32 = 100;
@@ -353,6 +361,8 @@ mod mewnala {
     #[pymodule_export]
     use super::Shader;
     #[pymodule_export]
+    use super::Table;
+    #[pymodule_export]
     use super::Topology;
     #[pymodule_export]
     use super::color::PyColor;
@@ -390,6 +400,13 @@ mod mewnala {
     #[pymodule_export]
     const BEVEL: u8 = 2;
 
+    // Stroke alignment
+    #[pymodule_export]
+    const INNER: u8 = 0;
+    // CENTER = 1
+    #[pymodule_export]
+    const OUTER: u8 = 2;
+
     // Shape kinds
     #[pymodule_export]
     const POLYGON: u8 = 0;
@@ -408,6 +425,14 @@ mod mewnala {
     #[pymodule_export]
     const QUAD_STRIP: u8 = 7;
 
+    // Gradient directions
+    #[pymodule_export]
+    const VERTICAL: u8 = 0;
+    #[pymodule_export]
+    const HORIZONTAL: u8 = 1;
+    #[pymodule_export]
+    const DIAGONAL: u8 = 2;
+
     // Shape modes
     #[pymodule_export]
     const CORNER: u8 = 0;
@@ -428,6 +453,21 @@ mod mewnala {
     #[pymodule_export]
     const CLOSE: bool = true;
 
+    // Text modes
+    #[pymodule_export]
+    const VECTOR: u8 = 0;
+    #[pymodule_export]
+    const SDF: u8 = 1;
+
+    // Vertical text alignment, LEFT, CENTER and RIGHT below cover the rest. CENTER is shared with
+    // the horizontal constants, so TOP and BOTTOM sit out of its way; see `Graphics.text_align`.
+    #[pymodule_export]
+    const BASELINE: u8 = 0;
+    #[pymodule_export]
+    const TOP: u8 = 101;
+    #[pymodule_export]
+    const BOTTOM: u8 = 102;
+
     // Mouse buttons
     #[pymodule_export]
     const LEFT: u8 = 0;
@@ -620,6 +660,18 @@ mod mewnala {
     #[pymodule_export]
     const RAD_TO_DEG: f32 = 180.0 / std::f32::consts::PI;
 
+    // Image filters
+    #[pymodule_export]
+    const GRAY: u8 = 0;
+    #[pymodule_export]
+    const INVERT: u8 = 1;
+    #[pymodule_export]
+    const THRESHOLD: u8 = 2;
+    #[pymodule_export]
+    const BLUR: u8 = 3;
+    #[pymodule_export]
+    const POSTERIZE: u8 = 4;
+
     // color space constants for color_mode()
     #[pymodule_export]
     const SRGB: u8 = 0;
@@ -641,6 +693,11 @@ mod mewnala {
     const LCH: u8 = 8;
     #[pymodule_export]
     const XYZ: u8 = 9;
+    // Processing's names for SRGB and HSV
+    #[pymodule_export]
+    const RGB: u8 = 0;
+    #[pymodule_export]
+    const HSB: u8 = 3;
 
     #[pymodule_init]
     fn init(module: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -706,6 +763,143 @@ mod mewnala {
         }
     }
 
+    /// Evaluate one coordinate of a cubic bezier at `t`, given the coordinate of its first anchor,
+    /// two control points and second anchor.
+    #[pyfunction]
+    fn bezier_point(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+        processing::prelude::render::primitive::bezier_point(a, b, c, d, t)
+    }
+
+    #[pyfunction]
+    fn bezier_tangent(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+        processing::prelude::render::primitive::bezier_tangent(a, b, c, d, t)
+    }
+
+    /// Evaluate one coordinate of a Catmull-Rom curve segment at `t`, like `curve` draws it.
+    #[pyfunction]
+    fn curve_point(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+        processing::prelude::render::primitive::curve_point(a, b, c, d, t)
+    }
+
+    #[pyfunction]
+    fn curve_tangent(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+        processing::prelude::render::primitive::curve_tangent(a, b, c, d, t)
+    }
+
+    /// Seeds the random number generators so `random`, `random_gaussian`, `shuffle`,
+    /// `random_sample` and the random vectors repeat from run to run.
+    #[pyfunction]
+    fn random_seed(seed: u64) -> PyResult<()> {
+        super::math::random_seed(seed)
+    }
+
+    /// `random(high)` returns a value from 0 up to `high`, and `random(low, high)` one from `low`
+    /// up to `high`. Neither ever returns `high`.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (low_or_high, high=None))]
+    fn random(module: &Bound<'_, PyModule>, low_or_high: f32, high: Option<f32>) -> PyResult<f32> {
+        ensure_graphics(module)?;
+        let (low, high) = match high {
+            Some(high) => (low_or_high, high),
+            None => (0.0, low_or_high),
+        };
+        processing::prelude::random_range(low, high)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// A normally distributed random value with mean 0 and standard deviation 1.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn random_gaussian(module: &Bound<'_, PyModule>) -> PyResult<f32> {
+        ensure_graphics(module)?;
+        processing::prelude::random_gaussian().map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Returns a shuffled copy of `seq`.
+    #[pyfunction]
+    fn shuffle(seq: Vec<Py<PyAny>>) -> PyResult<Vec<Py<PyAny>>> {
+        super::math::shuffle(seq)
+    }
+
+    /// Returns `n` distinct elements of `seq`, chosen at random.
+    #[pyfunction]
+    fn random_sample(seq: Vec<Py<PyAny>>, n: usize) -> PyResult<Vec<Py<PyAny>>> {
+        let len = seq.len();
+        super::math::random_sample(seq, n)?.ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "can't sample {n} elements from a sequence of {len}"
+            ))
+        })
+    }
+
+    /// Processing's Perlin noise at `x`, `(x, y)` or `(x, y, z)`, from 0 to 1 with the default
+    /// detail.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (x, y=0.0, z=0.0))]
+    fn noise(module: &Bound<'_, PyModule>, x: f32, y: f32, z: f32) -> PyResult<f32> {
+        ensure_graphics(module)?;
+        processing::prelude::noise_3d(x, y, z).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Returns `n` random values from `low` up to `high` as a float `array.array`, which NumPy
+    /// wraps without copying. Seeded by `random_seed`.
+    #[pyfunction]
+    #[pyo3(signature = (n, low=0.0, high=1.0))]
+    fn random_array(py: Python<'_>, n: usize, low: f32, high: f32) -> PyResult<Bound<'_, PyAny>> {
+        super::math::float_array(py, &super::math::random_array(n, low, high)?)
+    }
+
+    /// Returns `noise` at each of `coords`, which are numbers or sequences of up to three
+    /// components, as a float `array.array`.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn noise_array<'py>(
+        module: &Bound<'py, PyModule>,
+        coords: Vec<super::math::NoiseCoord>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        ensure_graphics(module)?;
+        let points = coords
+            .into_iter()
+            .map(super::math::NoiseCoord::into_vec3)
+            .collect::<PyResult<Vec<_>>>()?;
+        let values = processing::prelude::noise_array(&points)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        super::math::float_array(module.py(), &values)
+    }
+
+    /// A unit `Vec2` pointing along a noise based flow field at `(x, y)`, for steering particles
+    /// with `vel += flow_field(pos.x, pos.y)`. Smaller `scale`s give broader currents.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (x, y, scale=0.01))]
+    fn flow_field(
+        module: &Bound<'_, PyModule>,
+        x: f32,
+        y: f32,
+        scale: f32,
+    ) -> PyResult<super::math::PyVec2> {
+        ensure_graphics(module)?;
+        processing::prelude::flow_field(x, y, scale)
+            .map(super::math::PyVec2)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Seeds `noise` so it repeats from run to run, matching Processing for the same seed.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn noise_seed(module: &Bound<'_, PyModule>, seed: u64) -> PyResult<()> {
+        ensure_graphics(module)?;
+        processing::prelude::noise_seed(seed).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Sets the number of octaves `noise` sums and, optionally, how much each one falls off.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (octaves, falloff=0.0))]
+    fn noise_detail(module: &Bound<'_, PyModule>, octaves: i32, falloff: f32) -> PyResult<()> {
+        ensure_graphics(module)?;
+        processing::prelude::noise_detail(octaves, falloff)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
     // color constructors live at module level: a `color` submodule conflicted with `color()`
 
     #[pyfunction]
@@ -787,6 +981,41 @@ mod mewnala {
         Ok(Gltf::from_entity(entity))
     }
 
+    /// Loads a CSV or TSV file from the asset directory as a `Table`, with its first line as the
+    /// column names.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn load_table(module: &Bound<'_, PyModule>, path: &str) -> PyResult<Table> {
+        ensure_graphics(module)?;
+        let inner = ::processing::prelude::table_load(path)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Table { inner })
+    }
+
+    /// Loads a Wavefront `.obj` model from the asset directory as a `Geometry`.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn load_obj(module: &Bound<'_, PyModule>, path: &str) -> PyResult<Geometry> {
+        ensure_graphics(module)?;
+        let entity = ::processing::prelude::obj_load(path)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Geometry { entity })
+    }
+
+    /// Merges a list of geometries into one new `Geometry`, so they draw in a single call.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn merge_geometries(
+        module: &Bound<'_, PyModule>,
+        geometries: Vec<PyRef<Geometry>>,
+    ) -> PyResult<Geometry> {
+        ensure_graphics(module)?;
+        let entities: Vec<_> = geometries.iter().map(|g| g.entity).collect();
+        let entity = ::processing::prelude::geometry_merge(&entities, None)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Geometry { entity })
+    }
+
     #[pyfunction]
     #[pyo3(pass_module)]
     fn _poll_events(module: &Bound<'_, PyModule>) -> PyResult<bool> {
@@ -1162,6 +1391,15 @@ mod mewnala {
                     .ok_or_else(|| PyRuntimeError::new_err("call size() first"))?
                     .end_draw()?;
 
+                if let Ok(cb) = locals.get_item("after_frame")
+                    && cb.is_callable()
+                {
+                    let frame = processing::prelude::frame_count()
+                        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+                    cb.call1((frame,))
+                        .map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+                }
+
                 update_loop_state(|s| s.redraw_requested = false);
             }
 
@@ -1199,6 +1437,26 @@ mod mewnala {
         graphics!(module).camera_look_at(args)
     }
 
+    #[pyfunction]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(pass_module, signature = (eye_x, eye_y, eye_z, center_x, center_y, center_z, up_x=0.0, up_y=1.0, up_z=0.0))]
+    fn camera(
+        module: &Bound<'_, PyModule>,
+        eye_x: f32,
+        eye_y: f32,
+        eye_z: f32,
+        center_x: f32,
+        center_y: f32,
+        center_z: f32,
+        up_x: f32,
+        up_y: f32,
+        up_z: f32,
+    ) -> PyResult<()> {
+        graphics!(module).camera(
+            eye_x, eye_y, eye_z, center_x, center_y, center_z, up_x, up_y, up_z,
+        )
+    }
+
     #[pyfunction]
     #[pyo3(pass_module)]
     fn orbit_camera(module: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -1325,9 +1583,37 @@ mod mewnala {
     }
 
     #[pyfunction]
-    #[pyo3(pass_module, signature = (geometry))]
-    fn draw_geometry(module: &Bound<'_, PyModule>, geometry: &Bound<'_, Geometry>) -> PyResult<()> {
-        graphics!(module).draw_geometry(&*geometry.extract::<PyRef<Geometry>>()?)
+    #[pyo3(pass_module, signature = (geometry, material=None))]
+    fn draw_geometry(
+        module: &Bound<'_, PyModule>,
+        geometry: &Bound<'_, Geometry>,
+        material: Option<PyRef<'_, super::material::Material>>,
+    ) -> PyResult<()> {
+        graphics!(module).draw_geometry(
+            &*geometry.extract::<PyRef<Geometry>>()?,
+            material.as_deref(),
+        )
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (geometry, x=None, y=None, w=1.0, h=1.0, material=None))]
+    fn shape(
+        module: &Bound<'_, PyModule>,
+        geometry: &Bound<'_, Geometry>,
+        x: Option<f32>,
+        y: Option<f32>,
+        w: f32,
+        h: f32,
+        material: Option<PyRef<'_, super::material::Material>>,
+    ) -> PyResult<()> {
+        graphics!(module).shape(
+            &*geometry.extract::<PyRef<Geometry>>()?,
+            x,
+            y,
+            w,
+            h,
+            material.as_deref(),
+        )
     }
 
     #[pyfunction]
@@ -1368,6 +1654,59 @@ mod mewnala {
         }
     }
 
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn lerp_color(
+        module: &Bound<'_, PyModule>,
+        a: super::color::ColorLike,
+        b: super::color::ColorLike,
+        t: f32,
+    ) -> PyResult<super::color::PyColor> {
+        graphics!(module).lerp_color(a, b, t)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn red(module: &Bound<'_, PyModule>, color: super::color::ColorLike) -> PyResult<f32> {
+        graphics!(module).red(color)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn green(module: &Bound<'_, PyModule>, color: super::color::ColorLike) -> PyResult<f32> {
+        graphics!(module).green(color)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn blue(module: &Bound<'_, PyModule>, color: super::color::ColorLike) -> PyResult<f32> {
+        graphics!(module).blue(color)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn alpha(module: &Bound<'_, PyModule>, color: super::color::ColorLike) -> PyResult<f32> {
+        graphics!(module).alpha(color)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn hue(module: &Bound<'_, PyModule>, color: super::color::ColorLike) -> PyResult<f32> {
+        graphics!(module).hue(color)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn saturation(module: &Bound<'_, PyModule>, color: super::color::ColorLike) -> PyResult<f32> {
+        graphics!(module).saturation(color)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn brightness(module: &Bound<'_, PyModule>, color: super::color::ColorLike) -> PyResult<f32> {
+        graphics!(module).brightness(color)
+    }
+
     #[pyfunction]
     #[pyo3(pass_module, signature = (*args))]
     fn background(module: &Bound<'_, PyModule>, args: &Bound<'_, PyTuple>) -> PyResult<()> {
@@ -1437,6 +1776,12 @@ mod mewnala {
         graphics!(module).stroke_join(join)
     }
 
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn stroke_align(module: &Bound<'_, PyModule>, align: u8) -> PyResult<()> {
+        graphics!(module).stroke_align(align)
+    }
+
     #[pyfunction]
     #[pyo3(pass_module, signature = (mode))]
     fn blend_mode(module: &Bound<'_, PyModule>, mode: &Bound<'_, PyBlendMode>) -> PyResult<()> {
@@ -1484,11 +1829,15 @@ mod mewnala {
 
     /// Loads an image from a file and returns an Image object.
     #[pyfunction]
-    #[pyo3(pass_module, signature = (image_file))]
-    fn load_image(module: &Bound<'_, PyModule>, image_file: &str) -> PyResult<Image> {
+    #[pyo3(pass_module, signature = (image_file, auto_orient=true))]
+    fn load_image(
+        module: &Bound<'_, PyModule>,
+        image_file: &str,
+        auto_orient: bool,
+    ) -> PyResult<Image> {
         let graphics =
             get_graphics(module)?.ok_or_else(|| PyRuntimeError::new_err("call size() first"))?;
-        graphics.load_image(image_file)
+        graphics.load_image(image_file, auto_orient)
     }
 
     /// Draws an image to the screen.
@@ -1514,6 +1863,68 @@ mod mewnala {
         graphics!(module).image(source, dx, dy, d_width, d_height, sx, sy, s_width, s_height)
     }
 
+    /// Creates an offscreen `Graphics` to draw layers into, like Processing's `createGraphics`.
+    /// Draw to it between its `begin_draw()` and `end_draw()`, then draw it with `image()`.
+    ///
+    /// ```python
+    /// layer = create_graphics(200, 200)
+    /// layer.begin_draw()
+    /// layer.circle(100, 100, 50)
+    /// layer.end_draw()
+    /// image(layer, 0, 0)
+    /// ```
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn create_graphics(
+        module: &Bound<'_, PyModule>,
+        width: u32,
+        height: u32,
+    ) -> PyResult<Graphics> {
+        ensure_graphics(module)?;
+        Graphics::offscreen(width, height)
+    }
+
+    /// Copies a region of an image into a rectangle on screen, ignoring `image_mode()` and
+    /// `tint()`.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    #[allow(clippy::too_many_arguments)]
+    fn copy(
+        module: &Bound<'_, PyModule>,
+        source: graphics::ImageRef,
+        sx: f32,
+        sy: f32,
+        sw: f32,
+        sh: f32,
+        dx: f32,
+        dy: f32,
+        dw: f32,
+        dh: f32,
+    ) -> PyResult<()> {
+        graphics!(module).copy(source, sx, sy, sw, sh, dx, dy, dw, dh)
+    }
+
+    /// Blends a region of an image into a rectangle of the canvas's pixels with one of the preset
+    /// blend modes.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    #[allow(clippy::too_many_arguments)]
+    fn blend(
+        module: &Bound<'_, PyModule>,
+        source: graphics::ImageRef,
+        sx: u32,
+        sy: u32,
+        sw: u32,
+        sh: u32,
+        dx: u32,
+        dy: u32,
+        dw: u32,
+        dh: u32,
+        mode: PyRef<'_, PyBlendMode>,
+    ) -> PyResult<()> {
+        graphics!(module).blend(source, sx, sy, sw, sh, dx, dy, dw, dh, &mode)
+    }
+
     /// Sets a tint color applied when drawing images.
     #[pyfunction]
     #[pyo3(pass_module, signature = (*args))]
@@ -1539,6 +1950,79 @@ mod mewnala {
         graphics!(module).image_mode(mode)
     }
 
+    /// Reads back part of the surface: the region as an `Image` if `w` and `h` are given,
+    /// otherwise the `Color` of the pixel at `x`, `y`.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (x, y, w=None, h=None))]
+    fn get(
+        module: &Bound<'_, PyModule>,
+        x: u32,
+        y: u32,
+        w: Option<u32>,
+        h: Option<u32>,
+    ) -> PyResult<Py<PyAny>> {
+        graphics!(module).get(module.py(), x, y, w, h)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (c1, c2, direction=0))]
+    fn background_gradient(
+        module: &Bound<'_, PyModule>,
+        c1: &Bound<'_, PyAny>,
+        c2: &Bound<'_, PyAny>,
+        direction: u8,
+    ) -> PyResult<()> {
+        graphics!(module).background_gradient(c1, c2, direction)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn background_tiled(
+        module: &Bound<'_, PyModule>,
+        image: PyRef<'_, Image>,
+        tile_w: f32,
+        tile_h: f32,
+    ) -> PyResult<()> {
+        graphics!(module).background_tiled(&image, tile_w, tile_h)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (callback, batch=false))]
+    fn map_pixels(
+        module: &Bound<'_, PyModule>,
+        callback: &Bound<'_, PyAny>,
+        batch: bool,
+    ) -> PyResult<()> {
+        graphics!(module).map_pixels(callback, batch)
+    }
+
+    /// Saves the current frame at `path`, in the format its extension names.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (path, quality=90))]
+    fn save(module: &Bound<'_, PyModule>, path: &str, quality: u8) -> PyResult<()> {
+        graphics!(module).save(path, quality)
+    }
+
+    /// Saves the current frame as a numbered image, replacing the `#`s in `path` with the frame
+    /// count like Processing's `saveFrame`.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (path="screen-####.png", quality=90))]
+    fn save_frame(module: &Bound<'_, PyModule>, path: &str, quality: u8) -> PyResult<()> {
+        graphics!(module).save(path, quality)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (show=true))]
+    fn show_fps(module: &Bound<'_, PyModule>, show: bool) -> PyResult<()> {
+        graphics!(module).show_fps(show)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn frame_timings<'py>(module: &Bound<'py, PyModule>) -> PyResult<Bound<'py, PyDict>> {
+        graphics!(module).frame_timings(module.py())
+    }
+
     #[pyfunction]
     #[pyo3(pass_module)]
     fn create_image(module: &Bound<'_, PyModule>, width: u32, height: u32) -> PyResult<Image> {
@@ -1579,6 +2063,30 @@ mod mewnala {
         Ok(light)
     }
 
+    /// Draws geometry that has no material in magenta, so a forgotten `material()` call is easy
+    /// to spot instead of falling back to the plain fill color.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (required=true))]
+    fn require_material(module: &Bound<'_, PyModule>, required: bool) -> PyResult<()> {
+        ensure_graphics(module)?;
+        ::processing::prelude::set_require_material(required)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Processing's `ambientLight()`: lights every surface evenly, so PBR shapes aren't black
+    /// where no other light reaches. Brightness is in candela per square meter.
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (color, brightness=80.0))]
+    fn ambient_light(
+        module: &Bound<'_, PyModule>,
+        color: super::color::ColorLike,
+        brightness: f32,
+    ) -> PyResult<()> {
+        let graphics =
+            get_graphics(module)?.ok_or_else(|| PyRuntimeError::new_err("call size() first"))?;
+        graphics.light_ambient(color, brightness)
+    }
+
     #[pyfunction]
     #[pyo3(pass_module, signature = (color, intensity, range, radius, *, position=None, look_at=None))]
     fn point_light(
@@ -1672,9 +2180,9 @@ mod mewnala {
     }
 
     #[pyfunction]
-    #[pyo3(pass_module)]
-    fn line(module: &Bound<'_, PyModule>, x1: f32, y1: f32, x2: f32, y2: f32) -> PyResult<()> {
-        graphics!(module).line(x1, y1, x2, y2)
+    #[pyo3(pass_module, signature = (*args))]
+    fn line(module: &Bound<'_, PyModule>, args: &Bound<'_, PyTuple>) -> PyResult<()> {
+        graphics!(module).line(args)
     }
 
     #[pyfunction]
@@ -1707,10 +2215,48 @@ mod mewnala {
         graphics!(module).quad(x1, y1, x2, y2, x3, y3, x4, y4)
     }
 
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (x, y, z=None))]
+    fn point(module: &Bound<'_, PyModule>, x: f32, y: f32, z: Option<f32>) -> PyResult<()> {
+        graphics!(module).point(x, y, z)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn points(module: &Bound<'_, PyModule>, points: Vec<(f32, f32)>) -> PyResult<()> {
+        graphics!(module).points(points)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (content, x, y, *args, max_w=None, max_h=None))]
+    fn text(
+        module: &Bound<'_, PyModule>,
+        content: &str,
+        x: f32,
+        y: f32,
+        args: &Bound<'_, PyTuple>,
+        max_w: Option<f32>,
+        max_h: Option<f32>,
+    ) -> PyResult<()> {
+        graphics!(module).text(content, x, y, args, max_w, max_h)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn text_size(module: &Bound<'_, PyModule>, size: f32) -> PyResult<()> {
+        graphics!(module).text_size(size)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (h, v=None))]
+    fn text_align(module: &Bound<'_, PyModule>, h: u8, v: Option<u8>) -> PyResult<()> {
+        graphics!(module).text_align(h, v)
+    }
+
     #[pyfunction]
     #[pyo3(pass_module)]
-    fn point(module: &Bound<'_, PyModule>, x: f32, y: f32) -> PyResult<()> {
-        graphics!(module).point(x, y)
+    fn text_width(module: &Bound<'_, PyModule>, content: &str) -> PyResult<f32> {
+        graphics!(module).text_width(content)
     }
 
     #[pyfunction]
@@ -1778,6 +2324,18 @@ mod mewnala {
         graphics!(module).end_shape(close)
     }
 
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn begin_geometry(module: &Bound<'_, PyModule>) -> PyResult<()> {
+        graphics!(module).begin_geometry()
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn end_geometry(module: &Bound<'_, PyModule>) -> PyResult<Geometry> {
+        graphics!(module).end_geometry()
+    }
+
     #[pyfunction]
     #[pyo3(pass_module)]
     fn vertex(module: &Bound<'_, PyModule>, x: f32, y: f32) -> PyResult<()> {
@@ -1834,6 +2392,30 @@ mod mewnala {
         graphics!(module).reset_matrix()
     }
 
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn apply_matrix(module: &Bound<'_, PyModule>, matrix: [f32; 16]) -> PyResult<()> {
+        graphics!(module).apply_matrix(matrix)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn get_matrix(module: &Bound<'_, PyModule>) -> PyResult<[f32; 16]> {
+        graphics!(module).get_matrix()
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (x, y, z=0.0))]
+    fn screen_x(module: &Bound<'_, PyModule>, x: f32, y: f32, z: f32) -> PyResult<f32> {
+        graphics!(module).screen_x(x, y, z)
+    }
+
+    #[pyfunction]
+    #[pyo3(pass_module, signature = (x, y, z=0.0))]
+    fn screen_y(module: &Bound<'_, PyModule>, x: f32, y: f32, z: f32) -> PyResult<f32> {
+        graphics!(module).screen_y(x, y, z)
+    }
+
     #[pyfunction]
     #[pyo3(pass_module, signature = (*args))]
     fn scale(module: &Bound<'_, PyModule>, args: &Bound<'_, PyTuple>) -> PyResult<()> {
@@ -1858,6 +2440,12 @@ mod mewnala {
         graphics!(module).rect_mode(mode)
     }
 
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn shape_mode(module: &Bound<'_, PyModule>, mode: u8) -> PyResult<()> {
+        graphics!(module).shape_mode(mode)
+    }
+
     #[pyfunction]
     #[pyo3(pass_module)]
     fn ellipse_mode(module: &Bound<'_, PyModule>, mode: u8) -> PyResult<()> {
@@ -1983,6 +2571,19 @@ mod mewnala {
         input::key_just_pressed(key_code)
     }
 
+    /// Paths of the files dropped onto the window this frame.
+    #[pyfunction]
+    fn dropped_files() -> PyResult<Vec<String>> {
+        input::dropped_files()
+    }
+
+    /// Put the current frame on the system clipboard as an image.
+    #[pyfunction]
+    #[pyo3(pass_module)]
+    fn copy_to_clipboard(module: &Bound<'_, PyModule>) -> PyResult<()> {
+        graphics!(module).copy_to_clipboard()
+    }
+
     #[pyfunction]
     #[pyo3(pass_module, signature = (density=None))]
     fn pixel_density<'py>(
@@ -2038,4 +2639,14 @@ mod mewnala {
     fn primary_monitor() -> PyResult<Option<monitor::Monitor>> {
         monitor::primary()
     }
+
+    /// The GPUs available for rendering as `(name, backend)` tuples. Set `WGPU_ADAPTER_NAME` to
+    /// one of the names before the first draw to render with it.
+    #[pyfunction]
+    fn list_adapters() -> Vec<(String, String)> {
+        processing::prelude::list_adapters()
+            .into_iter()
+            .map(|a| (a.name, a.backend))
+            .collect()
+    }
 }
@@ -142,6 +142,13 @@ impl Material {
         };
         apply_kwargs(self.entity, kwargs)
     }
+
+    /// Sets a texture property, like `base_color_texture` or `normal_map_texture`, to an image.
+    /// Metallic-roughness, normal and occlusion maps are read as linear data, not sRGB color.
+    fn set_texture(&self, name: &str, image: ImageRef) -> PyResult<()> {
+        material_set_texture(self.entity, name, image.entity)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
 }
 
 impl Drop for Material {
@@ -1,11 +1,123 @@
+use std::cell::{Cell, RefCell};
 use std::hash::{Hash, Hasher};
 
 use bevy::math::{Affine2, EulerRot, Mat2, Quat, Vec2, Vec3, Vec4};
 use pyo3::{
-    exceptions::{PyAttributeError, PyTypeError},
+    exceptions::{PyAttributeError, PyRuntimeError, PyTypeError},
     prelude::*,
-    types::PyTuple,
+    types::{PyBytes, PyTuple},
 };
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+thread_local! {
+    // only for randomness asked for before there's an app, like `Vec2.random()` ahead of `size()`
+    static FALLBACK_RNG: RefCell<StdRng> = RefCell::new(StdRng::seed_from_u64(rand::random()));
+    // a `random_seed` from before there's an app, for the app `size()` goes on to create
+    static PENDING_SEED: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Seed the app's generator, or with no app yet the fallback and the app created next.
+pub(crate) fn random_seed(seed: u64) -> PyResult<()> {
+    use processing::prelude::error::ProcessingError;
+    match processing::prelude::random_seed(seed) {
+        Err(ProcessingError::NotInitialized) => {
+            FALLBACK_RNG.set(StdRng::seed_from_u64(seed));
+            PENDING_SEED.set(Some(seed));
+            Ok(())
+        }
+        result => result.map_err(|e| PyRuntimeError::new_err(format!("{e}"))),
+    }
+}
+
+/// Pass a seed given before the app existed on to the app just created.
+pub(crate) fn apply_pending_seed() -> PyResult<()> {
+    match PENDING_SEED.take() {
+        Some(seed) => random_seed(seed),
+        None => Ok(()),
+    }
+}
+
+/// Run `f` with the app's random number generator, which `random_seed` makes reproducible and a
+/// new app or a reloaded sketch starts over.
+pub(crate) fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> PyResult<T> {
+    use processing::prelude::error::ProcessingError;
+    let mut f = Some(f);
+    match processing::prelude::random_with(|rng| f.take().expect("only called once")(rng)) {
+        Ok(value) => Ok(value),
+        Err(ProcessingError::NotInitialized) => {
+            let f = f.expect("nothing is drawn without an app");
+            Ok(FALLBACK_RNG.with_borrow_mut(f))
+        }
+        Err(e) => Err(PyRuntimeError::new_err(format!("{e}"))),
+    }
+}
+
+/// A random permutation of `items`, like Processing's `shuffle()`.
+pub(crate) fn shuffle<T>(mut items: Vec<T>) -> PyResult<Vec<T>> {
+    with_rng(|rng| items.shuffle(rng))?;
+    Ok(items)
+}
+
+/// `n` distinct elements of `items` in random order. `None` if there are fewer than `n`.
+pub(crate) fn random_sample<T>(items: Vec<T>, n: usize) -> PyResult<Option<Vec<T>>> {
+    if n > items.len() {
+        return Ok(None);
+    }
+    let indices = with_rng(|rng| rand::seq::index::sample(rng, items.len(), n))?;
+    let mut items: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    Ok(Some(
+        indices
+            .into_iter()
+            .filter_map(|i| items[i].take())
+            .collect(),
+    ))
+}
+
+/// `n` uniform random values from `low` up to `high`, drawn from the sketch's generator in one
+/// pass so seeded sketches get the same values as `n` separate calls.
+pub(crate) fn random_array(n: usize, low: f32, high: f32) -> PyResult<Vec<f32>> {
+    use rand_distr::{Distribution, StandardUniform};
+    with_rng(|rng| {
+        StandardUniform
+            .sample_iter(rng)
+            .take(n)
+            .map(|t: f32| low + t * (high - low))
+            .collect()
+    })
+}
+
+/// Pack `values` into an `array.array('f')`, which NumPy and `memoryview` read without copying
+/// element by element.
+pub(crate) fn float_array<'py>(py: Python<'py>, values: &[f32]) -> PyResult<Bound<'py, PyAny>> {
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    py.import("array")?
+        .getattr("array")?
+        .call1(("f", PyBytes::new(py, &bytes)))
+}
+
+/// A coordinate for `noise_array`: a bare `x`, or a sequence of one to three components.
+#[derive(FromPyObject)]
+pub(crate) enum NoiseCoord {
+    X(f32),
+    Components(Vec<f32>),
+}
+
+impl NoiseCoord {
+    pub fn into_vec3(self) -> PyResult<Vec3> {
+        match self {
+            NoiseCoord::X(x) => Ok(Vec3::new(x, 0.0, 0.0)),
+            NoiseCoord::Components(c) if (1..=3).contains(&c.len()) => {
+                let mut v = [0.0; 3];
+                v[..c.len()].copy_from_slice(&c);
+                Ok(Vec3::from_array(v))
+            }
+            NoiseCoord::Components(c) => Err(PyTypeError::new_err(format!(
+                "noise coordinates have 1 to 3 components, got {}",
+                c.len()
+            ))),
+        }
+    }
+}
 
 pub fn hash_f32(val: f32, state: &mut impl Hasher) {
     if val == 0.0 {
@@ -536,10 +648,10 @@ impl_py_vec!(PyVec2, "Vec2", 2, [(x, set_x, 0), (y, set_y, 1)], Vec2, extra {
     }
 
     #[staticmethod]
-    fn random() -> Self {
+    fn random() -> PyResult<Self> {
         use rand_distr::{Distribution, UnitCircle};
-        let [x, y]: [f32; 2] = UnitCircle.sample(&mut rand::rng());
-        Self(Vec2::new(x, y))
+        let [x, y]: [f32; 2] = with_rng(|rng| UnitCircle.sample(rng))?;
+        Ok(Self(Vec2::new(x, y)))
     }
 
     fn extend(&self, z: f32) -> PyVec3 {
@@ -559,10 +671,10 @@ impl_py_vec!(PyVec3, "Vec3", 3, [(x, set_x, 0), (y, set_y, 1), (z, set_z, 2)], V
     }
 
     #[staticmethod]
-    fn random() -> Self {
+    fn random() -> PyResult<Self> {
         use rand_distr::{Distribution, UnitSphere};
-        let [x, y, z]: [f32; 3] = UnitSphere.sample(&mut rand::rng());
-        Self(Vec3::new(x, y, z))
+        let [x, y, z]: [f32; 3] = with_rng(|rng| UnitSphere.sample(rng))?;
+        Ok(Self(Vec3::new(x, y, z)))
     }
 
     fn extend(&self, w: f32) -> PyVec4 {
@@ -1162,15 +1274,52 @@ mod tests {
     #[test]
     fn test_vec2_random_is_unit() {
         for _ in 0..32 {
-            let v = PyVec2::random();
+            let v = PyVec2::random().unwrap();
             assert!((v.0.length() - 1.0).abs() < 1e-5);
         }
     }
 
+    #[test]
+    fn test_shuffle_is_deterministic_after_seed() {
+        random_seed(42).unwrap();
+        let first = shuffle((0..16).collect::<Vec<_>>()).unwrap();
+        random_seed(42).unwrap();
+        let second = shuffle((0..16).collect::<Vec<_>>()).unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, (0..16).collect::<Vec<_>>());
+        let mut sorted = first.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..16).collect::<Vec<_>>());
+
+        random_seed(7).unwrap();
+        let sample = random_sample((0..16).collect::<Vec<_>>(), 5)
+            .unwrap()
+            .unwrap();
+        random_seed(7).unwrap();
+        assert_eq!(
+            random_sample((0..16).collect::<Vec<_>>(), 5)
+                .unwrap()
+                .unwrap(),
+            sample
+        );
+        assert!(random_sample(vec![1, 2], 3).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_random_array_is_reproducible() {
+        random_seed(3).unwrap();
+        let first = random_array(1000, -2.0, 5.0).unwrap();
+        random_seed(3).unwrap();
+        assert_eq!(random_array(1000, -2.0, 5.0).unwrap(), first);
+        assert_eq!(first.len(), 1000);
+        assert!(first.iter().all(|v| (-2.0..5.0).contains(v)));
+    }
+
     #[test]
     fn test_vec3_random_is_unit() {
         for _ in 0..32 {
-            let v = PyVec3::random();
+            let v = PyVec3::random().unwrap();
             assert!((v.0.length() - 1.0).abs() < 1e-5);
         }
     }
@@ -64,46 +64,46 @@ impl Attribute {
     }
 
     #[staticmethod]
-    pub fn position() -> Self {
-        Self {
-            entity: geometry_attribute_position(),
-        }
+    pub fn position() -> PyResult<Self> {
+        let entity =
+            geometry_attribute_position().map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Self { entity })
     }
     #[staticmethod]
-    pub fn normal() -> Self {
-        Self {
-            entity: geometry_attribute_normal(),
-        }
+    pub fn normal() -> PyResult<Self> {
+        let entity =
+            geometry_attribute_normal().map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Self { entity })
     }
     #[staticmethod]
-    pub fn color() -> Self {
-        Self {
-            entity: geometry_attribute_color(),
-        }
+    pub fn color() -> PyResult<Self> {
+        let entity =
+            geometry_attribute_color().map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Self { entity })
     }
     #[staticmethod]
-    pub fn uv() -> Self {
-        Self {
-            entity: geometry_attribute_uv(),
-        }
+    pub fn uv() -> PyResult<Self> {
+        let entity =
+            geometry_attribute_uv().map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Self { entity })
     }
     #[staticmethod]
-    pub fn rotation() -> Self {
-        Self {
-            entity: geometry_attribute_rotation(),
-        }
+    pub fn rotation() -> PyResult<Self> {
+        let entity =
+            geometry_attribute_rotation().map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Self { entity })
     }
     #[staticmethod]
-    pub fn scale() -> Self {
-        Self {
-            entity: geometry_attribute_scale(),
-        }
+    pub fn scale() -> PyResult<Self> {
+        let entity =
+            geometry_attribute_scale().map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Self { entity })
     }
     #[staticmethod]
-    pub fn dead() -> Self {
-        Self {
-            entity: geometry_attribute_dead(),
-        }
+    pub fn dead() -> PyResult<Self> {
+        let entity =
+            geometry_attribute_dead().map_err(|e| PyRuntimeError::new_err(format!("{e}")))?;
+        Ok(Self { entity })
     }
 
     #[getter]
@@ -144,6 +144,11 @@ impl Surface {
         surface_center_on_monitor(self.entity, monitor.entity)
             .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
     }
+
+    /// Put the current frame on the system clipboard as an image.
+    pub fn copy_to_clipboard(&self) -> PyResult<()> {
+        surface_copy_to_clipboard(self.entity).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
 }
 
 impl Drop for Surface {
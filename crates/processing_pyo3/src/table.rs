@@ -0,0 +1,81 @@
+use processing::prelude::*;
+use pyo3::{exceptions::PyRuntimeError, prelude::*};
+
+/// Rows of text cells under named columns, loaded with `load_table` and written with `save`.
+#[pyclass(unsendable)]
+pub struct Table {
+    pub(crate) inner: table::Table,
+}
+
+impl Table {
+    fn column(&self, column: &Bound<'_, PyAny>) -> PyResult<usize> {
+        if let Ok(index) = column.extract::<usize>() {
+            return Ok(index);
+        }
+        let name: String = column.extract()?;
+        self.inner.column_index(&name).ok_or_else(|| {
+            pyo3::exceptions::PyKeyError::new_err(format!("no column named {name:?}"))
+        })
+    }
+}
+
+#[pymethods]
+impl Table {
+    #[new]
+    #[pyo3(signature = (columns=Vec::new()))]
+    pub fn new(columns: Vec<String>) -> Self {
+        Self {
+            inner: table::Table::new(columns),
+        }
+    }
+
+    #[getter]
+    pub fn columns(&self) -> Vec<String> {
+        self.inner.columns().to_vec()
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.inner.row_count()
+    }
+
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.inner.add_row(cells);
+    }
+
+    /// The cell at `row` in `column`, given by index or name, as a string.
+    pub fn get_string(&self, row: usize, column: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+        Ok(self
+            .inner
+            .get(row, self.column(column)?)
+            .map(str::to_string))
+    }
+
+    /// The cell at `row` in `column` as a float, or `None` if it isn't a number.
+    pub fn get_float(&self, row: usize, column: &Bound<'_, PyAny>) -> PyResult<Option<f32>> {
+        Ok(self.inner.get_float(row, self.column(column)?))
+    }
+
+    pub fn set_string(
+        &mut self,
+        row: usize,
+        column: &Bound<'_, PyAny>,
+        value: String,
+    ) -> PyResult<()> {
+        let column = self.column(column)?;
+        self.inner
+            .set(row, column, value)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    pub fn set_float(&mut self, row: usize, column: &Bound<'_, PyAny>, value: f32) -> PyResult<()> {
+        let column = self.column(column)?;
+        self.inner
+            .set_float(row, column, value)
+            .map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+
+    /// Write the table to `path` in the asset directory as CSV, or TSV if the path ends in `.tsv`.
+    pub fn save(&self, path: &str) -> PyResult<()> {
+        table_save(&self.inner, path).map_err(|e| PyRuntimeError::new_err(format!("{e}")))
+    }
+}
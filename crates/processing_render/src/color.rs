@@ -1,4 +1,7 @@
-use bevy::color::{Color, Hsla, Hsva, Hwba, Laba, Lcha, LinearRgba, Oklaba, Oklcha, Srgba, Xyza};
+use bevy::color::{
+    Color, ColorToComponents, ColorToPacked, Hsla, Hsva, Hwba, Laba, Lcha, LinearRgba, Mix, Oklaba,
+    Oklcha, Srgba, Xyza,
+};
 use bevy::prelude::Component;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,7 +51,13 @@ impl ColorSpace {
             Self::Srgb => Color::Srgba(Srgba::new(c1, c2, c3, alpha)),
             Self::Linear => Color::LinearRgba(LinearRgba::new(c1, c2, c3, alpha)),
             Self::Hsl => Color::Hsla(Hsla::new(c1, c2, c3, alpha)),
-            Self::Hsv => Color::Hsva(Hsva::new(c1, c2, c3, alpha)),
+            // like Processing's HSB, out of range channels clamp and a full turn of hue is red
+            Self::Hsv => Color::Hsva(Hsva::new(
+                c1.clamp(0.0, 360.0) % 360.0,
+                c2.clamp(0.0, 1.0),
+                c3.clamp(0.0, 1.0),
+                alpha,
+            )),
             Self::Hwb => Color::Hwba(Hwba::new(c1, c2, c3, alpha)),
             Self::Oklab => Color::Oklaba(Oklaba::new(c1, c2, c3, alpha)),
             Self::Oklch => Color::Oklcha(Oklcha::new(c1, c2, c3, alpha)),
@@ -58,6 +67,22 @@ impl ColorSpace {
         }
     }
 
+    /// The channels of `color` converted into this space, in its native ranges.
+    pub fn components(self, color: Color) -> [f32; 4] {
+        match self {
+            Self::Srgb => Srgba::from(color).to_f32_array(),
+            Self::Linear => LinearRgba::from(color).to_f32_array(),
+            Self::Hsl => Hsla::from(color).to_f32_array(),
+            Self::Hsv => Hsva::from(color).to_f32_array(),
+            Self::Hwb => Hwba::from(color).to_f32_array(),
+            Self::Oklab => Oklaba::from(color).to_f32_array(),
+            Self::Oklch => Oklcha::from(color).to_f32_array(),
+            Self::Lab => Laba::from(color).to_f32_array(),
+            Self::Lch => Lcha::from(color).to_f32_array(),
+            Self::Xyz => Xyza::from(color).to_f32_array(),
+        }
+    }
+
     pub fn gray(self, v: f32, alpha: f32) -> Color {
         match self {
             Self::Srgb | Self::Linear | Self::Xyz => self.color(v, v, v, alpha),
@@ -107,6 +132,56 @@ impl ColorMode {
         let native = self.space.default_maxes();
         value / self.max[ch] * native[ch]
     }
+
+    /// Processing's `red()`, `green()`, `blue()` and `alpha()`. Channels are in this mode's ranges
+    /// when it's an sRGB mode and from 0 to 1 otherwise.
+    pub fn rgba(&self, color: Color) -> [f32; 4] {
+        self.channels_in(ColorSpace::Srgb, color)
+    }
+
+    /// Processing's `hue()`, `saturation()` and `brightness()`, plus alpha. Channels are in this
+    /// mode's ranges when it's an HSV mode and in HSV's native ranges otherwise.
+    pub fn hsba(&self, color: Color) -> [f32; 4] {
+        self.channels_in(ColorSpace::Hsv, color)
+    }
+
+    fn channels_in(&self, space: ColorSpace, color: Color) -> [f32; 4] {
+        let native = space.default_maxes();
+        let max = if self.space == space {
+            self.max
+        } else {
+            [native[0], native[1], native[2], self.max[3]]
+        };
+        let c = space.components(color);
+        std::array::from_fn(|ch| c[ch] / native[ch] * max[ch])
+    }
+}
+
+/// Processing's `lerpColor()`: blend from `a` to `b` in `mode`'s color space, with `t` clamped to
+/// 0..1. RGB modes blend in linear light and hue based spaces go the short way around the hue
+/// circle.
+pub fn lerp_color(a: Color, b: Color, t: f32, mode: &ColorMode) -> Color {
+    let space = match mode.space {
+        ColorSpace::Srgb => ColorSpace::Linear,
+        space => space,
+    };
+    let [a1, a2, a3, aa] = space.components(a);
+    let [b1, b2, b3, ba] = space.components(b);
+    space
+        .color(a1, a2, a3, aa)
+        .mix(&space.color(b1, b2, b3, ba), t.clamp(0.0, 1.0))
+}
+
+/// Decode a packed `0xAARRGGBB` color like Processing's `color()` returns.
+pub fn from_argb(argb: u32) -> Color {
+    let [a, r, g, b] = argb.to_be_bytes();
+    Color::srgba_u8(r, g, b, a)
+}
+
+/// Pack a color into `0xAARRGGBB`.
+pub fn to_argb(color: Color) -> u32 {
+    let [r, g, b, a] = color.to_srgba().to_u8_array();
+    u32::from_be_bytes([a, r, g, b])
 }
 
 #[cfg(test)]
@@ -138,6 +213,48 @@ mod tests {
         assert!(s.green < 0.01);
     }
 
+    #[test]
+    fn test_hsv_matches_processing_hsb() {
+        // Processing's HSB to RGB conversion, from PGraphics.colorCalc
+        fn processing_hsb(h: f32, s: f32, b: f32) -> [f32; 3] {
+            let which = (h - h.floor()) * 6.0;
+            let f = which - which.floor();
+            let p = b * (1.0 - s);
+            let q = b * (1.0 - s * f);
+            let t = b * (1.0 - s * (1.0 - f));
+            match which as u32 {
+                0 => [b, t, p],
+                1 => [q, b, p],
+                2 => [p, b, t],
+                3 => [p, q, b],
+                4 => [t, p, b],
+                _ => [b, p, q],
+            }
+        }
+
+        for [h, s, b] in [
+            [0.0, 1.0, 1.0],
+            [30.0, 100.0, 100.0],
+            [200.0, 40.0, 80.0],
+            [300.0, 75.0, 50.0],
+            [360.0, 100.0, 100.0],
+            [120.0, 0.0, 60.0],
+        ] {
+            let mode = ColorMode::new(ColorSpace::Hsv, 360.0, 100.0, 100.0, 1.0);
+            let c = mode
+                .space
+                .color(mode.scale(h, 0), mode.scale(s, 1), mode.scale(b, 2), 1.0)
+                .to_srgba();
+            let expected = processing_hsb(h / 360.0, s / 100.0, b / 100.0);
+            for (actual, expected) in [c.red, c.green, c.blue].into_iter().zip(expected) {
+                assert!(
+                    (actual - expected).abs() < 1e-4,
+                    "hsb({h}, {s}, {b}): {actual} != {expected}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_gray_srgb() {
         let c = ColorSpace::Srgb.gray(0.5, 1.0);
@@ -174,4 +291,78 @@ mod tests {
         assert!((mode.scale(50.0, 1) - 0.5).abs() < 1e-4);
         assert!((mode.scale(50.0, 2) - 0.5).abs() < 1e-4);
     }
+
+    #[test]
+    fn test_channels_read_back_in_mode() {
+        let hsb = ColorMode::new(ColorSpace::Hsv, 360.0, 100.0, 100.0, 100.0);
+        let c = hsb.space.color(
+            hsb.scale(200.0, 0),
+            hsb.scale(40.0, 1),
+            hsb.scale(80.0, 2),
+            hsb.scale(50.0, 3),
+        );
+        let [h, s, b, a] = hsb.hsba(c);
+        assert!((h - 200.0).abs() < 0.01);
+        assert!((s - 40.0).abs() < 0.01);
+        assert!((b - 80.0).abs() < 0.01);
+        assert!((a - 50.0).abs() < 0.01);
+        // rgb channels outside an rgb mode are normalized
+        let [r, _, _, a] = hsb.rgba(Color::srgba(1.0, 0.0, 0.0, 0.5));
+        assert!((r - 1.0).abs() < 1e-6);
+        assert!((a - 50.0).abs() < 1e-4);
+
+        let rgb = ColorMode::with_uniform_max(ColorSpace::Srgb, 255.0);
+        let [r, g, b, _] = rgb.rgba(Color::srgb(1.0, 0.5, 0.0));
+        assert!((r - 255.0).abs() < 1e-3);
+        assert!((g - 127.5).abs() < 1e-3);
+        assert!(b.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_lerp_color_wraps_hue() {
+        let hsb = ColorMode::new(ColorSpace::Hsv, 360.0, 100.0, 100.0, 100.0);
+        let a = ColorSpace::Hsv.color(350.0, 1.0, 1.0, 1.0);
+        let b = ColorSpace::Hsv.color(10.0, 1.0, 1.0, 1.0);
+        // halfway between 350 and 10 is red, not the cyan at 180
+        let [h, s, v, _] = hsb.hsba(lerp_color(a, b, 0.5, &hsb));
+        assert!(!(1.0..359.0).contains(&h), "{h}");
+        assert!((s - 100.0).abs() < 0.01);
+        assert!((v - 100.0).abs() < 0.01);
+
+        let rgb = ColorMode::default();
+        let black = Color::srgb(0.0, 0.0, 0.0);
+        let white = Color::srgb(1.0, 1.0, 1.0);
+        // t is clamped
+        assert!((lerp_color(black, white, 2.0, &rgb).to_srgba().red - 1.0).abs() < 1e-5);
+        assert!(lerp_color(black, white, -1.0, &rgb).to_srgba().red.abs() < 1e-5);
+        let mid = lerp_color(black, white, 0.5, &rgb).to_linear();
+        assert!((mid.red - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_argb_round_trip() {
+        let orange = from_argb(0xFFFF8800);
+        assert_eq!(orange.to_srgba().to_u8_array(), [255, 136, 0, 255]);
+        assert_eq!(to_argb(orange), 0xFFFF8800);
+        assert_eq!(to_argb(from_argb(0x00FF8800)), 0x00FF8800);
+        assert_eq!(to_argb(from_argb(0x80FF8800)), 0x80FF8800);
+    }
+
+    #[test]
+    fn test_hsb_mode_packs_to_rgb() {
+        // colorMode(HSB, 360, 100, 100) then color(30, 100, 100) is orange
+        let mode = ColorMode::new(ColorSpace::Hsv, 360.0, 100.0, 100.0, 1.0);
+        let [h, s, b] = [30.0, 100.0, 100.0];
+        let c = mode.space.color(
+            mode.scale(h, 0),
+            mode.scale(s, 1),
+            mode.scale(b, 2),
+            mode.scale(1.0, 3),
+        );
+        let s = c.to_srgba();
+        assert!((s.red - 1.0).abs() < 0.01);
+        assert!((s.green - 0.5).abs() < 0.01);
+        assert!(s.blue < 0.01);
+        assert_eq!(to_argb(c) >> 24, 0xFF);
+    }
 }
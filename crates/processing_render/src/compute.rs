@@ -239,8 +239,7 @@ pub fn create_compute(app: &mut App, shader_entity: Entity) -> Result<Entity> {
     let pipeline_id = app
         .sub_app_mut(RenderApp)
         .world_mut()
-        .run_system_cached_with(queue_pipeline, descriptor)
-        .unwrap();
+        .run_system_cached_with(queue_pipeline, descriptor)?;
 
     const MAX_WAIT: u32 = 64;
     for _ in 0..MAX_WAIT {
@@ -248,8 +247,7 @@ pub fn create_compute(app: &mut App, shader_entity: Entity) -> Result<Entity> {
         let done = app
             .sub_app_mut(RenderApp)
             .world_mut()
-            .run_system_cached_with(pump_pipeline, pipeline_id)
-            .unwrap()?;
+            .run_system_cached_with(pump_pipeline, pipeline_id)??;
         if done {
             return Ok(app
                 .world_mut()
@@ -0,0 +1,132 @@
+//! Reading a graphics' depth attachment back to the CPU, for depth based effects and debugging.
+//!
+//! The attachment is usually multisampled, which rules out a plain texture to buffer copy, so a
+//! small compute pass loads the first sample of every pixel into a storage buffer instead.
+use std::borrow::Cow;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindingResource, BufferDescriptor, BufferUsages,
+            CommandEncoderDescriptor, ComputePassDescriptor, MapMode, PollType,
+            RawComputePipelineDescriptor, ShaderModuleDescriptor, ShaderSource, TextureAspect,
+            TextureViewDescriptor,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        sync_world::MainEntity,
+        view::ViewDepthTexture,
+    },
+};
+
+use processing_core::error::{ProcessingError, Result};
+
+const WORKGROUP_SIZE: u32 = 8;
+
+/// Copy the depth of the last frame drawn by the graphics whose main world entity is `entity`.
+/// Values follow Bevy's reversed depth: 1 on the near plane, falling towards 0 at the far plane,
+/// with 0 wherever nothing was drawn. Runs in the render world.
+pub fn readback(
+    In(entity): In<Entity>,
+    views: Query<(&MainEntity, &ViewDepthTexture)>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) -> Result<Vec<f32>> {
+    let texture = views
+        .iter()
+        .find(|(main_entity, _)| ***main_entity == entity)
+        .map(|(_, depth)| depth.texture.clone())
+        .ok_or(ProcessingError::GraphicsNotFound)?;
+    let (width, height) = (texture.width(), texture.height());
+    let multisampled = texture.sample_count() > 1;
+
+    let source = include_str!("depth.wgsl").replace(
+        "DEPTH_TEXTURE",
+        if multisampled {
+            "texture_depth_multisampled_2d"
+        } else {
+            "texture_depth_2d"
+        },
+    );
+    let device = render_device.wgpu_device();
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: Some("Depth Readback Shader"),
+        source: ShaderSource::Wgsl(Cow::Owned(source)),
+    });
+    let pipeline = device.create_compute_pipeline(&RawComputePipelineDescriptor {
+        label: Some("Depth Readback Pipeline"),
+        layout: None,
+        module: &module,
+        entry_point: Some("main"),
+        compilation_options: Default::default(),
+        cache: None,
+    });
+
+    let size = width as u64 * height as u64 * size_of::<f32>() as u64;
+    let storage = render_device.create_buffer(&BufferDescriptor {
+        label: Some("Depth Readback Storage"),
+        size,
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let readback_buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("Depth Readback Buffer"),
+        size,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let view = texture.create_view(&TextureViewDescriptor {
+        aspect: TextureAspect::DepthOnly,
+        ..default()
+    });
+    let bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Depth Readback Bind Group"),
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[
+            BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(&view),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: storage.as_entire_binding(),
+            },
+        ],
+    });
+
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        );
+    }
+    encoder.copy_buffer_to_buffer(&storage, 0, &readback_buffer, 0, size);
+    render_queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = readback_buffer.slice(..);
+    let (s, r) = crossbeam_channel::bounded(1);
+    slice.map_async(MapMode::Read, move |result| {
+        let _ = s.send(result);
+    });
+    render_device
+        .poll(PollType::wait_indefinitely())
+        .map_err(|e| ProcessingError::BufferMapError(format!("poll failed: {e}")))?;
+    r.recv()
+        .map_err(|e| ProcessingError::BufferMapError(format!("map channel closed: {e}")))?
+        .map_err(|e| ProcessingError::BufferMapError(format!("map failed: {e}")))?;
+
+    // Depth32Float texels, tightly packed by the shader
+    let depth = slice
+        .get_mapped_range()
+        .chunks_exact(size_of::<f32>())
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    readback_buffer.unmap();
+    Ok(depth)
+}
@@ -0,0 +1,16 @@
+// copies a depth attachment into a buffer of f32s, one per pixel in row-major order. depth
+// textures can't be copied to buffers when multisampled, so this reads the first sample instead.
+// DEPTH_TEXTURE is replaced with texture_depth_2d or texture_depth_multisampled_2d.
+
+@group(0) @binding(0) var depth: DEPTH_TEXTURE;
+@group(0) @binding(1) var<storage, read_write> out: array<f32>;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(depth);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+    // the last argument is the mip level or the sample index, 0 either way
+    out[id.y * size.x + id.x] = textureLoad(depth, vec2<i32>(id.xy), 0);
+}
@@ -9,13 +9,13 @@ use bevy::{
 
 use processing_core::error::{ProcessingError, Result};
 
-use super::{Geometry, hash_attr_name};
+use super::{DynamicGeometry, Geometry, hash_attr_name};
 
 fn clamp_range(range: Range<usize>, len: usize) -> Range<usize> {
     range.start.min(len)..range.end.min(len)
 }
 
-fn get_mesh<'a>(
+pub(super) fn get_mesh<'a>(
     entity: Entity,
     geometries: &Query<&Geometry>,
     meshes: &'a Assets<Mesh>,
@@ -28,19 +28,41 @@ fn get_mesh<'a>(
         .ok_or(ProcessingError::GeometryNotFound)
 }
 
-fn get_mesh_mut<'a>(
+pub(super) fn get_mesh_mut<'a>(
     entity: Entity,
-    geometries: &Query<&Geometry>,
+    geometries: &mut Query<&mut Geometry>,
     meshes: &'a mut Assets<Mesh>,
 ) -> Result<AssetMut<'a, Mesh>> {
-    let geometry = geometries
-        .get(entity)
+    let mut geometry = geometries
+        .get_mut(entity)
         .map_err(|_| ProcessingError::GeometryNotFound)?;
+    // whole-mesh operations move vertices around
+    geometry.positions_changed();
     meshes
         .get_mut(&geometry.handle)
         .ok_or(ProcessingError::GeometryNotFound)
 }
 
+/// [`get_mesh_mut`] for per-vertex edits. A [`DynamicGeometry`]'s mesh is written untracked and
+/// left for [`super::flag_dynamic_meshes`] to flag once per frame.
+pub(super) fn get_vertex_data_mut<'a>(
+    entity: Entity,
+    geometries: &mut Query<(&mut Geometry, Option<&mut DynamicGeometry>)>,
+    meshes: &'a mut Assets<Mesh>,
+) -> Result<&'a mut Mesh> {
+    let (geometry, dynamic) = geometries
+        .get_mut(entity)
+        .map_err(|_| ProcessingError::GeometryNotFound)?;
+    let mesh = match dynamic {
+        Some(mut dynamic) => {
+            dynamic.mark_dirty();
+            meshes.get_mut_untracked(&geometry.handle)
+        }
+        None => meshes.get_mut(&geometry.handle).map(|m| m.into_inner()),
+    };
+    mesh.ok_or(ProcessingError::GeometryNotFound)
+}
+
 macro_rules! impl_getter {
     ($name:ident, $attr:expr, $variant:ident, $type:ty) => {
         pub fn $name(
@@ -87,15 +109,18 @@ macro_rules! impl_setter {
     ($name:ident, $attr:expr, $variant:ident, $vec_ty:ty) => {
         pub fn $name(
             In((entity, index, value)): In<(Entity, u32, $vec_ty)>,
-            geometries: Query<&Geometry>,
+            mut geometries: Query<(&mut Geometry, Option<&mut DynamicGeometry>)>,
             mut meshes: ResMut<Assets<Mesh>>,
         ) -> Result<()> {
-            let mut mesh = get_mesh_mut(entity, &geometries, &mut meshes)?;
+            let mesh = get_vertex_data_mut(entity, &mut geometries, &mut meshes)?;
             match mesh.attribute_mut($attr) {
                 Some(VertexAttributeValues::$variant(data)) => {
                     let idx = index as usize;
                     if idx < data.len() {
                         data[idx] = value.to_array();
+                        if let Ok((mut geometry, _)) = geometries.get_mut(entity) {
+                            geometry.attribute_changed($attr.id);
+                        }
                         Ok(())
                     } else {
                         Err(ProcessingError::InvalidArgument(format!(
@@ -380,11 +405,14 @@ pub fn set_attribute(
         u32,
         AttributeValue,
     )>,
-    geometries: Query<&Geometry>,
+    mut geometries: Query<(&mut Geometry, Option<&mut DynamicGeometry>)>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) -> Result<()> {
-    let mut mesh = get_mesh_mut(entity, &geometries, &mut meshes)?;
+    let mesh = get_vertex_data_mut(entity, &mut geometries, &mut meshes)?;
     let idx = index as usize;
+    if let Ok((mut geometry, _)) = geometries.get_mut(entity) {
+        geometry.attribute_changed(attribute_id.id);
+    }
 
     let attr = mesh.attribute_mut(attribute_id).ok_or_else(|| {
         ProcessingError::InvalidArgument(format!(
@@ -423,3 +451,37 @@ pub fn set_attribute(
         )),
     }
 }
+
+/// Replace all of an attribute's per-vertex data at once. `values` must have one entry per vertex
+/// in the attribute's format; use [`super::set_positions`] to change the vertex count.
+pub fn set_vertex_data(
+    In((entity, attribute, values)): In<(Entity, MeshVertexAttribute, VertexAttributeValues)>,
+    mut geometries: Query<(&mut Geometry, Option<&mut DynamicGeometry>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let mesh = get_vertex_data_mut(entity, &mut geometries, &mut meshes)?;
+    let current = mesh.attribute(attribute.id).ok_or_else(|| {
+        ProcessingError::InvalidArgument(format!(
+            "Geometry does not have attribute {}",
+            attribute.name
+        ))
+    })?;
+    if VertexFormat::from(current) != VertexFormat::from(&values) {
+        return Err(ProcessingError::InvalidArgument(
+            "Attribute value type does not match attribute format".into(),
+        ));
+    }
+    let count = mesh.count_vertices();
+    if values.len() != count {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Expected {count} values for {}, one per vertex, got {}",
+            attribute.name,
+            values.len()
+        )));
+    }
+    mesh.insert_attribute(attribute, values);
+    if let Ok((mut geometry, _)) = geometries.get_mut(entity) {
+        geometry.attribute_changed(attribute.id);
+    }
+    Ok(())
+}
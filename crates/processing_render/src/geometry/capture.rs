@@ -0,0 +1,171 @@
+//! Capture shapes drawn between [`begin`] and [`end`] into a single retained [`Geometry`], like
+//! p5's `beginGeometry()`. While a graphics is capturing, its draw commands are kept aside
+//! instead of being drawn. 3d primitives and geometries are baked at the transform they were
+//! drawn with; other commands, such as fills and 2d shapes, are skipped.
+use bevy::{math::Affine3A, prelude::*};
+
+use processing_core::error::{ProcessingError, Result};
+
+use super::attribute::get_mesh;
+use super::ops::merge_meshes;
+use super::{BuiltinAttributes, Geometry, VertexLayout};
+use crate::graphics::Graphics;
+use crate::render::{
+    command::DrawCommand,
+    primitive::{
+        box_mesh, capsule_mesh, cone_mesh, conical_frustum_mesh, cylinder_mesh, plane_mesh,
+        sphere_mesh, tetrahedron_mesh, torus_mesh,
+    },
+    transform::TransformStack,
+};
+
+/// The commands recorded on a graphics since [`begin`].
+#[derive(Component, Debug, Default)]
+pub struct GeometryCapture {
+    pub commands: Vec<DrawCommand>,
+}
+
+enum Captured {
+    Mesh(Mesh),
+    Geometry(Entity),
+}
+
+/// Follow the transform commands and pick out what gets baked, each with the transform it was
+/// drawn at.
+fn captured(commands: Vec<DrawCommand>) -> Vec<(Captured, Affine3A)> {
+    let mut transform = TransformStack::new();
+    let mut shapes = Vec::new();
+    for command in commands {
+        if transform.apply_command(&command) {
+            continue;
+        }
+        let shape = match command {
+            DrawCommand::Geometry(entity)
+            | DrawCommand::GeometryWithMaterial {
+                geometry: entity, ..
+            } => Captured::Geometry(entity),
+            DrawCommand::Box {
+                width,
+                height,
+                depth,
+            } => Captured::Mesh(box_mesh(width, height, depth)),
+            DrawCommand::Sphere {
+                radius,
+                sectors,
+                stacks,
+            } => Captured::Mesh(sphere_mesh(radius, sectors, stacks)),
+            DrawCommand::Cylinder {
+                radius,
+                height,
+                detail,
+            } => Captured::Mesh(cylinder_mesh(radius, height, detail)),
+            DrawCommand::Cone {
+                radius,
+                height,
+                detail,
+            } => Captured::Mesh(cone_mesh(radius, height, detail)),
+            DrawCommand::Torus {
+                radius,
+                tube_radius,
+                major_segments,
+                minor_segments,
+            } => Captured::Mesh(torus_mesh(
+                radius,
+                tube_radius,
+                major_segments,
+                minor_segments,
+            )),
+            DrawCommand::Plane { width, height } => Captured::Mesh(plane_mesh(width, height)),
+            DrawCommand::Capsule {
+                radius,
+                length,
+                detail,
+            } => Captured::Mesh(capsule_mesh(radius, length, detail)),
+            DrawCommand::ConicalFrustum {
+                radius_top,
+                radius_bottom,
+                height,
+                detail,
+            } => Captured::Mesh(conical_frustum_mesh(
+                radius_top,
+                radius_bottom,
+                height,
+                detail,
+            )),
+            DrawCommand::Tetrahedron { radius } => Captured::Mesh(tetrahedron_mesh(radius)),
+            _ => continue,
+        };
+        shapes.push((shape, transform.current()));
+    }
+    shapes
+}
+
+/// Start capturing the graphics' draw commands into a geometry.
+pub fn begin(
+    In(graphics_entity): In<Entity>,
+    mut commands: Commands,
+    graphics: Query<Has<GeometryCapture>, With<Graphics>>,
+) -> Result<()> {
+    let capturing = graphics
+        .get(graphics_entity)
+        .map_err(|_| ProcessingError::GraphicsNotFound)?;
+    if capturing {
+        return Err(ProcessingError::InvalidArgument(
+            "geometry_begin called twice without geometry_end".into(),
+        ));
+    }
+    commands
+        .entity(graphics_entity)
+        .insert(GeometryCapture::default());
+    Ok(())
+}
+
+/// Stop capturing and bake what was drawn into a new geometry. Everything drawn must have the
+/// same attributes to be merged, which for geometries mixed with 3d primitives means the builtin
+/// position, normal, color and uv layout.
+pub fn end(
+    In(graphics_entity): In<Entity>,
+    mut commands: Commands,
+    mut graphics: Query<&mut GeometryCapture, With<Graphics>>,
+    geometries: Query<&Geometry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    builtins: Res<BuiltinAttributes>,
+) -> Result<Entity> {
+    let mut capture = graphics.get_mut(graphics_entity).map_err(|_| {
+        ProcessingError::InvalidArgument("geometry_end called without geometry_begin".into())
+    })?;
+    let shapes = captured(std::mem::take(&mut capture.commands));
+    commands.entity(graphics_entity).remove::<GeometryCapture>();
+    if shapes.is_empty() {
+        return Err(ProcessingError::InvalidArgument(
+            "Nothing was drawn between geometry_begin and geometry_end".into(),
+        ));
+    }
+
+    let mut sources = Vec::with_capacity(shapes.len());
+    let mut layout = None;
+    for (shape, transform) in &shapes {
+        let mesh = match shape {
+            Captured::Mesh(mesh) => mesh,
+            Captured::Geometry(entity) => {
+                layout = layout.or(geometries.get(*entity).ok().map(|g| g.layout));
+                get_mesh(*entity, &geometries, &meshes)?
+            }
+        };
+        sources.push((mesh, *transform));
+    }
+    let merged = merge_meshes(&sources)?;
+
+    let handle = meshes.add(merged);
+    let layout = layout.unwrap_or_else(|| {
+        commands
+            .spawn(VertexLayout::with_attributes(vec![
+                builtins.position,
+                builtins.normal,
+                builtins.color,
+                builtins.uv,
+            ]))
+            .id()
+    });
+    Ok(commands.spawn(Geometry::new(handle, layout)).id())
+}
@@ -0,0 +1,380 @@
+//! Exporting retained geometry to glTF binary (`.glb`) files, so procedurally built meshes can be
+//! opened in other tools or loaded back with `gltf_load`. The mesh and its material are both named
+//! after the file, so `cube.glb` loads back with `gltf_geometry(gltf, "cube")`.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use ::gltf::binary::{Glb, Header};
+use ::gltf::json::{
+    self,
+    accessor::{ComponentType, GenericComponentType, Type},
+    buffer::Target,
+    material::{AlphaCutoff, EmissiveFactor, PbrBaseColorFactor, StrengthFactor},
+    mesh::{Mode, Semantic},
+    validation::{Checked::Valid, USize64},
+};
+use bevy::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+use bevy::prelude::*;
+
+use super::Geometry;
+use crate::render::material::{ProcessingExtendedMaterial, UntypedMaterial};
+use processing_core::error::{ProcessingError, Result};
+
+/// Write the geometry to `path`, with `material` if given. Only PBR materials can be exported,
+/// and only their factors: textures are left out.
+pub fn save_gltf(
+    In((entity, path, material)): In<(Entity, PathBuf, Option<Entity>)>,
+    geometries: Query<&Geometry>,
+    meshes: Res<Assets<Mesh>>,
+    material_handles: Query<&UntypedMaterial>,
+    materials: Res<Assets<ProcessingExtendedMaterial>>,
+) -> Result<()> {
+    let geometry = geometries
+        .get(entity)
+        .map_err(|_| ProcessingError::GeometryNotFound)?;
+    let mesh = meshes
+        .get(&geometry.handle)
+        .ok_or(ProcessingError::GeometryNotFound)?;
+    let material = match material {
+        Some(material) => {
+            let handle = material_handles
+                .get(material)
+                .map_err(|_| ProcessingError::MaterialNotFound)?
+                .0
+                .clone()
+                .try_typed::<ProcessingExtendedMaterial>()
+                .map_err(|_| {
+                    ProcessingError::GltfSaveError("Only PBR materials can be exported".into())
+                })?;
+            let material = materials
+                .get(&handle)
+                .ok_or(ProcessingError::MaterialNotFound)?;
+            Some(&material.base)
+        }
+        None => None,
+    };
+
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("geometry");
+    let glb = mesh_to_glb(mesh, name, material)?;
+    std::fs::write(&path, glb)
+        .map_err(|e| ProcessingError::GltfSaveError(format!("{}: {e}", path.display())))
+}
+
+/// Serialize `mesh` as a glTF binary with a single mesh node called `name`. Positions, normals,
+/// uvs, colors and indices are written when present, and `material` as a material of the same
+/// name.
+pub fn mesh_to_glb(
+    mesh: &Mesh,
+    name: &str,
+    material: Option<&StandardMaterial>,
+) -> Result<Vec<u8>> {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return Err(ProcessingError::GltfSaveError(
+            "Geometry has no positions".to_string(),
+        ));
+    };
+    let mode = match mesh.primitive_topology() {
+        PrimitiveTopology::PointList => Mode::Points,
+        PrimitiveTopology::LineList => Mode::Lines,
+        PrimitiveTopology::LineStrip => Mode::LineStrip,
+        PrimitiveTopology::TriangleList => Mode::Triangles,
+        PrimitiveTopology::TriangleStrip => Mode::TriangleStrip,
+    };
+
+    let mut builder = GlbBuilder::new();
+    let mut attributes = BTreeMap::new();
+
+    // glTF requires bounds on positions
+    let (min, max) = positions.iter().fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(min, max), p| (min.min(Vec3::from(*p)), max.max(Vec3::from(*p))),
+    );
+    let accessor = builder.accessor(
+        positions.iter().flatten().copied(),
+        positions.len(),
+        Type::Vec3,
+        Some((min, max)),
+    );
+    attributes.insert(Valid(Semantic::Positions), accessor);
+
+    if let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    {
+        let accessor = builder.accessor(
+            normals.iter().flatten().copied(),
+            normals.len(),
+            Type::Vec3,
+            None,
+        );
+        attributes.insert(Valid(Semantic::Normals), accessor);
+    }
+    if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+        let accessor = builder.accessor(uvs.iter().flatten().copied(), uvs.len(), Type::Vec2, None);
+        attributes.insert(Valid(Semantic::TexCoords(0)), accessor);
+    }
+    if let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+        let accessor = builder.accessor(
+            colors.iter().flatten().copied(),
+            colors.len(),
+            Type::Vec4,
+            None,
+        );
+        attributes.insert(Valid(Semantic::Colors(0)), accessor);
+    }
+
+    let indices = mesh.indices().map(|indices| {
+        let values: Vec<u32> = match indices {
+            Indices::U16(i) => i.iter().map(|&i| i as u32).collect(),
+            Indices::U32(i) => i.clone(),
+        };
+        builder.index_accessor(&values)
+    });
+
+    let material = material.map(|material| builder.material(name, material));
+
+    let primitive = json::mesh::Primitive {
+        attributes,
+        extensions: Default::default(),
+        extras: Default::default(),
+        indices,
+        material,
+        mode: Valid(mode),
+        targets: None,
+    };
+    builder.finish(name, primitive)
+}
+
+const UNLIT: &str = "KHR_materials_unlit";
+
+struct GlbBuilder {
+    root: json::Root,
+    buffer: json::Index<json::Buffer>,
+    bin: Vec<u8>,
+}
+
+impl GlbBuilder {
+    fn new() -> Self {
+        let mut root = json::Root::default();
+        // the length is filled in once everything has been written
+        let buffer = root.push(json::Buffer {
+            byte_length: USize64(0),
+            extensions: Default::default(),
+            extras: Default::default(),
+            name: None,
+            uri: None,
+        });
+        Self {
+            root,
+            buffer,
+            bin: Vec::new(),
+        }
+    }
+
+    /// Append an `f32` vertex attribute with `count` elements of `type_`.
+    fn accessor(
+        &mut self,
+        values: impl Iterator<Item = f32>,
+        count: usize,
+        type_: Type,
+        bounds: Option<(Vec3, Vec3)>,
+    ) -> json::Index<json::Accessor> {
+        let bytes: Vec<u8> = values.flat_map(f32::to_le_bytes).collect();
+        let view = self.view(bytes, Target::ArrayBuffer);
+        self.root.push(json::Accessor {
+            buffer_view: Some(view),
+            byte_offset: Some(USize64(0)),
+            count: USize64::from(count),
+            component_type: Valid(GenericComponentType(ComponentType::F32)),
+            extensions: Default::default(),
+            extras: Default::default(),
+            type_: Valid(type_),
+            min: bounds.map(|(min, _)| json::Value::from(min.to_array().to_vec())),
+            max: bounds.map(|(_, max)| json::Value::from(max.to_array().to_vec())),
+            name: None,
+            normalized: false,
+            sparse: None,
+        })
+    }
+
+    fn index_accessor(&mut self, indices: &[u32]) -> json::Index<json::Accessor> {
+        let bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+        let view = self.view(bytes, Target::ElementArrayBuffer);
+        self.root.push(json::Accessor {
+            buffer_view: Some(view),
+            byte_offset: Some(USize64(0)),
+            count: USize64::from(indices.len()),
+            component_type: Valid(GenericComponentType(ComponentType::U32)),
+            extensions: Default::default(),
+            extras: Default::default(),
+            type_: Valid(Type::Scalar),
+            min: None,
+            max: None,
+            name: None,
+            normalized: false,
+            sparse: None,
+        })
+    }
+
+    /// Append `material`'s color, metallic, roughness, emissive and alpha settings.
+    fn material(&mut self, name: &str, material: &StandardMaterial) -> json::Index<json::Material> {
+        let (alpha_mode, alpha_cutoff) = match material.alpha_mode {
+            AlphaMode::Opaque => (json::material::AlphaMode::Opaque, None),
+            AlphaMode::Mask(cutoff) => (json::material::AlphaMode::Mask, Some(AlphaCutoff(cutoff))),
+            _ => (json::material::AlphaMode::Blend, None),
+        };
+        let extensions = material
+            .unlit
+            .then(|| json::extensions::material::Material {
+                unlit: Some(Default::default()),
+                ..Default::default()
+            });
+        if extensions.is_some() && !self.root.extensions_used.iter().any(|e| e == UNLIT) {
+            self.root.extensions_used.push(UNLIT.to_string());
+        }
+        self.root.push(json::Material {
+            name: Some(name.to_string()),
+            alpha_mode: Valid(alpha_mode),
+            alpha_cutoff,
+            // processing materials don't cull, so both sides are drawn
+            double_sided: material.double_sided || material.cull_mode.is_none(),
+            pbr_metallic_roughness: json::material::PbrMetallicRoughness {
+                base_color_factor: PbrBaseColorFactor(
+                    material.base_color.to_linear().to_f32_array(),
+                ),
+                metallic_factor: StrengthFactor(material.metallic),
+                roughness_factor: StrengthFactor(material.perceptual_roughness),
+                ..Default::default()
+            },
+            emissive_factor: EmissiveFactor(material.emissive.to_f32_array_no_alpha()),
+            extensions,
+            ..Default::default()
+        })
+    }
+
+    fn view(&mut self, bytes: Vec<u8>, target: Target) -> json::Index<json::buffer::View> {
+        // every component is 4 bytes wide, so views stay 4-byte aligned
+        let view = self.root.push(json::buffer::View {
+            buffer: self.buffer,
+            byte_length: USize64::from(bytes.len()),
+            byte_offset: Some(USize64::from(self.bin.len())),
+            byte_stride: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+            name: None,
+            target: Some(Valid(target)),
+        });
+        self.bin.extend(bytes);
+        view
+    }
+
+    fn finish(mut self, name: &str, primitive: json::mesh::Primitive) -> Result<Vec<u8>> {
+        let mesh = self.root.push(json::Mesh {
+            extensions: Default::default(),
+            extras: Default::default(),
+            name: Some(name.to_string()),
+            primitives: vec![primitive],
+            weights: None,
+        });
+        let node = self.root.push(json::Node {
+            mesh: Some(mesh),
+            ..Default::default()
+        });
+        let scene = self.root.push(json::Scene {
+            extensions: Default::default(),
+            extras: Default::default(),
+            name: None,
+            nodes: vec![node],
+        });
+        self.root.scene = Some(scene);
+        self.root.buffers[0].byte_length = USize64::from(self.bin.len());
+
+        let mut json = json::serialize::to_string(&self.root)
+            .map_err(|e| ProcessingError::GltfSaveError(e.to_string()))?
+            .into_bytes();
+        // chunks are padded to 4 bytes, json with spaces and binary with zeros
+        json.resize(json.len().next_multiple_of(4), b' ');
+        self.bin.resize(self.bin.len().next_multiple_of(4), 0);
+
+        let length = 12 + 8 + json.len() + 8 + self.bin.len();
+        let glb = Glb {
+            header: Header {
+                magic: *b"glTF",
+                version: 2,
+                length: length as u32,
+            },
+            json: Cow::Owned(json),
+            bin: Some(Cow::Owned(self.bin)),
+        };
+        glb.to_vec()
+            .map_err(|e| ProcessingError::GltfSaveError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_round_trips() {
+        let mesh = Mesh::from(Cuboid::new(1.0, 2.0, 3.0));
+        let glb = mesh_to_glb(&mesh, "cube", None).unwrap();
+
+        let gltf = ::gltf::Gltf::from_slice(&glb).unwrap();
+        let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+        let count = |semantic| primitive.get(&semantic).map(|a| a.count());
+
+        assert_eq!(
+            count(::gltf::Semantic::Positions),
+            Some(mesh.count_vertices())
+        );
+        assert_eq!(
+            count(::gltf::Semantic::Normals),
+            Some(mesh.count_vertices())
+        );
+        assert_eq!(
+            primitive.indices().map(|a| a.count()),
+            mesh.indices().map(|i| i.len())
+        );
+    }
+
+    #[test]
+    fn material_and_colors_are_written() {
+        let mut mesh = Mesh::from(Cuboid::new(1.0, 1.0, 1.0));
+        let count = mesh.count_vertices();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vec![[1.0, 0.5, 0.0, 1.0]; count]);
+        let material = StandardMaterial {
+            base_color: Color::linear_rgba(0.2, 0.4, 0.6, 0.5),
+            metallic: 0.25,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        };
+        let glb = mesh_to_glb(&mesh, "cube", Some(&material)).unwrap();
+
+        let gltf = ::gltf::Gltf::from_slice(&glb).unwrap();
+        let mesh = gltf.meshes().next().unwrap();
+        assert_eq!(mesh.name(), Some("cube"));
+        let primitive = mesh.primitives().next().unwrap();
+        assert_eq!(
+            primitive
+                .get(&::gltf::Semantic::Colors(0))
+                .map(|a| a.count()),
+            Some(count)
+        );
+        let material = primitive.material();
+        assert_eq!(material.name(), Some("cube"));
+        assert_eq!(
+            material.pbr_metallic_roughness().base_color_factor(),
+            [0.2, 0.4, 0.6, 0.5]
+        );
+        assert_eq!(material.pbr_metallic_roughness().metallic_factor(), 0.25);
+        assert_eq!(material.alpha_mode(), ::gltf::material::AlphaMode::Blend);
+        assert!(material.unlit());
+    }
+}
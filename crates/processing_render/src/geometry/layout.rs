@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use super::BuiltinAttributes;
+use super::{BuiltinAttributes, Geometry, rebuild_layout};
 use processing_core::error::{ProcessingError, Result};
 
 // bevy requires an attribute id for each unique vertex attribute. we don't really want to
@@ -48,6 +48,10 @@ impl VertexLayout {
         }
     }
 
+    pub fn remove(&mut self, attr: Entity) {
+        self.attributes.retain(|&a| a != attr);
+    }
+
     pub fn has_attribute(&self, attr_entity: Entity) -> bool {
         self.attributes.contains(&attr_entity)
     }
@@ -112,6 +116,35 @@ pub fn add_attribute(world: &mut World, layout_entity: Entity, attr_entity: Enti
     Ok(())
 }
 
+/// Drop `attr_entity` from the layout, and from the mesh data of every geometry using it so their
+/// attribute buffers stay as long as their positions.
+pub fn remove_attribute(
+    world: &mut World,
+    layout_entity: Entity,
+    attr_entity: Entity,
+) -> Result<()> {
+    let users: Vec<Entity> = world
+        .query::<(Entity, &Geometry)>()
+        .iter(world)
+        .filter(|(_, geometry)| geometry.layout == layout_entity)
+        .map(|(entity, _)| entity)
+        .collect();
+    if !users.is_empty() && attr_entity == world.resource::<BuiltinAttributes>().position {
+        return Err(ProcessingError::InvalidArgument(
+            "Can't remove the position attribute from a layout in use".into(),
+        ));
+    }
+
+    let mut layout = world
+        .get_mut::<VertexLayout>(layout_entity)
+        .ok_or(ProcessingError::LayoutNotFound)?;
+    layout.remove(attr_entity);
+    for entity in users {
+        world.run_system_cached_with(rebuild_layout, (entity, layout_entity))??;
+    }
+    Ok(())
+}
+
 pub fn destroy(In(entity): In<Entity>, mut commands: Commands) {
     commands.entity(entity).despawn();
 }
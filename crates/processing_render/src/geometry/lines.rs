@@ -0,0 +1,138 @@
+//! Wide lines for retained line geometry. wgpu only rasterizes 1px lines, so a line list or strip
+//! with a [`GeometryLineWidth`] is expanded into quads that face the camera each time it's drawn.
+use bevy::{
+    asset::RenderAssetUsages,
+    mesh::{Indices, VertexAttributeValues},
+    prelude::*,
+    render::render_resource::PrimitiveTopology,
+};
+
+use super::Geometry;
+use processing_core::error::{ProcessingError, Result};
+
+/// The width of a line geometry's lines, in the geometry's own units.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct GeometryLineWidth(pub f32);
+
+pub fn set_line_width(
+    In((entity, width)): In<(Entity, f32)>,
+    mut commands: Commands,
+    geometries: Query<(), With<Geometry>>,
+) -> Result<()> {
+    if !geometries.contains(entity) {
+        return Err(ProcessingError::GeometryNotFound);
+    }
+    if !width.is_finite() || width < 0.0 {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Line width must be a positive number, got {width}"
+        )));
+    }
+
+    // zero goes back to the hardware's hairlines
+    if width == 0.0 {
+        commands.entity(entity).remove::<GeometryLineWidth>();
+    } else {
+        commands.entity(entity).insert(GeometryLineWidth(width));
+    }
+    Ok(())
+}
+
+/// Expand the segments of a line list or strip into triangles, one `width` wide quad per segment
+/// turned to face `toward_viewer`, which gives the direction to the camera from a segment's
+/// midpoint, both in the mesh's space. Colors carry over from the line's ends. Meshes of any other
+/// topology give `None`.
+pub fn expand_lines(mesh: &Mesh, width: f32, toward_viewer: impl Fn(Vec3) -> Vec3) -> Option<Mesh> {
+    let strip = match mesh.primitive_topology() {
+        PrimitiveTopology::LineList => false,
+        PrimitiveTopology::LineStrip => true,
+        _ => return None,
+    };
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+    let colors = match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+        Some(VertexAttributeValues::Float32x4(colors)) => Some(colors),
+        _ => None,
+    };
+
+    let order: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+    let segments: Vec<(usize, usize)> = if strip {
+        order.windows(2).map(|s| (s[0], s[1])).collect()
+    } else {
+        order.chunks_exact(2).map(|s| (s[0], s[1])).collect()
+    };
+
+    let mut quad_positions = Vec::with_capacity(segments.len() * 4);
+    let mut quad_normals = Vec::with_capacity(segments.len() * 4);
+    let mut quad_uvs = Vec::with_capacity(segments.len() * 4);
+    let mut quad_colors = Vec::new();
+    let mut indices = Vec::with_capacity(segments.len() * 6);
+    for (a, b) in segments {
+        let (start, end) = (Vec3::from(positions[a]), Vec3::from(positions[b]));
+        let to_eye = toward_viewer(start.midpoint(end)).normalize_or(Vec3::Z);
+        let side = (end - start).cross(to_eye).normalize_or_zero() * (width * 0.5);
+        // a zero length segment, or one seen end on, has no width to show
+        if side == Vec3::ZERO {
+            continue;
+        }
+
+        let base = quad_positions.len() as u32;
+        quad_positions
+            .extend([start - side, start + side, end + side, end - side].map(Vec3::to_array));
+        quad_normals.extend([to_eye.to_array(); 4]);
+        quad_uvs.extend([[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]]);
+        if let Some(colors) = colors {
+            quad_colors.extend([colors[a], colors[a], colors[b], colors[b]]);
+        }
+        indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    let mut quads = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, quad_positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, quad_normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, quad_uvs)
+    .with_inserted_indices(Indices::U32(indices));
+    if colors.is_some() {
+        quads.insert_attribute(Mesh::ATTRIBUTE_COLOR, quad_colors);
+    }
+    Some(quads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_expands_to_camera_facing_quads() {
+        let mesh = Mesh::new(PrimitiveTopology::LineStrip, RenderAssetUsages::default())
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![[0.0, 0.0, 0.0], [10.0, 0.0, 0.0], [10.0, 10.0, 0.0]],
+            );
+        let eye = Vec3::new(0.0, 0.0, 100.0);
+        let quads = expand_lines(&mesh, 4.0, |midpoint| eye - midpoint).unwrap();
+        assert_eq!(quads.primitive_topology(), PrimitiveTopology::TriangleList);
+        assert_eq!(quads.indices().map(|i| i.len()), Some(12));
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            quads.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("expanded lines have no positions");
+        };
+        // the first segment runs along x, so it's widened along y and stays flat to the camera
+        assert!(Vec3::from(positions[0]).abs_diff_eq(Vec3::new(0.0, 2.0, 0.0), 1e-5));
+        assert!(Vec3::from(positions[1]).abs_diff_eq(Vec3::new(0.0, -2.0, 0.0), 1e-5));
+        assert!(positions[..4].iter().all(|p| p[2].abs() < 1e-5));
+
+        let triangles = Mesh::from(Cuboid::default());
+        assert!(expand_lines(&triangles, 4.0, |_| Vec3::Z).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+//! Distance based level of detail for retained geometry. A geometry with a [`GeometryLod`] is
+//! swapped out for a lower detail geometry when drawn far enough from the camera.
+use bevy::prelude::*;
+
+use super::Geometry;
+use processing_core::error::{ProcessingError, Result};
+
+#[derive(Component, Clone, Debug, Default)]
+pub struct GeometryLod {
+    // sorted by ascending distance
+    levels: Vec<(f32, Entity)>,
+}
+
+impl GeometryLod {
+    pub fn new(mut levels: Vec<(f32, Entity)>) -> Self {
+        levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { levels }
+    }
+
+    pub fn levels(&self) -> &[(f32, Entity)] {
+        &self.levels
+    }
+
+    /// The geometry to draw at `distance` from the camera. Each level takes over from
+    /// its distance onwards, and `base` is used when closer than all of them.
+    pub fn select(&self, base: Entity, distance: f32) -> Entity {
+        self.levels
+            .iter()
+            .take_while(|(d, _)| distance >= *d)
+            .last()
+            .map_or(base, |(_, e)| *e)
+    }
+
+    /// The geometry to draw for `base` placed by `transform`, measured from `camera` to the
+    /// center of `mesh`'s bounds.
+    pub(crate) fn select_for_draw(
+        &self,
+        base: Entity,
+        geometry: &Geometry,
+        mesh: Option<&Mesh>,
+        transform: &Transform,
+        camera: Vec3,
+    ) -> Entity {
+        let center = mesh
+            .and_then(|mesh| geometry.aabb(mesh))
+            .map_or(Vec3::ZERO, |aabb| Vec3::from(aabb.center));
+        self.select(base, camera.distance(transform.transform_point(center)))
+    }
+}
+
+pub fn set_lod(
+    In((entity, levels)): In<(Entity, Vec<(f32, Entity)>)>,
+    mut commands: Commands,
+    geometries: Query<(), With<Geometry>>,
+) -> Result<()> {
+    if !geometries.contains(entity) {
+        return Err(ProcessingError::GeometryNotFound);
+    }
+    for &(distance, level) in &levels {
+        if !distance.is_finite() || distance < 0.0 {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "LOD distance must be a positive number, got {distance}"
+            )));
+        }
+        if !geometries.contains(level) {
+            return Err(ProcessingError::GeometryNotFound);
+        }
+    }
+
+    if levels.is_empty() {
+        commands.entity(entity).remove::<GeometryLod>();
+    } else {
+        commands.entity(entity).insert(GeometryLod::new(levels));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn far_camera_picks_low_detail() {
+        let mut world = World::new();
+        let base = world.spawn_empty().id();
+        let medium = world.spawn_empty().id();
+        let low = world.spawn_empty().id();
+        let lod = GeometryLod::new(vec![(500.0, low), (100.0, medium)]);
+
+        assert_eq!(lod.select(base, 10.0), base);
+        assert_eq!(lod.select(base, 100.0), medium);
+        assert_eq!(lod.select(base, 250.0), medium);
+        assert_eq!(lod.select(base, 10_000.0), low);
+    }
+
+    #[test]
+    fn drawing_further_away_picks_lower_detail() {
+        let mut world = World::new();
+        let base = world.spawn_empty().id();
+        let medium = world.spawn_empty().id();
+        let low = world.spawn_empty().id();
+        let lod = GeometryLod::new(vec![(100.0, medium), (500.0, low)]);
+        // centered ten units along x, so drawn at the origin it sits ten units from the camera
+        let mesh = Mesh::from(Cuboid::new(2.0, 2.0, 2.0)).translated_by(Vec3::new(10.0, 0.0, 0.0));
+        let geometry = Geometry::new(Handle::default(), Entity::PLACEHOLDER);
+        let draw_at = |z: f32| {
+            lod.select_for_draw(
+                base,
+                &geometry,
+                Some(&mesh),
+                &Transform::from_xyz(0.0, 0.0, z),
+                Vec3::ZERO,
+            )
+        };
+
+        assert_eq!(draw_at(0.0), base);
+        assert_eq!(draw_at(-200.0), medium);
+        assert_eq!(draw_at(-1000.0), low);
+
+        // measured to the bounds' center, not the geometry's origin
+        let near = GeometryLod::new(vec![(5.0, low)]);
+        assert_eq!(
+            near.select_for_draw(
+                base,
+                &geometry,
+                Some(&mesh),
+                &Transform::IDENTITY,
+                Vec3::ZERO
+            ),
+            low
+        );
+    }
+}
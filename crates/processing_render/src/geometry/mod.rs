@@ -2,18 +2,27 @@
 //! rendering. Typically, Processing's "sketch" API creates new mesh data every frame, which can be
 //! inefficient for complex geometries. Geometry is backed by a Bevy [`Mesh`](Mesh) asset.
 pub(crate) mod attribute;
+pub mod capture;
+pub mod export;
 pub mod layout;
+pub mod lines;
+pub mod lod;
+pub mod ops;
+pub mod path;
 
 pub use attribute::*;
 pub use layout::{VertexLayout, hash_attr_name};
+pub use lod::GeometryLod;
+pub use path::GeometryPathBuilder;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::OnceLock};
 
 use bevy::{
     asset::RenderAssetUsages,
-    mesh::{Indices, MeshVertexAttributeId, VertexAttributeValues},
+    camera::primitives::Aabb,
+    mesh::{Indices, MeshVertexAttribute, MeshVertexAttributeId, VertexAttributeValues},
     prelude::*,
-    render::render_resource::PrimitiveTopology,
+    render::render_resource::{PrimitiveTopology, VertexFormat},
 };
 
 use crate::render::primitive::{box_mesh, grid_mesh, sphere_mesh};
@@ -23,7 +32,10 @@ pub struct GeometryPlugin;
 
 impl Plugin for GeometryPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<BuiltinAttributes>();
+        app.init_resource::<BuiltinAttributes>().add_systems(
+            PostUpdate,
+            flag_dynamic_meshes.before(bevy::asset::AssetEventSystems),
+        );
     }
 }
 
@@ -69,6 +81,11 @@ pub struct Geometry {
     pub current_color: [f32; 4],
     pub current_uv: [f32; 2],
     pub custom_current: HashMap<MeshVertexAttributeId, AttributeValue>,
+    /// Whether any vertex color has alpha below one, worked out on the first draw after the colors
+    /// change instead of on every draw.
+    translucent_colors: OnceLock<bool>,
+    /// Bounds of the vertex positions, worked out on the first use after the positions change.
+    aabb: OnceLock<Option<Aabb>>,
 }
 
 impl Geometry {
@@ -80,6 +97,93 @@ impl Geometry {
             current_color: [1.0, 1.0, 1.0, 1.0],
             current_uv: [0.0, 0.0],
             custom_current: HashMap::new(),
+            translucent_colors: OnceLock::new(),
+            aabb: OnceLock::new(),
+        }
+    }
+
+    /// Forget whatever is cached from the data of `attribute`, after writing it.
+    pub(crate) fn attribute_changed(&mut self, attribute: MeshVertexAttributeId) {
+        if attribute == Mesh::ATTRIBUTE_POSITION.id {
+            self.positions_changed();
+        } else if attribute == Mesh::ATTRIBUTE_COLOR.id {
+            self.colors_changed();
+        }
+    }
+
+    /// Forget the cached bounds. Every edit that writes the position attribute calls this.
+    pub(crate) fn positions_changed(&mut self) {
+        self.aabb.take();
+    }
+
+    /// Bounds of `mesh`'s vertex positions, or `None` if it has none.
+    pub(crate) fn aabb(&self, mesh: &Mesh) -> Option<Aabb> {
+        *self.aabb.get_or_init(|| mesh.compute_aabb())
+    }
+
+    /// Forget the cached vertex-color translucency. Every edit that writes the color attribute
+    /// calls this.
+    pub(crate) fn colors_changed(&mut self) {
+        self.translucent_colors.take();
+    }
+
+    /// Whether any of `mesh`'s vertex colors has alpha below one, meaning it needs blending even
+    /// if the fill is opaque.
+    pub(crate) fn has_translucent_colors(&self, mesh: &Mesh) -> bool {
+        *self
+            .translucent_colors
+            .get_or_init(|| has_translucent_vertex_colors(mesh))
+    }
+}
+
+fn has_translucent_vertex_colors(mesh: &Mesh) -> bool {
+    match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+        Some(VertexAttributeValues::Float32x4(colors)) => colors.iter().any(|c| c[3] < 1.0),
+        _ => false,
+    }
+}
+
+/// Marks a geometry whose vertices are rewritten every frame. Per-vertex edits to it don't flag
+/// the mesh as modified one by one; [`flag_dynamic_meshes`] flags it once before the frame is
+/// extracted, so thousands of `set_vertex` calls cost a single change event and re-upload.
+#[derive(Component, Default)]
+pub struct DynamicGeometry {
+    dirty: bool,
+}
+
+impl DynamicGeometry {
+    pub(crate) fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+}
+
+pub fn set_dynamic(
+    In((entity, dynamic)): In<(Entity, bool)>,
+    mut commands: Commands,
+    geometries: Query<&Geometry>,
+) -> Result<()> {
+    geometries
+        .get(entity)
+        .map_err(|_| ProcessingError::GeometryNotFound)?;
+    if dynamic {
+        // dirty, since edits made before the switch may not have been flagged yet
+        commands
+            .entity(entity)
+            .insert(DynamicGeometry { dirty: true });
+    } else {
+        commands.entity(entity).remove::<DynamicGeometry>();
+    }
+    Ok(())
+}
+
+pub fn flag_dynamic_meshes(
+    mut geometries: Query<(&Geometry, &mut DynamicGeometry)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (geometry, mut dynamic) in &mut geometries {
+        if std::mem::take(&mut dynamic.dirty) {
+            // touching the mesh is what queues its modified event
+            let _ = meshes.get_mut(&geometry.handle).map(|m| m.into_inner());
         }
     }
 }
@@ -275,14 +379,14 @@ pub fn attribute(
 
 pub fn vertex(
     In((entity, position)): In<(Entity, Vec3)>,
-    geometries: Query<&Geometry>,
+    mut geometries: Query<&mut Geometry>,
     layouts: Query<&VertexLayout>,
     attrs: Query<&Attribute>,
     builtins: Res<BuiltinAttributes>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) -> Result<()> {
-    let geometry = geometries
-        .get(entity)
+    let mut geometry = geometries
+        .get_mut(entity)
         .map_err(|_| ProcessingError::GeometryNotFound)?;
 
     let layout = layouts
@@ -294,10 +398,24 @@ pub fn vertex(
         .map(|m| m.into_inner())
         .ok_or(ProcessingError::GeometryNotFound)?;
 
+    push_vertex(mesh, &mut geometry, layout, &attrs, &builtins, position)
+}
+
+/// Append a vertex at `position`, filling the rest of the layout's attributes from the
+/// geometry's current normal/color/uv/custom values.
+pub(crate) fn push_vertex(
+    mesh: &mut Mesh,
+    geometry: &mut Geometry,
+    layout: &VertexLayout,
+    attrs: &Query<&Attribute>,
+    builtins: &BuiltinAttributes,
+    position: Vec3,
+) -> Result<()> {
     if let Some(VertexAttributeValues::Float32x3(positions)) =
         mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
     {
         positions.push(position.to_array());
+        geometry.positions_changed();
     }
 
     if layout.has_attribute(builtins.normal)
@@ -312,6 +430,7 @@ pub fn vertex(
             mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR)
     {
         colors.push(geometry.current_color);
+        geometry.colors_changed();
     }
 
     if layout.has_attribute(builtins.uv)
@@ -368,6 +487,11 @@ pub fn index(
         .map(|m| m.into_inner())
         .ok_or(ProcessingError::GeometryNotFound)?;
 
+    push_index(mesh, i);
+    Ok(())
+}
+
+pub(crate) fn push_index(mesh: &mut Mesh, i: u32) {
     match mesh.indices_mut() {
         Some(Indices::U32(indices)) => {
             indices.push(i);
@@ -379,8 +503,6 @@ pub fn index(
             mesh.insert_indices(Indices::U32(vec![i]));
         }
     }
-
-    Ok(())
 }
 
 pub fn vertex_count(
@@ -411,17 +533,639 @@ pub fn index_count(
     Ok(mesh.indices().map(|i| i.len() as u32).unwrap_or(0))
 }
 
+/// Empty every attribute buffer and drop the indices so the geometry can be refilled with
+/// [`vertex`] and [`index`]. The layout, mesh handle and buffer capacity are kept, so streaming
+/// geometry doesn't allocate a new mesh asset each frame.
+pub fn clear(
+    In(entity): In<Entity>,
+    mut geometries: Query<(&mut Geometry, Option<&mut DynamicGeometry>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let mesh = get_vertex_data_mut(entity, &mut geometries, &mut meshes)?;
+    for (_, values) in mesh.attributes_mut() {
+        match values {
+            VertexAttributeValues::Float32(v) => v.clear(),
+            VertexAttributeValues::Float32x2(v) => v.clear(),
+            VertexAttributeValues::Float32x3(v) => v.clear(),
+            VertexAttributeValues::Float32x4(v) => v.clear(),
+            _ => {}
+        }
+    }
+    mesh.remove_indices();
+    if let Ok((mut geometry, _)) = geometries.get_mut(entity) {
+        geometry.positions_changed();
+        geometry.colors_changed();
+    }
+    Ok(())
+}
+
+/// Replace every vertex position at once, for meshes rebuilt each frame. When the vertex count
+/// stays the same the other attributes are kept. When it changes they're refilled with the
+/// geometry's current normal, color, uv and custom values, as [`vertex`] would give each vertex.
+/// Shrinking fails while the index buffer still names a dropped vertex, so set the new indices
+/// first.
+pub fn set_positions(
+    In((entity, positions)): In<(Entity, Vec<[f32; 3]>)>,
+    mut geometries: Query<(&mut Geometry, Option<&mut DynamicGeometry>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let (normal, color, uv, custom) = {
+        let (geometry, _) = geometries
+            .get(entity)
+            .map_err(|_| ProcessingError::GeometryNotFound)?;
+        (
+            geometry.current_normal,
+            geometry.current_color,
+            geometry.current_uv,
+            geometry.custom_current.clone(),
+        )
+    };
+    let mesh = get_vertex_data_mut(entity, &mut geometries, &mut meshes)?;
+
+    let count = positions.len();
+    if let Some(i) = mesh
+        .indices()
+        .and_then(|indices| indices.iter().find(|&i| i >= count))
+    {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Index {i} out of bounds for {count} vertices, set the indices before shrinking"
+        )));
+    }
+
+    // a new vertex count refills every other attribute with its current value; build them all
+    // before touching the mesh so an unsupported one leaves it as it was
+    let mut refilled = Vec::new();
+    if count != mesh.count_vertices() {
+        for (attr, _) in mesh.attributes() {
+            if attr.id == Mesh::ATTRIBUTE_POSITION.id {
+                continue;
+            }
+            let current = if attr.id == Mesh::ATTRIBUTE_NORMAL.id {
+                Some(AttributeValue::Float3(normal))
+            } else if attr.id == Mesh::ATTRIBUTE_COLOR.id {
+                Some(AttributeValue::Float4(color))
+            } else if attr.id == Mesh::ATTRIBUTE_UV_0.id {
+                Some(AttributeValue::Float2(uv))
+            } else {
+                custom.get(&attr.id).cloned()
+            };
+            let values = match (current, attr.format) {
+                (Some(AttributeValue::Float(v)), _) => {
+                    VertexAttributeValues::Float32(vec![v; count])
+                }
+                (Some(AttributeValue::Float2(v)), _) => {
+                    VertexAttributeValues::Float32x2(vec![v; count])
+                }
+                (Some(AttributeValue::Float3(v)), _) => {
+                    VertexAttributeValues::Float32x3(vec![v; count])
+                }
+                (Some(AttributeValue::Float4(v)), _) => {
+                    VertexAttributeValues::Float32x4(vec![v; count])
+                }
+                (None, VertexFormat::Float32) => VertexAttributeValues::Float32(vec![0.0; count]),
+                (None, VertexFormat::Float32x2) => {
+                    VertexAttributeValues::Float32x2(vec![[0.0; 2]; count])
+                }
+                (None, VertexFormat::Float32x3) => {
+                    VertexAttributeValues::Float32x3(vec![[0.0; 3]; count])
+                }
+                (None, VertexFormat::Float32x4) => {
+                    VertexAttributeValues::Float32x4(vec![[0.0; 4]; count])
+                }
+                (None, format) => {
+                    return Err(ProcessingError::InvalidArgument(format!(
+                        "Unsupported attribute format {format:?}"
+                    )));
+                }
+            };
+            refilled.push((*attr, values));
+        }
+    }
+
+    let resized = !refilled.is_empty();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    for (attr, values) in refilled {
+        mesh.insert_attribute(attr, values);
+    }
+    if let Ok((mut geometry, _)) = geometries.get_mut(entity) {
+        geometry.positions_changed();
+        if resized {
+            geometry.colors_changed();
+        }
+    }
+    Ok(())
+}
+
+/// Replace the whole index buffer at once. Every index must name an existing vertex.
+pub fn set_indices(
+    In((entity, indices)): In<(Entity, Vec<u32>)>,
+    mut geometries: Query<(&mut Geometry, Option<&mut DynamicGeometry>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let mesh = get_vertex_data_mut(entity, &mut geometries, &mut meshes)?;
+    let count = mesh.count_vertices();
+    if let Some(i) = indices.iter().find(|&&i| i as usize >= count) {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Index {i} out of bounds for {count} vertices"
+        )));
+    }
+    mesh.insert_indices(Indices::U32(indices));
+    Ok(())
+}
+
+/// Switch a geometry to `layout_entity`, migrating its mesh data. Attributes in both layouts keep
+/// their values, new ones are filled from the geometry's current values and attributes missing
+/// from the new layout are dropped.
+pub fn rebuild_layout(
+    In((entity, layout_entity)): In<(Entity, Entity)>,
+    mut geometries: Query<&mut Geometry>,
+    layouts: Query<&VertexLayout>,
+    attrs: Query<&Attribute>,
+    builtins: Res<BuiltinAttributes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let mut geometry = geometries
+        .get_mut(entity)
+        .map_err(|_| ProcessingError::GeometryNotFound)?;
+    let layout = layouts
+        .get(layout_entity)
+        .map_err(|_| ProcessingError::LayoutNotFound)?;
+    if !layout.has_attribute(builtins.position) {
+        return Err(ProcessingError::InvalidArgument(
+            "Layout has no position attribute".into(),
+        ));
+    }
+
+    let mut targets = Vec::new();
+    for &attr_entity in layout.attributes() {
+        let attr = attrs
+            .get(attr_entity)
+            .map_err(|_| ProcessingError::InvalidEntity)?;
+        let fill = if attr_entity == builtins.normal {
+            AttributeValue::Float3(geometry.current_normal)
+        } else if attr_entity == builtins.color {
+            AttributeValue::Float4(geometry.current_color)
+        } else if attr_entity == builtins.uv {
+            AttributeValue::Float2(geometry.current_uv)
+        } else {
+            geometry
+                .custom_current
+                .get(&attr.inner.id)
+                .cloned()
+                .unwrap_or(match attr.format {
+                    AttributeFormat::Float => AttributeValue::Float(0.0),
+                    AttributeFormat::Float2 => AttributeValue::Float2([0.0; 2]),
+                    AttributeFormat::Float3 => AttributeValue::Float3([0.0; 3]),
+                    AttributeFormat::Float4 => AttributeValue::Float4([0.0; 4]),
+                })
+        };
+        targets.push((attr.inner, fill));
+    }
+
+    let mesh = meshes
+        .get_mut(&geometry.handle)
+        .map(|m| m.into_inner())
+        .ok_or(ProcessingError::GeometryNotFound)?;
+    migrate_attributes(mesh, &targets);
+    geometry.colors_changed();
+
+    geometry
+        .custom_current
+        .retain(|id, _| targets.iter().any(|(attr, _)| attr.id == *id));
+    geometry.layout = layout_entity;
+    Ok(())
+}
+
+/// Make `mesh` carry exactly the attributes in `layout`. Data for attributes it already has in the
+/// same format is kept, and the rest are filled with their paired value for every vertex.
+pub fn migrate_attributes(mesh: &mut Mesh, layout: &[(MeshVertexAttribute, AttributeValue)]) {
+    let count = mesh.count_vertices();
+    let stale: Vec<MeshVertexAttributeId> = mesh
+        .attributes()
+        .map(|(attr, _)| attr.id)
+        .filter(|id| !layout.iter().any(|(attr, _)| attr.id == *id))
+        .collect();
+    for id in stale {
+        mesh.remove_attribute(id);
+    }
+
+    for (attr, fill) in layout {
+        let compatible = mesh
+            .attribute(attr.id)
+            .is_some_and(|values| VertexFormat::from(values) == attr.format);
+        if compatible {
+            continue;
+        }
+        let values = match *fill {
+            AttributeValue::Float(v) => VertexAttributeValues::Float32(vec![v; count]),
+            AttributeValue::Float2(v) => VertexAttributeValues::Float32x2(vec![v; count]),
+            AttributeValue::Float3(v) => VertexAttributeValues::Float32x3(vec![v; count]),
+            AttributeValue::Float4(v) => VertexAttributeValues::Float32x4(vec![v; count]),
+        };
+        mesh.insert_attribute(*attr, values);
+    }
+}
+
+/// Drop `attr_entity` from a geometry and its mesh data. The layout is edited in place unless
+/// other geometry shares it, in which case this geometry gets its own copy and the others are left
+/// alone.
+pub fn remove_attribute(world: &mut World, entity: Entity, attr_entity: Entity) -> Result<()> {
+    let layout_entity = world
+        .get::<Geometry>(entity)
+        .ok_or(ProcessingError::GeometryNotFound)?
+        .layout;
+    let mut layout = world
+        .get::<VertexLayout>(layout_entity)
+        .ok_or(ProcessingError::LayoutNotFound)?
+        .clone();
+    layout.remove(attr_entity);
+
+    let shared = world
+        .query::<(Entity, &Geometry)>()
+        .iter(world)
+        .any(|(other, geometry)| other != entity && geometry.layout == layout_entity);
+    if shared {
+        let copy = world.spawn(layout).id();
+        let result = world.run_system_cached_with(rebuild_layout, (entity, copy))?;
+        if result.is_err() {
+            world.despawn(copy);
+        }
+        return result;
+    }
+
+    let previous = std::mem::replace(
+        &mut *world
+            .get_mut::<VertexLayout>(layout_entity)
+            .ok_or(ProcessingError::LayoutNotFound)?,
+        layout,
+    );
+    let result = world.run_system_cached_with(rebuild_layout, (entity, layout_entity))?;
+    if result.is_err()
+        && let Some(mut layout) = world.get_mut::<VertexLayout>(layout_entity)
+    {
+        *layout = previous;
+    }
+    result
+}
+
+/// Despawn the geometry. Its mesh is freed along with the last handle to it, so a mesh shared
+/// with other geometry or a loaded glTF stays alive for them.
 pub fn destroy(
     In(entity): In<Entity>,
     mut commands: Commands,
     geometries: Query<&Geometry>,
-    mut meshes: ResMut<Assets<Mesh>>,
 ) -> Result<()> {
-    let geometry = geometries
+    geometries
         .get(entity)
         .map_err(|_| ProcessingError::GeometryNotFound)?;
 
-    meshes.remove(&geometry.handle);
     commands.entity(entity).despawn();
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertex_alpha_marks_mesh_translucent() {
+        let mut mesh = Mesh::from(Triangle2d::default());
+        let mut geometry = Geometry::new(Handle::default(), Entity::PLACEHOLDER);
+        assert!(!geometry.has_translucent_colors(&mesh));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vec![[1.0, 1.0, 1.0, 1.0]; 3]);
+        assert!(!has_translucent_vertex_colors(&mesh));
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_COLOR,
+            vec![
+                [1.0, 1.0, 1.0, 1.0],
+                [1.0, 0.0, 0.0, 0.5],
+                [1.0, 1.0, 1.0, 1.0],
+            ],
+        );
+        assert!(has_translucent_vertex_colors(&mesh));
+        // cached until the colors are written through the geometry
+        assert!(!geometry.has_translucent_colors(&mesh));
+        geometry.colors_changed();
+        assert!(geometry.has_translucent_colors(&mesh));
+    }
+
+    #[test]
+    fn migrating_without_uv_drops_uvs() {
+        let mut mesh = Mesh::from(Cuboid::new(1.0, 1.0, 1.0));
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION).cloned();
+        let count = mesh.count_vertices();
+
+        migrate_attributes(
+            &mut mesh,
+            &[
+                (Mesh::ATTRIBUTE_POSITION, AttributeValue::Float3([0.0; 3])),
+                (Mesh::ATTRIBUTE_NORMAL, AttributeValue::Float3([0.0; 3])),
+                (Mesh::ATTRIBUTE_COLOR, AttributeValue::Float4([1.0; 4])),
+            ],
+        );
+
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_none());
+        assert_eq!(mesh.count_vertices(), count);
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                .map(|v| v.get_bytes().to_vec()),
+            positions.map(|v| v.get_bytes().to_vec())
+        );
+        // the cuboid had no colors, so they're filled in
+        assert!(matches!(
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR),
+            Some(VertexAttributeValues::Float32x4(colors)) if colors.len() == count
+        ));
+    }
+
+    #[test]
+    fn dynamic_geometry_coalesces_vertex_edits() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy::asset::AssetPlugin::default(),
+            GeometryPlugin,
+        ))
+        .init_asset::<Mesh>();
+
+        // change events for a box after rewriting its first vertex a thousand times in one frame
+        let mut modified_after_edits = |dynamic: bool| {
+            let world = app.world_mut();
+            let entity = world
+                .run_system_cached_with(create_box, (1.0, 1.0, 1.0))
+                .unwrap();
+            world
+                .run_system_cached_with(set_dynamic, (entity, dynamic))
+                .unwrap()
+                .unwrap();
+            for i in 0..1000 {
+                world
+                    .run_system_cached_with(set_vertex, (entity, 0, Vec3::splat(i as f32)))
+                    .unwrap()
+                    .unwrap();
+            }
+            app.update();
+
+            let id = app.world().get::<Geometry>(entity).unwrap().handle.id();
+            app.world()
+                .resource::<bevy::ecs::message::Messages<AssetEvent<Mesh>>>()
+                .iter_current_update_messages()
+                .filter(|e| **e == AssetEvent::Modified { id })
+                .count()
+        };
+
+        assert_eq!(modified_after_edits(false), 1000);
+        assert_eq!(modified_after_edits(true), 1);
+    }
+
+    #[test]
+    fn removing_attributes_reuses_unshared_layouts() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy::asset::AssetPlugin::default(),
+            GeometryPlugin,
+        ))
+        .init_asset::<Mesh>();
+        let world = app.world_mut();
+        let builtins = world.resource::<BuiltinAttributes>();
+        let (position, color, uv) = (builtins.position, builtins.color, builtins.uv);
+        let entity = world
+            .run_system_cached_with(create_box, (1.0, 1.0, 1.0))
+            .unwrap();
+        let layout = world.get::<Geometry>(entity).unwrap().layout;
+        let layouts = world.query::<&VertexLayout>().iter(world).count();
+
+        remove_attribute(world, entity, uv).unwrap();
+        remove_attribute(world, entity, color).unwrap();
+        assert_eq!(world.get::<Geometry>(entity).unwrap().layout, layout);
+        assert_eq!(world.query::<&VertexLayout>().iter(world).count(), layouts);
+
+        // a failed removal leaves the layout as it was
+        assert!(remove_attribute(world, entity, position).is_err());
+        assert!(
+            world
+                .get::<VertexLayout>(layout)
+                .unwrap()
+                .has_attribute(position)
+        );
+    }
+
+    #[test]
+    fn removing_from_a_used_layout_updates_its_geometry() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy::asset::AssetPlugin::default(),
+            GeometryPlugin,
+        ))
+        .init_asset::<Mesh>();
+        let world = app.world_mut();
+        let uv = world.resource::<BuiltinAttributes>().uv;
+        let entity = world
+            .run_system_cached_with(create_box, (1.0, 1.0, 1.0))
+            .unwrap();
+        let layout = world.get::<Geometry>(entity).unwrap().layout;
+
+        layout::remove_attribute(world, layout, uv).unwrap();
+        world
+            .run_system_cached_with(vertex, (entity, Vec3::ONE))
+            .unwrap()
+            .unwrap();
+
+        let geometry = world.get::<Geometry>(entity).unwrap();
+        let mesh = world
+            .resource::<Assets<Mesh>>()
+            .get(&geometry.handle)
+            .unwrap();
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_none());
+        let count = mesh.count_vertices();
+        assert!(mesh.attributes().all(|(_, values)| values.len() == count));
+    }
+
+    #[test]
+    fn moving_a_vertex_updates_cached_bounds() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy::asset::AssetPlugin::default(),
+            GeometryPlugin,
+        ))
+        .init_asset::<Mesh>();
+        let world = app.world_mut();
+        let entity = world
+            .run_system_cached_with(create_box, (1.0, 1.0, 1.0))
+            .unwrap();
+        let max_x = |world: &World| {
+            let geometry = world.get::<Geometry>(entity).unwrap();
+            let mesh = world
+                .resource::<Assets<Mesh>>()
+                .get(&geometry.handle)
+                .unwrap();
+            geometry.aabb(mesh).unwrap().max().x
+        };
+        assert_eq!(max_x(world), 0.5);
+
+        world
+            .run_system_cached_with(set_vertex, (entity, 0, Vec3::new(4.0, 0.0, 0.0)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(max_x(world), 4.0);
+    }
+
+    #[test]
+    fn cleared_geometry_keeps_its_mesh() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy::asset::AssetPlugin::default(),
+            GeometryPlugin,
+        ))
+        .init_asset::<Mesh>();
+        let world = app.world_mut();
+        let entity = world
+            .run_system_cached_with(create_box, (1.0, 1.0, 1.0))
+            .unwrap();
+        let handle = world.get::<Geometry>(entity).unwrap().handle.clone();
+
+        world
+            .run_system_cached_with(clear, entity)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            world
+                .run_system_cached_with(vertex_count, entity)
+                .unwrap()
+                .unwrap(),
+            0
+        );
+        assert_eq!(world.get::<Geometry>(entity).unwrap().handle, handle);
+        let mesh = world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        assert!(mesh.indices().is_none());
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_some());
+    }
+
+    #[test]
+    fn shrinking_positions_checks_indices() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy::asset::AssetPlugin::default(),
+            GeometryPlugin,
+        ))
+        .init_asset::<Mesh>();
+        let world = app.world_mut();
+        let entity = world
+            .run_system_cached_with(create, Topology::TriangleList)
+            .unwrap();
+        let quad = vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ];
+        world
+            .run_system_cached_with(set_positions, (entity, quad.clone()))
+            .unwrap()
+            .unwrap();
+        world
+            .run_system_cached_with(set_indices, (entity, vec![0, 1, 2, 0, 2, 3]))
+            .unwrap()
+            .unwrap();
+
+        // index 3 would be left dangling, so the mesh is untouched
+        let triangle = quad[..3].to_vec();
+        assert!(
+            world
+                .run_system_cached_with(set_positions, (entity, triangle.clone()))
+                .unwrap()
+                .is_err()
+        );
+        assert_eq!(
+            world
+                .run_system_cached_with(vertex_count, entity)
+                .unwrap()
+                .unwrap(),
+            4
+        );
+
+        world
+            .run_system_cached_with(set_indices, (entity, vec![0, 1, 2]))
+            .unwrap()
+            .unwrap();
+        world
+            .run_system_cached_with(set_positions, (entity, triangle))
+            .unwrap()
+            .unwrap();
+        let handle = world.get::<Geometry>(entity).unwrap().handle.clone();
+        let mesh = world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        assert_eq!(mesh.count_vertices(), 3);
+        assert!(matches!(
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR),
+            Some(VertexAttributeValues::Float32x4(colors)) if colors.len() == 3
+        ));
+    }
+
+    #[test]
+    fn resizing_positions_refills_colors_or_leaves_the_mesh_alone() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy::asset::AssetPlugin::default(),
+            GeometryPlugin,
+        ))
+        .init_asset::<Mesh>();
+        let world = app.world_mut();
+        let entity = world
+            .run_system_cached_with(create, Topology::TriangleList)
+            .unwrap();
+        let triangle = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]];
+        world
+            .run_system_cached_with(set_positions, (entity, triangle.clone()))
+            .unwrap()
+            .unwrap();
+        let handle = world.get::<Geometry>(entity).unwrap().handle.clone();
+        let opaque = {
+            let mesh = world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+            world
+                .get::<Geometry>(entity)
+                .unwrap()
+                .has_translucent_colors(mesh)
+        };
+        assert!(!opaque);
+
+        // the refilled colors are translucent, which the next draw has to see
+        world.get_mut::<Geometry>(entity).unwrap().current_color = [1.0, 0.0, 0.0, 0.5];
+        let mut quad = triangle.clone();
+        quad.push([0.0, 1.0, 0.0]);
+        world
+            .run_system_cached_with(set_positions, (entity, quad.clone()))
+            .unwrap()
+            .unwrap();
+        let mesh = world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        assert!(
+            world
+                .get::<Geometry>(entity)
+                .unwrap()
+                .has_translucent_colors(mesh)
+        );
+
+        // an attribute that can't be refilled fails the resize before anything is written
+        let unsupported =
+            MeshVertexAttribute::new("Unsupported", 988_540_917, VertexFormat::Uint32);
+        world
+            .resource_mut::<Assets<Mesh>>()
+            .get_mut(&handle)
+            .unwrap()
+            .insert_attribute(unsupported, vec![0_u32; 4]);
+        assert!(
+            world
+                .run_system_cached_with(set_positions, (entity, triangle))
+                .unwrap()
+                .is_err()
+        );
+        let mesh = world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        assert_eq!(mesh.count_vertices(), 4);
+        assert!(mesh.attributes().all(|(_, values)| values.len() == 4));
+    }
+}
@@ -0,0 +1,709 @@
+//! Mesh processing operations on retained geometry.
+use std::collections::HashMap;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    math::{Affine3A, Vec3A},
+    mesh::{Indices, MeshVertexAttribute, VertexAttributeValues},
+    prelude::*,
+    render::render_resource::PrimitiveTopology,
+};
+
+use processing_core::error::{ProcessingError, Result};
+
+use super::attribute::{get_mesh, get_mesh_mut};
+use super::{BuiltinAttributes, Geometry, VertexLayout};
+
+fn triangle_list(mesh: &Mesh) -> Result<()> {
+    match mesh.primitive_topology() {
+        PrimitiveTopology::TriangleList => Ok(()),
+        other => Err(ProcessingError::InvalidArgument(format!(
+            "Expected a triangle list geometry, got {other:?}"
+        ))),
+    }
+}
+
+fn positions(mesh: &Mesh) -> Result<&[[f32; 3]]> {
+    match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(data)) => Ok(data),
+        _ => Err(ProcessingError::InvalidArgument(
+            "Geometry missing Mesh::ATTRIBUTE_POSITION".into(),
+        )),
+    }
+}
+
+fn normals(mesh: &Mesh) -> Result<&[[f32; 3]]> {
+    match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(data)) => Ok(data),
+        _ => Err(ProcessingError::InvalidArgument(
+            "Geometry missing Mesh::ATTRIBUTE_NORMAL".into(),
+        )),
+    }
+}
+
+/// Mesh indices as `u32`, with non-indexed meshes treated as `0..vertex_count`.
+fn indices_u32(mesh: &Mesh) -> Vec<u32> {
+    match mesh.indices() {
+        Some(Indices::U32(data)) => data.clone(),
+        Some(Indices::U16(data)) => data.iter().map(|&i| i as u32).collect(),
+        None => (0..mesh.count_vertices() as u32).collect(),
+    }
+}
+
+/// Flip every triangle whose winding disagrees with its vertex normals so that all triangles are
+/// counter-clockwise when viewed from the side their normals point to. Returns the number of
+/// triangles flipped.
+pub fn fix_winding_indices(
+    positions: &[[f32; 3]],
+    normals: &[[f32; 3]],
+    indices: &mut [u32],
+) -> u32 {
+    let mut flipped = 0;
+    for tri in indices.chunks_exact_mut(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| i as usize);
+        if a.max(b).max(c) >= positions.len().min(normals.len()) {
+            continue;
+        }
+        let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(positions[i]));
+        let face = (pb - pa).cross(pc - pa);
+        let vertex_normal =
+            Vec3::from(normals[a]) + Vec3::from(normals[b]) + Vec3::from(normals[c]);
+        if face.dot(vertex_normal) < 0.0 {
+            tri.swap(1, 2);
+            flipped += 1;
+        }
+    }
+    flipped
+}
+
+pub fn fix_winding(
+    In(entity): In<Entity>,
+    mut geometries: Query<&mut Geometry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<u32> {
+    let mut mesh = get_mesh_mut(entity, &mut geometries, &mut meshes)?;
+    triangle_list(&mesh)?;
+
+    let mut indices = indices_u32(&mesh);
+    let flipped = fix_winding_indices(positions(&mesh)?, normals(&mesh)?, &mut indices);
+    if flipped > 0 {
+        mesh.insert_indices(Indices::U32(indices));
+    }
+    Ok(flipped)
+}
+
+/// Smooth vertex normals for a triangle list: each triangle's face normal, weighted by its area,
+/// summed into its three vertices and normalized, like three.js `computeVertexNormals`. Vertices
+/// no triangle uses get a zero normal.
+pub fn compute_normals_of(positions: &[[f32; 3]], indices: &[u32]) -> Vec<[f32; 3]> {
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| i as usize);
+        if a.max(b).max(c) >= positions.len() {
+            continue;
+        }
+        let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(positions[i]));
+        // the cross product's length is twice the triangle's area, which is the weighting we want
+        let face = (pb - pa).cross(pc - pa);
+        for i in [a, b, c] {
+            normals[i] += face;
+        }
+    }
+    normals
+        .into_iter()
+        .map(|n| n.normalize_or_zero().to_array())
+        .collect()
+}
+
+/// Replace a triangle list geometry's normals with [`compute_normals_of`] its positions. The
+/// geometry's layout must have the normal attribute.
+pub fn compute_normals(
+    In(entity): In<Entity>,
+    mut geometries: Query<&mut Geometry>,
+    layouts: Query<&VertexLayout>,
+    builtins: Res<BuiltinAttributes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let geometry = geometries
+        .get(entity)
+        .map_err(|_| ProcessingError::GeometryNotFound)?;
+    let layout = layouts
+        .get(geometry.layout)
+        .map_err(|_| ProcessingError::LayoutNotFound)?;
+    if !layout.has_attribute(builtins.normal) {
+        return Err(ProcessingError::InvalidArgument(
+            "Geometry layout has no normal attribute".into(),
+        ));
+    }
+
+    let mut mesh = get_mesh_mut(entity, &mut geometries, &mut meshes)?;
+    triangle_list(&mesh)?;
+    let normals = compute_normals_of(positions(&mesh)?, &indices_u32(&mesh));
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    Ok(())
+}
+
+/// Laplacian smoothing: move every vertex `factor` of the way toward the average of its
+/// neighbors, `iterations` times. Neighbors are the vertices sharing a triangle edge with it, so
+/// vertices split along a seam are smoothed separately. Vertices no triangle uses don't move.
+pub fn smooth_positions(
+    positions: &[[f32; 3]],
+    indices: &[u32],
+    iterations: u32,
+    factor: f32,
+) -> Vec<[f32; 3]> {
+    let mut neighbors = vec![Vec::new(); positions.len()];
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| i as usize);
+        if a.max(b).max(c) >= positions.len() {
+            continue;
+        }
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            neighbors[from].push(to);
+            neighbors[to].push(from);
+        }
+    }
+    for list in &mut neighbors {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    let mut current: Vec<Vec3> = positions.iter().map(|&p| Vec3::from(p)).collect();
+    for _ in 0..iterations {
+        current = current
+            .iter()
+            .zip(&neighbors)
+            .map(|(&p, around)| {
+                if around.is_empty() {
+                    return p;
+                }
+                let average =
+                    around.iter().map(|&i| current[i]).sum::<Vec3>() / around.len() as f32;
+                p.lerp(average, factor)
+            })
+            .collect();
+    }
+    current.into_iter().map(Vec3::to_array).collect()
+}
+
+/// Apply [`smooth_positions`] to a triangle list geometry. Normals are left as they were, so
+/// follow with [`compute_normals`] if they should match the new shape.
+pub fn smooth(
+    In((entity, iterations, factor)): In<(Entity, u32, f32)>,
+    mut geometries: Query<&mut Geometry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    if !factor.is_finite() {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Smoothing factor must be a number, got {factor}"
+        )));
+    }
+    let mut mesh = get_mesh_mut(entity, &mut geometries, &mut meshes)?;
+    triangle_list(&mesh)?;
+    let smoothed = smooth_positions(positions(&mesh)?, &indices_u32(&mesh), iterations, factor);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, smoothed);
+    Ok(())
+}
+
+/// Move positions by `transform` and turn normals with its inverse transpose, renormalized so
+/// scaling doesn't stretch them. Other attributes are left alone.
+fn transform_values(
+    attr: &MeshVertexAttribute,
+    values: &mut VertexAttributeValues,
+    transform: Affine3A,
+) {
+    if attr.id == Mesh::ATTRIBUTE_POSITION.id
+        && let VertexAttributeValues::Float32x3(positions) = values
+    {
+        for p in positions {
+            *p = transform.transform_point3(Vec3::from(*p)).to_array();
+        }
+    } else if attr.id == Mesh::ATTRIBUTE_NORMAL.id
+        && let VertexAttributeValues::Float32x3(normals) = values
+    {
+        let normal_matrix = transform.matrix3.inverse().transpose();
+        for n in normals {
+            *n = (normal_matrix * Vec3A::from(*n)).normalize_or_zero().into();
+        }
+    }
+}
+
+/// Bake `transform` into a geometry's vertices, unlike the draw transform which is applied each
+/// time it's drawn.
+pub fn apply_transform(
+    In((entity, transform)): In<(Entity, Affine3A)>,
+    mut geometries: Query<&mut Geometry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let mut mesh = get_mesh_mut(entity, &mut geometries, &mut meshes)?;
+    for (attr, values) in mesh.attributes_mut() {
+        transform_values(attr, values, transform);
+    }
+    Ok(())
+}
+
+/// Append `src` to `dst` when both hold the same float format.
+fn extend_values(dst: &mut VertexAttributeValues, src: &VertexAttributeValues) -> bool {
+    match (dst, src) {
+        (VertexAttributeValues::Float32(d), VertexAttributeValues::Float32(s)) => d.extend(s),
+        (VertexAttributeValues::Float32x2(d), VertexAttributeValues::Float32x2(s)) => d.extend(s),
+        (VertexAttributeValues::Float32x3(d), VertexAttributeValues::Float32x3(s)) => d.extend(s),
+        (VertexAttributeValues::Float32x4(d), VertexAttributeValues::Float32x4(s)) => d.extend(s),
+        _ => return false,
+    }
+    true
+}
+
+/// Concatenate meshes into one, each moved by its transform first, with indices offset to the
+/// vertices they came from. Every mesh must have the same attributes and the same point, line or
+/// triangle list topology; strips can't be joined without stitching them together.
+pub fn merge_meshes(sources: &[(&Mesh, Affine3A)]) -> Result<Mesh> {
+    let Some(&(first, _)) = sources.first() else {
+        return Err(ProcessingError::InvalidArgument(
+            "Nothing to merge, no geometries given".into(),
+        ));
+    };
+    let topology = first.primitive_topology();
+    if matches!(
+        topology,
+        PrimitiveTopology::LineStrip | PrimitiveTopology::TriangleStrip
+    ) {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Can't merge {topology:?} geometry, only lists"
+        )));
+    }
+    let indexed = sources.iter().any(|(mesh, _)| mesh.indices().is_some());
+
+    let mut merged = Mesh::new(topology, RenderAssetUsages::default());
+    let mut indices = Vec::new();
+    let mut base = 0u32;
+    for (i, &(mesh, transform)) in sources.iter().enumerate() {
+        if mesh.primitive_topology() != topology
+            || mesh.attributes().count() != first.attributes().count()
+        {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "Geometry {i} has a different layout or topology than the first"
+            )));
+        }
+        for (attr, values) in mesh.attributes() {
+            let mut values = values.clone();
+            transform_values(attr, &mut values, transform);
+
+            let compatible = match merged.attribute_mut(attr.id) {
+                Some(existing) => extend_values(existing, &values),
+                None if i == 0 => {
+                    merged.insert_attribute(*attr, values);
+                    true
+                }
+                None => false,
+            };
+            if !compatible {
+                return Err(ProcessingError::InvalidArgument(format!(
+                    "Geometry {i} has a different {} attribute than the first",
+                    attr.name
+                )));
+            }
+        }
+
+        if indexed {
+            indices.extend(indices_u32(mesh).into_iter().map(|index| index + base));
+        }
+        base += mesh.count_vertices() as u32;
+    }
+    if indexed {
+        merged.insert_indices(Indices::U32(indices));
+    }
+    Ok(merged)
+}
+
+/// Merge geometries into a new one with [`merge_meshes`], placing each by its transform when
+/// `transforms` is given. The result shares the first geometry's layout, and the sources are left
+/// as they were.
+pub fn merge(
+    In((entities, transforms)): In<(Vec<Entity>, Option<Vec<Affine3A>>)>,
+    mut commands: Commands,
+    geometries: Query<&Geometry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<Entity> {
+    if let Some(transforms) = &transforms
+        && transforms.len() != entities.len()
+    {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Got {} transforms for {} geometries",
+            transforms.len(),
+            entities.len()
+        )));
+    }
+    let mut sources = Vec::with_capacity(entities.len());
+    for (i, &entity) in entities.iter().enumerate() {
+        let mesh = get_mesh(entity, &geometries, &meshes)?;
+        let transform = transforms.as_ref().map_or(Affine3A::IDENTITY, |t| t[i]);
+        sources.push((mesh, transform));
+    }
+    let merged = merge_meshes(&sources)?;
+
+    let layout = geometries
+        .get(entities[0])
+        .map_err(|_| ProcessingError::GeometryNotFound)?
+        .layout;
+    let handle = meshes.add(merged);
+    Ok(commands.spawn(Geometry::new(handle, layout)).id())
+}
+
+/// Give every corner its own vertex and drop the index buffer.
+pub fn unindex_mesh(mesh: &mut Mesh) {
+    mesh.duplicate_vertices();
+}
+
+/// Share vertices whose attributes are all bitwise equal, keeping the first of each in order, and
+/// index them. Already indexed meshes are welded further where they repeat vertices.
+pub fn index_mesh(mesh: &mut Mesh) {
+    let count = mesh.count_vertices();
+    let attributes: Vec<(&[u8], usize)> = mesh
+        .attributes()
+        .filter_map(|(_, values)| {
+            let bytes = values.get_bytes();
+            (count > 0).then(|| (bytes, bytes.len() / count))
+        })
+        .collect();
+
+    let mut seen: HashMap<Vec<u8>, u32> = HashMap::new();
+    let mut kept = Vec::new();
+    let mut remap = Vec::with_capacity(count);
+    for vertex in 0..count {
+        let key: Vec<u8> = attributes
+            .iter()
+            .flat_map(|(bytes, stride)| &bytes[vertex * stride..(vertex + 1) * stride])
+            .copied()
+            .collect();
+        let index = *seen.entry(key).or_insert_with(|| {
+            kept.push(vertex as u32);
+            kept.len() as u32 - 1
+        });
+        remap.push(index);
+    }
+    let indices: Vec<u32> = indices_u32(mesh)
+        .into_iter()
+        .map(|i| remap[i as usize])
+        .collect();
+
+    // gather the kept vertices by expanding them as if they were the index buffer
+    mesh.insert_indices(Indices::U32(kept));
+    mesh.duplicate_vertices();
+    mesh.insert_indices(Indices::U32(indices));
+}
+
+/// Convert a geometry to use an index buffer with [`index_mesh`], or to give every corner its own
+/// vertex with [`unindex_mesh`], as flat shading and some exporters need.
+pub fn set_indexed(
+    In((entity, indexed)): In<(Entity, bool)>,
+    mut geometries: Query<&mut Geometry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let mut mesh = get_mesh_mut(entity, &mut geometries, &mut meshes)?;
+    if indexed {
+        index_mesh(&mut mesh);
+    } else {
+        unindex_mesh(&mut mesh);
+    }
+    Ok(())
+}
+
+/// Problems found by [`validate_indices`]. Triangles are numbered by their position in the index
+/// buffer and edges are pairs of vertex indices, smallest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeometryReport {
+    /// Triangles with zero area, repeated vertices or out of range indices.
+    pub degenerate_triangles: Vec<u32>,
+    /// Vertices that no triangle uses.
+    pub unreferenced_vertices: Vec<u32>,
+    /// Edges shared by more than two triangles.
+    pub non_manifold_edges: Vec<(u32, u32)>,
+}
+
+impl GeometryReport {
+    pub fn is_manifold(&self) -> bool {
+        self.non_manifold_edges.is_empty()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.degenerate_triangles.is_empty()
+            && self.unreferenced_vertices.is_empty()
+            && self.non_manifold_edges.is_empty()
+    }
+}
+
+pub fn validate_indices(positions: &[[f32; 3]], indices: &[u32]) -> GeometryReport {
+    let mut report = GeometryReport::default();
+    let mut referenced = vec![false; positions.len()];
+    let mut edges: HashMap<(u32, u32), u32> = HashMap::new();
+
+    for (t, tri) in indices.chunks_exact(3).enumerate() {
+        let [a, b, c] = [tri[0], tri[1], tri[2]];
+        if [a, b, c].iter().any(|&i| i as usize >= positions.len()) {
+            report.degenerate_triangles.push(t as u32);
+            continue;
+        }
+        for i in [a, b, c] {
+            referenced[i as usize] = true;
+        }
+        let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(positions[i as usize]));
+        if a == b || b == c || a == c || (pb - pa).cross(pc - pa).length_squared() <= f32::EPSILON {
+            report.degenerate_triangles.push(t as u32);
+            continue;
+        }
+        for (u, v) in [(a, b), (b, c), (c, a)] {
+            *edges.entry((u.min(v), u.max(v))).or_default() += 1;
+        }
+    }
+
+    report.unreferenced_vertices = referenced
+        .iter()
+        .enumerate()
+        .filter(|(_, used)| !**used)
+        .map(|(i, _)| i as u32)
+        .collect();
+    report.non_manifold_edges = edges
+        .into_iter()
+        .filter(|(_, count)| *count > 2)
+        .map(|(edge, _)| edge)
+        .collect();
+    report.non_manifold_edges.sort_unstable();
+    report
+}
+
+/// Check a triangle list geometry for degenerate triangles, unused vertices and non-manifold
+/// edges without modifying it.
+pub fn validate(
+    In(entity): In<Entity>,
+    geometries: Query<&Geometry>,
+    meshes: Res<Assets<Mesh>>,
+) -> Result<GeometryReport> {
+    let mesh = get_mesh(entity, &geometries, &meshes)?;
+    triangle_list(mesh)?;
+    Ok(validate_indices(positions(mesh)?, &indices_u32(mesh)))
+}
+
+/// A sphere around `positions` centered on their bounding box, as `(center, radius)`. Not the
+/// tightest possible sphere, but cheap and good enough for culling and framing.
+pub fn bounding_sphere_of(positions: &[[f32; 3]]) -> (Vec3, f32) {
+    if positions.is_empty() {
+        return (Vec3::ZERO, 0.0);
+    }
+    let (min, max) = positions.iter().fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(min, max), p| (min.min(Vec3::from(*p)), max.max(Vec3::from(*p))),
+    );
+    let center = (min + max) * 0.5;
+    let radius = positions
+        .iter()
+        .map(|p| center.distance(Vec3::from(*p)))
+        .fold(0.0, f32::max);
+    (center, radius)
+}
+
+/// The bounding sphere of a geometry's positions in its local space.
+pub fn bounding_sphere(
+    In(entity): In<Entity>,
+    geometries: Query<&Geometry>,
+    meshes: Res<Assets<Mesh>>,
+) -> Result<(Vec3, f32)> {
+    let mesh = get_mesh(entity, &geometries, &meshes)?;
+    Ok(bounding_sphere_of(positions(mesh)?))
+}
+
+/// The `(min, max)` corners of the box around `positions`, or `None` when there are none.
+pub fn bounds_of(positions: &[[f32; 3]]) -> Option<(Vec3, Vec3)> {
+    let (first, rest) = positions.split_first()?;
+    Some(
+        rest.iter()
+            .fold((Vec3::from(*first), Vec3::from(*first)), |(min, max), p| {
+                (min.min(Vec3::from(*p)), max.max(Vec3::from(*p)))
+            }),
+    )
+}
+
+/// The axis aligned bounding box of a geometry's positions in its local space, as `(min, max)`.
+pub fn bounds(
+    In(entity): In<Entity>,
+    geometries: Query<&Geometry>,
+    meshes: Res<Assets<Mesh>>,
+) -> Result<(Vec3, Vec3)> {
+    let mesh = get_mesh(entity, &geometries, &meshes)?;
+    bounds_of(positions(mesh)?)
+        .ok_or_else(|| ProcessingError::InvalidArgument("Geometry has no vertices to bound".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flipped_triangle_is_rewound() {
+        let positions = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+        ];
+        let normals = [[0.0, 0.0, 1.0]; 4];
+        // the second triangle is clockwise when viewed from +z
+        let mut indices = [0, 1, 2, 1, 2, 3];
+
+        assert_eq!(fix_winding_indices(&positions, &normals, &mut indices), 1);
+        assert_eq!(indices, [0, 1, 2, 1, 3, 2]);
+        assert_eq!(fix_winding_indices(&positions, &normals, &mut indices), 0);
+    }
+
+    #[test]
+    fn shared_vertex_normals_are_area_weighted() {
+        // a large triangle facing +z and a small one facing -x, meeting at vertices 0 and 1
+        let positions = [
+            [0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [-4.0, 0.0, 0.0],
+            [0.0, 0.0, 0.25],
+        ];
+        let normals = compute_normals_of(&positions, &[0, 1, 2, 0, 3, 1]);
+
+        assert!(Vec3::from(normals[2]).abs_diff_eq(Vec3::Z, 1e-6));
+        assert!(Vec3::from(normals[3]).abs_diff_eq(Vec3::NEG_X, 1e-6));
+        // the shared vertices lean toward the larger triangle
+        let shared = Vec3::from(normals[0]);
+        assert!((shared.length() - 1.0).abs() < 1e-5);
+        assert!(shared.z.abs() > shared.x.abs() * 10.0);
+        assert_eq!(normals[0], normals[1]);
+
+        // non-indexed meshes are read as consecutive triples
+        let flat = compute_normals_of(&positions[..3], &[0, 1, 2]);
+        assert!(
+            flat.iter()
+                .all(|n| Vec3::from(*n).abs_diff_eq(Vec3::Z, 1e-6))
+        );
+    }
+
+    #[test]
+    fn smoothing_pulls_a_spike_toward_its_neighbors() {
+        // a flat fan around vertex 0, which is pushed up out of the plane
+        let positions = [
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [-1.0, 0.0, 0.0],
+            [0.0, -1.0, 0.0],
+        ];
+        let indices = [0, 1, 2, 0, 2, 3, 0, 3, 4, 0, 4, 1];
+
+        let once = smooth_positions(&positions, &indices, 1, 0.5);
+        assert!(Vec3::from(once[0]).abs_diff_eq(Vec3::new(0.0, 0.0, 0.5), 1e-6));
+        let many = smooth_positions(&positions, &indices, 10, 0.5);
+        assert!(many[0][2] < once[0][2]);
+        assert_eq!(smooth_positions(&positions, &indices, 0, 0.5), positions);
+    }
+
+    #[test]
+    fn merged_indices_point_at_their_own_vertices() {
+        let cube = Mesh::from(Cuboid::new(1.0, 1.0, 1.0));
+        let count = cube.count_vertices();
+        let moved = Affine3A::from_translation(Vec3::new(10.0, 0.0, 0.0));
+        let merged = merge_meshes(&[(&cube, Affine3A::IDENTITY), (&cube, moved)]).unwrap();
+
+        assert_eq!(merged.count_vertices(), count * 2);
+        let indices = indices_u32(&merged);
+        assert_eq!(indices.len(), indices_u32(&cube).len() * 2);
+        assert!(
+            indices[indices.len() / 2..]
+                .iter()
+                .all(|&i| i as usize >= count)
+        );
+        let (min, max) = bounds_of(positions(&merged).unwrap()).unwrap();
+        assert_eq!((min.x, max.x), (-0.5, 10.5));
+
+        let lines = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; 2]);
+        assert!(merge_meshes(&[(&cube, Affine3A::IDENTITY), (&lines, moved)]).is_err());
+        assert!(merge_meshes(&[]).is_err());
+    }
+
+    #[test]
+    fn quad_round_trips_through_nonindexed() {
+        let mut quad = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            ],
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 4])
+        .with_inserted_indices(Indices::U32(vec![0, 1, 2, 0, 2, 3]));
+
+        unindex_mesh(&mut quad);
+        assert_eq!(quad.count_vertices(), 6);
+        assert!(quad.indices().is_none());
+
+        index_mesh(&mut quad);
+        assert_eq!(quad.count_vertices(), 4);
+        assert_eq!(indices_u32(&quad), [0, 1, 2, 0, 2, 3]);
+        assert_eq!(positions(&quad).unwrap()[3], [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn scaled_normals_stay_unit_length() {
+        let attr = Mesh::ATTRIBUTE_NORMAL;
+        let mut normals = VertexAttributeValues::Float32x3(vec![[1.0, 1.0, 0.0]]);
+        transform_values(
+            &attr,
+            &mut normals,
+            Affine3A::from_scale(Vec3::new(2.0, 1.0, 1.0)),
+        );
+        let VertexAttributeValues::Float32x3(normals) = normals else {
+            unreachable!();
+        };
+        // stretching along x tilts the normal toward y
+        let expected = Vec3::new(0.5, 1.0, 0.0).normalize();
+        assert!(Vec3::from(normals[0]).abs_diff_eq(expected, 1e-6));
+    }
+
+    #[test]
+    fn degenerate_triangle_is_flagged() {
+        let positions = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [5.0, 5.0, 5.0],
+        ];
+        // the second triangle is collinear and vertex 4 is never used
+        let indices = [0, 1, 2, 0, 1, 3];
+
+        let report = validate_indices(&positions, &indices);
+        assert_eq!(report.degenerate_triangles, [1]);
+        assert_eq!(report.unreferenced_vertices, [4]);
+        assert!(report.is_manifold());
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn bounds_cover_every_position() {
+        let positions = [[1.0, -2.0, 3.0], [-4.0, 5.0, 0.5], [2.0, 0.0, -6.0]];
+        assert_eq!(
+            bounds_of(&positions),
+            Some((Vec3::new(-4.0, -2.0, -6.0), Vec3::new(2.0, 5.0, 3.0)))
+        );
+        assert_eq!(bounds_of(&[]), None);
+    }
+
+    #[test]
+    fn unit_box_sphere_is_half_diagonal() {
+        let mesh = Mesh::from(Cuboid::new(1.0, 1.0, 1.0));
+        let (center, radius) = bounding_sphere_of(positions(&mesh).unwrap());
+        assert!(center.length() < 1e-6);
+        assert!((radius - 3.0_f32.sqrt() / 2.0).abs() < 1e-5);
+    }
+}
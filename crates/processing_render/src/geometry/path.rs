@@ -0,0 +1,215 @@
+//! Building retained geometry from 2D path commands. Between [`begin_path`] and [`end_path`] a
+//! geometry collects lines, beziers, curves and arcs like `beginShape`/`endShape` does; ending the
+//! path tessellates its fill into the geometry's vertex and index buffers.
+use bevy::{mesh::VertexAttributeValues, prelude::*};
+
+use processing_core::error::{ProcessingError, Result};
+
+use super::{Attribute, BuiltinAttributes, Geometry, VertexLayout, push_index, push_vertex};
+use crate::render::command::ShapeKind;
+use crate::render::primitive::{
+    ShapeBuilder, StrokeConfig, VertexType, arc_points, build_polygon_fill, empty_mesh,
+};
+
+#[derive(Component, Debug, Clone)]
+pub struct GeometryPathBuilder(pub ShapeBuilder);
+
+impl Default for GeometryPathBuilder {
+    fn default() -> Self {
+        Self(ShapeBuilder::new(ShapeKind::Polygon))
+    }
+}
+
+/// Tessellate the fill of `path` into a standalone mesh with positions and `u32` indices.
+pub fn tessellate(path: &GeometryPathBuilder, close: bool) -> Mesh {
+    let mut mesh = empty_mesh();
+    build_polygon_fill(
+        &mut mesh,
+        &path.0,
+        close,
+        Color::WHITE,
+        &StrokeConfig::default(),
+    );
+    mesh
+}
+
+pub fn begin_path(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    geometries: Query<(), With<Geometry>>,
+) -> Result<()> {
+    if !geometries.contains(entity) {
+        return Err(ProcessingError::GeometryNotFound);
+    }
+    commands
+        .entity(entity)
+        .insert(GeometryPathBuilder::default());
+    Ok(())
+}
+
+pub fn path_vertex(
+    In((entity, vertex)): In<(Entity, VertexType)>,
+    mut paths: Query<&mut GeometryPathBuilder>,
+) -> Result<()> {
+    let mut path = paths.get_mut(entity).map_err(|_| no_path())?;
+    path.0.push_vertex(vertex);
+    Ok(())
+}
+
+/// Append an elliptical arc around `center` with `radii`, from angle `start` to `stop` in radians,
+/// as a run of path vertices. The path runs straight from its last vertex to the arc's start.
+pub fn path_arc(
+    In((entity, center, radii, start, stop)): In<(Entity, Vec2, Vec2, f32, f32)>,
+    mut paths: Query<&mut GeometryPathBuilder>,
+) -> Result<()> {
+    let mut path = paths.get_mut(entity).map_err(|_| no_path())?;
+    if !(center.is_finite() && radii.is_finite() && start.is_finite() && stop.is_finite()) {
+        return Err(ProcessingError::InvalidArgument(
+            "Arc center, radii and angles must be finite".into(),
+        ));
+    }
+    if radii.min_element() < 0.0 {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Arc radii must not be negative, got {radii}"
+        )));
+    }
+    for point in arc_points(center.x, center.y, radii.x, radii.y, start, stop) {
+        path.0.push_vertex(VertexType::Normal(point.x, point.y));
+    }
+    Ok(())
+}
+
+pub fn path_contour(
+    In((entity, begin)): In<(Entity, bool)>,
+    mut paths: Query<&mut GeometryPathBuilder>,
+) -> Result<()> {
+    let mut path = paths.get_mut(entity).map_err(|_| no_path())?;
+    if begin {
+        path.0.begin_contour();
+    } else {
+        path.0.end_contour();
+    }
+    Ok(())
+}
+
+pub fn end_path(
+    In((entity, close)): In<(Entity, bool)>,
+    mut commands: Commands,
+    mut geometries: Query<(&mut Geometry, &GeometryPathBuilder)>,
+    layouts: Query<&VertexLayout>,
+    attrs: Query<&Attribute>,
+    builtins: Res<BuiltinAttributes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) -> Result<()> {
+    let (mut geometry, path) = geometries.get_mut(entity).map_err(|_| no_path())?;
+    let layout = layouts
+        .get(geometry.layout)
+        .map_err(|_| ProcessingError::LayoutNotFound)?;
+    // check the layout up front so a bad attribute can't leave the path half appended
+    if layout
+        .attributes()
+        .iter()
+        .any(|&attr| !attrs.contains(attr))
+    {
+        return Err(ProcessingError::InvalidEntity);
+    }
+    let mesh = meshes
+        .get_mut(&geometry.handle)
+        .map(|m| m.into_inner())
+        .ok_or(ProcessingError::GeometryNotFound)?;
+
+    let tessellated = tessellate(path, close);
+    let base = mesh.count_vertices() as u32;
+    // the path is indexed, so index any loose vertices added before it to keep them drawn
+    if mesh.indices().is_none() {
+        for i in 0..base {
+            push_index(mesh, i);
+        }
+    }
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        tessellated.attribute(Mesh::ATTRIBUTE_POSITION)
+    {
+        for &position in positions {
+            push_vertex(
+                mesh,
+                &mut geometry,
+                layout,
+                &attrs,
+                &builtins,
+                Vec3::from(position),
+            )?;
+        }
+    }
+    if let Some(indices) = tessellated.indices() {
+        for i in indices.iter() {
+            push_index(mesh, base + i as u32);
+        }
+    }
+
+    commands.entity(entity).remove::<GeometryPathBuilder>();
+    Ok(())
+}
+
+fn no_path() -> ProcessingError {
+    ProcessingError::InvalidArgument("No path in progress, call begin_path first".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyon::geom::{CubicBezierSegment, point};
+    use lyon::tessellation::FillOptions;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn bezier_path_is_flattened() {
+        let mut path = GeometryPathBuilder::default();
+        path.0.push_vertex(VertexType::Normal(0.0, 0.0));
+        path.0.push_vertex(VertexType::CubicBezier {
+            cx1: 0.0,
+            cy1: 100.0,
+            cx2: 100.0,
+            cy2: 100.0,
+            x: 100.0,
+            y: 0.0,
+        });
+        let mesh = tessellate(&path, true);
+
+        let segment = CubicBezierSegment {
+            from: point(0.0, 0.0),
+            ctrl1: point(0.0, 100.0),
+            ctrl2: point(100.0, 100.0),
+            to: point(100.0, 0.0),
+        };
+        let mut flattened = 1;
+        segment.for_each_flattened(FillOptions::DEFAULT_TOLERANCE, &mut |_| flattened += 1);
+
+        assert_eq!(mesh.count_vertices(), flattened);
+        let index_count = mesh.indices().map_or(0, |i| i.len());
+        assert_eq!(index_count, (flattened - 2) * 3);
+    }
+
+    #[test]
+    fn arc_appends_nothing_when_invalid() {
+        let mut world = World::new();
+        let entity = world.spawn(GeometryPathBuilder::default()).id();
+        let arc = |world: &mut World, radii: Vec2| {
+            world.run_system_cached_with(path_arc, (entity, Vec2::ZERO, radii, 0.0, PI))
+        };
+
+        assert!(arc(&mut world, Vec2::new(-1.0, 10.0)).unwrap().is_err());
+        assert!(arc(&mut world, Vec2::new(f32::NAN, 10.0)).unwrap().is_err());
+        let path = &world.get::<GeometryPathBuilder>(entity).unwrap().0;
+        assert!(path.contours.iter().all(|c| c.vertices.is_empty()));
+
+        arc(&mut world, Vec2::splat(10.0)).unwrap().unwrap();
+        let path = &world.get::<GeometryPathBuilder>(entity).unwrap().0;
+        let vertices: Vec<_> = path.contours.iter().flat_map(|c| &c.vertices).collect();
+        assert!(
+            matches!(vertices.first(), Some(VertexType::Normal(x, y)) if *x == 10.0 && *y == 0.0)
+        );
+        assert!(
+            matches!(vertices.last(), Some(VertexType::Normal(x, _)) if (*x + 10.0).abs() < 1e-4)
+        );
+    }
+}
@@ -2,10 +2,7 @@
 //! materials, cameras, and lights.
 
 use bevy::{
-    asset::{
-        AssetPath, LoadState, handle_internal_asset_events,
-        io::{AssetSourceId, embedded::GetAssetServer},
-    },
+    asset::{AssetPath, LoadState, handle_internal_asset_events, io::embedded::GetAssetServer},
     camera::visibility::RenderLayers,
     ecs::system::RunSystemOnce,
     gltf::{Gltf, GltfMaterial, GltfMeshName},
@@ -18,7 +15,7 @@ use crate::geometry::{BuiltinAttributes, Geometry, layout::VertexLayout};
 use crate::graphics;
 use crate::material::ProcessingMaterial;
 use crate::render::material::{ProcessingExtendedMaterial, UntypedMaterial};
-use processing_core::config::{Config, ConfigKey};
+use processing_core::config::Config;
 use processing_core::error::{ProcessingError, Result};
 
 #[derive(Component)]
@@ -26,8 +23,8 @@ pub struct GltfNodeTransform(pub Transform);
 
 fn resolve_asset_path(config: &Config, path: &str) -> AssetPath<'static> {
     let asset_path = AssetPath::parse(path).into_owned();
-    match config.get(ConfigKey::AssetRootPath) {
-        Some(_) => asset_path.with_source(AssetSourceId::from("assets_directory")),
+    match crate::image::asset_source(config) {
+        Some(source) => asset_path.with_source(source),
         None => asset_path,
     }
 }
@@ -128,6 +125,21 @@ pub fn load(
     Ok(entity)
 }
 
+/// Despawn the scene `gltf_entity` spawned, including any lights taken from it, and drop its
+/// handle. Geometry and materials taken from the file hold their own handles, so the assets they
+/// use are only freed once those are destroyed too.
+pub fn unload(In(gltf_entity): In<Entity>, world: &mut World) -> Result<()> {
+    let instance_id = world
+        .get::<GltfHandle>(gltf_entity)
+        .ok_or(ProcessingError::InvalidEntity)?
+        .instance_id;
+    world.resource_scope(|world, mut spawner: Mut<WorldInstanceSpawner>| {
+        spawner.despawn_instance_sync(world, &instance_id);
+    });
+    world.despawn(gltf_entity);
+    Ok(())
+}
+
 pub fn geometry(
     In((gltf_entity, name)): In<(Entity, String)>,
     world: &mut World,
@@ -243,7 +255,7 @@ pub fn material(
         .resource_mut::<Assets<ProcessingExtendedMaterial>>()
         .add(ExtendedMaterial {
             base: standard,
-            extension: ProcessingMaterial { blend_state: None },
+            extension: ProcessingMaterial::default(),
         });
     let entity = world.spawn(UntypedMaterial(handle.untyped())).id();
     Ok(entity)
@@ -304,27 +316,23 @@ pub fn camera(In((gltf_entity, index)): In<(Entity, usize)>, world: &mut World)
 
     match projection {
         Projection::Perspective(p) => {
-            world
-                .run_system_cached_with(graphics::perspective, (graphics_entity, p))
-                .unwrap()?;
+            world.run_system_cached_with(graphics::perspective, (graphics_entity, p))??;
         }
         Projection::Orthographic(o) => {
-            world
-                .run_system_cached_with(
-                    graphics::ortho,
-                    (
-                        graphics_entity,
-                        graphics::OrthoArgs {
-                            left: o.area.min.x,
-                            right: o.area.max.x,
-                            bottom: o.area.min.y,
-                            top: o.area.max.y,
-                            near: o.near,
-                            far: o.far,
-                        },
-                    ),
-                )
-                .unwrap()?;
+            world.run_system_cached_with(
+                graphics::ortho,
+                (
+                    graphics_entity,
+                    graphics::OrthoArgs {
+                        left: o.area.min.x,
+                        right: o.area.max.x,
+                        bottom: o.area.min.y,
+                        top: o.area.max.y,
+                        near: o.near,
+                        far: o.far,
+                    },
+                ),
+            )??;
         }
         Projection::Custom(_) => {
             return Err(ProcessingError::GltfLoadError(
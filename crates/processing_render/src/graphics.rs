@@ -3,14 +3,18 @@
 //!
 //! In Bevy terms, a graphics object is represented as an entity with a camera component
 //! configured to render to a specific surface (either a window or an offscreen image).
+use std::sync::Arc;
+use std::time::Duration;
+
 use bevy::{
     camera::{
         CameraMainTextureUsages, CameraOutputMode, CameraProjection, ClearColorConfig, Hdr,
         ImageRenderTarget, MsaaWriteback, Projection, RenderTarget, visibility::RenderLayers,
     },
     core_pipeline::tonemapping::Tonemapping,
-    ecs::query::QueryEntityError,
+    ecs::{query::QueryEntityError, system::InMut},
     math::{Mat4, Vec3A},
+    platform::time::Instant,
     prelude::*,
     render::{
         RenderApp,
@@ -22,17 +26,22 @@ use bevy::{
         sync_world::MainEntity,
         view::ViewTarget,
     },
-    window::WindowRef,
+    window::{WindowRef, WindowResized},
 };
 
 use crate::{
     Flush,
-    image::{Image, create_readback_buffer, pixel_size, pixels_to_bytes},
+    geometry::capture::GeometryCapture,
+    image::{
+        Image, SaveFormat, bytes_to_pixels, bytes_to_pixels_into, create_readback_buffer, encode,
+        pixel_size, pixels_to_bytes, to_srgba8,
+    },
     render::{
         BATCH_INDEX_STEP, RenderState,
         command::{CommandBuffer, DrawCommand},
     },
     surface::Surface,
+    time::ProcessingFrame,
 };
 use processing_core::error::{ProcessingError, Result};
 
@@ -43,7 +52,8 @@ pub struct GraphicsPlugin;
 impl Plugin for GraphicsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<RenderLayersManager>()
-            .add_systems(PostUpdate, sync_to_surface);
+            .add_systems(PreUpdate, resize_to_window)
+            .add_systems(PostUpdate, (sync_to_surface, resize_fps_overlay));
     }
 }
 
@@ -54,6 +64,126 @@ pub struct Graphics {
     pub size: Extent3d,
 }
 
+/// Draws the frame rate and frame count over the sketch at the end of every frame. The overlay
+/// renders through its own 2D camera and render layer, so the sketch's camera, projection and
+/// depth buffer don't affect it.
+#[derive(Component, Debug)]
+pub struct FpsOverlay {
+    camera: Entity,
+    pub(crate) render_layers: RenderLayers,
+    last_frame: Option<Instant>,
+    fps: f32,
+}
+
+/// The camera drawing a graphics' [`FpsOverlay`], active while that graphics is flushing.
+#[derive(Component, Debug)]
+pub struct FpsOverlayCamera(pub Entity);
+
+impl FpsOverlay {
+    fn new(camera: Entity, render_layers: RenderLayers) -> Self {
+        Self {
+            camera,
+            render_layers,
+            last_frame: None,
+            fps: 0.0,
+        }
+    }
+
+    /// Fold the time since the previous frame into a smoothed frame rate.
+    fn tick(&mut self, now: Instant) -> f32 {
+        if let Some(last) = self.last_frame {
+            let dt = now.duration_since(last).as_secs_f32();
+            if dt > 0.0 {
+                let fps = 1.0 / dt;
+                self.fps = if self.fps == 0.0 {
+                    fps
+                } else {
+                    self.fps * 0.9 + fps * 0.1
+                };
+            }
+        }
+        self.last_frame = Some(now);
+        self.fps
+    }
+}
+
+pub fn show_fps(
+    In((entity, show)): In<(Entity, bool)>,
+    mut commands: Commands,
+    mut layer_manager: ResMut<RenderLayersManager>,
+    graphics: Query<(&RenderTarget, &SurfaceSize, Has<Hdr>, Option<&FpsOverlay>), With<Graphics>>,
+) -> Result<()> {
+    let (target, size, hdr, overlay) = graphics
+        .get(entity)
+        .map_err(|_| ProcessingError::GraphicsNotFound)?;
+    match (show, overlay) {
+        (true, None) => {
+            let render_layers = layer_manager.allocate();
+            let mut camera = commands.spawn((
+                // the default depth load op clears depth, so the sketch can't hide the overlay
+                Camera3d::default(),
+                Camera {
+                    order: 1,
+                    clear_color: ClearColorConfig::None,
+                    msaa_writeback: MsaaWriteback::Auto,
+                    is_active: false,
+                    ..default()
+                },
+                target.clone(),
+                Tonemapping::None,
+                Projection::custom(ProcessingProjection::new(size.0 as f32, size.1 as f32)),
+                Transform::from_xyz(0.0, 0.0, BATCH_INDEX_STEP),
+                render_layers.clone(),
+                FpsOverlayCamera(entity),
+            ));
+            // cameras sharing a target need matching main textures
+            if hdr {
+                camera.insert(Hdr);
+            }
+            let camera = camera.id();
+            commands
+                .entity(entity)
+                .insert(FpsOverlay::new(camera, render_layers));
+        }
+        (false, Some(overlay)) => {
+            layer_manager.free(overlay.render_layers.clone());
+            commands.entity(overlay.camera).despawn();
+            commands.entity(entity).remove::<FpsOverlay>();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Queue the FPS overlay after everything the sketch drew this frame, so it lands on top.
+pub fn record_fps_overlay(
+    In(entity): In<Entity>,
+    mut overlays: Query<(&mut FpsOverlay, &mut CommandBuffer)>,
+    frame: Option<Res<ProcessingFrame>>,
+) {
+    let Ok((mut overlay, mut command_buffer)) = overlays.get_mut(entity) else {
+        return;
+    };
+    let fps = overlay.tick(Instant::now());
+    let frame = frame.map(|f| f.0).unwrap_or(0);
+    command_buffer.push(DrawCommand::DebugText(format!(
+        "fps: {fps:.1}\nframe: {frame}"
+    )));
+}
+
+/// Where the time went in the most recent flush and readback of a graphics object.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct FrameTimings {
+    /// Replaying the command buffer into batched meshes, including tessellation.
+    pub command_flush: Duration,
+    /// The part of `command_flush` spent tessellating shapes and text.
+    pub tessellation: Duration,
+    /// The rest of the app update: extracting, preparing and submitting the frame to the GPU.
+    pub gpu_submit: Duration,
+    /// Copying the surface back to the CPU, when it was read this frame.
+    pub readback: Duration,
+}
+
 pub fn view_target(app: &mut App, entity: Entity) -> Result<&ViewTarget> {
     let rw = app.sub_app_mut(RenderApp).world_mut();
     let mut query = rw.query::<(&MainEntity, &ViewTarget)>();
@@ -76,6 +206,11 @@ macro_rules! graphics_mut {
 #[derive(Component)]
 pub struct SurfaceSize(pub u32, pub u32);
 
+/// Called with the frame count after `end_draw` presents each frame, for side effects such as
+/// encoding or sending the frame that need to happen once it's on screen.
+#[derive(Component, Clone)]
+pub struct EndDrawCallback(pub Arc<dyn Fn(u32) + Send + Sync>);
+
 /// Custom orthographic projection for Processing's coordinate system.
 /// Origin at top-left, Y-axis down, in pixel units (aka screen space).
 #[derive(Debug, Clone, Reflect)]
@@ -219,6 +354,7 @@ pub fn create(
         RenderState::default(),
         crate::color::ColorMode::default(),
         SurfaceSize(width, height),
+        FrameTimings::default(),
         Graphics {
             readback_buffer,
             texture_format,
@@ -236,22 +372,46 @@ pub fn create(
     Ok(entity)
 }
 
-#[allow(dead_code)]
-pub fn resize(
-    In((entity, width, height)): In<(Entity, u32, u32)>,
-    mut graphics_query: Query<&mut Projection>,
-) -> Result<()> {
-    let mut projection = graphics_query
-        .get_mut(entity)
-        .map_err(|_| ProcessingError::GraphicsNotFound)?;
+/// Resize the graphics drawing to a window when the window is resized, updating its size and the
+/// projection of its camera to match. The size is in logical pixels.
+pub fn resize_to_window(
+    mut resized: MessageReader<WindowResized>,
+    mut graphics_query: Query<(&RenderTarget, &mut SurfaceSize, &mut Projection), With<Graphics>>,
+) {
+    for event in resized.read() {
+        let (width, height) = (event.width as u32, event.height as u32);
+        for (target, mut size, mut projection) in graphics_query.iter_mut() {
+            if *target != RenderTarget::Window(WindowRef::Entity(event.window)) {
+                continue;
+            }
+            *size = SurfaceSize(width, height);
+            resize_projection(&mut projection, width, height);
+        }
+    }
+}
 
-    if let Projection::Custom(ref mut custom_proj) = *projection {
-        custom_proj.update(width as f32, height as f32);
-        Ok(())
-    } else {
-        panic!(
-            "Expected custom projection for Processing graphics entity, this should not happen. If you are seeing this message, please report a bug."
-        );
+/// Rebuild the FPS overlay's projection whenever its graphics changes size, however it was
+/// resized, so the text stays in the corner at its usual size.
+pub fn resize_fps_overlay(
+    overlays: Query<(&SurfaceSize, &FpsOverlay), Changed<SurfaceSize>>,
+    mut projections: Query<&mut Projection, With<FpsOverlayCamera>>,
+) {
+    for (size, overlay) in overlays.iter() {
+        if let Ok(mut projection) = projections.get_mut(overlay.camera) {
+            *projection =
+                Projection::custom(ProcessingProjection::new(size.0 as f32, size.1 as f32));
+        }
+    }
+}
+
+fn resize_projection(projection: &mut Projection, width: u32, height: u32) {
+    match *projection {
+        Projection::Custom(ref mut custom_proj) => custom_proj.update(width as f32, height as f32),
+        Projection::Perspective(ref mut perspective) => {
+            perspective.aspect_ratio = width as f32 / height.max(1) as f32
+        }
+        // an orthographic projection set by the sketch keeps its own extents
+        Projection::Orthographic(_) => {}
     }
 }
 
@@ -358,6 +518,19 @@ pub fn mode_2d(
     Ok(())
 }
 
+/// Place the camera at `eye` looking at `center`, with `up` as the direction that points up on
+/// screen, like Processing's `camera()`. Tilting `up` rolls the camera.
+pub fn camera(
+    In((entity, eye, center, up)): In<(Entity, Vec3, Vec3, Vec3)>,
+    mut transforms: Query<&mut Transform, With<Graphics>>,
+) -> Result<()> {
+    let mut transform = transforms
+        .get_mut(entity)
+        .map_err(|_| ProcessingError::GraphicsNotFound)?;
+    *transform = Transform::from_translation(eye).looking_at(center, up);
+    Ok(())
+}
+
 pub fn perspective(
     In((
         entity,
@@ -429,13 +602,17 @@ pub fn destroy(
     In(entity): In<Entity>,
     mut commands: Commands,
     mut layer_manager: ResMut<RenderLayersManager>,
-    graphics_query: Query<&RenderLayers>,
+    graphics_query: Query<(&RenderLayers, Option<&FpsOverlay>)>,
 ) -> Result<()> {
-    let Ok(render_layers) = graphics_query.get(entity) else {
+    let Ok((render_layers, overlay)) = graphics_query.get(entity) else {
         return Err(ProcessingError::GraphicsNotFound);
     };
 
     layer_manager.free(render_layers.clone());
+    if let Some(overlay) = overlay {
+        layer_manager.free(overlay.render_layers.clone());
+        commands.entity(overlay.camera).despawn();
+    }
     commands.entity(entity).despawn();
     Ok(())
 }
@@ -449,9 +626,24 @@ pub fn begin_draw(In(entity): In<Entity>, mut state_query: Query<&mut RenderStat
 }
 
 pub fn flush(app: &mut App, entity: Entity) -> Result<()> {
+    // pixels set on images since the last flush need to reach the GPU before anything draws them
+    crate::image::upload_pixel_caches(app)?;
     graphics_mut!(app, entity).insert(Flush);
+    let start = Instant::now();
     app.update();
-    graphics_mut!(app, entity).remove::<Flush>();
+    let elapsed = start.elapsed();
+    let mut graphics = graphics_mut!(app, entity);
+    graphics.remove::<Flush>();
+    // pixels written by `update_region_write` are in the multisampled texture now
+    if let Some(mut camera) = graphics.get_mut::<Camera>()
+        && matches!(camera.msaa_writeback, MsaaWriteback::Always)
+    {
+        camera.msaa_writeback = MsaaWriteback::Auto;
+    }
+    if let Some(mut timings) = graphics.get_mut::<FrameTimings>() {
+        timings.gpu_submit = elapsed.saturating_sub(timings.command_flush);
+        timings.readback = Duration::ZERO;
+    }
     Ok(())
 }
 
@@ -474,9 +666,29 @@ pub fn present(app: &mut App, entity: Entity) -> Result<()> {
 
 /// End the current draw
 pub fn end_draw(app: &mut App, entity: Entity) -> Result<()> {
+    app.world_mut()
+        .run_system_cached_with(record_fps_overlay, entity)?;
     present(app, entity)
 }
 
+/// Keep the graphics flushing and presenting every frame, for when Bevy's own runner drives the
+/// app rather than explicit `flush`/`end_draw` calls.
+pub fn present_continuously(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    mut cameras: Query<&mut Camera, With<Graphics>>,
+) -> Result<()> {
+    let mut camera = cameras
+        .get_mut(entity)
+        .map_err(|_| ProcessingError::GraphicsNotFound)?;
+    camera.output_mode = CameraOutputMode::Write {
+        blend_state: None,
+        clear_color: ClearColorConfig::None,
+    };
+    commands.entity(entity).insert(Flush);
+    Ok(())
+}
+
 /// Do some work on the GPU to ensure that the render target texture is initialized and can be read
 /// from/written to.
 ///
@@ -486,26 +698,81 @@ pub fn end_draw(app: &mut App, entity: Entity) -> Result<()> {
 // TODO: why is metal particularly affected by this? can we remove this?
 pub fn warmup(app: &mut App, entity: Entity) -> Result<()> {
     for _ in 0..3 {
-        app.world_mut()
-            .run_system_cached_with(
-                record_command,
-                (entity, DrawCommand::BackgroundColor(DEFAULT_CLEAR_COLOR)),
-            )
-            .unwrap()?;
+        app.world_mut().run_system_cached_with(
+            record_command,
+            (entity, DrawCommand::BackgroundColor(DEFAULT_CLEAR_COLOR)),
+        )??;
         flush(app, entity)?;
     }
     Ok(())
 }
 
+/// The transform the next shape would be drawn with, like Processing's `getMatrix()`. Matrix
+/// commands recorded since the last flush are replayed on top of the flushed transform.
+pub fn current_matrix(
+    In(entity): In<Entity>,
+    graphics_query: Query<(&RenderState, &CommandBuffer)>,
+) -> Result<Mat4> {
+    let (state, command_buffer) = graphics_query
+        .get(entity)
+        .map_err(|_| ProcessingError::GraphicsNotFound)?;
+    Ok(pending_matrix(state, command_buffer))
+}
+
+fn pending_matrix(state: &RenderState, command_buffer: &CommandBuffer) -> Mat4 {
+    let mut transform = state.transform.clone();
+    for command in &command_buffer.commands {
+        transform.apply_command(command);
+    }
+    Mat4::from(transform.current())
+}
+
+/// Map a point through `clip_from_model` into pixels, with the origin at the top left.
+fn project_to_screen(clip_from_model: Mat4, point: Vec3, width: f32, height: f32) -> Vec2 {
+    let ndc = clip_from_model.project_point3(point);
+    vec2((ndc.x + 1.0) / 2.0 * width, (1.0 - ndc.y) / 2.0 * height)
+}
+
+/// Where a point drawn at the current transform lands on screen, like Processing's
+/// `screenX()`/`screenY()`.
+pub fn screen_xy(
+    In((entity, point)): In<(Entity, Vec3)>,
+    graphics_query: Query<(
+        &RenderState,
+        &CommandBuffer,
+        &Projection,
+        &Transform,
+        &SurfaceSize,
+    )>,
+) -> Result<Vec2> {
+    let (state, command_buffer, projection, camera_transform, SurfaceSize(width, height)) =
+        graphics_query
+            .get(entity)
+            .map_err(|_| ProcessingError::GraphicsNotFound)?;
+    let clip_from_model = projection.get_clip_from_view()
+        * camera_transform.to_matrix().inverse()
+        * pending_matrix(state, command_buffer);
+    Ok(project_to_screen(
+        clip_from_model,
+        point,
+        *width as f32,
+        *height as f32,
+    ))
+}
+
 pub fn record_command(
     In((graphics_entity, cmd)): In<(Entity, DrawCommand)>,
-    mut graphics_query: Query<&mut CommandBuffer>,
+    mut graphics_query: Query<(&mut CommandBuffer, Option<&mut GeometryCapture>)>,
 ) -> Result<()> {
-    let mut command_buffer = graphics_query
+    let (mut command_buffer, capture) = graphics_query
         .get_mut(graphics_entity)
         .map_err(|_| ProcessingError::GraphicsNotFound)?;
 
-    command_buffer.push(cmd);
+    // commands drawn between geometry_begin and geometry_end are baked, not drawn
+    match capture {
+        Some(mut capture) => capture.commands.push(cmd),
+        None => command_buffer.push(cmd),
+    }
     Ok(())
 }
 
@@ -516,16 +783,153 @@ pub struct ReadbackData {
     pub height: u32,
 }
 
+impl ReadbackData {
+    /// The pixels as tightly packed 8-bit sRGB RGBA, converting float formats as needed.
+    pub fn to_srgba8(&self) -> Result<Vec<u8>> {
+        if self.format == TextureFormat::Rgba8UnormSrgb {
+            return Ok(self.bytes.clone());
+        }
+        let bytes_per_row = self.width as usize * pixel_size(self.format)?;
+        let pixels = bytes_to_pixels(
+            &self.bytes,
+            self.format,
+            self.width,
+            self.height,
+            bytes_per_row,
+        )?;
+        Ok(to_srgba8(&pixels))
+    }
+
+    /// The pixels encoded as `format`, see [`crate::image::encode`].
+    pub fn encode(&self, format: SaveFormat) -> Result<Vec<u8>> {
+        encode(&self.to_srgba8()?, self.width, self.height, format)
+    }
+
+    /// The pixels encoded as an 8-bit sRGB PNG.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        self.encode(SaveFormat::Png)
+    }
+}
+
+/// Replace the run of `#`s in `path` with `frame`, zero padded to the length of the run, like
+/// Processing's `saveFrame("frame-####.png")`. Paths without a `#` come back unchanged.
+pub fn insert_frame(path: &str, frame: u32) -> String {
+    let (Some(start), Some(last)) = (path.find('#'), path.rfind('#')) else {
+        return path.to_string();
+    };
+    let width = last + 1 - start;
+    format!("{}{frame:0width$}{}", &path[..start], &path[last + 1..])
+}
+
+/// Find the graphics that renders to `surface_entity`.
+pub fn for_surface(
+    In(surface_entity): In<Entity>,
+    graphics: Query<(Entity, &RenderTarget), With<Graphics>>,
+    p_images: Query<&Image, With<Surface>>,
+) -> Result<Entity> {
+    let image = p_images.get(surface_entity).ok().map(|i| i.handle.id());
+    graphics
+        .iter()
+        .find(|(_, target)| match target {
+            RenderTarget::Window(WindowRef::Entity(window)) => *window == surface_entity,
+            RenderTarget::Image(target) => Some(target.handle.id()) == image,
+            _ => false,
+        })
+        .map(|(entity, _)| entity)
+        .ok_or(ProcessingError::GraphicsNotFound)
+}
+
 pub fn readback_raw(
     In((entity, texture)): In<(Entity, Texture)>,
-    graphics_query: Query<&Graphics>,
+    mut graphics_query: Query<(&Graphics, &mut FrameTimings)>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
 ) -> Result<ReadbackData> {
-    let graphics = graphics_query
-        .get(entity)
+    let start = Instant::now();
+    let (graphics, mut timings) = graphics_query
+        .get_mut(entity)
         .map_err(|_| ProcessingError::GraphicsNotFound)?;
 
+    let px_size = pixel_size(graphics.texture_format)?;
+    let (data, padded_bytes_per_row) = map_readback(
+        graphics,
+        &texture,
+        &render_device,
+        &render_queue,
+        |data, padded_bytes_per_row| (data.to_vec(), padded_bytes_per_row),
+    )?;
+
+    // strip row padding
+    let bytes_per_row = graphics.size.width as usize * px_size;
+    let unpadded = if padded_bytes_per_row != bytes_per_row {
+        data.chunks_exact(padded_bytes_per_row)
+            .take(graphics.size.height as usize)
+            .flat_map(|row| &row[..bytes_per_row])
+            .copied()
+            .collect()
+    } else {
+        data
+    };
+    timings.readback = start.elapsed();
+
+    Ok(ReadbackData {
+        bytes: unpadded,
+        format: graphics.texture_format,
+        width: graphics.size.width,
+        height: graphics.size.height,
+    })
+}
+
+/// Read the surface back into `pixels`, decoding straight from the mapped buffer so nothing is
+/// allocated per call.
+pub fn readback_into(
+    (In((entity, texture)), InMut(pixels)): (In<(Entity, Texture)>, InMut<[LinearRgba]>),
+    mut graphics_query: Query<(&Graphics, &mut FrameTimings)>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) -> Result<()> {
+    let start = Instant::now();
+    let (graphics, mut timings) = graphics_query
+        .get_mut(entity)
+        .map_err(|_| ProcessingError::GraphicsNotFound)?;
+    let size = graphics.size;
+    let expected = size.width as usize * size.height as usize;
+    if pixels.len() != expected {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Buffer size mismatch: expected {expected}, got {}",
+            pixels.len()
+        )));
+    }
+
+    map_readback(
+        graphics,
+        &texture,
+        &render_device,
+        &render_queue,
+        |data, padded_bytes_per_row| {
+            bytes_to_pixels_into(
+                data,
+                graphics.texture_format,
+                size.width,
+                size.height,
+                padded_bytes_per_row,
+                pixels,
+            )
+        },
+    )??;
+    timings.readback = start.elapsed();
+    Ok(())
+}
+
+/// Copy `texture` into the graphics' readback buffer, map it and hand `f` the mapped bytes along
+/// with the padded length of each row.
+fn map_readback<R>(
+    graphics: &Graphics,
+    texture: &Texture,
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+    f: impl FnOnce(&[u8], usize) -> R,
+) -> Result<R> {
     let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor::default());
 
     let px_size = pixel_size(graphics.texture_format)?;
@@ -566,28 +970,44 @@ pub fn readback_raw(
 
     r.recv().expect("Failed to receive the map_async message");
 
-    let data = buffer_slice.get_mapped_range().to_vec();
+    let result = f(&buffer_slice.get_mapped_range(), padded_bytes_per_row);
 
     graphics.readback_buffer.unmap();
 
-    // strip row padding
-    let bytes_per_row = graphics.size.width as usize * px_size;
-    let unpadded = if padded_bytes_per_row != bytes_per_row {
-        data.chunks_exact(padded_bytes_per_row)
-            .take(graphics.size.height as usize)
-            .flat_map(|row| &row[..bytes_per_row])
+    Ok(result)
+}
+
+impl ReadbackData {
+    /// Copy out the `width` x `height` region at `x`, `y`.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Result<ReadbackData> {
+        if width == 0
+            || height == 0
+            || x.saturating_add(width) > self.width
+            || y.saturating_add(height) > self.height
+        {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "Region {width}x{height} at ({x}, {y}) is outside the {}x{} surface",
+                self.width, self.height
+            )));
+        }
+        let px_size = pixel_size(self.format)?;
+        let bytes_per_row = self.width as usize * px_size;
+        let bytes = self
+            .bytes
+            .chunks_exact(bytes_per_row)
+            .skip(y as usize)
+            .take(height as usize)
+            .flat_map(|row| &row[x as usize * px_size..(x + width) as usize * px_size])
             .copied()
-            .collect()
-    } else {
-        data
-    };
+            .collect();
 
-    Ok(ReadbackData {
-        bytes: unpadded,
-        format: graphics.texture_format,
-        width: graphics.size.width,
-        height: graphics.size.height,
-    })
+        Ok(ReadbackData {
+            bytes,
+            format: self.format,
+            width,
+            height,
+        })
+    }
 }
 
 pub fn update_region_write(
@@ -601,11 +1021,11 @@ pub fn update_region_write(
         Vec<u8>,
         u32,
     )>,
-    graphics_query: Query<&Graphics>,
+    mut graphics_query: Query<(&Graphics, &mut Camera)>,
     render_queue: Res<RenderQueue>,
 ) -> Result<()> {
-    let graphics = graphics_query
-        .get(entity)
+    let (graphics, mut camera) = graphics_query
+        .get_mut(entity)
         .map_err(|_| ProcessingError::GraphicsNotFound)?;
 
     // bounds check
@@ -636,6 +1056,9 @@ pub fn update_region_write(
             depth_or_array_layers: 1,
         },
     );
+    // with msaa the next frame draws into the multisampled texture and resolves it over the one
+    // written here, so copy this one into it first. `flush` switches the copy back off after.
+    camera.msaa_writeback = MsaaWriteback::Always;
 
     Ok(())
 }
@@ -731,6 +1154,99 @@ impl RenderLayersManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn float_readback_converts_to_srgba8() {
+        let bytes = [1.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.5]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let raw = ReadbackData {
+            bytes,
+            format: TextureFormat::Rgba32Float,
+            width: 2,
+            height: 1,
+        };
+        assert_eq!(raw.to_srgba8().unwrap(), [255, 0, 0, 255, 0, 0, 255, 128]);
+    }
+
+    #[test]
+    fn frame_number_fills_the_hashes() {
+        assert_eq!(insert_frame("frame-####.png", 7), "frame-0007.png");
+        assert_eq!(insert_frame("out/#.png", 1234), "out/1234.png");
+        assert_eq!(insert_frame("still.png", 7), "still.png");
+
+        let raw = ReadbackData {
+            bytes: vec![255, 0, 0, 255],
+            format: TextureFormat::Rgba8UnormSrgb,
+            width: 1,
+            height: 1,
+        };
+        assert!(raw.to_png().unwrap().starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn fps_overlay_adds_text_each_frame() {
+        let mut world = World::new();
+        world.init_resource::<ProcessingFrame>();
+        let entity = world
+            .spawn((
+                CommandBuffer::new(),
+                FpsOverlay::new(Entity::PLACEHOLDER, RenderLayers::none()),
+            ))
+            .id();
+        let plain = world.spawn(CommandBuffer::new()).id();
+
+        for frame in 1..=3 {
+            world.resource_mut::<ProcessingFrame>().0 = frame;
+            world
+                .run_system_cached_with(record_fps_overlay, entity)
+                .unwrap();
+            world
+                .run_system_cached_with(record_fps_overlay, plain)
+                .unwrap();
+
+            let commands = &world.get::<CommandBuffer>(entity).unwrap().commands;
+            assert_eq!(commands.len(), frame as usize);
+            let Some(DrawCommand::DebugText(text)) = commands.last() else {
+                panic!("expected overlay text, got {commands:?}");
+            };
+            assert!(text.contains(&format!("frame: {frame}")));
+        }
+        assert!(
+            world
+                .get::<CommandBuffer>(plain)
+                .unwrap()
+                .commands
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn fps_overlay_follows_surface_size() {
+        let mut world = World::new();
+        let camera = world
+            .spawn((
+                Projection::custom(ProcessingProjection::new(32.0, 32.0)),
+                FpsOverlayCamera(Entity::PLACEHOLDER),
+            ))
+            .id();
+        let graphics = world
+            .spawn((
+                SurfaceSize(32, 32),
+                FpsOverlay::new(camera, RenderLayers::none()),
+            ))
+            .id();
+        world.run_system_cached(resize_fps_overlay).unwrap();
+
+        world.get_mut::<SurfaceSize>(graphics).unwrap().0 = 64;
+        world.run_system_cached(resize_fps_overlay).unwrap();
+        let Projection::Custom(projection) = world.get::<Projection>(camera).unwrap() else {
+            panic!("expected the overlay's custom projection");
+        };
+        let corner = projection.get_clip_from_view() * Vec4::new(64.0, 32.0, 0.0, 1.0);
+        assert!((corner.x - 1.0).abs() < 1e-5);
+    }
+
     #[test]
     fn test_processing_projection() {
         let proj = ProcessingProjection {
@@ -746,6 +1262,53 @@ mod tests {
         assert!((clip_matrix.w_axis.z - expected).abs() < 1e-6);
     }
 
+    #[test]
+    fn screen_projection_matches_pixels() {
+        let (width, height) = (800.0, 600.0);
+        let model = Mat4::from_translation(Vec3::new(10.0, 20.0, 0.0));
+
+        // 2d: pixel units with the camera just in front of the drawing
+        let ortho = ProcessingProjection::new(width, height).get_clip_from_view()
+            * Transform::from_xyz(0.0, 0.0, BATCH_INDEX_STEP)
+                .to_matrix()
+                .inverse();
+        let screen = project_to_screen(ortho * model, Vec3::new(5.0, 5.0, 0.0), width, height);
+        assert!((screen - vec2(15.0, 25.0)).abs().max_element() < 1e-3);
+
+        // 3d: the origin is centered in front of the camera
+        let perspective =
+            Mat4::perspective_rh(std::f32::consts::PI / 3.0, width / height, 1.0, 5000.0)
+                * Transform::from_xyz(0.0, 0.0, 520.0)
+                    .looking_at(Vec3::ZERO, Vec3::Y)
+                    .to_matrix()
+                    .inverse();
+        let screen = project_to_screen(perspective, Vec3::ZERO, width, height);
+        assert!(
+            (screen - vec2(width / 2.0, height / 2.0))
+                .abs()
+                .max_element()
+                < 1e-3
+        );
+    }
+
+    #[test]
+    fn crop_copies_region() {
+        // 4x3 Rgba8Unorm surface where each pixel's red channel is its index
+        let raw = ReadbackData {
+            bytes: (0..12u8).flat_map(|i| [i, 0, 0, 255]).collect(),
+            format: TextureFormat::Rgba8Unorm,
+            width: 4,
+            height: 3,
+        };
+
+        let region = raw.crop(1, 1, 2, 2).unwrap();
+        assert_eq!((region.width, region.height), (2, 2));
+        let reds: Vec<u8> = region.bytes.chunks_exact(4).map(|px| px[0]).collect();
+        assert_eq!(reds, vec![5, 6, 9, 10]);
+
+        assert!(raw.crop(3, 0, 2, 1).is_err());
+    }
+
     #[test]
     fn test_layer_reservation() {
         let mut manager = RenderLayersManager::default();
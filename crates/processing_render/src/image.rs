@@ -1,14 +1,14 @@
 //! An image in Processing is a 2D texture that can be used for rendering.
 //!
 //! It can be created from raw pixel data, loaded from disk, resized, and read back to CPU memory.
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use bevy::{
     asset::{
         AssetPath, LoadState, RenderAssetUsages, handle_internal_asset_events,
         io::{AssetSourceId, embedded::GetAssetServer},
     },
-    ecs::system::RunSystemOnce,
+    ecs::system::{InMut, RunSystemOnce},
     image::{ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor},
     prelude::*,
     render::{
@@ -28,12 +28,23 @@ use half::f16;
 use processing_core::config::{Config, ConfigKey};
 use processing_core::error::{ProcessingError, Result};
 
+use crate::render::command::BlendMode;
+
 pub struct ImagePlugin;
 
 impl Plugin for ImagePlugin {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LinearImages>();
+    }
 }
 
+/// Linear copies of sRGB images, keyed by the image they were made from, so setting the same
+/// normal map every frame doesn't make a new texture each time.
+#[derive(Resource, Default)]
+pub struct LinearImages(
+    bevy::platform::collections::HashMap<AssetId<bevy::image::Image>, Handle<bevy::image::Image>>,
+);
+
 #[derive(Component)]
 pub struct Image {
     pub handle: Handle<bevy::image::Image>,
@@ -119,12 +130,17 @@ pub fn from_handle(
         .id())
 }
 
-pub fn load(In(path): In<PathBuf>, world: &mut World) -> Result<Entity> {
+/// Load an image from the asset directory. With `auto_orient`, photos carrying an EXIF orientation
+/// tag are rotated and flipped upright, since the image decoder leaves them as stored.
+pub fn load(In((path, auto_orient)): In<(PathBuf, bool)>, world: &mut World) -> Result<Entity> {
     let config = world.resource_mut::<Config>();
-    let path: AssetPath = match config.get(ConfigKey::AssetRootPath) {
-        Some(_) => {
-            AssetPath::from_path_buf(path).with_source(AssetSourceId::from("assets_directory"))
-        }
+    let orientation = if auto_orient {
+        exif_orientation(&asset_file_path(&config, &path)).unwrap_or(1)
+    } else {
+        1
+    };
+    let path: AssetPath = match asset_source(&config) {
+        Some(source) => AssetPath::from_path_buf(path).with_source(source),
         None => AssetPath::from_path_buf(path),
     };
 
@@ -133,11 +149,35 @@ pub fn load(In(path): In<PathBuf>, world: &mut World) -> Result<Entity> {
     while let LoadState::Loading = world.get_asset_server().load_state(&handle) {
         world.run_system_once(handle_internal_asset_events).unwrap();
     }
-    let images = world.resource::<Assets<bevy::image::Image>>();
-    let image = images.get(&handle).ok_or(ProcessingError::ImageNotFound)?;
-
-    let size = image.texture_descriptor.size;
-    let texture_format = image.texture_descriptor.format;
+    let (size, texture_format) = {
+        let mut images = world.resource_mut::<Assets<bevy::image::Image>>();
+        let mut image = images
+            .get_mut(&handle)
+            .ok_or(ProcessingError::ImageNotFound)?;
+        if orientation != 1
+            && let Some(texel_size) = image.texture_descriptor.format.block_copy_size(None)
+            && let Some(data) = image.data.as_ref()
+        {
+            let size = image.texture_descriptor.size;
+            let (data, width, height) = apply_orientation(
+                data,
+                size.width,
+                size.height,
+                texel_size as usize,
+                orientation,
+            );
+            image.data = Some(data);
+            image.texture_descriptor.size = Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            };
+        }
+        (
+            image.texture_descriptor.size,
+            image.texture_descriptor.format,
+        )
+    };
 
     let render_device = world.resource::<RenderDevice>();
     let readback_buffer = create_readback_buffer(
@@ -158,6 +198,127 @@ pub fn load(In(path): In<PathBuf>, world: &mut World) -> Result<Entity> {
         .id())
 }
 
+/// The asset source relative paths load from: the configured [`ConfigKey::AssetSource`], then
+/// the configured asset directory. `None` leaves paths on Bevy's default source.
+pub(crate) fn asset_source(config: &Config) -> Option<AssetSourceId<'static>> {
+    match config.get(ConfigKey::AssetSource) {
+        Some(source) => Some(AssetSourceId::from(source.clone())),
+        None => config
+            .get(ConfigKey::AssetRootPath)
+            .map(|_| AssetSourceId::from("assets_directory")),
+    }
+}
+
+/// Read the bytes at `path` through the same asset source images and models load from, so files
+/// in an embedded or custom source are found too.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read_asset(world: &World, path: &Path) -> std::result::Result<Vec<u8>, String> {
+    use bevy::asset::io::Reader;
+
+    let source = asset_source(world.resource::<Config>()).unwrap_or(AssetSourceId::Default);
+    let source = world
+        .resource::<AssetServer>()
+        .get_source(source)
+        .map_err(|e| e.to_string())?;
+    bevy::tasks::block_on(async {
+        let mut reader = source
+            .reader()
+            .read(path)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut data = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(data)
+    })
+}
+
+/// Write `bytes` to `path` through the asset source [`read_asset`] reads from, so a saved file
+/// loads back by the same name.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn write_asset(
+    world: &World,
+    path: &Path,
+    bytes: &[u8],
+) -> std::result::Result<(), String> {
+    let source = asset_source(world.resource::<Config>()).unwrap_or(AssetSourceId::Default);
+    let source = world
+        .resource::<AssetServer>()
+        .get_source(source)
+        .map_err(|e| e.to_string())?;
+    let writer = source.writer().map_err(|e| e.to_string())?;
+    bevy::tasks::block_on(writer.write_bytes(path, bytes)).map_err(|e| e.to_string())
+}
+
+/// Where the asset server reads `path` from on disk.
+#[cfg(not(target_arch = "wasm32"))]
+fn asset_file_path(config: &Config, path: &Path) -> PathBuf {
+    match config.get(ConfigKey::AssetRootPath) {
+        Some(root) => Path::new(root).join(path),
+        None => bevy::asset::io::file::FileAssetReader::get_base_path()
+            .join("assets")
+            .join(path),
+    }
+}
+
+/// The EXIF orientation tag of an image file, from 1 (upright) to 8.
+#[cfg(not(target_arch = "wasm32"))]
+fn exif_orientation(path: &Path) -> Option<u32> {
+    let file = std::fs::File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut std::io::BufReader::new(file))
+        .ok()?;
+    exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
+        .value
+        .get_uint(0)
+}
+
+// assets are fetched over the network on the web, so there's no file to read the tag from
+#[cfg(target_arch = "wasm32")]
+fn asset_file_path(_config: &Config, path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn exif_orientation(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// Rotate and flip tightly packed pixel rows so an image stored with EXIF `orientation` displays
+/// upright. Returns the new data, width and height; orientations 5 to 8 swap the dimensions.
+pub fn apply_orientation(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    texel_size: usize,
+    orientation: u32,
+) -> (Vec<u8>, u32, u32) {
+    let (w, h) = (width as usize, height as usize);
+    let (out_w, out_h) = if orientation >= 5 { (h, w) } else { (w, h) };
+    let mut out = vec![0; data.len()];
+    for y in 0..out_h {
+        for x in 0..out_w {
+            // the stored pixel shown at (x, y)
+            let (sx, sy) = match orientation {
+                2 => (w - 1 - x, y),
+                3 => (w - 1 - x, h - 1 - y),
+                4 => (x, h - 1 - y),
+                5 => (y, x),
+                6 => (y, h - 1 - x),
+                7 => (w - 1 - y, h - 1 - x),
+                8 => (w - 1 - y, x),
+                _ => (x, y),
+            };
+            let src = (sy * w + sx) * texel_size;
+            let dst = (y * out_w + x) * texel_size;
+            out[dst..dst + texel_size].copy_from_slice(&data[src..src + texel_size]);
+        }
+    }
+    (out, out_w as u32, out_h as u32)
+}
+
 pub fn resize(
     In((entity, new_size)): In<(Entity, Extent3d)>,
     mut p_images: Query<&mut Image>,
@@ -185,6 +346,35 @@ pub fn resize(
     Ok(())
 }
 
+/// A handle that reads `handle`'s bytes as linear, for textures like normal maps that hold data
+/// rather than color. sRGB images get a copy with the same bytes and a linear format, made once
+/// per image; the image itself keeps decoding as sRGB wherever else it's drawn.
+pub fn linear_view(
+    handle: &Handle<bevy::image::Image>,
+    images: &mut Assets<bevy::image::Image>,
+    linear_images: &mut LinearImages,
+) -> Handle<bevy::image::Image> {
+    let Some(image) = images.get(handle) else {
+        return handle.clone();
+    };
+    let format = image.texture_descriptor.format;
+    let linear = format.remove_srgb_suffix();
+    if linear == format {
+        return handle.clone();
+    }
+    if let Some(copy) = linear_images.0.get(&handle.id())
+        && images.contains(copy)
+    {
+        return copy.clone();
+    }
+
+    let mut copy = image.clone();
+    copy.texture_descriptor.format = linear;
+    let copy = images.add(copy);
+    linear_images.0.insert(handle.id(), copy.clone());
+    copy
+}
+
 pub fn readback(
     In((entity, texture)): In<(Entity, Texture)>,
     p_images: Query<&Image>,
@@ -195,6 +385,55 @@ pub fn readback(
     let p_image = p_images
         .get(entity)
         .map_err(|_| ProcessingError::ImageNotFound)?;
+    let mut pixels =
+        vec![LinearRgba::NONE; p_image.size.width as usize * p_image.size.height as usize];
+    readback_pixels_into(
+        p_image,
+        &texture,
+        &mut images,
+        &render_device,
+        &render_queue,
+        &mut pixels,
+    )?;
+    Ok(pixels)
+}
+
+/// [`readback`] into `pixels`, which must hold exactly width * height colors.
+pub fn readback_into(
+    (In((entity, texture)), InMut(pixels)): (In<(Entity, Texture)>, InMut<[LinearRgba]>),
+    p_images: Query<&Image>,
+    mut images: ResMut<Assets<bevy::image::Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: ResMut<RenderQueue>,
+) -> Result<()> {
+    let p_image = p_images
+        .get(entity)
+        .map_err(|_| ProcessingError::ImageNotFound)?;
+    readback_pixels_into(
+        p_image,
+        &texture,
+        &mut images,
+        &render_device,
+        &render_queue,
+        pixels,
+    )
+}
+
+fn readback_pixels_into(
+    p_image: &Image,
+    texture: &Texture,
+    images: &mut Assets<bevy::image::Image>,
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+    pixels: &mut [LinearRgba],
+) -> Result<()> {
+    let expected = p_image.size.width as usize * p_image.size.height as usize;
+    if pixels.len() != expected {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Buffer size mismatch: expected {expected}, got {}",
+            pixels.len()
+        )));
+    }
 
     let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor::default());
 
@@ -236,22 +475,29 @@ pub fn readback(
 
     r.recv().expect("Failed to receive the map_async message");
 
-    let data = buffer_slice.get_mapped_range().to_vec();
+    let data = buffer_slice.get_mapped_range();
 
     let mut image = images
         .get_mut(&p_image.handle)
         .ok_or(ProcessingError::ImageNotFound)?;
-    image.data = Some(data.clone());
-
-    p_image.readback_buffer.unmap();
+    // keep the CPU copy in sync, reusing its allocation when the size hasn't changed
+    let cpu = &mut image.data;
+    match cpu {
+        Some(bytes) if bytes.len() == data.len() => bytes.copy_from_slice(&data),
+        _ => *cpu = Some(data.to_vec()),
+    }
 
-    bytes_to_pixels(
+    let result = bytes_to_pixels_into(
         &data,
         p_image.texture_format,
         p_image.size.width,
         p_image.size.height,
         padded_bytes_per_row,
-    )
+        pixels,
+    );
+    drop(data);
+    p_image.readback_buffer.unmap();
+    result
 }
 
 pub fn update_region_write(
@@ -331,6 +577,88 @@ pub fn prepare_update_region(
     Ok((data, px_size))
 }
 
+/// A CPU copy of an image's pixels for reading and writing single pixels without a readback each
+/// time. It's filled on the first `get` or `set`, and the pixels set since the last upload are
+/// written back as one region by [`upload_pixel_caches`].
+#[derive(Component)]
+pub struct PixelCache {
+    pixels: Vec<LinearRgba>,
+    width: u32,
+    height: u32,
+    dirty: Option<URect>,
+}
+
+impl PixelCache {
+    pub fn new(pixels: Vec<LinearRgba>, width: u32, height: u32) -> Self {
+        Self {
+            pixels,
+            width,
+            height,
+            dirty: None,
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Result<usize> {
+        if x >= self.width || y >= self.height {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "pixel ({x}, {y}) is outside the {}x{} image",
+                self.width, self.height
+            )));
+        }
+        Ok((y * self.width + x) as usize)
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Result<LinearRgba> {
+        Ok(self.pixels[self.index(x, y)?])
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, color: LinearRgba) -> Result<()> {
+        let i = self.index(x, y)?;
+        self.pixels[i] = color;
+        let pixel = URect::new(x, y, x + 1, y + 1);
+        self.dirty = Some(self.dirty.map_or(pixel, |dirty| dirty.union(pixel)));
+        Ok(())
+    }
+
+    /// The bounds of every pixel set since the last call and the pixels inside them, row by row.
+    pub fn take_dirty(&mut self) -> Option<(URect, Vec<LinearRgba>)> {
+        let dirty = self.dirty.take()?;
+        let pixels = (dirty.min.y..dirty.max.y)
+            .flat_map(|y| {
+                let row = (y * self.width) as usize;
+                &self.pixels[row + dirty.min.x as usize..row + dirty.max.x as usize]
+            })
+            .copied()
+            .collect();
+        Some((dirty, pixels))
+    }
+}
+
+/// Write the pixels set through every image's [`PixelCache`] to its texture.
+pub fn upload_pixel_caches(app: &mut App) -> Result<()> {
+    let mut pending = Vec::new();
+    let mut caches = app.world_mut().query::<(Entity, &mut PixelCache)>();
+    for (entity, mut cache) in caches.iter_mut(app.world_mut()) {
+        if let Some(dirty) = cache.take_dirty() {
+            pending.push((entity, dirty));
+        }
+    }
+
+    for (entity, (rect, pixels)) in pending {
+        let texture = gpu_image(app, entity)?.texture.clone();
+        let world = app.world_mut();
+        let (width, height) = (rect.width(), rect.height());
+        let (data, px_size) = prepare_update_region(world, entity, width, height, &pixels)?;
+        world.run_system_cached_with(
+            update_region_write,
+            (
+                entity, texture, rect.min.x, rect.min.y, width, height, data, px_size,
+            ),
+        )??;
+    }
+    Ok(())
+}
+
 pub fn destroy(
     In(entity): In<Entity>,
     mut commands: Commands,
@@ -401,50 +729,276 @@ pub fn bytes_to_pixels(
     height: u32,
     padded_bytes_per_row: usize,
 ) -> Result<Vec<LinearRgba>> {
+    let mut pixels = vec![LinearRgba::NONE; width as usize * height as usize];
+    bytes_to_pixels_into(
+        data,
+        format,
+        width,
+        height,
+        padded_bytes_per_row,
+        &mut pixels,
+    )?;
+    Ok(pixels)
+}
+
+/// [`bytes_to_pixels`] into `pixels`, which must hold exactly `width * height` colors.
+pub fn bytes_to_pixels_into(
+    data: &[u8],
+    format: TextureFormat,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: usize,
+    pixels: &mut [LinearRgba],
+) -> Result<()> {
+    let expected = width as usize * height as usize;
+    if pixels.len() != expected {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Buffer size mismatch: expected {expected}, got {}",
+            pixels.len()
+        )));
+    }
     let px_size = pixel_size(format)?;
     let bytes_per_row = width as usize * px_size;
 
-    let pixels = match format {
-        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => data
-            .chunks_exact(padded_bytes_per_row)
-            .take(height as usize)
-            .flat_map(|row| {
-                row[..bytes_per_row].chunks_exact(px_size).map(|chunk| {
-                    LinearRgba::from_u8_array([chunk[0], chunk[1], chunk[2], chunk[3]])
-                })
-            })
-            .collect(),
-        TextureFormat::Rgba16Float => data
-            .chunks_exact(padded_bytes_per_row)
-            .take(height as usize)
-            .flat_map(|row| {
-                row[..bytes_per_row].chunks_exact(px_size).map(|chunk| {
-                    let r = f16::from_bits(u16::from_le_bytes([chunk[0], chunk[1]])).to_f32();
-                    let g = f16::from_bits(u16::from_le_bytes([chunk[2], chunk[3]])).to_f32();
-                    let b = f16::from_bits(u16::from_le_bytes([chunk[4], chunk[5]])).to_f32();
-                    let a = f16::from_bits(u16::from_le_bytes([chunk[6], chunk[7]])).to_f32();
-                    LinearRgba::from_f32_array([r, g, b, a])
-                })
-            })
-            .collect(),
-        TextureFormat::Rgba32Float => data
-            .chunks_exact(padded_bytes_per_row)
-            .take(height as usize)
-            .flat_map(|row| {
-                row[..bytes_per_row].chunks_exact(px_size).map(|chunk| {
-                    let r = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
-                    let g = f32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
-                    let b = f32::from_le_bytes([chunk[8], chunk[9], chunk[10], chunk[11]]);
-                    let a = f32::from_le_bytes([chunk[12], chunk[13], chunk[14], chunk[15]]);
-                    LinearRgba::from_f32_array([r, g, b, a])
-                })
-            })
-            .collect(),
+    let decode: fn(&[u8]) -> LinearRgba = match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+            |chunk| LinearRgba::from_u8_array([chunk[0], chunk[1], chunk[2], chunk[3]])
+        }
+        TextureFormat::Rgba16Float => |chunk| {
+            let r = f16::from_bits(u16::from_le_bytes([chunk[0], chunk[1]])).to_f32();
+            let g = f16::from_bits(u16::from_le_bytes([chunk[2], chunk[3]])).to_f32();
+            let b = f16::from_bits(u16::from_le_bytes([chunk[4], chunk[5]])).to_f32();
+            let a = f16::from_bits(u16::from_le_bytes([chunk[6], chunk[7]])).to_f32();
+            LinearRgba::from_f32_array([r, g, b, a])
+        },
+        TextureFormat::Rgba32Float => |chunk| {
+            let r = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            let g = f32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+            let b = f32::from_le_bytes([chunk[8], chunk[9], chunk[10], chunk[11]]);
+            let a = f32::from_le_bytes([chunk[12], chunk[13], chunk[14], chunk[15]]);
+            LinearRgba::from_f32_array([r, g, b, a])
+        },
         // TODO: Handle more formats as needed
         _ => return Err(ProcessingError::UnsupportedTextureFormat),
     };
 
-    Ok(pixels)
+    let texels = data
+        .chunks_exact(padded_bytes_per_row)
+        .take(height as usize)
+        .flat_map(|row| row[..bytes_per_row].chunks_exact(px_size));
+    for (pixel, texel) in pixels.iter_mut().zip(texels) {
+        *pixel = decode(texel);
+    }
+    Ok(())
+}
+
+/// Replace each pixel of a row-major buffer `width` pixels wide with `f(x, y, pixel)`.
+pub fn map_pixels(
+    pixels: &mut [LinearRgba],
+    width: u32,
+    mut f: impl FnMut(u32, u32, LinearRgba) -> LinearRgba,
+) {
+    for (i, pixel) in pixels.iter_mut().enumerate() {
+        let x = i as u32 % width;
+        let y = i as u32 / width;
+        *pixel = f(x, y, *pixel);
+    }
+}
+
+/// An effect applied to every pixel of an image, like Processing's `filter()`. All of them work on
+/// the sRGB values, as Processing does, and leave alpha alone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    /// Replace each pixel with its luminance.
+    Gray,
+    /// Replace each channel `c` with `1 - c`.
+    Invert,
+    /// Turn pixels white when their brightest channel is at least the level, from 0 to 1, and
+    /// black otherwise.
+    Threshold(f32),
+    /// Gaussian blur with the given radius, in pixels, as the standard deviation.
+    Blur(f32),
+    /// Limit each channel to the given number of levels, from 2 to 255.
+    Posterize(u8),
+}
+
+impl Filter {
+    /// A filter from its number (0 gray, 1 invert, 2 threshold, 3 blur, 4 posterize) and the
+    /// parameter the numbered filter takes, for bindings that can't pass enums.
+    pub fn from_parts(kind: u8, param: f32) -> Result<Self> {
+        match kind {
+            0 => Ok(Self::Gray),
+            1 => Ok(Self::Invert),
+            2 => Ok(Self::Threshold(param)),
+            3 => Ok(Self::Blur(param)),
+            4 => Ok(Self::Posterize(param as u8)),
+            _ => Err(ProcessingError::InvalidArgument(format!(
+                "unknown filter: {kind}"
+            ))),
+        }
+    }
+}
+
+/// Apply `filter` to row-major `pixels` of an image `width` pixels wide.
+pub fn apply_filter(pixels: &mut [LinearRgba], width: u32, filter: Filter) -> Result<()> {
+    let per_pixel = |f: &dyn Fn(Srgba) -> Srgba, pixels: &mut [LinearRgba]| {
+        for pixel in pixels.iter_mut() {
+            *pixel = f(Srgba::from(*pixel)).into();
+        }
+    };
+    match filter {
+        Filter::Gray => per_pixel(
+            &|c| {
+                let luma = 0.299 * c.red + 0.587 * c.green + 0.114 * c.blue;
+                Srgba::new(luma, luma, luma, c.alpha)
+            },
+            pixels,
+        ),
+        Filter::Invert => per_pixel(
+            &|c| Srgba::new(1.0 - c.red, 1.0 - c.green, 1.0 - c.blue, c.alpha),
+            pixels,
+        ),
+        Filter::Threshold(level) => per_pixel(
+            &|c| {
+                let v = if c.red.max(c.green).max(c.blue) >= level {
+                    1.0
+                } else {
+                    0.0
+                };
+                Srgba::new(v, v, v, c.alpha)
+            },
+            pixels,
+        ),
+        Filter::Posterize(levels) => {
+            if levels < 2 {
+                return Err(ProcessingError::InvalidArgument(format!(
+                    "posterize needs at least 2 levels, got {levels}"
+                )));
+            }
+            let levels = levels as f32;
+            let step = |v: f32| (v * levels).floor().clamp(0.0, levels - 1.0) / (levels - 1.0);
+            per_pixel(
+                &|c| Srgba::new(step(c.red), step(c.green), step(c.blue), c.alpha),
+                pixels,
+            )
+        }
+        Filter::Blur(radius) => {
+            if radius > 0.0 {
+                blur(pixels, width as usize, radius);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Count `pixels` into `bins` equal buckets per channel, red, green, blue then alpha, over their
+/// sRGB values like Processing's 0 to 255 channels. Values outside 0 to 1 land in the end bins.
+pub fn histogram(pixels: &[LinearRgba], bins: usize) -> Result<[Vec<u32>; 4]> {
+    if bins == 0 {
+        return Err(ProcessingError::InvalidArgument(
+            "a histogram needs at least 1 bin".into(),
+        ));
+    }
+    let bin = |v: f32| ((v.clamp(0.0, 1.0) * bins as f32) as usize).min(bins - 1);
+    let mut counts = std::array::from_fn(|_| vec![0; bins]);
+    for pixel in pixels {
+        let srgb = Srgba::from(*pixel);
+        for (channel, v) in [srgb.red, srgb.green, srgb.blue, srgb.alpha]
+            .into_iter()
+            .enumerate()
+        {
+            counts[channel][bin(v)] += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// Separable Gaussian blur with standard deviation `sigma`, clamping at the edges.
+fn blur(pixels: &mut [LinearRgba], width: usize, sigma: f32) {
+    let height = pixels.len() / width;
+    let reach = (sigma * 3.0).ceil() as isize;
+    let mut kernel: Vec<f32> = (-reach..=reach)
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|w| *w /= total);
+
+    let mut srgb: Vec<Vec4> = pixels
+        .iter()
+        .map(|p| Vec4::from_array(Srgba::from(*p).to_f32_array()))
+        .collect();
+    let pass = |srgb: &[Vec4], step: (isize, isize)| -> Vec<Vec4> {
+        (0..srgb.len())
+            .map(|i| {
+                let (x, y) = ((i % width) as isize, (i / width) as isize);
+                kernel
+                    .iter()
+                    .zip(-reach..=reach)
+                    .map(|(w, k)| {
+                        let sx = (x + k * step.0).clamp(0, width as isize - 1);
+                        let sy = (y + k * step.1).clamp(0, height as isize - 1);
+                        srgb[sy as usize * width + sx as usize] * *w
+                    })
+                    .sum()
+            })
+            .collect()
+    };
+    srgb = pass(&srgb, (1, 0));
+    srgb = pass(&srgb, (0, 1));
+
+    for (pixel, c) in pixels.iter_mut().zip(srgb) {
+        *pixel = Srgba::from_f32_array(c.to_array()).into();
+    }
+}
+
+/// Composite `src` onto `dst` like Processing's `blend()`. Channels mix on their sRGB values by the
+/// source's alpha, and the alphas add. `Replace` takes the source as is.
+pub fn blend_pixel(src: LinearRgba, dst: LinearRgba, mode: BlendMode) -> LinearRgba {
+    if mode == BlendMode::Replace {
+        return src;
+    }
+    let (s, d) = (Srgba::from(src), Srgba::from(dst));
+    let f = |s: f32, d: f32| match mode {
+        BlendMode::Blend | BlendMode::Replace => s,
+        BlendMode::Add => (d + s).min(1.0),
+        BlendMode::Subtract => (d - s).max(0.0),
+        BlendMode::Darkest => d.min(s),
+        BlendMode::Lightest => d.max(s),
+        BlendMode::Difference => (d - s).abs(),
+        BlendMode::Exclusion => d + s - 2.0 * d * s,
+        BlendMode::Multiply => d * s,
+        BlendMode::Screen => 1.0 - (1.0 - d) * (1.0 - s),
+    };
+    let alpha = s.alpha;
+    let mix = |s: f32, d: f32| d + (f(s, d) - d) * alpha;
+    Srgba::new(
+        mix(s.red, d.red),
+        mix(s.green, d.green),
+        mix(s.blue, d.blue),
+        (d.alpha + s.alpha).min(1.0),
+    )
+    .into()
+}
+
+/// Scale the `src_rect` region of `src`, an image `src_width` pixels wide, to `size` and blend it
+/// into `dst` with `mode`, sampling the nearest source pixel. `dst` is the top left `width`
+/// pixels wide part of the scaled region, which is all of it unless it was clipped.
+pub fn blend_region(
+    src: &[LinearRgba],
+    src_width: u32,
+    src_rect: URect,
+    size: UVec2,
+    dst: &mut [LinearRgba],
+    width: u32,
+    mode: BlendMode,
+) {
+    let scale = src_rect.size().as_vec2() / size.as_vec2();
+    for (i, pixel) in dst.iter_mut().enumerate() {
+        let (x, y) = (i as u32 % width, i as u32 / width);
+        let at = (Vec2::new(x as f32 + 0.5, y as f32 + 0.5) * scale).as_uvec2();
+        let at = src_rect.min + at.min(src_rect.size() - 1);
+        *pixel = blend_pixel(src[(at.y * src_width + at.x) as usize], *pixel, mode);
+    }
 }
 
 /// Create a readback buffer for the given texture dimensions and format.
@@ -511,6 +1065,85 @@ pub fn set_sampler(
     Ok(())
 }
 
+/// JPEG quality used when a save doesn't ask for one.
+pub const DEFAULT_JPEG_QUALITY: u8 = 90;
+
+/// File formats images and graphics can be saved in, picked from the file extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaveFormat {
+    Png,
+    Jpeg { quality: u8 },
+    Tga,
+    Bmp,
+}
+
+impl SaveFormat {
+    /// The format for `path`'s extension. `quality`, from 1 to 100, only applies to JPEG.
+    pub fn from_path(path: &Path, quality: u8) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("png") => Ok(Self::Png),
+            Some("jpg" | "jpeg") => Ok(Self::Jpeg {
+                quality: quality.clamp(1, 100),
+            }),
+            Some("tga") => Ok(Self::Tga),
+            Some("bmp") => Ok(Self::Bmp),
+            _ => Err(ProcessingError::ImageSaveError(format!(
+                "can't tell the format of {}, use .png, .jpg, .tga or .bmp",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Convert pixels to tightly packed 8-bit sRGB RGBA for export. HDR values outside 0 to 1 are
+/// clipped rather than tone mapped, so a saved `Rgba16Float` surface matches an 8-bit one drawn
+/// the same way.
+pub fn to_srgba8(pixels: &[LinearRgba]) -> Vec<u8> {
+    pixels
+        .iter()
+        .flat_map(|pixel| Srgba::from(*pixel).to_u8_array())
+        .collect()
+}
+
+/// Encode 8-bit sRGB RGBA pixels as `format`. JPEG has no alpha channel, so it's dropped there.
+pub fn encode(rgba: &[u8], width: u32, height: u32, format: SaveFormat) -> Result<Vec<u8>> {
+    use ::image::{
+        ExtendedColorType, ImageEncoder,
+        codecs::{bmp::BmpEncoder, jpeg::JpegEncoder, png::PngEncoder, tga::TgaEncoder},
+    };
+
+    let mut out = Vec::new();
+    match format {
+        SaveFormat::Png => {
+            PngEncoder::new(&mut out).write_image(rgba, width, height, ExtendedColorType::Rgba8)
+        }
+        SaveFormat::Jpeg { quality } => {
+            let rgb: Vec<u8> = rgba
+                .chunks_exact(4)
+                .flat_map(|p| [p[0], p[1], p[2]])
+                .collect();
+            JpegEncoder::new_with_quality(&mut out, quality).write_image(
+                &rgb,
+                width,
+                height,
+                ExtendedColorType::Rgb8,
+            )
+        }
+        SaveFormat::Tga => {
+            TgaEncoder::new(&mut out).write_image(rgba, width, height, ExtendedColorType::Rgba8)
+        }
+        SaveFormat::Bmp => {
+            BmpEncoder::new(&mut out).write_image(rgba, width, height, ExtendedColorType::Rgba8)
+        }
+    }
+    .map_err(|e| ProcessingError::ImageSaveError(e.to_string()))?;
+    Ok(out)
+}
+
 pub fn gpu_image(app: &mut App, entity: Entity) -> Result<&GpuImage> {
     let handle = app
         .world()
@@ -525,3 +1158,250 @@ pub fn gpu_image(app: &mut App, entity: Entity) -> Result<&GpuImage> {
         .ok_or(ProcessingError::ImageNotFound)?;
     Ok(gpu_image)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_view_leaves_the_source_srgb() {
+        let mut images = Assets::<bevy::image::Image>::default();
+        let mut linear_images = LinearImages::default();
+        let source = images.add(bevy::image::Image::new_fill(
+            Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[128, 128, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        ));
+
+        let view = linear_view(&source, &mut images, &mut linear_images);
+        assert_ne!(view, source);
+        assert_eq!(
+            images.get(&source).unwrap().texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        let copy = images.get(&view).unwrap();
+        assert_eq!(copy.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(copy.data, images.get(&source).unwrap().data);
+
+        // asking again reuses the copy, and linear images are used as they are
+        assert_eq!(linear_view(&source, &mut images, &mut linear_images), view);
+        assert_eq!(linear_view(&view, &mut images, &mut linear_images), view);
+    }
+
+    #[test]
+    fn blend_region_scales_and_blends() {
+        let (red, blue) = (LinearRgba::RED, LinearRgba::BLUE);
+        // a 2x1 source scaled up onto a 4x2 region
+        let src = [red, blue];
+        let mut dst = vec![LinearRgba::WHITE; 8];
+        blend_region(
+            &src,
+            2,
+            URect::new(0, 0, 2, 1),
+            UVec2::new(4, 2),
+            &mut dst,
+            4,
+            BlendMode::Replace,
+        );
+        assert_eq!(dst, [red, red, blue, blue, red, red, blue, blue]);
+
+        // clipped to its left half, the region keeps its scale
+        let mut dst = vec![LinearRgba::WHITE; 4];
+        blend_region(
+            &src,
+            2,
+            URect::new(0, 0, 2, 1),
+            UVec2::new(4, 2),
+            &mut dst,
+            2,
+            BlendMode::Replace,
+        );
+        assert_eq!(dst, [red; 4]);
+
+        // blending goes through sRGB, so compare loosely
+        let close = |a: &[LinearRgba], b: &[LinearRgba]| {
+            a.iter()
+                .zip(b)
+                .all(|(a, b)| (a.to_vec4() - b.to_vec4()).abs().max_element() < 1e-5)
+        };
+        let mut dst = vec![LinearRgba::WHITE; 2];
+        blend_region(
+            &src,
+            2,
+            URect::new(0, 0, 2, 1),
+            UVec2::new(2, 1),
+            &mut dst,
+            2,
+            BlendMode::Multiply,
+        );
+        assert!(close(&dst, &[red, blue]));
+        blend_region(
+            &src,
+            2,
+            URect::new(1, 0, 2, 1),
+            UVec2::new(2, 1),
+            &mut dst,
+            2,
+            BlendMode::Lightest,
+        );
+        assert!(close(&dst, &[LinearRgba::new(1.0, 0.0, 1.0, 1.0), blue]));
+    }
+
+    #[test]
+    fn half_black_half_white_peaks_at_the_ends() {
+        let mut pixels = vec![LinearRgba::BLACK; 8];
+        pixels.extend([LinearRgba::WHITE; 8]);
+        let [red, green, blue, alpha] = histogram(&pixels, 4).unwrap();
+
+        for channel in [&red, &green, &blue] {
+            assert_eq!(channel, &[8, 0, 0, 8]);
+        }
+        assert_eq!(alpha, [0, 0, 0, 16]);
+        assert!(histogram(&pixels, 0).is_err());
+    }
+
+    #[test]
+    fn filters_match_processing() {
+        let gray = LinearRgba::from(Srgba::new(0.5, 0.5, 0.5, 1.0));
+        let mut pixels = vec![LinearRgba::RED, gray];
+
+        apply_filter(&mut pixels, 2, Filter::Invert).unwrap();
+        let inverted = Srgba::from(pixels[0]);
+        assert!(inverted.red.abs() < 1e-5 && (inverted.green - 1.0).abs() < 1e-5);
+        apply_filter(&mut pixels, 2, Filter::Threshold(0.6)).unwrap();
+        assert_eq!(pixels, vec![LinearRgba::WHITE, LinearRgba::BLACK]);
+
+        let mut pixels = vec![
+            Srgba::new(0.3, 0.3, 0.3, 1.0).into(),
+            Srgba::new(0.7, 0.7, 0.7, 1.0).into(),
+        ];
+        apply_filter(&mut pixels, 2, Filter::Posterize(2)).unwrap();
+        assert_eq!(pixels, vec![LinearRgba::BLACK, LinearRgba::WHITE]);
+        assert!(apply_filter(&mut pixels, 2, Filter::Posterize(1)).is_err());
+
+        let mut pixels = vec![LinearRgba::RED];
+        apply_filter(&mut pixels, 1, Filter::Gray).unwrap();
+        let luma = Srgba::from(pixels[0]);
+        assert!((luma.red - 0.299).abs() < 1e-5 && luma.red == luma.blue);
+
+        // a blurred dot spreads out evenly and keeps its total
+        let mut pixels = vec![LinearRgba::BLACK; 49];
+        pixels[24] = LinearRgba::WHITE;
+        apply_filter(&mut pixels, 7, Filter::Blur(1.0)).unwrap();
+        let srgb: Vec<f32> = pixels.iter().map(|p| Srgba::from(*p).red).collect();
+        assert!(srgb[24] < 1.0 && srgb[24] > srgb[23]);
+        assert!((srgb[23] - srgb[25]).abs() < 1e-5 && (srgb[17] - srgb[31]).abs() < 1e-5);
+        assert!((srgb.iter().sum::<f32>() - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn pixel_cache_uploads_only_what_was_set() {
+        let mut cache = PixelCache::new(vec![LinearRgba::BLACK; 16], 4, 4);
+        assert!(cache.take_dirty().is_none());
+
+        cache.set(1, 1, LinearRgba::RED).unwrap();
+        cache.set(2, 3, LinearRgba::BLUE).unwrap();
+        cache.set(1, 1, LinearRgba::GREEN).unwrap();
+        assert_eq!(cache.get(1, 1).unwrap(), LinearRgba::GREEN);
+        assert!(cache.get(4, 0).is_err());
+        assert!(cache.set(0, 4, LinearRgba::RED).is_err());
+
+        // both sets coalesce into one 2x3 region
+        let (rect, pixels) = cache.take_dirty().unwrap();
+        assert_eq!(rect, URect::new(1, 1, 3, 4));
+        assert_eq!(pixels.len(), 6);
+        assert_eq!(pixels[0], LinearRgba::GREEN);
+        assert_eq!(pixels[5], LinearRgba::BLUE);
+        assert!(cache.take_dirty().is_none());
+    }
+
+    #[test]
+    fn rotated_photo_is_turned_upright() {
+        // a minimal jpeg holding only an exif block that says "rotate 90 clockwise"
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend(8u32.to_le_bytes());
+        tiff.extend(1u16.to_le_bytes());
+        tiff.extend(0x0112u16.to_le_bytes());
+        tiff.extend(3u16.to_le_bytes());
+        tiff.extend(1u32.to_le_bytes());
+        tiff.extend([6, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        let mut app1 = b"Exif\0\0".to_vec();
+        app1.extend(tiff);
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend((app1.len() as u16 + 2).to_be_bytes());
+        jpeg.extend(app1);
+        jpeg.extend([0xFF, 0xD9]);
+
+        let path = std::env::temp_dir().join("processing_exif_orientation.jpg");
+        std::fs::write(&path, jpeg).unwrap();
+        let orientation = exif_orientation(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(orientation, Some(6));
+
+        // stored as a 3x2 image, rows "abc" and "def"
+        let (data, width, height) = apply_orientation(b"abcdef", 3, 2, 1, 6);
+        assert_eq!((width, height), (2, 3));
+        assert_eq!(data, b"daebfc");
+        let (data, width, height) = apply_orientation(b"abcdef", 3, 2, 1, 8);
+        assert_eq!((width, height), (2, 3));
+        assert_eq!(data, b"cfbead");
+    }
+
+    #[test]
+    fn save_format_follows_the_extension() {
+        let format = |path: &str| SaveFormat::from_path(Path::new(path), 80).ok();
+        assert_eq!(format("out.png"), Some(SaveFormat::Png));
+        assert_eq!(format("out.JPG"), Some(SaveFormat::Jpeg { quality: 80 }));
+        assert_eq!(format("out.jpeg"), Some(SaveFormat::Jpeg { quality: 80 }));
+        assert_eq!(format("out.tga"), Some(SaveFormat::Tga));
+        assert_eq!(format("out.bmp"), Some(SaveFormat::Bmp));
+        assert_eq!(format("out.gif"), None);
+        assert_eq!(format("out"), None);
+
+        // hdr values are clipped to white, not wrapped or tone mapped
+        let rgba = to_srgba8(&[LinearRgba::new(4.0, -1.0, 0.5, 1.0)]);
+        assert_eq!(rgba[..2], [255, 0]);
+
+        let signatures: [(SaveFormat, &[u8]); 3] = [
+            (SaveFormat::Png, b"\x89PNG"),
+            (SaveFormat::Jpeg { quality: 80 }, &[0xFF, 0xD8]),
+            (SaveFormat::Bmp, b"BM"),
+        ];
+        for (format, signature) in signatures {
+            let encoded = encode(&rgba.repeat(4), 2, 2, format).unwrap();
+            assert!(encoded.starts_with(signature), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn inverting_callback_inverts_pixels() {
+        let mut pixels = vec![
+            LinearRgba::new(0.0, 0.25, 1.0, 1.0),
+            LinearRgba::new(1.0, 0.5, 0.0, 1.0),
+            LinearRgba::new(0.1, 0.2, 0.3, 0.5),
+            LinearRgba::BLACK,
+        ];
+        let original = pixels.clone();
+        let mut visited = Vec::new();
+
+        map_pixels(&mut pixels, 2, |x, y, p| {
+            visited.push((x, y));
+            LinearRgba::new(1.0 - p.red, 1.0 - p.green, 1.0 - p.blue, p.alpha)
+        });
+
+        assert_eq!(visited, [(0, 0), (1, 0), (0, 1), (1, 1)]);
+        for (inverted, p) in pixels.iter().zip(&original) {
+            assert_eq!(inverted.red, 1.0 - p.red);
+            assert_eq!(inverted.green, 1.0 - p.green);
+            assert_eq!(inverted.blue, 1.0 - p.blue);
+            assert_eq!(inverted.alpha, p.alpha);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 pub mod camera;
 pub mod color;
 pub mod compute;
+pub mod depth;
 pub mod geometry;
 pub mod gltf;
 pub mod graphics;
@@ -10,19 +11,25 @@ pub mod image;
 pub mod light;
 pub mod material;
 pub mod monitor;
+pub mod noise;
+pub mod obj;
 pub mod particles;
+pub mod random;
 pub mod render;
 pub mod shader_value;
 pub mod sketch;
 pub mod surface;
+pub mod table;
 pub mod text;
 pub mod time;
 pub mod transform;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use bevy::{
     asset::AssetEventSystems,
+    math::Affine3A,
+    mesh::{MeshVertexAttribute, VertexAttributeValues},
     prelude::*,
     render::render_resource::{Extent3d, TextureFormat},
 };
@@ -33,7 +40,8 @@ use processing_core::error;
 use crate::geometry::{AttributeFormat, AttributeValue};
 use crate::graphics::flush;
 use crate::image::gpu_image;
-use crate::render::command::DrawCommand;
+use crate::render::command::{BlendMode, DrawCommand};
+use crate::render::primitive::VertexType;
 
 #[derive(Component)]
 pub struct Flush;
@@ -48,6 +56,8 @@ impl Plugin for ProcessingRenderPlugin {
         let config = app.world().resource::<Config>().clone();
 
         app.init_resource::<time::ProcessingFrame>();
+        app.init_resource::<noise::Noise>();
+        app.init_resource::<random::Rng>();
 
         let has_sketch_file = config
             .get(ConfigKey::SketchFileName)
@@ -81,6 +91,7 @@ impl Plugin for ProcessingRenderPlugin {
                     add_processing_materials,
                     add_custom_materials,
                     particles::material::add_particles_materials,
+                    text::sdf::add_sdf_text_materials,
                 )
                     .chain()
                     .before(AssetEventSystems),
@@ -97,12 +108,10 @@ pub fn surface_create_macos(
     scale_factor: f32,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                surface::create_surface_macos,
-                (window_handle, width, height, scale_factor),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            surface::create_surface_macos,
+            (window_handle, width, height, scale_factor),
+        )?
     })
 }
 
@@ -115,12 +124,10 @@ pub fn surface_create_windows(
     scale_factor: f32,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                surface::create_surface_windows,
-                (window_handle, width, height, scale_factor),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            surface::create_surface_windows,
+            (window_handle, width, height, scale_factor),
+        )?
     })
 }
 
@@ -134,12 +141,10 @@ pub fn surface_create_wayland(
     scale_factor: f32,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                surface::create_surface_wayland,
-                (window_handle, display_handle, width, height, scale_factor),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            surface::create_surface_wayland,
+            (window_handle, display_handle, width, height, scale_factor),
+        )?
     })
 }
 
@@ -153,12 +158,10 @@ pub fn surface_create_x11(
     scale_factor: f32,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                surface::create_surface_x11,
-                (window_handle, display_handle, width, height, scale_factor),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            surface::create_surface_x11,
+            (window_handle, display_handle, width, height, scale_factor),
+        )?
     })
 }
 
@@ -219,12 +222,10 @@ pub fn surface_create_web(
     scale_factor: f32,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                surface::create_surface_web,
-                (window_handle, width, height, scale_factor),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            surface::create_surface_web,
+            (window_handle, width, height, scale_factor),
+        )?
     })
 }
 
@@ -238,9 +239,8 @@ pub fn surface_create_offscreen(
         let (size, data, texture_format) =
             surface::prepare_offscreen(width, height, scale_factor, texture_format)?;
         let world = app.world_mut();
-        let image_entity = world
-            .run_system_cached_with(image::create, (size, data, texture_format))
-            .unwrap();
+        let image_entity =
+            world.run_system_cached_with(image::create, (size, data, texture_format))?;
         world.entity_mut(image_entity).insert(surface::Surface);
         Ok(image_entity)
     })
@@ -281,8 +281,7 @@ pub fn surface_create_from_canvas(
 pub fn surface_destroy(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::destroy, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(surface::destroy, graphics_entity)?
     })
 }
 
@@ -290,24 +289,21 @@ pub fn surface_destroy(graphics_entity: Entity) -> error::Result<()> {
 pub fn surface_resize(graphics_entity: Entity, width: u32, height: u32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::resize, (graphics_entity, width, height))
-            .unwrap()
+            .run_system_cached_with(surface::resize, (graphics_entity, width, height))?
     })
 }
 
 pub fn surface_set_pixel_density(entity: Entity, density: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_pixel_density, (entity, density))
-            .unwrap()
+            .run_system_cached_with(surface::set_pixel_density, (entity, density))?
     })
 }
 
 pub fn surface_set_title(entity: Entity, title: String) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_title, (entity, title))
-            .unwrap()
+            .run_system_cached_with(surface::set_title, (entity, title))?
     })
 }
 
@@ -315,40 +311,35 @@ pub fn surface_position(entity: Entity) -> error::Result<bevy::math::IVec2> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::position, entity)
-            .unwrap())
+            .run_system_cached_with(surface::position, entity)?)
     })
 }
 
 pub fn surface_set_position(entity: Entity, x: i32, y: i32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_position, (entity, x, y))
-            .unwrap()
+            .run_system_cached_with(surface::set_position, (entity, x, y))?
     })
 }
 
 pub fn surface_set_visible(entity: Entity, visible: bool) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_visible, (entity, visible))
-            .unwrap()
+            .run_system_cached_with(surface::set_visible, (entity, visible))?
     })
 }
 
 pub fn surface_set_resizable(entity: Entity, resizable: bool) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_resizable, (entity, resizable))
-            .unwrap()
+            .run_system_cached_with(surface::set_resizable, (entity, resizable))?
     })
 }
 
 pub fn surface_set_decorated(entity: Entity, decorated: bool) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_decorated, (entity, decorated))
-            .unwrap()
+            .run_system_cached_with(surface::set_decorated, (entity, decorated))?
     })
 }
 
@@ -358,8 +349,7 @@ pub fn surface_set_window_level(
 ) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_window_level, (entity, level))
-            .unwrap()
+            .run_system_cached_with(surface::set_window_level, (entity, level))?
     })
 }
 
@@ -369,48 +359,42 @@ pub fn surface_set_window_mode(
 ) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_window_mode, (entity, mode))
-            .unwrap()
+            .run_system_cached_with(surface::set_window_mode, (entity, mode))?
     })
 }
 
 pub fn surface_set_opacity(entity: Entity, opacity: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::set_opacity, (entity, opacity))
-            .unwrap()
+            .run_system_cached_with(surface::set_opacity, (entity, opacity))?
     })
 }
 
 pub fn surface_iconify(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::iconify, entity)
-            .unwrap()
+            .run_system_cached_with(surface::iconify, entity)?
     })
 }
 
 pub fn surface_restore(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::restore, entity)
-            .unwrap()
+            .run_system_cached_with(surface::restore, entity)?
     })
 }
 
 pub fn surface_maximize(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::maximize, entity)
-            .unwrap()
+            .run_system_cached_with(surface::maximize, entity)?
     })
 }
 
 pub fn surface_focus(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::focus, entity)
-            .unwrap()
+            .run_system_cached_with(surface::focus, entity)?
     })
 }
 
@@ -422,16 +406,14 @@ pub fn surface_position_on_monitor(
 ) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::position_on_monitor, (surface, monitor, x, y))
-            .unwrap()
+            .run_system_cached_with(surface::position_on_monitor, (surface, monitor, x, y))?
     })
 }
 
 pub fn surface_center_on_monitor(surface: Entity, monitor: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(surface::center_on_monitor, (surface, monitor))
-            .unwrap()
+            .run_system_cached_with(surface::center_on_monitor, (surface, monitor))?
     })
 }
 
@@ -443,13 +425,10 @@ pub fn graphics_create(
     texture_format: TextureFormat,
 ) -> error::Result<Entity> {
     app_mut(|app| -> error::Result<Entity> {
-        let entity = app
-            .world_mut()
-            .run_system_cached_with(
-                graphics::create,
-                (width, height, surface_entity, texture_format),
-            )
-            .unwrap()?;
+        let entity = app.world_mut().run_system_cached_with(
+            graphics::create,
+            (width, height, surface_entity, texture_format),
+        )??;
 
         graphics::warmup(app, entity)?;
         Ok(entity)
@@ -460,8 +439,7 @@ pub fn graphics_create(
 pub fn graphics_begin_draw(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(graphics::begin_draw, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(graphics::begin_draw, graphics_entity)?
     })
 }
 
@@ -477,15 +455,53 @@ pub fn graphics_present(graphics_entity: Entity) -> error::Result<()> {
 
 /// End the current draw pass for the graphics surface.
 pub fn graphics_end_draw(graphics_entity: Entity) -> error::Result<()> {
-    app_mut(|app| graphics::end_draw(app, graphics_entity))
+    let presented = app_mut(|app| {
+        graphics::end_draw(app, graphics_entity)?;
+        let callback = app
+            .world()
+            .get::<graphics::EndDrawCallback>(graphics_entity)
+            .cloned();
+        let frame = app.world_mut().run_system_cached(time::frame_count)?;
+        Ok(callback.map(|callback| (callback, frame)))
+    })?;
+    // called once the app is released, so the callback can use the rest of the API
+    if let Some((callback, frame)) = presented {
+        (callback.0)(frame);
+    }
+    Ok(())
+}
+
+/// Call `callback` with [`frame_count`] after each `graphics_end_draw` has presented the frame,
+/// the same number the sketch sees while drawing it. Replaces any callback set before.
+pub fn graphics_on_end_draw(
+    graphics_entity: Entity,
+    callback: impl Fn(u32) + Send + Sync + 'static,
+) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .get_entity_mut(graphics_entity)
+            .map_err(|_| error::ProcessingError::GraphicsNotFound)?
+            .insert(graphics::EndDrawCallback(std::sync::Arc::new(callback)));
+        Ok(())
+    })
+}
+
+/// Stop calling the callback set with [`graphics_on_end_draw`].
+pub fn graphics_clear_end_draw_callback(graphics_entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .get_entity_mut(graphics_entity)
+            .map_err(|_| error::ProcessingError::GraphicsNotFound)?
+            .remove::<graphics::EndDrawCallback>();
+        Ok(())
+    })
 }
 
 /// Destroy the graphics surface and free its resources.
 pub fn graphics_destroy(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(graphics::destroy, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(graphics::destroy, graphics_entity)?
     })
 }
 
@@ -496,12 +512,64 @@ pub fn graphics_readback_raw(graphics_entity: Entity) -> error::Result<graphics:
         let vt = graphics::view_target(app, graphics_entity)?;
         let texture = vt.main_texture().clone();
         app.world_mut()
-            .run_system_cached_with(graphics::readback_raw, (graphics_entity, texture))
-            .unwrap()
+            .run_system_cached_with(graphics::readback_raw, (graphics_entity, texture))?
+    })
+}
+
+/// Read back the depth of the graphics' current frame, one value per pixel in row-major order.
+/// Depth is reversed, as in Bevy: nearer surfaces have larger values and empty pixels are 0.
+pub fn graphics_readback_depth(graphics_entity: Entity) -> error::Result<Vec<f32>> {
+    app_mut(|app| {
+        graphics::flush(app, graphics_entity)?;
+        app.sub_app_mut(bevy::render::RenderApp)
+            .world_mut()
+            .run_system_cached_with(depth::readback, graphics_entity)?
     })
 }
 
-/// Read back pixel data from the graphics surface as LinearRgba.
+/// Copy the current frame of the graphics drawing to `surface_entity` onto the system clipboard.
+/// Fails where there's no clipboard to talk to, like a headless session.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn surface_copy_to_clipboard(surface_entity: Entity) -> error::Result<()> {
+    let graphics_entity = app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(graphics::for_surface, surface_entity)?
+    })?;
+    let raw = graphics_readback_raw(graphics_entity)?;
+    let rgba = raw.to_srgba8()?;
+    surface::copy_to_clipboard(raw.width, raw.height, rgba)
+}
+
+/// Save the graphics surface to `path`, in the format its extension names (`.png`, `.jpg`,
+/// `.jpeg`, `.tga` or `.bmp`). A run of `#`s in the file name is replaced with the zero-padded
+/// frame count, like Processing's `saveFrame("frame-####.png")`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn graphics_save(graphics_entity: Entity, path: &str) -> error::Result<()> {
+    graphics_save_with_quality(graphics_entity, path, image::DEFAULT_JPEG_QUALITY)
+}
+
+/// [`graphics_save`] with a JPEG `quality` from 1 to 100, which other formats ignore.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn graphics_save_with_quality(
+    graphics_entity: Entity,
+    path: &str,
+    quality: u8,
+) -> error::Result<()> {
+    let path = graphics::insert_frame(path, frame_count()?);
+    let format = image::SaveFormat::from_path(std::path::Path::new(&path), quality)?;
+    let bytes = graphics_readback_raw(graphics_entity)?.encode(format)?;
+    write_saved_file(&path, bytes)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_saved_file(path: &str, bytes: Vec<u8>) -> error::Result<()> {
+    std::fs::write(path, bytes)
+        .map_err(|e| error::ProcessingError::ImageSaveError(format!("{path}: {e}")))
+}
+
+/// Read back pixel data from the graphics surface as LinearRgba. The pixels are the surface's
+/// physical ones, so a surface with a scale factor of 2 gives four times as many as its logical
+/// size.
 pub fn graphics_readback(graphics_entity: Entity) -> error::Result<Vec<LinearRgba>> {
     let raw = graphics_readback_raw(graphics_entity)?;
     let px_size = image::pixel_size(raw.format)?;
@@ -515,6 +583,127 @@ pub fn graphics_readback(graphics_entity: Entity) -> error::Result<Vec<LinearRgb
     )
 }
 
+/// [`graphics_readback`] into `pixels`, reusing the caller's allocation from frame to frame.
+/// `pixels` must hold exactly width * height colors, in physical pixels.
+pub fn graphics_readback_into(
+    graphics_entity: Entity,
+    pixels: &mut [LinearRgba],
+) -> error::Result<()> {
+    app_mut(|app| {
+        graphics::flush(app, graphics_entity)?;
+        let vt = graphics::view_target(app, graphics_entity)?;
+        let texture = vt.main_texture().clone();
+        app.world_mut().run_system_cached_with(
+            graphics::readback_into,
+            ((graphics_entity, texture), pixels),
+        )?
+    })
+}
+
+/// The graphics surface's pixels as raw bytes in its own texture format, row by row without
+/// padding. Skips the conversion to `LinearRgba`, for callers that know the format.
+pub fn graphics_load_pixels_bytes(graphics_entity: Entity) -> error::Result<Vec<u8>> {
+    Ok(graphics_readback_raw(graphics_entity)?.bytes)
+}
+
+/// Overwrite the graphics surface with raw bytes in its texture format, laid out as
+/// [`graphics_load_pixels_bytes`] returns them.
+pub fn graphics_update_pixels_bytes(graphics_entity: Entity, bytes: &[u8]) -> error::Result<()> {
+    app_mut(|app| {
+        let vt = graphics::view_target(app, graphics_entity)?;
+        let texture = vt.main_texture().clone();
+        let world = app.world_mut();
+        let graphics = world
+            .get::<graphics::Graphics>(graphics_entity)
+            .ok_or(error::ProcessingError::GraphicsNotFound)?;
+        let size = graphics.size;
+        let px_size = image::pixel_size(graphics.texture_format)?;
+        let expected = size.width as usize * size.height as usize * px_size;
+        if bytes.len() != expected {
+            return Err(error::ProcessingError::InvalidArgument(format!(
+                "Expected {expected} bytes for a {}x{} surface, got {}",
+                size.width,
+                size.height,
+                bytes.len()
+            )));
+        }
+        world.run_system_cached_with(
+            graphics::update_region_write,
+            (
+                graphics_entity,
+                texture,
+                0,
+                0,
+                size.width,
+                size.height,
+                bytes.to_vec(),
+                px_size as u32,
+            ),
+        )?
+    })
+}
+
+/// Read the graphics surface back to the CPU, let `f` rewrite the whole row-major pixel buffer
+/// (given with the surface width) and write the result back. When `f` fails the surface is left
+/// as it was and `f`'s error is returned inside the `Ok`.
+pub fn graphics_map_batch<E>(
+    graphics_entity: Entity,
+    f: impl FnOnce(&mut [LinearRgba], u32) -> Result<(), E>,
+) -> error::Result<Result<(), E>> {
+    let width = app_mut(|app| {
+        app.world()
+            .get::<graphics::Graphics>(graphics_entity)
+            .map(|graphics| graphics.size.width)
+            .ok_or(error::ProcessingError::GraphicsNotFound)
+    })?;
+    let mut pixels = graphics_readback(graphics_entity)?;
+    if let Err(e) = f(&mut pixels, width) {
+        return Ok(Err(e));
+    }
+    graphics_update(graphics_entity, &pixels)?;
+    Ok(Ok(()))
+}
+
+/// Replace every pixel of the graphics surface with `f(x, y, pixel)`, computed on the CPU.
+pub fn graphics_map(
+    graphics_entity: Entity,
+    f: impl FnMut(u32, u32, LinearRgba) -> LinearRgba,
+) -> error::Result<()> {
+    graphics_map_batch(graphics_entity, |pixels, width| {
+        image::map_pixels(pixels, width, f);
+        Ok::<_, std::convert::Infallible>(())
+    })
+    .map(|_| ())
+}
+
+/// Read back the color of one pixel of the graphics surface, like Processing's `get(x, y)`.
+pub fn graphics_get_pixel(graphics_entity: Entity, x: u32, y: u32) -> error::Result<LinearRgba> {
+    let pixel = graphics_readback_raw(graphics_entity)?.crop(x, y, 1, 1)?;
+    let px_size = image::pixel_size(pixel.format)?;
+    Ok(image::bytes_to_pixels(&pixel.bytes, pixel.format, 1, 1, px_size)?[0])
+}
+
+/// Read back a region of the graphics surface into a new image, like Processing's
+/// `get(x, y, w, h)`. The image has the surface's texture format.
+pub fn graphics_get_image(
+    graphics_entity: Entity,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> error::Result<Entity> {
+    let region = graphics_readback_raw(graphics_entity)?.crop(x, y, width, height)?;
+    image_create(
+        Extent3d {
+            width: region.width,
+            height: region.height,
+            depth_or_array_layers: 1,
+        },
+        region.bytes,
+        region.format,
+    )
+}
+
 /// Update the graphics surface with new pixel data.
 pub fn graphics_update(graphics_entity: Entity, pixels: &[LinearRgba]) -> error::Result<()> {
     app_mut(|app| {
@@ -532,21 +721,19 @@ pub fn graphics_update(graphics_entity: Entity, pixels: &[LinearRgba]) -> error:
             size.height,
             pixels,
         )?;
-        world
-            .run_system_cached_with(
-                graphics::update_region_write,
-                (
-                    graphics_entity,
-                    texture,
-                    0,
-                    0,
-                    size.width,
-                    size.height,
-                    data,
-                    px_size,
-                ),
-            )
-            .unwrap()
+        world.run_system_cached_with(
+            graphics::update_region_write,
+            (
+                graphics_entity,
+                texture,
+                0,
+                0,
+                size.width,
+                size.height,
+                data,
+                px_size,
+            ),
+        )?
     })
 }
 
@@ -565,12 +752,29 @@ pub fn graphics_update_region(
         let world = app.world_mut();
         let (data, px_size) =
             graphics::prepare_update_region(world, graphics_entity, width, height, pixels)?;
-        world
-            .run_system_cached_with(
-                graphics::update_region_write,
-                (graphics_entity, texture, x, y, width, height, data, px_size),
-            )
-            .unwrap()
+        world.run_system_cached_with(
+            graphics::update_region_write,
+            (graphics_entity, texture, x, y, width, height, data, px_size),
+        )?
+    })
+}
+
+/// Show or hide the frame rate and frame count overlay, drawn over the sketch at the end of each
+/// frame.
+pub fn graphics_show_fps(graphics_entity: Entity, show: bool) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(graphics::show_fps, (graphics_entity, show))?
+    })
+}
+
+/// Timings for the most recent flush and readback of the graphics surface.
+pub fn graphics_frame_timings(graphics_entity: Entity) -> error::Result<graphics::FrameTimings> {
+    app_mut(|app| {
+        app.world()
+            .get::<graphics::FrameTimings>(graphics_entity)
+            .copied()
+            .ok_or(error::ProcessingError::GraphicsNotFound)
     })
 }
 
@@ -601,21 +805,86 @@ pub fn graphics_get_color_mode(graphics_entity: Entity) -> error::Result<color::
     })
 }
 
-/// Record a drawing command for a window
+/// Blend between two colors in the graphics' current color mode, like Processing's `lerpColor()`.
+pub fn graphics_lerp_color(
+    graphics_entity: Entity,
+    a: Color,
+    b: Color,
+    t: f32,
+) -> error::Result<Color> {
+    let mode = graphics_get_color_mode(graphics_entity)?;
+    Ok(color::lerp_color(a, b, t, &mode))
+}
+
+/// The current transform as a column-major 4x4 matrix, including matrix commands recorded but not
+/// yet flushed. Its inverse maps mouse coordinates into the space shapes are drawn in.
+pub fn graphics_get_matrix(graphics_entity: Entity) -> error::Result<[f32; 16]> {
+    app_mut(|app| {
+        let matrix = app
+            .world_mut()
+            .run_system_cached_with(graphics::current_matrix, graphics_entity)??;
+        Ok(matrix.to_cols_array())
+    })
+}
+
+/// Where the point `(x, y, z)`, drawn at the current transform, lands on the graphics in pixels.
+/// Use it to place 2d labels over 3d shapes or to hit-test them.
+pub fn graphics_screen_xy(
+    graphics_entity: Entity,
+    x: f32,
+    y: f32,
+    z: f32,
+) -> error::Result<(f32, f32)> {
+    app_mut(|app| {
+        let screen = app
+            .world_mut()
+            .run_system_cached_with(graphics::screen_xy, (graphics_entity, Vec3::new(x, y, z)))??;
+        Ok((screen.x, screen.y))
+    })
+}
+
+/// Record a drawing command for a window. Off the render thread the command is sent to it, in
+/// order with everything else sent from this thread, and any error recording it is returned here.
 pub fn graphics_record_command(graphics_entity: Entity, cmd: DrawCommand) -> error::Result<()> {
+    if processing_core::is_off_render_thread() {
+        return processing_core::run_on_render_thread(move || {
+            graphics_record_command(graphics_entity, cmd)
+        });
+    }
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(graphics::record_command, (graphics_entity, cmd))
-            .unwrap()
+            .run_system_cached_with(graphics::record_command, (graphics_entity, cmd))?
     })
 }
 
+/// Fill a graphics with copies of `image`, each drawn `tile_w` x `tile_h` pixels from the top left
+/// corner. The image itself is left as it is, so later `image()` draws of it don't tile.
+pub fn graphics_background_tiled(
+    graphics_entity: Entity,
+    image_entity: Entity,
+    tile_w: f32,
+    tile_h: f32,
+) -> error::Result<()> {
+    if !(tile_w > 0.0 && tile_h > 0.0) {
+        return Err(error::ProcessingError::InvalidArgument(format!(
+            "tile size must be positive, got {tile_w}x{tile_h}"
+        )));
+    }
+    graphics_record_command(
+        graphics_entity,
+        DrawCommand::BackgroundTiled {
+            image: image_entity,
+            tile_w,
+            tile_h,
+        },
+    )
+}
+
 pub fn graphics_mode_3d(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         flush(app, graphics_entity)?;
         app.world_mut()
-            .run_system_cached_with(graphics::mode_3d, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(graphics::mode_3d, graphics_entity)?
     })
 }
 
@@ -623,88 +892,91 @@ pub fn graphics_mode_2d(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         flush(app, graphics_entity)?;
         app.world_mut()
-            .run_system_cached_with(graphics::mode_2d, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(graphics::mode_2d, graphics_entity)?
+    })
+}
+
+/// Position the camera with an eye point, the point it looks at and an up direction in one call.
+pub fn graphics_camera(
+    graphics_entity: Entity,
+    eye: Vec3,
+    center: Vec3,
+    up: Vec3,
+) -> error::Result<()> {
+    app_mut(|app| {
+        flush(app, graphics_entity)?;
+        app.world_mut()
+            .run_system_cached_with(graphics::camera, (graphics_entity, eye, center, up))?
     })
 }
 
 pub fn graphics_orbit_camera(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::enable_orbit_camera, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(camera::enable_orbit_camera, graphics_entity)?
     })
 }
 
 pub fn graphics_free_camera(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::enable_free_camera, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(camera::enable_free_camera, graphics_entity)?
     })
 }
 
 pub fn graphics_pan_camera(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::enable_pan_camera, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(camera::enable_pan_camera, graphics_entity)?
     })
 }
 
 pub fn graphics_disable_camera_controller(graphics_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::disable_camera_controller, graphics_entity)
-            .unwrap()
+            .run_system_cached_with(camera::disable_camera_controller, graphics_entity)?
     })
 }
 
 pub fn camera_set_distance(entity: Entity, distance: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::set_distance, (entity, distance))
-            .unwrap()
+            .run_system_cached_with(camera::set_distance, (entity, distance))?
     })
 }
 
 pub fn camera_set_center(entity: Entity, center: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::set_center, (entity, center))
-            .unwrap()
+            .run_system_cached_with(camera::set_center, (entity, center))?
     })
 }
 
 pub fn camera_set_min_distance(entity: Entity, min: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::set_min_distance, (entity, min))
-            .unwrap()
+            .run_system_cached_with(camera::set_min_distance, (entity, min))?
     })
 }
 
 pub fn camera_set_max_distance(entity: Entity, max: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::set_max_distance, (entity, max))
-            .unwrap()
+            .run_system_cached_with(camera::set_max_distance, (entity, max))?
     })
 }
 
 pub fn camera_set_speed(entity: Entity, speed: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::set_speed, (entity, speed))
-            .unwrap()
+            .run_system_cached_with(camera::set_speed, (entity, speed))?
     })
 }
 
 pub fn camera_reset(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(camera::reset_camera, entity)
-            .unwrap()
+            .run_system_cached_with(camera::reset_camera, entity)?
     })
 }
 
@@ -718,21 +990,19 @@ pub fn graphics_perspective(
 ) -> error::Result<()> {
     app_mut(|app| {
         flush(app, graphics_entity)?;
-        app.world_mut()
-            .run_system_cached_with(
-                graphics::perspective,
-                (
-                    graphics_entity,
-                    PerspectiveProjection {
-                        fov,
-                        aspect_ratio,
-                        near,
-                        far,
-                        near_clip_plane,
-                    },
-                ),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            graphics::perspective,
+            (
+                graphics_entity,
+                PerspectiveProjection {
+                    fov,
+                    aspect_ratio,
+                    near,
+                    far,
+                    near_clip_plane,
+                },
+            ),
+        )?
     })
 }
 
@@ -748,110 +1018,97 @@ pub fn graphics_ortho(
 ) -> error::Result<()> {
     app_mut(|app| {
         flush(app, graphics_entity)?;
-        app.world_mut()
-            .run_system_cached_with(
-                graphics::ortho,
-                (
-                    graphics_entity,
-                    graphics::OrthoArgs {
-                        left,
-                        right,
-                        bottom,
-                        top,
-                        near,
-                        far,
-                    },
-                ),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            graphics::ortho,
+            (
+                graphics_entity,
+                graphics::OrthoArgs {
+                    left,
+                    right,
+                    bottom,
+                    top,
+                    near,
+                    far,
+                },
+            ),
+        )?
     })
 }
 
 pub fn transform_set_position(entity: Entity, position: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::set_position, (entity, position))
-            .unwrap()
+            .run_system_cached_with(transform::set_position, (entity, position))?
     })
 }
 
 pub fn transform_translate(entity: Entity, offset: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::translate, (entity, offset))
-            .unwrap()
+            .run_system_cached_with(transform::translate, (entity, offset))?
     })
 }
 
 pub fn transform_set_rotation(entity: Entity, euler: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::set_rotation, (entity, euler))
-            .unwrap()
+            .run_system_cached_with(transform::set_rotation, (entity, euler))?
     })
 }
 
 pub fn transform_rotate_x(entity: Entity, angle: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::rotate_x, (entity, angle))
-            .unwrap()
+            .run_system_cached_with(transform::rotate_x, (entity, angle))?
     })
 }
 
 pub fn transform_rotate_y(entity: Entity, angle: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::rotate_y, (entity, angle))
-            .unwrap()
+            .run_system_cached_with(transform::rotate_y, (entity, angle))?
     })
 }
 
 pub fn transform_rotate_z(entity: Entity, angle: f32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::rotate_z, (entity, angle))
-            .unwrap()
+            .run_system_cached_with(transform::rotate_z, (entity, angle))?
     })
 }
 
 pub fn transform_rotate_axis(entity: Entity, angle: f32, axis: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::rotate_axis, (entity, angle, axis))
-            .unwrap()
+            .run_system_cached_with(transform::rotate_axis, (entity, angle, axis))?
     })
 }
 
 pub fn transform_set_scale(entity: Entity, scale: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::set_scale, (entity, scale))
-            .unwrap()
+            .run_system_cached_with(transform::set_scale, (entity, scale))?
     })
 }
 
 pub fn transform_scale(entity: Entity, factor: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::scale, (entity, factor))
-            .unwrap()
+            .run_system_cached_with(transform::scale, (entity, factor))?
     })
 }
 
 pub fn transform_look_at(entity: Entity, target: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::look_at, (entity, target))
-            .unwrap()
+            .run_system_cached_with(transform::look_at, (entity, target))?
     })
 }
 
 pub fn transform_reset(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(transform::reset, entity)
-            .unwrap()
+            .run_system_cached_with(transform::reset, entity)?
     })
 }
 
@@ -864,19 +1121,35 @@ pub fn image_create(
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(image::create, (size, data, texture_format))
-            .unwrap())
+            .run_system_cached_with(image::create, (size, data, texture_format))?)
+    })
+}
+
+/// Compile an asset into the binary under `path` in the `embedded` asset source, for example
+/// `asset_embed("logo.png", include_bytes!("../assets/logo.png"))`. With
+/// [`Config::asset_source`] set to `embedded`, loading `path` then reads these bytes.
+pub fn asset_embed(path: &str, bytes: &'static [u8]) -> error::Result<()> {
+    app_mut(|app| {
+        app.world()
+            .resource::<bevy::asset::io::embedded::EmbeddedAssetRegistry>()
+            .insert_asset(PathBuf::from(path), Path::new(path), bytes);
+        Ok(())
     })
 }
 
 /// Load an image from disk.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn image_load(path: &str) -> error::Result<Entity> {
+    image_load_with_orientation(path, true)
+}
+
+/// Load an image, choosing whether photos with an EXIF orientation tag are turned upright.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn image_load_with_orientation(path: &str, auto_orient: bool) -> error::Result<Entity> {
     let path = PathBuf::from(path);
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(image::load, path)
-            .unwrap()
+            .run_system_cached_with(image::load, (path, auto_orient))?
     })
 }
 
@@ -914,33 +1187,116 @@ pub async fn image_load(path: &str) -> error::Result<Entity> {
 
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(image::from_handle, handle)
-            .unwrap()
+            .run_system_cached_with(image::from_handle, handle)?
     })
 }
 
 /// Resize an existing image to new size.
 pub fn image_resize(entity: Entity, new_size: Extent3d) -> error::Result<()> {
     app_mut(|app| {
+        drop_pixel_cache(app, entity);
         app.world_mut()
-            .run_system_cached_with(image::resize, (entity, new_size))
-            .unwrap()
+            .run_system_cached_with(image::resize, (entity, new_size))?
     })
 }
 
 /// Read back image data from GPU to CPU.
 pub fn image_readback(entity: Entity) -> error::Result<Vec<LinearRgba>> {
     app_mut(|app| {
+        // pixels set since the last flush haven't been uploaded yet
+        image::upload_pixel_caches(app)?;
+        let texture = gpu_image(app, entity)?.texture.clone();
+        app.world_mut()
+            .run_system_cached_with(image::readback, (entity, texture))?
+    })
+}
+
+/// [`image_readback`] into `pixels`, reusing the caller's allocation. `pixels` must hold exactly
+/// width * height colors.
+pub fn image_readback_into(entity: Entity, pixels: &mut [LinearRgba]) -> error::Result<()> {
+    app_mut(|app| {
+        image::upload_pixel_caches(app)?;
         let texture = gpu_image(app, entity)?.texture.clone();
         app.world_mut()
-            .run_system_cached_with(image::readback, (entity, texture))
-            .unwrap()
+            .run_system_cached_with(image::readback_into, ((entity, texture), pixels))?
+    })
+}
+
+/// Save an image to `path`, in the format its extension names. See [`graphics_save`].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn image_save(entity: Entity, path: &str) -> error::Result<()> {
+    image_save_with_quality(entity, path, image::DEFAULT_JPEG_QUALITY)
+}
+
+/// [`image_save`] with a JPEG `quality` from 1 to 100, which other formats ignore.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn image_save_with_quality(entity: Entity, path: &str, quality: u8) -> error::Result<()> {
+    let format = image::SaveFormat::from_path(std::path::Path::new(path), quality)?;
+    let size = app_mut(|app| {
+        app.world()
+            .get::<image::Image>(entity)
+            .map(|image| image.size)
+            .ok_or(error::ProcessingError::ImageNotFound)
+    })?;
+    let pixels = image_readback(entity)?;
+    let bytes = image::encode(&image::to_srgba8(&pixels), size.width, size.height, format)?;
+    write_saved_file(path, bytes)
+}
+
+/// The color of one pixel of an image. The image is read back once and kept on the CPU, so
+/// later calls are cheap until the image is updated or resized.
+pub fn image_get_pixel(entity: Entity, x: u32, y: u32) -> error::Result<LinearRgba> {
+    fill_pixel_cache(entity)?;
+    app_mut(|app| {
+        app.world()
+            .get::<image::PixelCache>(entity)
+            .ok_or(error::ProcessingError::ImageNotFound)?
+            .get(x, y)
+    })
+}
+
+/// Set one pixel of an image. Pixels set between flushes are uploaded together as one region
+/// before the next draw.
+pub fn image_set_pixel(entity: Entity, x: u32, y: u32, color: LinearRgba) -> error::Result<()> {
+    fill_pixel_cache(entity)?;
+    app_mut(|app| {
+        app.world_mut()
+            .get_mut::<image::PixelCache>(entity)
+            .ok_or(error::ProcessingError::ImageNotFound)?
+            .set(x, y, color)
     })
 }
 
+fn fill_pixel_cache(entity: Entity) -> error::Result<()> {
+    let cached = app_mut(|app| Ok(app.world().get::<image::PixelCache>(entity).is_some()))?;
+    if cached {
+        return Ok(());
+    }
+    let pixels = image_readback(entity)?;
+    app_mut(|app| {
+        let size = app
+            .world()
+            .get::<image::Image>(entity)
+            .ok_or(error::ProcessingError::ImageNotFound)?
+            .size;
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(image::PixelCache::new(pixels, size.width, size.height));
+        Ok(())
+    })
+}
+
+/// Forget an image's CPU copy once its texture is rewritten some other way.
+fn drop_pixel_cache(app: &mut App, entity: Entity) {
+    if let Ok(mut image) = app.world_mut().get_entity_mut(entity) {
+        image.remove::<image::PixelCache>();
+    }
+}
+
 /// Update an existing image with new pixel data.
 pub fn image_update(entity: Entity, pixels: &[LinearRgba]) -> error::Result<()> {
     app_mut(|app| {
+        drop_pixel_cache(app, entity);
         let texture = gpu_image(app, entity)?.texture.clone();
         let world = app.world_mut();
         let size = world
@@ -949,21 +1305,19 @@ pub fn image_update(entity: Entity, pixels: &[LinearRgba]) -> error::Result<()>
             .size;
         let (data, px_size) =
             image::prepare_update_region(world, entity, size.width, size.height, pixels)?;
-        world
-            .run_system_cached_with(
-                image::update_region_write,
-                (
-                    entity,
-                    texture,
-                    0,
-                    0,
-                    size.width,
-                    size.height,
-                    data,
-                    px_size,
-                ),
-            )
-            .unwrap()
+        world.run_system_cached_with(
+            image::update_region_write,
+            (
+                entity,
+                texture,
+                0,
+                0,
+                size.width,
+                size.height,
+                data,
+                px_size,
+            ),
+        )?
     })
 }
 
@@ -977,24 +1331,173 @@ pub fn image_update_region(
     pixels: &[LinearRgba],
 ) -> error::Result<()> {
     app_mut(|app| {
+        // pixels set outside the region still need uploading before the copy is dropped
+        image::upload_pixel_caches(app)?;
+        drop_pixel_cache(app, entity);
         let texture = gpu_image(app, entity)?.texture.clone();
         let world = app.world_mut();
         let (data, px_size) = image::prepare_update_region(world, entity, width, height, pixels)?;
-        world
-            .run_system_cached_with(
-                image::update_region_write,
-                (entity, texture, x, y, width, height, data, px_size),
-            )
-            .unwrap()
+        world.run_system_cached_with(
+            image::update_region_write,
+            (entity, texture, x, y, width, height, data, px_size),
+        )?
     })
 }
 
+/// Read an image back to the CPU, let `f` rewrite the whole row-major pixel buffer (given with the
+/// image width) and upload the result. When `f` fails the image is left as it was and `f`'s error
+/// is returned inside the `Ok`.
+pub fn image_map_batch<E>(
+    entity: Entity,
+    f: impl FnOnce(&mut [LinearRgba], u32) -> Result<(), E>,
+) -> error::Result<Result<(), E>> {
+    let width = app_mut(|app| {
+        app.world()
+            .get::<image::Image>(entity)
+            .map(|image| image.size.width)
+            .ok_or(error::ProcessingError::ImageNotFound)
+    })?;
+    let mut pixels = image_readback(entity)?;
+    if let Err(e) = f(&mut pixels, width) {
+        return Ok(Err(e));
+    }
+    image_update(entity, &pixels)?;
+    Ok(Ok(()))
+}
+
+/// Replace every pixel of an image with `f(x, y, pixel)`, computed on the CPU.
+pub fn image_map(
+    entity: Entity,
+    f: impl FnMut(u32, u32, LinearRgba) -> LinearRgba,
+) -> error::Result<()> {
+    image_map_batch(entity, |pixels, width| {
+        image::map_pixels(pixels, width, f);
+        Ok::<_, std::convert::Infallible>(())
+    })
+    .map(|_| ())
+}
+
+/// Apply a Processing style filter to an image, computed on the CPU over its readback.
+pub fn image_filter(entity: Entity, filter: image::Filter) -> error::Result<()> {
+    image_map_batch(entity, |pixels, width| {
+        image::apply_filter(pixels, width, filter)
+    })?
+}
+
+/// Per-channel histograms of an image or graphics surface, red, green, blue then alpha, each with
+/// `bins` counts over the channel's 0 to 1 sRGB range.
+pub fn image_histogram(entity: Entity, bins: usize) -> error::Result<[Vec<u32>; 4]> {
+    let (pixels, _) = readback_pixels(entity)?;
+    image::histogram(&pixels, bins)
+}
+
+/// Copy the `sw` x `sh` region at `sx`, `sy` of `src` into the `dw` x `dh` region at `dx`, `dy` of
+/// `dst`, scaling it to fit, like Processing's `copy()`. Either may be an image or a graphics
+/// surface, and they may be the same one. Regions reaching past either edge are clipped.
+#[allow(clippy::too_many_arguments)]
+pub fn image_copy(
+    src: Entity,
+    sx: u32,
+    sy: u32,
+    sw: u32,
+    sh: u32,
+    dst: Entity,
+    dx: u32,
+    dy: u32,
+    dw: u32,
+    dh: u32,
+) -> error::Result<()> {
+    image_blend(src, sx, sy, sw, sh, dst, dx, dy, dw, dh, BlendMode::Replace)
+}
+
+/// [`image_copy`], blending the scaled region into `dst` with `mode` like Processing's `blend()`.
+/// Computed on the CPU over readbacks of both.
+#[allow(clippy::too_many_arguments)]
+pub fn image_blend(
+    src: Entity,
+    sx: u32,
+    sy: u32,
+    sw: u32,
+    sh: u32,
+    dst: Entity,
+    dx: u32,
+    dy: u32,
+    dw: u32,
+    dh: u32,
+    mode: BlendMode,
+) -> error::Result<()> {
+    if sw == 0 || sh == 0 || dw == 0 || dh == 0 {
+        return Ok(());
+    }
+    // everything is read before anything is written, so copying within one surface is safe
+    let (src_pixels, src_width) = readback_pixels(src)?;
+    let (dst_pixels, dst_width) = if dst == src {
+        (src_pixels.clone(), src_width)
+    } else {
+        readback_pixels(dst)?
+    };
+    let src_height = src_pixels.len() as u32 / src_width;
+    let dst_height = dst_pixels.len() as u32 / dst_width;
+    // like Processing, the source region is clamped to the source before it's scaled, and what
+    // lands outside the destination is dropped
+    let clip = |x: u32, w: u32, width: u32| x.saturating_add(w).min(width).saturating_sub(x);
+    let (sw, sh) = (clip(sx, sw, src_width), clip(sy, sh, src_height));
+    let (vw, vh) = (clip(dx, dw, dst_width), clip(dy, dh, dst_height));
+    if sw == 0 || sh == 0 || vw == 0 || vh == 0 {
+        return Ok(());
+    }
+
+    let mut region: Vec<LinearRgba> = (dy..dy + vh)
+        .flat_map(|y| {
+            let row = (y * dst_width + dx) as usize;
+            dst_pixels[row..row + vw as usize].iter().copied()
+        })
+        .collect();
+    image::blend_region(
+        &src_pixels,
+        src_width,
+        URect::new(sx, sy, sx + sw, sy + sh),
+        UVec2::new(dw, dh),
+        &mut region,
+        vw,
+        mode,
+    );
+    if is_graphics(dst)? {
+        graphics_update_region(dst, dx, dy, vw, vh, &region)
+    } else {
+        image_update_region(dst, dx, dy, vw, vh, &region)
+    }
+}
+
+fn is_graphics(entity: Entity) -> error::Result<bool> {
+    app_mut(|app| Ok(app.world().get::<graphics::Graphics>(entity).is_some()))
+}
+
+/// The pixels of an image or graphics surface, with its width.
+fn readback_pixels(entity: Entity) -> error::Result<(Vec<LinearRgba>, u32)> {
+    if is_graphics(entity)? {
+        let width = app_mut(|app| {
+            app.world()
+                .get::<graphics::Graphics>(entity)
+                .map(|graphics| graphics.size.width)
+                .ok_or(error::ProcessingError::GraphicsNotFound)
+        })?;
+        return Ok((graphics_readback(entity)?, width));
+    }
+    let width = app_mut(|app| {
+        app.world()
+            .get::<image::Image>(entity)
+            .map(|image| image.size.width)
+            .ok_or(error::ProcessingError::ImageNotFound)
+    })?;
+    Ok((image_readback(entity)?, width))
+}
+
 /// Set the sampler for an image (filter mode + wrap modes).
 pub fn image_set_sampler(entity: Entity, filter: u8, wrap_x: u8, wrap_y: u8) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(image::set_sampler, (entity, filter, wrap_x, wrap_y))
-            .unwrap()
+            .run_system_cached_with(image::set_sampler, (entity, filter, wrap_x, wrap_y))?
     })
 }
 
@@ -1002,8 +1505,7 @@ pub fn image_set_sampler(entity: Entity, filter: u8, wrap_x: u8, wrap_y: u8) ->
 pub fn image_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(image::destroy, entity)
-            .unwrap()
+            .run_system_cached_with(image::destroy, entity)?
     })
 }
 
@@ -1012,13 +1514,52 @@ pub fn light_create_directional(
     color: Color,
     illuminance: f32,
 ) -> error::Result<Entity> {
+    app_mut(|app| {
+        app.world_mut().run_system_cached_with(
+            light::create_directional,
+            (graphics_entity, color, illuminance),
+        )?
+    })
+}
+
+/// Turn a light off, or back on, keeping its color, intensity and transform.
+pub fn light_set_enabled(entity: Entity, enabled: bool) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(
-                light::create_directional,
-                (graphics_entity, color, illuminance),
-            )
-            .unwrap()
+            .run_system_cached_with(light::set_enabled, (entity, enabled))?
+    })
+}
+
+/// Remove a light. Lights last until destroyed, so create them in setup rather than every frame.
+pub fn light_destroy(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(light::destroy, entity)?
+    })
+}
+
+/// Give the graphics an ambient light of `color`, `brightness` candela per square meter, that
+/// lights its 3D scene evenly from every direction. Replaces any ambient light it already had.
+pub fn light_create_ambient(
+    graphics_entity: Entity,
+    color: Color,
+    brightness: f32,
+) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(light::create_ambient, (graphics_entity, color, brightness))?
+    })
+}
+
+/// Change the graphics' ambient light created by [`light_create_ambient`].
+pub fn light_set_ambient(
+    graphics_entity: Entity,
+    color: Color,
+    brightness: f32,
+) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(light::set_ambient, (graphics_entity, color, brightness))?
     })
 }
 
@@ -1030,12 +1571,10 @@ pub fn light_create_point(
     radius: f32,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                light::create_point,
-                (graphics_entity, color, intensity, range, radius),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            light::create_point,
+            (graphics_entity, color, intensity, range, radius),
+        )?
     })
 }
 
@@ -1049,20 +1588,18 @@ pub fn light_create_spot(
     outer_angle: f32,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                light::create_spot,
-                (
-                    graphics_entity,
-                    color,
-                    intensity,
-                    range,
-                    radius,
-                    inner_angle,
-                    outer_angle,
-                ),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            light::create_spot,
+            (
+                graphics_entity,
+                color,
+                intensity,
+                range,
+                radius,
+                inner_angle,
+                outer_angle,
+            ),
+        )?
     })
 }
 
@@ -1070,8 +1607,7 @@ pub fn geometry_layout_create() -> error::Result<Entity> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(geometry::layout::create, ())
-            .unwrap())
+            .run_system_cached_with(geometry::layout::create, ())?)
     })
 }
 
@@ -1098,11 +1634,17 @@ pub fn geometry_layout_add_attribute(
     app_mut(|app| geometry::layout::add_attribute(app.world_mut(), layout_entity, attr_entity))
 }
 
+pub fn geometry_layout_remove_attribute(
+    layout_entity: Entity,
+    attr_entity: Entity,
+) -> error::Result<()> {
+    app_mut(|app| geometry::layout::remove_attribute(app.world_mut(), layout_entity, attr_entity))
+}
+
 pub fn geometry_layout_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::layout::destroy, entity)
-            .unwrap();
+            .run_system_cached_with(geometry::layout::destroy, entity)?;
         Ok(())
     })
 }
@@ -1113,56 +1655,53 @@ pub fn geometry_attribute_create(
 ) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::attribute::create, (name.into(), format))
-            .unwrap()
+            .run_system_cached_with(geometry::attribute::create, (name.into(), format))?
     })
 }
 
-pub fn geometry_attribute_position() -> Entity {
+fn builtin_attribute(
+    pick: impl FnOnce(&geometry::BuiltinAttributes) -> Entity,
+) -> error::Result<Entity> {
     app_mut(|app| {
-        Ok(app
-            .world()
-            .resource::<geometry::BuiltinAttributes>()
-            .position)
+        app.world()
+            .get_resource::<geometry::BuiltinAttributes>()
+            .map(pick)
+            .ok_or(error::ProcessingError::NotInitialized)
     })
-    .unwrap()
 }
 
-pub fn geometry_attribute_normal() -> Entity {
-    app_mut(|app| Ok(app.world().resource::<geometry::BuiltinAttributes>().normal)).unwrap()
+pub fn geometry_attribute_position() -> error::Result<Entity> {
+    builtin_attribute(|attrs| attrs.position)
 }
 
-pub fn geometry_attribute_color() -> Entity {
-    app_mut(|app| Ok(app.world().resource::<geometry::BuiltinAttributes>().color)).unwrap()
+pub fn geometry_attribute_normal() -> error::Result<Entity> {
+    builtin_attribute(|attrs| attrs.normal)
 }
 
-pub fn geometry_attribute_uv() -> Entity {
-    app_mut(|app| Ok(app.world().resource::<geometry::BuiltinAttributes>().uv)).unwrap()
+pub fn geometry_attribute_color() -> error::Result<Entity> {
+    builtin_attribute(|attrs| attrs.color)
 }
 
-pub fn geometry_attribute_rotation() -> Entity {
-    app_mut(|app| {
-        Ok(app
-            .world()
-            .resource::<geometry::BuiltinAttributes>()
-            .rotation)
-    })
-    .unwrap()
+pub fn geometry_attribute_uv() -> error::Result<Entity> {
+    builtin_attribute(|attrs| attrs.uv)
+}
+
+pub fn geometry_attribute_rotation() -> error::Result<Entity> {
+    builtin_attribute(|attrs| attrs.rotation)
 }
 
-pub fn geometry_attribute_scale() -> Entity {
-    app_mut(|app| Ok(app.world().resource::<geometry::BuiltinAttributes>().scale)).unwrap()
+pub fn geometry_attribute_scale() -> error::Result<Entity> {
+    builtin_attribute(|attrs| attrs.scale)
 }
 
-pub fn geometry_attribute_dead() -> Entity {
-    app_mut(|app| Ok(app.world().resource::<geometry::BuiltinAttributes>().dead)).unwrap()
+pub fn geometry_attribute_dead() -> error::Result<Entity> {
+    builtin_attribute(|attrs| attrs.dead)
 }
 
 pub fn geometry_attribute_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::attribute::destroy, entity)
-            .unwrap()?;
+            .run_system_cached_with(geometry::attribute::destroy, entity)??;
         Ok(())
     })
 }
@@ -1181,22 +1720,42 @@ pub fn geometry_create(topology: geometry::Topology) -> error::Result<Entity> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(geometry::create, topology)
-            .unwrap())
+            .run_system_cached_with(geometry::create, topology)?)
+    })
+}
+
+/// Mark a geometry as rewritten every frame, so per-vertex edits to it are coalesced into one
+/// change per frame instead of one each.
+pub fn geometry_set_dynamic(entity: Entity, dynamic: bool) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::set_dynamic, (entity, dynamic))?
+    })
+}
+
+pub fn geometry_create_with_layout(
+    layout_entity: Entity,
+    topology: geometry::Topology,
+) -> error::Result<Entity> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::create_with_layout, (layout_entity, topology))?
     })
 }
 
-pub fn geometry_create_with_layout(
-    layout_entity: Entity,
-    topology: geometry::Topology,
-) -> error::Result<Entity> {
+/// Move a geometry onto another layout, keeping the data of attributes both layouts share.
+pub fn geometry_rebuild_layout(entity: Entity, layout_entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::create_with_layout, (layout_entity, topology))
-            .unwrap()
+            .run_system_cached_with(geometry::rebuild_layout, (entity, layout_entity))?
     })
 }
 
+/// Drop an attribute and its data from a single geometry.
+pub fn geometry_remove_attribute(entity: Entity, attr_entity: Entity) -> error::Result<()> {
+    app_mut(|app| geometry::remove_attribute(app.world_mut(), entity, attr_entity))
+}
+
 pub fn geometry_normal(entity: Entity, normal: Vec3) -> error::Result<()> {
     app_mut(|app| geometry::normal(app.world_mut(), entity, normal))
 }
@@ -1262,32 +1821,28 @@ pub fn geometry_attribute_float4(
 pub fn geometry_vertex(entity: Entity, position: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::vertex, (entity, position))
-            .unwrap()
+            .run_system_cached_with(geometry::vertex, (entity, position))?
     })
 }
 
 pub fn geometry_index(entity: Entity, i: u32) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::index, (entity, i))
-            .unwrap()
+            .run_system_cached_with(geometry::index, (entity, i))?
     })
 }
 
 pub fn geometry_vertex_count(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::vertex_count, entity)
-            .unwrap()
+            .run_system_cached_with(geometry::vertex_count, entity)?
     })
 }
 
 pub fn geometry_index_count(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::index_count, entity)
-            .unwrap()
+            .run_system_cached_with(geometry::index_count, entity)?
     })
 }
 
@@ -1298,8 +1853,7 @@ pub fn geometry_get_positions(
 ) -> error::Result<Vec<[f32; 3]>> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::get_positions, (entity, start..end))
-            .unwrap()
+            .run_system_cached_with(geometry::get_positions, (entity, start..end))?
     })
 }
 
@@ -1310,8 +1864,7 @@ pub fn geometry_get_normals(
 ) -> error::Result<Vec<[f32; 3]>> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::get_normals, (entity, start..end))
-            .unwrap()
+            .run_system_cached_with(geometry::get_normals, (entity, start..end))?
     })
 }
 
@@ -1322,64 +1875,108 @@ pub fn geometry_get_colors(
 ) -> error::Result<Vec<[f32; 4]>> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::get_colors, (entity, start..end))
-            .unwrap()
+            .run_system_cached_with(geometry::get_colors, (entity, start..end))?
     })
 }
 
 pub fn geometry_get_uvs(entity: Entity, start: usize, end: usize) -> error::Result<Vec<[f32; 2]>> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::get_uvs, (entity, start..end))
-            .unwrap()
+            .run_system_cached_with(geometry::get_uvs, (entity, start..end))?
     })
 }
 
 pub fn geometry_get_indices(entity: Entity, start: usize, end: usize) -> error::Result<Vec<u32>> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::get_indices, (entity, start..end))
-            .unwrap()
+            .run_system_cached_with(geometry::get_indices, (entity, start..end))?
     })
 }
 
 pub fn geometry_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::destroy, entity)
-            .unwrap()
+            .run_system_cached_with(geometry::destroy, entity)?
     })
 }
 
 pub fn geometry_set_vertex(entity: Entity, index: u32, position: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::set_vertex, (entity, index, position))
-            .unwrap()
+            .run_system_cached_with(geometry::set_vertex, (entity, index, position))?
     })
 }
 
 pub fn geometry_set_normal(entity: Entity, index: u32, normal: Vec3) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::set_normal, (entity, index, normal))
-            .unwrap()
+            .run_system_cached_with(geometry::set_normal, (entity, index, normal))?
     })
 }
 
 pub fn geometry_set_color(entity: Entity, index: u32, color: Vec4) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::set_color, (entity, index, color))
-            .unwrap()
+            .run_system_cached_with(geometry::set_color, (entity, index, color))?
     })
 }
 
 pub fn geometry_set_uv(entity: Entity, index: u32, uv: Vec2) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(geometry::set_uv, (entity, index, uv))
-            .unwrap()
+            .run_system_cached_with(geometry::set_uv, (entity, index, uv))?
+    })
+}
+
+/// Empty a geometry's vertices and indices while keeping its layout and mesh, for geometry that's
+/// refilled every frame.
+pub fn geometry_clear(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::clear, entity)?
+    })
+}
+
+/// Replace every vertex position in one go. Changing the vertex count refills the other
+/// attributes with the geometry's current values, so set them after the positions.
+pub fn geometry_set_positions(entity: Entity, positions: &[[f32; 3]]) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::set_positions, (entity, positions.to_vec()))?
+    })
+}
+
+fn geometry_set_vertex_data(
+    entity: Entity,
+    attribute: MeshVertexAttribute,
+    values: VertexAttributeValues,
+) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::set_vertex_data, (entity, attribute, values))?
+    })
+}
+
+/// Replace every vertex normal, one per vertex.
+pub fn geometry_set_normals(entity: Entity, normals: &[[f32; 3]]) -> error::Result<()> {
+    geometry_set_vertex_data(entity, Mesh::ATTRIBUTE_NORMAL, normals.to_vec().into())
+}
+
+/// Replace every vertex color, one linear RGBA color per vertex.
+pub fn geometry_set_colors(entity: Entity, colors: &[[f32; 4]]) -> error::Result<()> {
+    geometry_set_vertex_data(entity, Mesh::ATTRIBUTE_COLOR, colors.to_vec().into())
+}
+
+/// Replace every vertex uv, one per vertex.
+pub fn geometry_set_uvs(entity: Entity, uvs: &[[f32; 2]]) -> error::Result<()> {
+    geometry_set_vertex_data(entity, Mesh::ATTRIBUTE_UV_0, uvs.to_vec().into())
+}
+
+/// Replace the whole index buffer.
+pub fn geometry_set_indices(entity: Entity, indices: &[u32]) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::set_indices, (entity, indices.to_vec()))?
     })
 }
 
@@ -1395,8 +1992,7 @@ pub fn geometry_get_attribute(
             .ok_or(error::ProcessingError::InvalidEntity)?;
         let inner = attr.inner;
         app.world_mut()
-            .run_system_cached_with(geometry::get_attribute, (geo_entity, inner, index))
-            .unwrap()
+            .run_system_cached_with(geometry::get_attribute, (geo_entity, inner, index))?
     })
 }
 
@@ -1413,8 +2009,7 @@ pub fn geometry_get_attributes(
             .ok_or(error::ProcessingError::InvalidEntity)?;
         let inner = attr.inner;
         app.world_mut()
-            .run_system_cached_with(geometry::get_attributes, (geo_entity, inner, start..end))
-            .unwrap()
+            .run_system_cached_with(geometry::get_attributes, (geo_entity, inner, start..end))?
     })
 }
 
@@ -1431,8 +2026,243 @@ pub fn geometry_set_attribute(
             .ok_or(error::ProcessingError::InvalidEntity)?;
         let inner = attr.inner;
         app.world_mut()
-            .run_system_cached_with(geometry::set_attribute, (geo_entity, inner, index, value))
-            .unwrap()
+            .run_system_cached_with(geometry::set_attribute, (geo_entity, inner, index, value))?
+    })
+}
+
+/// Start capturing what's drawn on the graphics into a geometry. Until [`geometry_end`], 3D
+/// primitives and geometries are recorded with their transforms instead of being drawn.
+pub fn geometry_begin(graphics_entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::capture::begin, graphics_entity)?
+    })
+}
+
+/// Bake everything drawn since [`geometry_begin`] into a single new geometry, so a scene built
+/// from many shapes can be drawn each frame in one call.
+pub fn geometry_end(graphics_entity: Entity) -> error::Result<Entity> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::capture::end, graphics_entity)?
+    })
+}
+
+/// Start building a 2D path into `entity`. Path vertices are collected until
+/// [`geometry_end_path`], which tessellates the path's fill into the geometry.
+pub fn geometry_begin_path(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::path::begin_path, entity)?
+    })
+}
+
+pub fn geometry_path_vertex(entity: Entity, x: f32, y: f32) -> error::Result<()> {
+    geometry_path_push(entity, VertexType::Normal(x, y))
+}
+
+pub fn geometry_path_bezier_vertex(
+    entity: Entity,
+    cx1: f32,
+    cy1: f32,
+    cx2: f32,
+    cy2: f32,
+    x: f32,
+    y: f32,
+) -> error::Result<()> {
+    geometry_path_push(
+        entity,
+        VertexType::CubicBezier {
+            cx1,
+            cy1,
+            cx2,
+            cy2,
+            x,
+            y,
+        },
+    )
+}
+
+pub fn geometry_path_quadratic_vertex(
+    entity: Entity,
+    cx: f32,
+    cy: f32,
+    x: f32,
+    y: f32,
+) -> error::Result<()> {
+    geometry_path_push(entity, VertexType::QuadraticBezier { cx, cy, x, y })
+}
+
+pub fn geometry_path_curve_vertex(entity: Entity, x: f32, y: f32) -> error::Result<()> {
+    geometry_path_push(entity, VertexType::CurveVertex(x, y))
+}
+
+fn geometry_path_push(entity: Entity, vertex: VertexType) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::path::path_vertex, (entity, vertex))?
+    })
+}
+
+/// Append an elliptical arc around (`cx`, `cy`) with radii `rx` and `ry`, from angle `start` to
+/// `stop` in radians, to the path started with [`geometry_begin_path`].
+pub fn geometry_path_arc(
+    entity: Entity,
+    cx: f32,
+    cy: f32,
+    rx: f32,
+    ry: f32,
+    start: f32,
+    stop: f32,
+) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut().run_system_cached_with(
+            geometry::path::path_arc,
+            (entity, Vec2::new(cx, cy), Vec2::new(rx, ry), start, stop),
+        )?
+    })
+}
+
+pub fn geometry_path_begin_contour(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::path::path_contour, (entity, true))?
+    })
+}
+
+pub fn geometry_path_end_contour(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::path::path_contour, (entity, false))?
+    })
+}
+
+/// Tessellate the path started with [`geometry_begin_path`] into the geometry's vertices and
+/// indices, using its current normal, color and uv.
+pub fn geometry_end_path(entity: Entity, close: bool) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::path::end_path, (entity, close))?
+    })
+}
+
+/// Rewind triangles so they're all counter-clockwise relative to their vertex normals. Returns
+/// the number of triangles that were flipped.
+pub fn geometry_fix_winding(entity: Entity) -> error::Result<u32> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::fix_winding, entity)?
+    })
+}
+
+/// Replace a triangle list geometry's normals with smooth ones averaged from its faces, weighted by
+/// area, like three.js `computeVertexNormals`. Errors if its layout has no normal attribute.
+pub fn geometry_compute_normals(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::compute_normals, entity)?
+    })
+}
+
+/// Laplacian smoothing: move each vertex `factor` of the way toward the average of its
+/// neighbors, repeated `iterations` times. Normals are kept, so follow with
+/// [`geometry_compute_normals`] to match them to the smoothed shape.
+pub fn geometry_smooth(entity: Entity, iterations: u32, factor: f32) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::smooth, (entity, iterations, factor))?
+    })
+}
+
+/// Report degenerate triangles, unreferenced vertices and non-manifold edges in a triangle list
+/// geometry.
+pub fn geometry_validate(entity: Entity) -> error::Result<geometry::ops::GeometryReport> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::validate, entity)?
+    })
+}
+
+/// A `(center, radius)` sphere enclosing the geometry's positions in its local space, for manual
+/// culling or framing a camera around it.
+pub fn geometry_bounding_sphere(entity: Entity) -> error::Result<(Vec3, f32)> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::bounding_sphere, entity)?
+    })
+}
+
+/// The `(min, max)` corners of the box around the geometry's positions in its local space, for
+/// framing a camera on a loaded model or normalizing its scale. Errors if it has no vertices.
+pub fn geometry_bounds(entity: Entity) -> error::Result<(Vec3, Vec3)> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::bounds, entity)?
+    })
+}
+
+/// Move every vertex of a geometry by `transform` and turn its normals to match, changing the
+/// mesh itself rather than how it's drawn.
+pub fn geometry_apply_transform(entity: Entity, transform: Affine3A) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::apply_transform, (entity, transform))?
+    })
+}
+
+/// Share identical vertices between triangles through an index buffer.
+pub fn geometry_to_indexed(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::set_indexed, (entity, true))?
+    })
+}
+
+/// Give every triangle corner its own vertex and drop the index buffer, e.g. before flat
+/// shading, so each face can have its own normals.
+pub fn geometry_to_nonindexed(entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::ops::set_indexed, (entity, false))?
+    })
+}
+
+/// Merge geometries into one new geometry to draw them in a single call. Each source is first
+/// moved by the matching entry of `transforms`, when given. All sources need the same attributes
+/// and list topology.
+pub fn geometry_merge(
+    entities: &[Entity],
+    transforms: Option<&[Affine3A]>,
+) -> error::Result<Entity> {
+    app_mut(|app| {
+        app.world_mut().run_system_cached_with(
+            geometry::ops::merge,
+            (entities.to_vec(), transforms.map(<[Affine3A]>::to_vec)),
+        )?
+    })
+}
+
+/// The center of [`geometry_bounds`].
+pub fn geometry_center(entity: Entity) -> error::Result<Vec3> {
+    let (min, max) = geometry_bounds(entity)?;
+    Ok((min + max) * 0.5)
+}
+
+/// Draw a line list or strip geometry with lines `width` units wide, as quads facing the camera.
+/// Zero goes back to 1px lines.
+pub fn geometry_line_width(entity: Entity, width: f32) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::lines::set_line_width, (entity, width))?
+    })
+}
+
+/// Draw lower detail geometries in place of `entity` when it's far from the camera. Each
+/// `(distance, geometry)` level is used from `distance` onwards; an empty list clears the LOD.
+pub fn geometry_set_lod(entity: Entity, levels: Vec<(f32, Entity)>) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(geometry::lod::set_lod, (entity, levels))?
     })
 }
 
@@ -1440,8 +2270,7 @@ pub fn geometry_create_from_mesh(mesh: Mesh) -> error::Result<Entity> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(geometry::create_from_mesh, mesh)
-            .unwrap())
+            .run_system_cached_with(geometry::create_from_mesh, mesh)?)
     })
 }
 
@@ -1449,8 +2278,7 @@ pub fn geometry_box(width: f32, height: f32, depth: f32) -> error::Result<Entity
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(geometry::create_box, (width, height, depth))
-            .unwrap())
+            .run_system_cached_with(geometry::create_box, (width, height, depth))?)
     })
 }
 
@@ -1458,8 +2286,7 @@ pub fn geometry_sphere(radius: f32, sectors: u32, stacks: u32) -> error::Result<
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(geometry::create_sphere, (radius, sectors, stacks))
-            .unwrap())
+            .run_system_cached_with(geometry::create_sphere, (radius, sectors, stacks))?)
     })
 }
 
@@ -1470,8 +2297,7 @@ pub fn geometry_grid(nx: u32, ny: u32, nz: u32, spacing: f32) -> error::Result<E
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(geometry::create_grid, (nx, ny, nz, spacing))
-            .unwrap())
+            .run_system_cached_with(geometry::create_grid, (nx, ny, nz, spacing))?)
     })
 }
 
@@ -1479,16 +2305,14 @@ pub fn poll_for_sketch_updates() -> error::Result<Option<sketch::Sketch>> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached(sketch::sketch_update_handler)
-            .unwrap())
+            .run_system_cached(sketch::sketch_update_handler)?)
     })
 }
 
 pub fn shader_create(source: &str) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(material::custom::create_shader, source.to_string())
-            .unwrap()
+            .run_system_cached_with(material::custom::create_shader, source.to_string())?
     })
 }
 
@@ -1498,36 +2322,38 @@ pub fn shader_load(path: &str) -> error::Result<Entity> {
     let path = path.to_string();
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(material::custom::load_shader, path)
-            .unwrap()
+            .run_system_cached_with(material::custom::load_shader, path)?
     })
 }
 
 pub fn shader_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(material::custom::destroy_shader, entity)
-            .unwrap()
+            .run_system_cached_with(material::custom::destroy_shader, entity)?
     })
 }
 
 pub fn material_create_custom(shader: Entity) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(material::custom::create_custom, shader)
-            .unwrap()
+            .run_system_cached_with(material::custom::create_custom, shader)?
     })
 }
 
-pub fn material_create_pbr() -> error::Result<Entity> {
+/// Show geometry drawn without a material in magenta, or stop doing so. Starts out as set by
+/// [`ConfigKey::RequireMaterial`].
+pub fn set_require_material(required: bool) -> error::Result<()> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached(material::create_pbr)
-            .unwrap())
+            .run_system_cached_with(material::set_require_material, required)?)
     })
 }
 
+pub fn material_create_pbr() -> error::Result<Entity> {
+    app_mut(|app| Ok(app.world_mut().run_system_cached(material::create_pbr)?))
+}
+
 /// `material_create_pbr` with `unlit = true` set on the base StandardMaterial.
 pub fn material_create_unlit() -> error::Result<Entity> {
     let entity = material_create_pbr()?;
@@ -1582,7 +2408,7 @@ pub fn material_set_albedo_color(entity: Entity, color: [f32; 4]) -> error::Resu
             .resource_mut::<Assets<DefaultMat>>()
             .add(ExtendedMaterial {
                 base: preserved,
-                extension: ProcessingMaterial { blend_state: None },
+                extension: ProcessingMaterial::default(),
             });
         world
             .entity_mut(entity)
@@ -1664,16 +2490,26 @@ pub fn material_set(
 ) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(material::set_property, (entity, name.into(), value))
-            .unwrap()
+            .run_system_cached_with(material::set_property, (entity, name.into(), value))?
     })
 }
 
+/// Set a texture property of a material to `image`, such as a PBR material's
+/// `base_color_texture`, `emissive_texture`, `metallic_roughness_texture`, `normal_map_texture`
+/// or `occlusion_texture`. Base color and emissive textures are sRGB color. The other three hold
+/// linear data, so an sRGB image set on one of them is switched to the matching linear format.
+pub fn material_set_texture(
+    entity: Entity,
+    name: impl Into<String>,
+    image: Entity,
+) -> error::Result<()> {
+    material_set(entity, name, shader_value::ShaderValue::Texture(image))
+}
+
 pub fn material_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(material::destroy, entity)
-            .unwrap()
+            .run_system_cached_with(material::destroy, entity)?
     })
 }
 
@@ -1681,8 +2517,7 @@ pub fn surface_focused(entity: Entity) -> error::Result<bool> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::focused, entity)
-            .unwrap())
+            .run_system_cached_with(surface::focused, entity)?)
     })
 }
 
@@ -1690,8 +2525,7 @@ pub fn surface_scale_factor(entity: Entity) -> error::Result<f32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::scale_factor, entity)
-            .unwrap())
+            .run_system_cached_with(surface::scale_factor, entity)?)
     })
 }
 
@@ -1699,8 +2533,7 @@ pub fn surface_physical_width(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::physical_width, entity)
-            .unwrap())
+            .run_system_cached_with(surface::physical_width, entity)?)
     })
 }
 
@@ -1708,8 +2541,7 @@ pub fn surface_physical_height(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::physical_height, entity)
-            .unwrap())
+            .run_system_cached_with(surface::physical_height, entity)?)
     })
 }
 
@@ -1717,8 +2549,7 @@ pub fn surface_width(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::width, entity)
-            .unwrap())
+            .run_system_cached_with(surface::width, entity)?)
     })
 }
 
@@ -1726,25 +2557,23 @@ pub fn surface_height(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::height, entity)
-            .unwrap())
+            .run_system_cached_with(surface::height, entity)?)
     })
 }
 
 pub fn monitor_list() -> error::Result<Vec<Entity>> {
-    app_mut(|app| Ok(app.world_mut().run_system_cached(monitor::list).unwrap()))
+    app_mut(|app| Ok(app.world_mut().run_system_cached(monitor::list)?))
 }
 
 pub fn monitor_primary() -> error::Result<Option<Entity>> {
-    app_mut(|app| Ok(app.world_mut().run_system_cached(monitor::primary).unwrap()))
+    app_mut(|app| Ok(app.world_mut().run_system_cached(monitor::primary)?))
 }
 
 pub fn monitor_width(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(monitor::width, entity)
-            .unwrap())
+            .run_system_cached_with(monitor::width, entity)?)
     })
 }
 
@@ -1752,8 +2581,7 @@ pub fn monitor_height(entity: Entity) -> error::Result<u32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(monitor::height, entity)
-            .unwrap())
+            .run_system_cached_with(monitor::height, entity)?)
     })
 }
 
@@ -1761,8 +2589,7 @@ pub fn monitor_scale_factor(entity: Entity) -> error::Result<f64> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(monitor::scale_factor, entity)
-            .unwrap())
+            .run_system_cached_with(monitor::scale_factor, entity)?)
     })
 }
 
@@ -1770,8 +2597,7 @@ pub fn monitor_refresh_rate_millihertz(entity: Entity) -> error::Result<Option<u
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(monitor::refresh_rate_millihertz, entity)
-            .unwrap())
+            .run_system_cached_with(monitor::refresh_rate_millihertz, entity)?)
     })
 }
 
@@ -1779,8 +2605,7 @@ pub fn monitor_name(entity: Entity) -> error::Result<Option<String>> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(monitor::name, entity)
-            .unwrap())
+            .run_system_cached_with(monitor::name, entity)?)
     })
 }
 
@@ -1788,8 +2613,7 @@ pub fn monitor_position(entity: Entity) -> error::Result<bevy::math::IVec2> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::monitor_position, entity)
-            .unwrap())
+            .run_system_cached_with(surface::monitor_position, entity)?)
     })
 }
 
@@ -1797,39 +2621,156 @@ pub fn monitor_workarea(entity: Entity) -> error::Result<bevy::math::IRect> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(surface::monitor_workarea, entity)
-            .unwrap())
+            .run_system_cached_with(surface::monitor_workarea, entity)?)
     })
 }
 
 pub fn frame_count() -> error::Result<u32> {
-    app_mut(|app| {
-        Ok(app
-            .world_mut()
-            .run_system_cached(time::frame_count)
-            .unwrap())
-    })
+    app_mut(|app| Ok(app.world_mut().run_system_cached(time::frame_count)?))
 }
 
 pub fn advance_frame_count() -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached(time::advance_frame_count)
-            .unwrap();
+            .run_system_cached(time::advance_frame_count)?;
         Ok(())
     })
 }
 
 pub fn delta_time() -> error::Result<f32> {
-    app_mut(|app| Ok(app.world_mut().run_system_cached(time::delta_secs).unwrap()))
+    app_mut(|app| Ok(app.world_mut().run_system_cached(time::delta_secs)?))
 }
 
 pub fn elapsed_time() -> error::Result<f32> {
+    app_mut(|app| Ok(app.world_mut().run_system_cached(time::elapsed_secs)?))
+}
+
+/// Processing's `random(high)`: a value from 0 up to but not including `high`.
+pub fn random(high: f32) -> error::Result<f32> {
+    random_range(0.0, high)
+}
+
+/// A value from `low` up to but not including `high`, or `low` if the range is empty.
+pub fn random_range(low: f32, high: f32) -> error::Result<f32> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached(time::elapsed_secs)
-            .unwrap())
+            .resource_mut::<random::Rng>()
+            .range(low, high))
+    })
+}
+
+/// Make `random` and `random_gaussian` repeat the same sequence from run to run.
+pub fn random_seed(seed: u64) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut().resource_mut::<random::Rng>().seed(seed);
+        Ok(())
+    })
+}
+
+/// Run `f` with the app's random number generator, so bindings drawing shuffles, samples or
+/// random vectors follow the same `random_seed` as `random`.
+pub fn random_with<T>(f: impl FnOnce(&mut rand::rngs::StdRng) -> T) -> error::Result<T> {
+    app_mut(|app| Ok(f(app.world_mut().resource_mut::<random::Rng>().generator())))
+}
+
+/// A normally distributed random value with mean 0 and standard deviation 1.
+pub fn random_gaussian() -> error::Result<f32> {
+    app_mut(|app| Ok(app.world_mut().resource_mut::<random::Rng>().gaussian()))
+}
+
+/// Processing's one dimensional Perlin `noise(x)`, from 0 to 1 with the default detail.
+pub fn noise_1d(x: f32) -> error::Result<f32> {
+    noise_3d(x, 0.0, 0.0)
+}
+
+pub fn noise_2d(x: f32, y: f32) -> error::Result<f32> {
+    noise_3d(x, y, 0.0)
+}
+
+pub fn noise_3d(x: f32, y: f32, z: f32) -> error::Result<f32> {
+    app_mut(|app| Ok(app.world().resource::<noise::Noise>().get(x, y, z)))
+}
+
+/// Noise at every point in `points`, sampled under a single borrow of the app.
+pub fn noise_array(points: &[Vec3]) -> error::Result<Vec<f32>> {
+    app_mut(|app| Ok(app.world().resource::<noise::Noise>().sample(points)))
+}
+
+/// A unit steering vector from a noise based angle field at `x`, `y`, with the coordinates
+/// multiplied by `scale`. Seeded by `noise_seed`.
+pub fn flow_field(x: f32, y: f32, scale: f32) -> error::Result<Vec2> {
+    app_mut(|app| Ok(app.world().resource::<noise::Noise>().flow(x, y, scale)))
+}
+
+/// Make `noise` reproducible, matching Processing's `noiseSeed` for the same seed.
+pub fn noise_seed(seed: u64) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut().resource_mut::<noise::Noise>().seed(seed);
+        Ok(())
+    })
+}
+
+/// Set how many octaves `noise` sums and the amplitude falloff between them. Zero or negative
+/// values keep the current setting.
+pub fn noise_detail(octaves: i32, falloff: f32) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .resource_mut::<noise::Noise>()
+            .detail(octaves, falloff);
+        Ok(())
+    })
+}
+
+/// Save a geometry's mesh to a glTF binary (`.glb`) file at `path`, with `material` if given.
+/// Both are named after the file, so `cube.glb` loads back with `gltf_geometry(gltf, "cube")` and
+/// `gltf_material(gltf, "cube")`. Only a PBR material's factors are saved, not its textures.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn geometry_save_gltf(
+    entity: Entity,
+    path: &str,
+    material: Option<Entity>,
+) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut().run_system_cached_with(
+            geometry::export::save_gltf,
+            (entity, PathBuf::from(path), material),
+        )?
+    })
+}
+
+/// Load a CSV or TSV file through the asset source as a table, using its first line as the
+/// header. Files ending in `.tsv` are read as tab separated.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn table_load(path: &str) -> error::Result<table::Table> {
+    let table_error =
+        |e: &dyn std::fmt::Display| error::ProcessingError::TableError(format!("{path}: {e}"));
+    let bytes = app_mut(|app| {
+        image::read_asset(app.world(), Path::new(path)).map_err(|e| table_error(&e))
+    })?;
+    let text = String::from_utf8(bytes).map_err(|e| table_error(&e))?;
+    table::Table::parse(&text, table::delimiter_for(Path::new(path)))
+}
+
+/// Write a table to `path` through the asset source as CSV, or TSV if it ends in `.tsv`, with its
+/// header first. It goes where [`table_load`] reads from, so a saved table loads back by the same
+/// name.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn table_save(table: &table::Table, path: &str) -> error::Result<()> {
+    let text = table.to_delimited(table::delimiter_for(Path::new(path)));
+    app_mut(|app| {
+        image::write_asset(app.world(), Path::new(path), text.as_bytes())
+            .map_err(|e| error::ProcessingError::TableError(format!("{path}: {e}")))
+    })
+}
+
+/// Load a Wavefront `.obj` model from the asset directory as a geometry. Faces are triangulated
+/// and normals the file leaves out are computed.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn obj_load(path: &str) -> error::Result<Entity> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(obj::load, path.to_string())?
     })
 }
 
@@ -1837,8 +2778,16 @@ pub fn elapsed_time() -> error::Result<f32> {
 pub fn gltf_load(graphics_entity: Entity, path: &str) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(gltf::load, (graphics_entity, path.to_string()))
-            .unwrap()
+            .run_system_cached_with(gltf::load, (graphics_entity, path.to_string()))?
+    })
+}
+
+/// Free a loaded glTF scene. See [`gltf::unload`] for what stays alive afterwards.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn gltf_unload(gltf_entity: Entity) -> error::Result<()> {
+    app_mut(|app| {
+        app.world_mut()
+            .run_system_cached_with(gltf::unload, gltf_entity)?
     })
 }
 
@@ -1846,8 +2795,7 @@ pub fn gltf_load(graphics_entity: Entity, path: &str) -> error::Result<Entity> {
 pub fn gltf_geometry(gltf_entity: Entity, name: &str) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(gltf::geometry, (gltf_entity, name.to_string()))
-            .unwrap()
+            .run_system_cached_with(gltf::geometry, (gltf_entity, name.to_string()))?
     })
 }
 
@@ -1855,8 +2803,7 @@ pub fn gltf_geometry(gltf_entity: Entity, name: &str) -> error::Result<Entity> {
 pub fn gltf_material(gltf_entity: Entity, name: &str) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(gltf::material, (gltf_entity, name.to_string()))
-            .unwrap()
+            .run_system_cached_with(gltf::material, (gltf_entity, name.to_string()))?
     })
 }
 
@@ -1864,8 +2811,7 @@ pub fn gltf_material(gltf_entity: Entity, name: &str) -> error::Result<Entity> {
 pub fn gltf_mesh_names(gltf_entity: Entity) -> error::Result<Vec<String>> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(gltf::mesh_names, gltf_entity)
-            .unwrap()
+            .run_system_cached_with(gltf::mesh_names, gltf_entity)?
     })
 }
 
@@ -1873,8 +2819,7 @@ pub fn gltf_mesh_names(gltf_entity: Entity) -> error::Result<Vec<String>> {
 pub fn gltf_material_names(gltf_entity: Entity) -> error::Result<Vec<String>> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(gltf::material_names, gltf_entity)
-            .unwrap()
+            .run_system_cached_with(gltf::material_names, gltf_entity)?
     })
 }
 
@@ -1882,8 +2827,7 @@ pub fn gltf_material_names(gltf_entity: Entity) -> error::Result<Vec<String>> {
 pub fn gltf_camera(gltf_entity: Entity, index: usize) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(gltf::camera, (gltf_entity, index))
-            .unwrap()
+            .run_system_cached_with(gltf::camera, (gltf_entity, index))?
     })
 }
 
@@ -1891,8 +2835,7 @@ pub fn gltf_camera(gltf_entity: Entity, index: usize) -> error::Result<()> {
 pub fn gltf_light(gltf_entity: Entity, index: usize) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(gltf::light, (gltf_entity, index))
-            .unwrap()
+            .run_system_cached_with(gltf::light, (gltf_entity, index))?
     })
 }
 
@@ -1900,8 +2843,7 @@ pub fn buffer_create(size: u64) -> error::Result<Entity> {
     app_mut(|app| {
         let entity = app
             .world_mut()
-            .run_system_cached_with(compute::create_buffer, size)
-            .unwrap();
+            .run_system_cached_with(compute::create_buffer, size)?;
         app.update();
         Ok(entity)
     })
@@ -1911,8 +2853,7 @@ pub fn buffer_create_with_data(data: Vec<u8>) -> error::Result<Entity> {
     app_mut(|app| {
         let entity = app
             .world_mut()
-            .run_system_cached_with(compute::create_buffer_with_data, data)
-            .unwrap();
+            .run_system_cached_with(compute::create_buffer_with_data, data)?;
         app.update();
         Ok(entity)
     })
@@ -1958,8 +2899,7 @@ fn ensure_buffer_synced(app: &mut App, entity: Entity) -> error::Result<()> {
         .run_system_cached_with(
             compute::read_buffer_gpu,
             (handle.clone(), readback_buffer, size),
-        )
-        .unwrap()?;
+        )??;
 
     let world = app.world_mut();
     {
@@ -2009,8 +2949,7 @@ fn buffer_write_range(
         }
         ensure_buffer_synced(app, entity)?;
         app.world_mut()
-            .run_system_cached_with(compute::write_buffer_cpu, (handle, offset, data))
-            .unwrap()
+            .run_system_cached_with(compute::write_buffer_cpu, (handle, offset, data))?
     })
 }
 
@@ -2059,8 +2998,7 @@ fn buffer_read_range(entity: Entity, offset: u64, len: u64) -> error::Result<Vec
 pub fn buffer_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(compute::destroy_buffer, entity)
-            .unwrap()
+            .run_system_cached_with(compute::destroy_buffer, entity)?
     })
 }
 
@@ -2075,8 +3013,7 @@ pub fn compute_set(
 ) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(compute::set_compute_property, (entity, name.into(), value))
-            .unwrap()
+            .run_system_cached_with(compute::set_compute_property, (entity, name.into(), value))?
     })
 }
 
@@ -2100,24 +3037,21 @@ pub fn compute_dispatch(entity: Entity, x: u32, y: u32, z: u32) -> error::Result
         };
         app.sub_app_mut(bevy::render::RenderApp)
             .world_mut()
-            .run_system_cached_with(compute::dispatch, args)
-            .unwrap()
+            .run_system_cached_with(compute::dispatch, args)?
     })
 }
 
 pub fn compute_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(compute::destroy_compute, entity)
-            .unwrap()
+            .run_system_cached_with(compute::destroy_compute, entity)?
     })
 }
 
 pub fn particles_create(capacity: u32, attribute_entities: Vec<Entity>) -> error::Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(particles::create, (capacity, attribute_entities))
-            .unwrap()
+            .run_system_cached_with(particles::create, (capacity, attribute_entities))?
     })
 }
 
@@ -2129,20 +3063,17 @@ pub fn particles_create_from_geometry(
     attribute_entities: Vec<Entity>,
 ) -> error::Result<Entity> {
     app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(
-                particles::create_from_geometry,
-                (geometry_entity, attribute_entities),
-            )
-            .unwrap()
+        app.world_mut().run_system_cached_with(
+            particles::create_from_geometry,
+            (geometry_entity, attribute_entities),
+        )?
     })
 }
 
 pub fn particles_destroy(entity: Entity) -> error::Result<()> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(particles::destroy, entity)
-            .unwrap()
+            .run_system_cached_with(particles::destroy, entity)?
     })
 }
 
@@ -2379,38 +3310,42 @@ pub fn particles_apply(particles_entity: Entity, compute_entity: Entity) -> erro
 
 // --- Font API ---
 
-/// Load a font file and return a font entity handle.
+/// Load a font file and return a font entity handle. With [`ConfigKey::AssetSource`] set, `path`
+/// is read from that asset source instead of the filesystem.
 ///
 /// Reading fonts from the filesystem is not available on wasm; callers should
 /// register font bytes through another path there.
 pub fn font_load(path: &str) -> error::Result<Entity> {
-    use text::font::{Font, TextContext};
+    use text::font::{Font, TextContext, read_from_asset_source};
 
-    #[cfg(target_arch = "wasm32")]
-    {
-        let _ = path;
-        return Err(error::ProcessingError::FontLoadError(
-            "loading fonts from a file is not supported on wasm".to_string(),
-        ));
-    }
+    let data = match app_mut(|app| read_from_asset_source(app.world(), path).transpose())? {
+        Some(data) => data,
+        None => read_font_file(path)?,
+    };
 
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let data = std::fs::read(path)
-            .map_err(|e| error::ProcessingError::FontLoadError(format!("{}: {}", path, e)))?;
+    app_mut(|app| {
+        let text_cx = app.world().resource::<TextContext>().clone();
+        let family_name = text_cx
+            .load_font(data)
+            .ok_or(error::ProcessingError::FontLoadError(
+                "Could not determine font family name".to_string(),
+            ))?;
+        let entity = app.world_mut().spawn(Font { family_name }).id();
+        Ok(entity)
+    })
+}
 
-        app_mut(|app| {
-            let text_cx = app.world().resource::<TextContext>().clone();
-            let family_name =
-                text_cx
-                    .load_font(data)
-                    .ok_or(error::ProcessingError::FontLoadError(
-                        "Could not determine font family name".to_string(),
-                    ))?;
-            let entity = app.world_mut().spawn(Font { family_name }).id();
-            Ok(entity)
-        })
-    }
+#[cfg(not(target_arch = "wasm32"))]
+fn read_font_file(path: &str) -> error::Result<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| error::ProcessingError::FontLoadError(format!("{}: {}", path, e)))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_font_file(_path: &str) -> error::Result<Vec<u8>> {
+    Err(error::ProcessingError::FontLoadError(
+        "loading fonts from a file is not supported on wasm".to_string(),
+    ))
 }
 
 /// Create a font handle from an existing font family name.
@@ -2511,6 +3446,13 @@ pub fn graphics_text_wrap(graphics_entity: Entity, mode: u8) -> error::Result<()
     )
 }
 
+/// Set how text is drawn. 0=VECTOR tessellates glyph outlines, 1=SDF draws quads sampling
+/// glyph distance fields, which stay crisp when scaled or transformed.
+pub fn graphics_text_mode(graphics_entity: Entity, mode: u8) -> error::Result<()> {
+    use render::command::TextMode;
+    graphics_record_command(graphics_entity, DrawCommand::TextMode(TextMode::from(mode)))
+}
+
 pub fn graphics_text_weight(graphics_entity: Entity, weight: f32) -> error::Result<()> {
     graphics_record_command(graphics_entity, DrawCommand::TextWeight(weight))
 }
@@ -1,5 +1,7 @@
 //! A light in Processing
 //!
+//! Lights are entities that stay until destroyed, so create them once in setup rather than every
+//! frame, and use [`set_enabled`] to switch one off for a while.
 
 use bevy::{camera::visibility::RenderLayers, prelude::*};
 
@@ -84,3 +86,70 @@ pub fn create_spot(
         ))
         .id())
 }
+
+type AnyLight = Or<(With<DirectionalLight>, With<PointLight>, With<SpotLight>)>;
+
+/// Switch a light off or back on without losing its settings. Hidden lights aren't extracted for
+/// rendering, so a disabled light costs nothing.
+pub fn set_enabled(
+    In((entity, enabled)): In<(Entity, bool)>,
+    mut lights: Query<&mut Visibility, AnyLight>,
+) -> Result<(), ProcessingError> {
+    let mut visibility = lights
+        .get_mut(entity)
+        .map_err(|_| ProcessingError::LightNotFound)?;
+    *visibility = if enabled {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    Ok(())
+}
+
+pub fn destroy(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    lights: Query<(), AnyLight>,
+) -> Result<(), ProcessingError> {
+    if !lights.contains(entity) {
+        return Err(ProcessingError::LightNotFound);
+    }
+    commands.entity(entity).despawn();
+    Ok(())
+}
+
+/// Light every surface drawn by the graphics evenly from all directions, so the sides of PBR
+/// geometry facing away from other lights aren't pitch black. Ambient light is set on the
+/// graphics' camera rather than spawned, so each graphics has at most one and it never lights
+/// another graphics' scene.
+pub fn create_ambient(
+    In((entity, color, brightness)): In<(Entity, Color, f32)>,
+    mut commands: Commands,
+    graphics: Query<(), With<Graphics>>,
+) -> Result<(), ProcessingError> {
+    if !graphics.contains(entity) {
+        return Err(ProcessingError::GraphicsNotFound);
+    }
+    commands.entity(entity).insert(AmbientLight {
+        color,
+        brightness,
+        ..default()
+    });
+    Ok(())
+}
+
+/// Change the color and brightness of the ambient light added by [`create_ambient`].
+pub fn set_ambient(
+    In((entity, color, brightness)): In<(Entity, Color, f32)>,
+    mut graphics: Query<Option<&mut AmbientLight>, With<Graphics>>,
+) -> Result<(), ProcessingError> {
+    let mut ambient = graphics
+        .get_mut(entity)
+        .map_err(|_| ProcessingError::GraphicsNotFound)?
+        .ok_or_else(|| {
+            ProcessingError::InvalidArgument("Graphics has no ambient light to set".into())
+        })?;
+    ambient.color = color;
+    ambient.brightness = brightness;
+    Ok(())
+}
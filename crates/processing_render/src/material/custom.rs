@@ -53,7 +53,7 @@ use bevy::shader::Shader as ShaderAsset;
 
 use crate::render::material::UntypedMaterial;
 use crate::shader_value::ShaderValue;
-use processing_core::config::{Config, ConfigKey};
+use processing_core::config::Config;
 use processing_core::error::{ProcessingError, Result};
 
 #[derive(Clone, Hash, PartialEq)]
@@ -176,8 +176,7 @@ pub fn create_shader(
 
 pub fn load_shader(In(path): In<String>, world: &mut World) -> Result<Entity> {
     use bevy::asset::{
-        AssetPath, LoadState, handle_internal_asset_events,
-        io::{AssetSourceId, embedded::GetAssetServer},
+        AssetPath, LoadState, handle_internal_asset_events, io::embedded::GetAssetServer,
     };
     use bevy::ecs::system::RunSystemOnce;
 
@@ -187,10 +186,8 @@ pub fn load_shader(In(path): In<String>, world: &mut World) -> Result<Entity> {
     } else {
         let config = world.resource::<Config>();
         let path = std::path::PathBuf::from(path);
-        match config.get(ConfigKey::AssetRootPath) {
-            Some(_) => {
-                AssetPath::from_path_buf(path).with_source(AssetSourceId::from("assets_directory"))
-            }
+        match crate::image::asset_source(config) {
+            Some(source) => AssetPath::from_path_buf(path).with_source(source),
             None => AssetPath::from_path_buf(path),
         }
     };
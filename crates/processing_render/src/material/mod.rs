@@ -11,9 +11,11 @@ use bevy::pbr::{
     ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
 };
 use bevy::prelude::*;
-use bevy::render::render_resource::{AsBindGroup, BlendState};
+use bevy::render::render_resource::{AsBindGroup, BlendState, PolygonMode};
+use bevy::render::renderer::RenderDevice;
 use bevy::shader::ShaderRef;
 use bevy_naga_reflect::reflect::ParameterCategory;
+use processing_core::config::{Config, ConfigKey};
 use processing_core::error::{self, ProcessingError};
 
 pub struct ProcessingMaterialPlugin;
@@ -35,12 +37,45 @@ impl Plugin for ProcessingMaterialPlugin {
             });
         let entity = world.spawn(UntypedMaterial(handle.untyped())).id();
         world.insert_resource(DefaultMaterial(entity));
+
+        let missing = world
+            .resource_mut::<Assets<ExtendedMaterial<StandardMaterial, ProcessingMaterial>>>()
+            .add(ExtendedMaterial {
+                base: StandardMaterial {
+                    base_color: Color::srgb(1.0, 0.0, 1.0),
+                    unlit: true,
+                    cull_mode: None,
+                    ..default()
+                },
+                extension: ProcessingMaterial::default(),
+            });
+        world.insert_resource(MissingMaterial(missing.untyped()));
+
+        let required = world
+            .get_resource::<Config>()
+            .and_then(|config| config.get(ConfigKey::RequireMaterial))
+            .is_some_and(|v| v == "true");
+        world.insert_resource(RequireMaterial(required));
     }
 }
 
 #[derive(Resource)]
 pub struct DefaultMaterial(pub Entity);
 
+/// Flat magenta for geometry drawn with no material while [`RequireMaterial`] is set, made once
+/// and shared by every such draw.
+#[derive(Resource)]
+pub struct MissingMaterial(pub UntypedHandle);
+
+/// Whether geometry drawn without a material shows up magenta, from
+/// [`ConfigKey::RequireMaterial`] or [`set_require_material`].
+#[derive(Resource, Default)]
+pub struct RequireMaterial(pub bool);
+
+pub fn set_require_material(In(required): In<bool>, mut require: ResMut<RequireMaterial>) {
+    require.0 = required;
+}
+
 pub fn create_pbr(
     mut commands: Commands,
     mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, ProcessingMaterial>>>,
@@ -51,7 +86,7 @@ pub fn create_pbr(
             cull_mode: None,
             ..default()
         },
-        extension: ProcessingMaterial { blend_state: None },
+        extension: ProcessingMaterial::default(),
     });
     commands.spawn(UntypedMaterial(handle.untyped())).id()
 }
@@ -60,10 +95,13 @@ pub fn set_property(
     In((entity, name, value)): In<(Entity, String, ShaderValue)>,
     material_handles: Query<&UntypedMaterial>,
     images: Query<&crate::image::Image>,
+    mut image_assets: ResMut<Assets<bevy::image::Image>>,
+    mut linear_images: ResMut<crate::image::LinearImages>,
     mut extended_materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, ProcessingMaterial>>>,
     mut particles_materials: ResMut<Assets<crate::particles::material::ParticlesMaterial>>,
     mut custom_materials: ResMut<Assets<custom::CustomMaterial>>,
     mut p_buffers: Query<&mut compute::Buffer>,
+    render_device: Res<RenderDevice>,
 ) -> error::Result<()> {
     let texture_handle = match &value {
         ShaderValue::Texture(img_entity) => Some(
@@ -75,6 +113,16 @@ pub fn set_property(
         ),
         _ => None,
     };
+    // PBR data textures are sampled without sRGB decoding, through a linear copy so the image
+    // still reads as color everywhere else
+    let mut texture_for = |name: &str| match &texture_handle {
+        Some(handle) if pbr::is_linear_texture(name) => Some(crate::image::linear_view(
+            handle,
+            &mut image_assets,
+            &mut linear_images,
+        )),
+        other => other.clone(),
+    };
 
     let untyped = material_handles
         .get(entity)
@@ -88,7 +136,12 @@ pub fn set_property(
         let mut extended = extended_materials
             .get_mut(&handle)
             .ok_or(ProcessingError::MaterialNotFound)?;
-        return pbr::set_property(&mut extended.base, &name, &value, texture_handle);
+        if let Some(mode) = pbr::polygon_mode(&name, &value)? {
+            pbr::check_polygon_mode(mode, render_device.features())?;
+            extended.extension.polygon_mode = mode;
+            return Ok(());
+        }
+        return pbr::set_property(&mut extended.base, &name, &value, texture_for(&name));
     }
 
     if let Ok(handle) = untyped
@@ -99,7 +152,7 @@ pub fn set_property(
         let mut extended = particles_materials
             .get_mut(&handle)
             .ok_or(ProcessingError::MaterialNotFound)?;
-        return pbr::set_property(&mut extended.base, &name, &value, texture_handle);
+        return pbr::set_property(&mut extended.base, &name, &value, texture_for(&name));
     }
 
     if let Ok(handle) = untyped.0.clone().try_typed::<custom::CustomMaterial>() {
@@ -165,18 +218,24 @@ pub fn destroy(
 #[bind_group_data(ProcessingMaterialKey)]
 pub struct ProcessingMaterial {
     pub blend_state: Option<BlendState>,
+    /// Draw triangles filled, as outlines or as their corner points. Outlines and points are one
+    /// pixel wide.
+    #[reflect(ignore)]
+    pub polygon_mode: PolygonMode,
 }
 
 #[repr(C)]
 #[derive(Eq, PartialEq, Hash, Copy, Clone)]
 pub struct ProcessingMaterialKey {
     blend_state: Option<BlendState>,
+    polygon_mode: PolygonMode,
 }
 
 impl From<&ProcessingMaterial> for ProcessingMaterialKey {
     fn from(mat: &ProcessingMaterial) -> Self {
         ProcessingMaterialKey {
             blend_state: mat.blend_state,
+            polygon_mode: mat.polygon_mode,
         }
     }
 }
@@ -205,6 +264,7 @@ impl MaterialExtension for ProcessingMaterial {
                 }
             });
         }
+        descriptor.primitive.polygon_mode = key.bind_group_data.polygon_mode;
         Ok(())
     }
 }
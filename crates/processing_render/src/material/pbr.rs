@@ -1,8 +1,20 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    render::{render_resource::PolygonMode, settings::WgpuFeatures},
+};
 
 use crate::shader_value::ShaderValue;
 use processing_core::error::{ProcessingError, Result};
 
+/// Whether texture property `name` holds data rather than color. Metallic-roughness, normal and
+/// occlusion maps are linear, base color and emissive textures are sRGB.
+pub fn is_linear_texture(name: &str) -> bool {
+    matches!(
+        name,
+        "metallic_roughness_texture" | "normal_map_texture" | "occlusion_texture"
+    )
+}
+
 pub fn set_property(
     material: &mut StandardMaterial,
     name: &str,
@@ -87,13 +99,27 @@ pub fn set_property(
                 }
             };
         }
-        "base_color_texture" | "texture" => {
+        "base_color_texture"
+        | "texture"
+        | "emissive_texture"
+        | "metallic_roughness_texture"
+        | "normal_map_texture"
+        | "occlusion_texture" => {
             let Some(handle) = texture_handle else {
                 return Err(ProcessingError::InvalidArgument(format!(
                     "'{name}' expects Texture, got {value:?}"
                 )));
             };
-            material.base_color_texture = Some(handle);
+            let slot = match name {
+                "emissive_texture" => &mut material.emissive_texture,
+                // roughness is read from the green channel and metallic from the blue
+                "metallic_roughness_texture" => &mut material.metallic_roughness_texture,
+                // only affects geometries that have tangents
+                "normal_map_texture" => &mut material.normal_map_texture,
+                "occlusion_texture" => &mut material.occlusion_texture,
+                _ => &mut material.base_color_texture,
+            };
+            *slot = Some(handle);
         }
         _ => {
             return Err(ProcessingError::UnknownShaderProperty(name.to_string()));
@@ -101,3 +127,80 @@ pub fn set_property(
     }
     Ok(())
 }
+
+/// The polygon mode set by a `"wireframe"` flag or a `"polygon_mode"` of 0 for filled, 1 for
+/// lines or 2 for points, or `None` for other properties. The mode belongs to the material
+/// extension rather than the [`StandardMaterial`], so it's set apart from [`set_property`].
+pub fn polygon_mode(name: &str, value: &ShaderValue) -> Result<Option<PolygonMode>> {
+    let mode = match name {
+        "wireframe" => {
+            let ShaderValue::Float(v) = value else {
+                return Err(ProcessingError::InvalidArgument(format!(
+                    "'{name}' expects Float, got {value:?}"
+                )));
+            };
+            if *v > 0.5 {
+                PolygonMode::Line
+            } else {
+                PolygonMode::Fill
+            }
+        }
+        "polygon_mode" => {
+            let ShaderValue::Int(v) = value else {
+                return Err(ProcessingError::InvalidArgument(format!(
+                    "'{name}' expects Int, got {value:?}"
+                )));
+            };
+            match v {
+                0 => PolygonMode::Fill,
+                1 => PolygonMode::Line,
+                2 => PolygonMode::Point,
+                _ => {
+                    return Err(ProcessingError::InvalidArgument(format!(
+                        "unknown polygon_mode value: {v}"
+                    )));
+                }
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(mode))
+}
+
+/// Outlines and points are optional GPU features. Checking for them when the mode is set gives an
+/// error the caller can act on, where the pipeline would otherwise fail to build at draw time.
+pub fn check_polygon_mode(mode: PolygonMode, features: WgpuFeatures) -> Result<()> {
+    let needed = match mode {
+        PolygonMode::Fill => return Ok(()),
+        PolygonMode::Line => WgpuFeatures::POLYGON_MODE_LINE,
+        PolygonMode::Point => WgpuFeatures::POLYGON_MODE_POINT,
+    };
+    if features.contains(needed) {
+        Ok(())
+    } else {
+        Err(ProcessingError::InvalidArgument(format!(
+            "{mode:?} polygon mode isn't supported by this GPU"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygon_modes_need_their_features() {
+        let none = WgpuFeatures::empty();
+        assert!(check_polygon_mode(PolygonMode::Fill, none).is_ok());
+        assert!(matches!(
+            check_polygon_mode(PolygonMode::Line, none),
+            Err(ProcessingError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            check_polygon_mode(PolygonMode::Point, WgpuFeatures::POLYGON_MODE_LINE),
+            Err(ProcessingError::InvalidArgument(_))
+        ));
+        assert!(check_polygon_mode(PolygonMode::Line, WgpuFeatures::POLYGON_MODE_LINE).is_ok());
+        assert!(check_polygon_mode(PolygonMode::Point, WgpuFeatures::POLYGON_MODE_POINT).is_ok());
+    }
+}
@@ -0,0 +1,207 @@
+//! Processing's Perlin `noise()`. Values come from a table of random floats filled by a
+//! `java.util.Random` compatible generator, so a sketch seeded with `noiseSeed` produces the same
+//! noise as it does in Processing.
+use std::hash::{BuildHasher, RandomState};
+
+use bevy::prelude::*;
+
+const PERLIN_YWRAPB: u32 = 4;
+const PERLIN_YWRAP: usize = 1 << PERLIN_YWRAPB;
+const PERLIN_ZWRAPB: u32 = 8;
+const PERLIN_ZWRAP: usize = 1 << PERLIN_ZWRAPB;
+const PERLIN_SIZE: usize = 4095;
+// Processing eases with a cosine table in half degree steps
+const SINCOS_LENGTH: usize = 720;
+
+#[derive(Resource)]
+pub struct Noise {
+    table: Vec<f32>,
+    cos_table: Vec<f32>,
+    octaves: u32,
+    falloff: f32,
+}
+
+impl Default for Noise {
+    fn default() -> Self {
+        // like Processing, unseeded noise differs from run to run
+        Self::new(RandomState::new().hash_one(0u8))
+    }
+}
+
+impl Noise {
+    pub fn new(seed: u64) -> Self {
+        let cos_table = (0..SINCOS_LENGTH)
+            .map(|i| (i as f32 * 0.5).to_radians().cos())
+            .collect();
+        let mut noise = Self {
+            table: Vec::new(),
+            cos_table,
+            octaves: 4,
+            falloff: 0.5,
+        };
+        noise.seed(seed);
+        noise
+    }
+
+    /// Refill the table from `seed`, making the noise reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        let mut random = JavaRandom::new(seed);
+        self.table = (0..=PERLIN_SIZE).map(|_| random.next_float()).collect();
+    }
+
+    /// Set the number of octaves summed and how much each one's amplitude falls off. Values of
+    /// zero or less leave the current setting, and a falloff above 0.5 can push results past 1.
+    pub fn detail(&mut self, octaves: i32, falloff: f32) {
+        if octaves > 0 {
+            self.octaves = octaves as u32;
+        }
+        if falloff > 0.0 {
+            self.falloff = falloff;
+        }
+    }
+
+    /// Noise at a point, from 0 to 1 with the default detail. Negative coordinates mirror the
+    /// positive ones, as in Processing.
+    pub fn get(&self, x: f32, y: f32, z: f32) -> f32 {
+        let (x, y, z) = (x.abs(), y.abs(), z.abs());
+        let (mut xi, mut yi, mut zi) = (x as usize, y as usize, z as usize);
+        let (mut xf, mut yf, mut zf) = (x - xi as f32, y - yi as f32, z - zi as f32);
+        let at = |i: usize| self.table[i & PERLIN_SIZE];
+
+        let mut r = 0.0;
+        let mut amplitude = 0.5;
+        for _ in 0..self.octaves {
+            // masked up front so the offsets below can't overflow for huge coordinates
+            let mut of = xi
+                .wrapping_add(yi << PERLIN_YWRAPB)
+                .wrapping_add(zi << PERLIN_ZWRAPB)
+                & PERLIN_SIZE;
+            let rxf = self.ease(xf);
+            let ryf = self.ease(yf);
+
+            let mut n1 = at(of);
+            n1 += rxf * (at(of + 1) - n1);
+            let mut n2 = at(of + PERLIN_YWRAP);
+            n2 += rxf * (at(of + PERLIN_YWRAP + 1) - n2);
+            n1 += ryf * (n2 - n1);
+
+            of = (of + PERLIN_ZWRAP) & PERLIN_SIZE;
+            n2 = at(of);
+            n2 += rxf * (at(of + 1) - n2);
+            let mut n3 = at(of + PERLIN_YWRAP);
+            n3 += rxf * (at(of + PERLIN_YWRAP + 1) - n3);
+            n2 += ryf * (n3 - n2);
+
+            n1 += self.ease(zf) * (n2 - n1);
+            r += n1 * amplitude;
+            amplitude *= self.falloff;
+
+            (xi, xf) = next_octave(xi, xf);
+            (yi, yf) = next_octave(yi, yf);
+            (zi, zf) = next_octave(zi, zf);
+        }
+        r
+    }
+
+    /// [`Noise::get`] at each of `points`.
+    pub fn sample(&self, points: &[Vec3]) -> Vec<f32> {
+        points.iter().map(|p| self.get(p.x, p.y, p.z)).collect()
+    }
+
+    /// A unit vector pointing along the noise angle field at `x`, `y`, with the coordinates
+    /// multiplied by `scale` first. Smaller scales give broader, smoother currents, for steering
+    /// particles with `velocity += flow(position)`.
+    pub fn flow(&self, x: f32, y: f32, scale: f32) -> Vec2 {
+        // noise rarely strays far from 0.5, so two full turns keeps every direction in play
+        let angle = self.get(x * scale, y * scale, 0.0) * std::f32::consts::TAU * 2.0;
+        Vec2::from_angle(angle)
+    }
+
+    fn ease(&self, t: f32) -> f32 {
+        let i = (t * (SINCOS_LENGTH / 2) as f32) as usize % SINCOS_LENGTH;
+        0.5 * (1.0 - self.cos_table[i])
+    }
+}
+
+fn next_octave(i: usize, f: f32) -> (usize, f32) {
+    let (i, f) = (i.wrapping_shl(1), f * 2.0);
+    if f >= 1.0 { (i + 1, f - 1.0) } else { (i, f) }
+}
+
+/// The linear congruential generator behind `java.util.Random`.
+struct JavaRandom(u64);
+
+impl JavaRandom {
+    const MULTIPLIER: u64 = 0x5DEECE66D;
+    const MASK: u64 = (1 << 48) - 1;
+
+    fn new(seed: u64) -> Self {
+        Self((seed ^ Self::MULTIPLIER) & Self::MASK)
+    }
+
+    fn next(&mut self, bits: u32) -> u32 {
+        self.0 = (self.0.wrapping_mul(Self::MULTIPLIER).wrapping_add(0xB)) & Self::MASK;
+        (self.0 >> (48 - bits)) as u32
+    }
+
+    fn next_float(&mut self) -> f32 {
+        self.next(24) as f32 / (1 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_noise_matches_processing() {
+        // new java.util.Random(42).nextFloat()
+        assert_eq!(JavaRandom::new(42).next_float(), 0.727_563_7);
+
+        // noiseSeed(42); noise(0) sums the first table entry over four octaves
+        let mut noise = Noise::new(42);
+        assert!((noise.get(0.0, 0.0, 0.0) - 0.727_563_7 * 0.9375).abs() < 1e-6);
+
+        let samples: Vec<f32> = (0..200)
+            .map(|i| noise.get(i as f32 * 0.173, i as f32 * 0.071, 3.5))
+            .collect();
+        assert!(samples.iter().all(|n| (0.0..1.0).contains(n)));
+        noise.detail(8, 0.25);
+        noise.seed(42);
+        noise.detail(4, 0.5);
+        let again: Vec<f32> = (0..200)
+            .map(|i| noise.get(i as f32 * 0.173, i as f32 * 0.071, 3.5))
+            .collect();
+        assert_eq!(samples, again);
+
+        let points: Vec<Vec3> = (0..200)
+            .map(|i| Vec3::new(i as f32 * 0.173, i as f32 * 0.071, 3.5))
+            .collect();
+        assert_eq!(noise.sample(&points), samples);
+    }
+
+    #[test]
+    fn huge_coordinates_stay_in_range() {
+        let noise = Noise::new(42);
+        for (x, y, z) in [
+            (1e30, 0.0, 0.0),
+            (0.0, -1e30, 0.0),
+            (f32::MAX, f32::MAX, f32::MAX),
+        ] {
+            assert!((0.0..1.0).contains(&noise.get(x, y, z)));
+        }
+    }
+
+    #[test]
+    fn flow_is_unit_length_and_seeded() {
+        let flow = |noise: &Noise| -> Vec<Vec2> {
+            (0..100)
+                .map(|i| noise.flow(i as f32 * 13.0, i as f32 * 7.0, 0.01))
+                .collect()
+        };
+        let vectors = flow(&Noise::new(7));
+        assert!(vectors.iter().all(|v| (v.length() - 1.0).abs() < 1e-5));
+        assert_eq!(vectors, flow(&Noise::new(7)));
+        assert_ne!(vectors, flow(&Noise::new(8)));
+    }
+}
@@ -0,0 +1,191 @@
+//! Load Wavefront `.obj` models as a [`Geometry`]. Only the shape is read, from the `v`, `vt`,
+//! `vn` and `f` directives, with faces of more than three corners fanned into triangles.
+//! Materials, groups and smoothing are skipped.
+//!
+//! [`Geometry`]: crate::geometry::Geometry
+use std::collections::HashMap;
+
+use bevy::{
+    asset::RenderAssetUsages, mesh::Indices, prelude::*, render::render_resource::PrimitiveTopology,
+};
+
+use crate::geometry::ops::compute_normals_of;
+use processing_core::error::{ProcessingError, Result};
+
+fn parse_error(line: usize, message: impl std::fmt::Display) -> ProcessingError {
+    ProcessingError::ObjLoadError(format!("line {line}: {message}"))
+}
+
+/// The first `N` numbers after a directive. Extra ones, like a position's `w`, are ignored.
+fn floats<'a, const N: usize>(
+    mut words: impl Iterator<Item = &'a str>,
+    line: usize,
+) -> Result<[f32; N]> {
+    let mut values = [0.0; N];
+    for value in &mut values {
+        let word = words
+            .next()
+            .ok_or_else(|| parse_error(line, format!("expected {N} numbers")))?;
+        *value = word
+            .parse()
+            .map_err(|_| parse_error(line, format!("`{word}` is not a number")))?;
+    }
+    Ok(values)
+}
+
+/// A 1-based index into the `count` elements read so far, or a negative one counting back from
+/// the last of them.
+fn resolve(index: &str, count: usize, line: usize) -> Result<usize> {
+    let i: i64 = index
+        .parse()
+        .map_err(|_| parse_error(line, format!("`{index}` is not an index")))?;
+    let resolved = if i > 0 { i - 1 } else { count as i64 + i };
+    if i == 0 || resolved < 0 || resolved >= count as i64 {
+        return Err(parse_error(line, format!("index {i} is out of range")));
+    }
+    Ok(resolved as usize)
+}
+
+/// Build a triangle list mesh with positions, normals, uvs and white vertex colors from the text
+/// of an `.obj` file. Normals missing from the file are computed from the faces.
+pub fn parse(source: &str) -> Result<Mesh> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+
+    // every distinct `v/vt/vn` combination becomes one vertex
+    let mut corners: HashMap<(usize, Option<usize>, Option<usize>), u32> = HashMap::new();
+    let mut vertex_positions = Vec::new();
+    let mut vertex_uvs = Vec::new();
+    let mut vertex_normals = Vec::new();
+    let mut missing_normals = false;
+    let mut indices = Vec::new();
+
+    for (n, text) in source.lines().enumerate() {
+        let line = n + 1;
+        let text = text.split('#').next().unwrap_or_default();
+        let mut words = text.split_whitespace();
+        match words.next() {
+            Some("v") => positions.push(floats(words, line)?),
+            Some("vt") => {
+                // obj textures count v up from the bottom
+                let [u, v] = floats(words, line)?;
+                uvs.push([u, 1.0 - v]);
+            }
+            Some("vn") => normals.push(floats(words, line)?),
+            Some("f") => {
+                let mut face = Vec::new();
+                for corner in words {
+                    let mut parts = corner.split('/');
+                    let position =
+                        resolve(parts.next().unwrap_or_default(), positions.len(), line)?;
+                    let uv = match parts.next() {
+                        Some("") | None => None,
+                        Some(i) => Some(resolve(i, uvs.len(), line)?),
+                    };
+                    let normal = match parts.next() {
+                        Some("") | None => None,
+                        Some(i) => Some(resolve(i, normals.len(), line)?),
+                    };
+                    missing_normals |= normal.is_none();
+
+                    let index = *corners.entry((position, uv, normal)).or_insert_with(|| {
+                        vertex_positions.push(positions[position]);
+                        vertex_uvs.push(uv.map_or([0.0, 0.0], |i| uvs[i]));
+                        vertex_normals.push(normal.map_or([0.0, 0.0, 0.0], |i| normals[i]));
+                        vertex_positions.len() as u32 - 1
+                    });
+                    face.push(index);
+                }
+                if face.len() < 3 {
+                    return Err(parse_error(line, "a face needs at least 3 corners"));
+                }
+                for i in 1..face.len() - 1 {
+                    indices.extend([face[0], face[i], face[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if missing_normals {
+        vertex_normals = compute_normals_of(&vertex_positions, &indices);
+    }
+    let colors = vec![[1.0, 1.0, 1.0, 1.0]; vertex_positions.len()];
+    Ok(Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vertex_positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vertex_normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vertex_uvs)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    .with_inserted_indices(Indices::U32(indices)))
+}
+
+/// Load an `.obj` file through the asset source as a geometry with the builtin position, normal,
+/// color and uv layout.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(In(path): In<String>, world: &mut World) -> Result<Entity> {
+    let load_error =
+        |e: &dyn std::fmt::Display| ProcessingError::ObjLoadError(format!("{path}: {e}"));
+    let bytes = crate::image::read_asset(world, path.as_ref()).map_err(|e| load_error(&e))?;
+    let source = String::from_utf8(bytes).map_err(|e| load_error(&e))?;
+    let mesh = parse(&source)?;
+    Ok(world.run_system_cached_with(crate::geometry::create_from_mesh, mesh)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::mesh::VertexAttributeValues;
+
+    use super::*;
+
+    #[test]
+    fn quad_is_fanned_and_corners_are_shared() {
+        let source = "\
+# a unit quad facing +z
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+f 1/1 2/2 3/3 4/4
+";
+        let mesh = parse(source).unwrap();
+        assert_eq!(mesh.count_vertices(), 4);
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert_eq!(indices, [0, 1, 2, 0, 2, 3]);
+
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("parsed mesh has no normals");
+        };
+        assert!(normals.iter().all(|n| *n == [0.0, 0.0, 1.0]));
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("parsed mesh has no uvs");
+        };
+        assert_eq!(uvs[2], [1.0, 0.0]);
+    }
+
+    #[test]
+    fn normals_and_negative_indices_are_read() {
+        let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 -1\nf -3//1 -2//1 -1//1\n";
+        let mesh = parse(source).unwrap();
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("parsed mesh has no normals");
+        };
+        // the file's normals are kept even though they disagree with the winding
+        assert!(normals.iter().all(|n| *n == [0.0, 0.0, -1.0]));
+
+        assert!(parse("v 0 0 0\nf 1 2 3\n").is_err());
+        assert!(parse("v 0 0 0\nv 1 0 0\nf 1 2\n").is_err());
+    }
+}
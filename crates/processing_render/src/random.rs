@@ -0,0 +1,135 @@
+//! Processing's `random()`, `randomSeed()` and `randomGaussian()`, all drawn from one generator
+//! so a seeded sketch gets the same sequence on every run.
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use rand::{
+    SeedableRng,
+    distr::{Distribution, StandardUniform},
+    rngs::StdRng,
+};
+
+#[derive(Resource)]
+pub struct Rng(StdRng);
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self(StdRng::seed_from_u64(rand::random()))
+    }
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+
+    pub fn seed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    /// The underlying generator, for drawing from distributions this type doesn't wrap while
+    /// keeping to the one seeded sequence.
+    pub fn generator(&mut self) -> &mut StdRng {
+        &mut self.0
+    }
+
+    /// A value from `low` up to but never including `high`. Returns `low` when the range is
+    /// empty, as Processing does.
+    pub fn range(&mut self, low: f32, high: f32) -> f32 {
+        if high <= low {
+            return low;
+        }
+        // rounding can land exactly on `high`, which Processing redraws
+        loop {
+            let t: f32 = StandardUniform.sample(&mut self.0);
+            let value = low + t * (high - low);
+            if value < high {
+                return value;
+            }
+        }
+    }
+
+    /// A normally distributed value with mean 0 and standard deviation 1, from a Box–Muller
+    /// transform.
+    pub fn gaussian(&mut self) -> f32 {
+        let u1: f32 = StandardUniform.sample(&mut self.0);
+        let u2: f32 = StandardUniform.sample(&mut self.0);
+        // 1 - u1 is in (0, 1], keeping the log finite
+        (-2.0 * (1.0 - u1).ln()).sqrt() * (TAU * u2).cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_sequences_repeat() {
+        let draw = |rng: &mut Rng| -> Vec<f32> {
+            (0..100)
+                .map(|i| {
+                    if i % 2 == 0 {
+                        rng.range(-5.0, 5.0)
+                    } else {
+                        rng.gaussian()
+                    }
+                })
+                .collect()
+        };
+        let mut rng = Rng::new(9);
+        let first = draw(&mut rng);
+        rng.seed(9);
+        assert_eq!(draw(&mut rng), first);
+
+        assert!(first.iter().step_by(2).all(|v| (-5.0..5.0).contains(v)));
+        assert_eq!(rng.range(3.0, 3.0), 3.0);
+        assert_eq!(rng.range(4.0, 1.0), 4.0);
+    }
+
+    #[test]
+    fn new_app_starts_unseeded() {
+        // what `init` does, on the first run and again after the previous app is gone
+        let new_app = || {
+            let mut app = App::new();
+            app.init_resource::<Rng>();
+            app
+        };
+        let seeded_draws = |app: &mut App| -> Vec<f32> {
+            let mut rng = app.world_mut().resource_mut::<Rng>();
+            rng.seed(9);
+            (0..8).map(|_| rng.range(0.0, 1.0)).collect()
+        };
+
+        let unseeded_draws = |app: &mut App| -> Vec<f32> {
+            let mut rng = app.world_mut().resource_mut::<Rng>();
+            (0..8).map(|_| rng.range(0.0, 1.0)).collect()
+        };
+
+        // two fresh apps don't share a sequence until they're given the same seed
+        let mut app = new_app();
+        let mut other = new_app();
+        assert_ne!(unseeded_draws(&mut app), unseeded_draws(&mut other));
+
+        let first = seeded_draws(&mut app);
+        assert_eq!(seeded_draws(&mut other), first);
+        let mut app = new_app();
+        assert_eq!(seeded_draws(&mut app), first);
+
+        let mut rng = Rng::new(9);
+        let expected: Vec<f32> = (0..8).map(|_| rng.range(0.0, 1.0)).collect();
+        assert_eq!(first, expected);
+    }
+
+    #[test]
+    fn gaussian_is_standard_normal() {
+        let mut rng = Rng::new(1);
+        let samples: Vec<f32> = (0..20_000).map(|_| rng.gaussian()).collect();
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let variance =
+            samples.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / samples.len() as f32;
+
+        assert!(mean.abs() < 0.05);
+        assert!((variance.sqrt() - 1.0).abs() < 0.05);
+        assert!(samples.iter().all(|v| v.is_finite()));
+    }
+}
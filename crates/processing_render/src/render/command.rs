@@ -2,21 +2,32 @@ use bevy::prelude::*;
 use bevy::render::render_resource::{BlendComponent, BlendFactor, BlendOperation, BlendState};
 use processing_core::error::{self, ProcessingError};
 
+/// Horizontal `text_align` values.
+pub const TEXT_ALIGN_LEFT: u8 = 0;
+pub const TEXT_ALIGN_CENTER: u8 = 1;
+pub const TEXT_ALIGN_RIGHT: u8 = 2;
+/// Vertical `text_align` values. These are numbered apart from the horizontal ones, so a binding
+/// with a single `CENTER` for both axes maps it to [`TEXT_ALIGN_MIDDLE`] itself.
+pub const TEXT_ALIGN_BASELINE: u8 = 0;
+pub const TEXT_ALIGN_TOP: u8 = 1;
+pub const TEXT_ALIGN_MIDDLE: u8 = 2;
+pub const TEXT_ALIGN_BOTTOM: u8 = 3;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
 pub enum TextAlignH {
     #[default]
-    Left = 0,
-    Center = 1,
-    Right = 2,
+    Left = TEXT_ALIGN_LEFT,
+    Center = TEXT_ALIGN_CENTER,
+    Right = TEXT_ALIGN_RIGHT,
 }
 
 impl From<u8> for TextAlignH {
     fn from(v: u8) -> Self {
         match v {
-            0 => Self::Left,
-            1 => Self::Center,
-            2 => Self::Right,
+            TEXT_ALIGN_LEFT => Self::Left,
+            TEXT_ALIGN_CENTER => Self::Center,
+            TEXT_ALIGN_RIGHT => Self::Right,
             _ => Self::default(),
         }
     }
@@ -26,19 +37,19 @@ impl From<u8> for TextAlignH {
 #[repr(u8)]
 pub enum TextAlignV {
     #[default]
-    Baseline = 0,
-    Top = 1,
-    Center = 2,
-    Bottom = 3,
+    Baseline = TEXT_ALIGN_BASELINE,
+    Top = TEXT_ALIGN_TOP,
+    Center = TEXT_ALIGN_MIDDLE,
+    Bottom = TEXT_ALIGN_BOTTOM,
 }
 
 impl From<u8> for TextAlignV {
     fn from(v: u8) -> Self {
         match v {
-            0 => Self::Baseline,
-            1 => Self::Top,
-            2 => Self::Center,
-            3 => Self::Bottom,
+            TEXT_ALIGN_BASELINE => Self::Baseline,
+            TEXT_ALIGN_TOP => Self::Top,
+            TEXT_ALIGN_MIDDLE => Self::Center,
+            TEXT_ALIGN_BOTTOM => Self::Bottom,
             _ => Self::default(),
         }
     }
@@ -84,6 +95,26 @@ impl From<u8> for TextStyle {
     }
 }
 
+/// How text is drawn: tessellated from glyph outlines, or as quads sampling signed distance
+/// fields of the glyphs, which stay crisp when scaled up or transformed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TextMode {
+    #[default]
+    Vector = 0,
+    Sdf = 1,
+}
+
+impl From<u8> for TextMode {
+    fn from(v: u8) -> Self {
+        match v {
+            0 => Self::Vector,
+            1 => Self::Sdf,
+            _ => Self::default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
 pub enum StrokeCapMode {
@@ -124,6 +155,50 @@ impl From<u8> for StrokeJoinMode {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GradientDirection {
+    /// `from` at the top, `to` at the bottom.
+    #[default]
+    Vertical = 0,
+    /// `from` on the left, `to` on the right.
+    Horizontal = 1,
+    /// `from` in the top left corner, `to` in the bottom right.
+    Diagonal = 2,
+}
+
+impl From<u8> for GradientDirection {
+    fn from(v: u8) -> Self {
+        match v {
+            0 => Self::Vertical,
+            1 => Self::Horizontal,
+            2 => Self::Diagonal,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Where a closed shape's stroke sits relative to its edge. Open paths are always centered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StrokeAlignMode {
+    Inner = 0,
+    #[default]
+    Center = 1,
+    Outer = 2,
+}
+
+impl From<u8> for StrokeAlignMode {
+    fn from(v: u8) -> Self {
+        match v {
+            0 => Self::Inner,
+            1 => Self::Center,
+            2 => Self::Outer,
+            _ => Self::default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
 pub enum ArcMode {
@@ -378,6 +453,18 @@ impl BlendMode {
 pub enum DrawCommand {
     BackgroundColor(Color),
     BackgroundImage(Entity),
+    /// Fill the surface with copies of `image`, each `tile_w` x `tile_h` pixels, starting at the
+    /// top left. Each tile is its own quad, so the image's sampler doesn't need to repeat.
+    BackgroundTiled {
+        image: Entity,
+        tile_w: f32,
+        tile_h: f32,
+    },
+    BackgroundGradient {
+        from: Color,
+        to: Color,
+        direction: GradientDirection,
+    },
     Fill(Color),
     /// per-instance albedo for `Particles`: a `compute::Buffer` of `Float4`
     /// colors indexed by tag. mutually exclusive with `Fill(Color)`.
@@ -388,6 +475,7 @@ pub enum DrawCommand {
     StrokeWeight(f32),
     StrokeCap(StrokeCapMode),
     StrokeJoin(StrokeJoinMode),
+    StrokeAlign(StrokeAlignMode),
     Roughness(f32),
     Metallic(f32),
     Emissive(Color),
@@ -406,6 +494,20 @@ pub enum DrawCommand {
         s_width: Option<f32>,
         s_height: Option<f32>,
     },
+    /// Blit the `sw` x `sh` region of `image` at `sx`, `sy` into the `dw` x `dh` rect at `dx`,
+    /// `dy`, like Processing's `copy()`. Unlike `Image` it ignores `image_mode` and `tint`, but is
+    /// still batched with and transformed like other draws.
+    DrawImageRegion {
+        image: Entity,
+        sx: f32,
+        sy: f32,
+        sw: f32,
+        sh: f32,
+        dx: f32,
+        dy: f32,
+        dw: f32,
+        dh: f32,
+    },
     RectMode(ShapeMode),
     EllipseMode(ShapeMode),
     Rect {
@@ -427,6 +529,11 @@ pub enum DrawCommand {
         x2: f32,
         y2: f32,
     },
+    /// A line between points in 3d, stroked as a ribbon that turns to face the camera.
+    Line3d {
+        start: Vec3,
+        end: Vec3,
+    },
     Triangle {
         x1: f32,
         y1: f32,
@@ -449,6 +556,10 @@ pub enum DrawCommand {
         x: f32,
         y: f32,
     },
+    /// A point in 3d, drawn as a dot facing the camera.
+    Point3d(Vec3),
+    /// Many points in one command, for particle sketches that plot thousands a frame.
+    Points(Vec<[f32; 2]>),
     Arc {
         cx: f32,
         cy: f32,
@@ -511,6 +622,9 @@ pub enum DrawCommand {
     PushMatrix,
     PopMatrix,
     ResetMatrix,
+    /// Multiply the current transform by a 4x4 matrix given in column-major order, like
+    /// Processing's `applyMatrix()`.
+    ApplyMatrix([f32; 16]),
     Translate(Vec2),
     Rotate {
         angle: f32,
@@ -532,6 +646,23 @@ pub enum DrawCommand {
         angle: f32,
     },
     Geometry(Entity),
+    /// Draw a geometry with `material` in place of the current material, for this draw only.
+    GeometryWithMaterial {
+        geometry: Entity,
+        material: Entity,
+    },
+    ShapeMode(ShapeMode),
+    /// Draw a geometry modeled in the unit square, `(0, 0)` to `(1, 1)`, stretched over the rect
+    /// at `x`, `y`, `w`, `h` as read by the shape mode, like Processing's `shape(s, x, y, w, h)`.
+    /// `material`, when set, stands in for the current material for this draw only.
+    Shape {
+        geometry: Entity,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        material: Option<Entity>,
+    },
     Particles {
         particles: Entity,
         geometry: Entity,
@@ -605,6 +736,7 @@ pub enum DrawCommand {
     },
     TextLeading(f32),
     TextWrap(TextWrapMode),
+    TextMode(TextMode),
     TextGlyphColors(Vec<Color>),
     Text {
         content: String,
@@ -614,6 +746,12 @@ pub enum DrawCommand {
         max_w: Option<f32>,
         max_h: Option<f32>,
     },
+    /// Text on a dark backing in the top left corner, drawn in a fixed style without the sketch's
+    /// transform, fill or text settings. Drawn through the graphics' [`FpsOverlay`] camera, so it
+    /// stays on top in 3D sketches too.
+    ///
+    /// [`FpsOverlay`]: crate::graphics::FpsOverlay
+    DebugText(String),
 }
 
 #[derive(Debug, Default, Component)]
@@ -635,4 +773,52 @@ impl CommandBuffer {
     pub fn clear(&mut self) {
         self.commands.clear();
     }
+
+    /// Take the recorded commands for flushing. Hand the spent `Vec` back with
+    /// [`recycle`](Self::recycle) so steady state sketches don't regrow it every frame.
+    pub fn take(&mut self) -> Vec<DrawCommand> {
+        std::mem::take(&mut self.commands)
+    }
+
+    /// Reuse the allocation of a `Vec` returned by [`take`](Self::take). Anything recorded since
+    /// the take stays queued.
+    pub fn recycle(&mut self, mut spent: Vec<DrawCommand>) {
+        spent.clear();
+        spent.append(&mut self.commands);
+        self.commands = spent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertical_text_align_keeps_its_numbers() {
+        // callers across the FFI and wasm boundaries pass these as plain numbers
+        assert_eq!(TextAlignV::from(0), TextAlignV::Baseline);
+        assert_eq!(TextAlignV::from(1), TextAlignV::Top);
+        assert_eq!(TextAlignV::from(2), TextAlignV::Center);
+        assert_eq!(TextAlignV::from(3), TextAlignV::Bottom);
+    }
+
+    #[test]
+    fn recycled_buffer_keeps_its_capacity() {
+        let mut buffer = CommandBuffer::new();
+        for _ in 0..100 {
+            buffer.push(DrawCommand::NoFill);
+        }
+        let commands = buffer.take();
+        let capacity = commands.capacity();
+        let ptr = commands.as_ptr();
+        buffer.recycle(commands);
+
+        assert!(buffer.commands.is_empty());
+        assert_eq!(buffer.commands.capacity(), capacity);
+        for _ in 0..100 {
+            buffer.push(DrawCommand::NoFill);
+        }
+        // the next frame fits without reallocating
+        assert_eq!(buffer.commands.as_ptr(), ptr);
+    }
 }
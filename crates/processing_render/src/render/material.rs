@@ -33,6 +33,9 @@ pub enum MaterialKey {
         entity: Entity,
         blend_state: Option<BlendState>,
     },
+    SdfText {
+        atlas: Handle<Image>,
+    },
 }
 
 pub struct PbrFields {
@@ -102,6 +105,31 @@ impl MaterialKey {
             MaterialKey::Color { blend_state, .. } => *blend_state,
             MaterialKey::Pbr { blend_state, .. } => *blend_state,
             MaterialKey::Custom { blend_state, .. } => *blend_state,
+            MaterialKey::SdfText { .. } => None,
+        }
+    }
+
+    /// Blend this material even when its own color is opaque, for meshes whose vertex colors
+    /// carry alpha. Custom materials and text pick their own alpha mode and are left alone.
+    pub fn translucent(self) -> Self {
+        match self {
+            MaterialKey::Color {
+                background_image,
+                uv_transform,
+                blend_state,
+                ..
+            } => MaterialKey::Color {
+                transparent: true,
+                background_image,
+                uv_transform,
+                blend_state,
+            },
+            MaterialKey::Pbr { .. } => {
+                let mut pbr = self.as_pbr();
+                pbr.blend_state.get_or_insert(BlendState::ALPHA_BLENDING);
+                pbr.into()
+            }
+            key => key,
         }
     }
 
@@ -132,7 +160,7 @@ impl MaterialKey {
                 emissive,
                 base_color_texture,
                 uv_transform,
-                ..
+                blend_state,
             } => {
                 let base_color = Color::srgba(
                     albedo[0] as f32 / 255.0,
@@ -154,10 +182,15 @@ impl MaterialKey {
                     ),
                     base_color_texture: base_color_texture.clone(),
                     uv_transform: *uv_transform,
+                    alpha_mode: if blend_state.is_some() || albedo[3] < 255 {
+                        AlphaMode::Blend
+                    } else {
+                        AlphaMode::Opaque
+                    },
                     ..default()
                 }
             }
-            MaterialKey::Custom { .. } => unreachable!(),
+            MaterialKey::Custom { .. } | MaterialKey::SdfText { .. } => unreachable!(),
         }
     }
 
@@ -169,7 +202,10 @@ impl MaterialKey {
         let base = self.to_standard_material();
         let extended = ProcessingExtendedMaterial {
             base,
-            extension: ProcessingMaterial { blend_state },
+            extension: ProcessingMaterial {
+                blend_state,
+                ..default()
+            },
         };
         materials.add(extended).untyped()
     }
@@ -192,3 +228,34 @@ pub fn add_custom_materials(mut commands: Commands, meshes: Query<(Entity, &Unty
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translucent_keys_blend() {
+        let color = MaterialKey::Color {
+            transparent: false,
+            background_image: None,
+            uv_transform: Affine2::IDENTITY,
+            blend_state: None,
+        };
+        assert_eq!(color.to_standard_material().alpha_mode, AlphaMode::Opaque);
+        let color = color.translucent();
+        assert_eq!(color.to_standard_material().alpha_mode, AlphaMode::Blend);
+
+        let pbr = MaterialKey::from(PbrFields::default());
+        assert_eq!(pbr.to_standard_material().alpha_mode, AlphaMode::Opaque);
+        assert_eq!(
+            pbr.translucent().to_standard_material().alpha_mode,
+            AlphaMode::Blend
+        );
+
+        let faded = MaterialKey::from(PbrFields {
+            albedo: [255, 255, 255, 128],
+            ..default()
+        });
+        assert_eq!(faded.to_standard_material().alpha_mode, AlphaMode::Blend);
+    }
+}
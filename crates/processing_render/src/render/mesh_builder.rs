@@ -60,6 +60,16 @@ impl<'a> MeshBuilder<'a> {
         }
     }
 
+    fn signed_area(&self, a: usize, b: usize, c: usize) -> f32 {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            self.mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return 0.0;
+        };
+        let [a, b, c] = [a, b, c].map(|i| Vec2::new(positions[i][0], positions[i][1]));
+        (b - a).perp_dot(c - a)
+    }
+
     fn vertex_count(&self) -> usize {
         if let Some(VertexAttributeValues::Float32x3(positions)) =
             self.mesh.attribute(Mesh::ATTRIBUTE_POSITION)
@@ -77,9 +87,17 @@ impl<'a> GeometryBuilder for MeshBuilder<'a> {
     }
 
     fn add_triangle(&mut self, a: VertexId, b: VertexId, c: VertexId) {
-        self.push_index(a.to_usize() as u32);
-        self.push_index(b.to_usize() as u32);
-        self.push_index(c.to_usize() as u32);
+        let (a, b, c) = (a.to_usize(), b.to_usize(), c.to_usize());
+        // lyon doesn't promise a winding. The 2D projection flips y, so a triangle that is
+        // clockwise in these y-down coordinates lands counter-clockwise, front facing, on screen
+        let (b, c) = if self.signed_area(a, b, c) > 0.0 {
+            (c, b)
+        } else {
+            (b, c)
+        };
+        self.push_index(a as u32);
+        self.push_index(b as u32);
+        self.push_index(c as u32);
     }
 
     fn abort_geometry(&mut self) {
@@ -4,36 +4,46 @@ pub mod mesh_builder;
 pub mod primitive;
 pub mod transform;
 
+use std::time::Duration;
+
 use bevy::{
     camera::{primitives::Aabb, visibility::RenderLayers},
     ecs::system::SystemParam,
     math::{Affine2, Affine3A, Mat4, Vec3A, Vec4},
     pbr::gpu_instance_batch::GpuBatchedMesh3d,
+    platform::time::Instant,
     prelude::*,
     render::render_resource::BlendState,
 };
 use command::{
-    CommandBuffer, DrawCommand, ShapeMode, TextAlignH, TextAlignV, TextStyle, TextWrapMode,
+    CommandBuffer, DrawCommand, GradientDirection, ShapeMode, TextAlignH, TextAlignV, TextMode,
+    TextStyle, TextWrapMode,
 };
 use material::{MaterialKey, ProcessingExtendedMaterial};
 use primitive::{
-    ShapeBuilder, StrokeConfig, TessellationMode, VertexType, arc_fill, arc_stroke, bezier,
-    box_mesh, build_direct_fill, build_direct_stroke, build_polygon_fill, build_polygon_stroke,
-    capsule_mesh, cone_mesh, conical_frustum_mesh, curve, cylinder_mesh, ellipse, empty_mesh, line,
-    plane_mesh, quad, sphere_mesh, tetrahedron_mesh, torus_mesh, triangle,
+    ShapeBuilder, StrokeConfig, TessellationMode, VertexType, apply_shape_mode, arc_fill,
+    arc_stroke, bezier, box_mesh, build_direct_fill, build_direct_stroke, build_polygon_fill,
+    build_polygon_stroke, capsule_mesh, cone_mesh, conical_frustum_mesh, curve, cylinder_mesh,
+    ellipse, empty_mesh, line, line_3d, plane_mesh, point, point_3d, quad, radial_bounds,
+    shape_placement, sphere_mesh, tetrahedron_mesh, torus_mesh, triangle,
 };
 use transform::TransformStack;
 
 use crate::{
     Flush,
-    geometry::Geometry,
+    geometry::{
+        Geometry, GeometryLod,
+        lines::{GeometryLineWidth, expand_lines},
+    },
     gltf::GltfNodeTransform,
+    graphics::{FpsOverlay, FpsOverlayCamera, FrameTimings, SurfaceSize},
     image::Image,
-    material::ProcessingMaterial,
     material::custom::CustomMaterial,
+    material::{MissingMaterial, ProcessingMaterial, RequireMaterial},
     particles::{Particles, ParticlesDraw},
     render::{material::UntypedMaterial, primitive::rect},
     text::font::TextContext,
+    text::sdf::{SdfAtlas, SdfTextExtension, SdfTextMaterial},
 };
 
 pub(crate) const BATCH_INDEX_STEP: f32 = 0.001;
@@ -53,6 +63,9 @@ pub struct RenderResources<'w, 's> {
     materials: ResMut<'w, Assets<ProcessingExtendedMaterial>>,
     custom_materials: ResMut<'w, Assets<CustomMaterial>>,
     particles_materials: ResMut<'w, Assets<crate::particles::material::ParticlesMaterial>>,
+    sdf_materials: ResMut<'w, Assets<SdfTextMaterial>>,
+    sdf_atlas: ResMut<'w, SdfAtlas>,
+    images: ResMut<'w, Assets<bevy::image::Image>>,
     particle_buffers: Query<'w, 's, &'static crate::compute::Buffer>,
 }
 
@@ -63,6 +76,9 @@ struct BatchState {
     draw_index: u32,
     render_layers: RenderLayers,
     graphics_entity: Entity,
+    tessellation: Duration,
+    /// meshes spawned so far in this flush, which an opaque background discards
+    spawned: Vec<Entity>,
 }
 
 impl BatchState {
@@ -74,6 +90,8 @@ impl BatchState {
             draw_index: 0,
             render_layers,
             graphics_entity,
+            tessellation: Duration::ZERO,
+            spawned: Vec::new(),
         }
     }
 }
@@ -94,6 +112,7 @@ pub struct RenderState {
     pub image_mode: ShapeMode,
     pub rect_mode: ShapeMode,
     pub ellipse_mode: ShapeMode,
+    pub shape_mode: ShapeMode,
     pub shape_builder: Option<ShapeBuilder>,
     pub text_font_family: Option<String>,
     pub text_style: TextStyle,
@@ -106,6 +125,7 @@ pub struct RenderState {
     pub text_leading: Option<f32>,
     pub text_wrap: TextWrapMode,
     pub text_glyph_colors: Option<Vec<Color>>,
+    pub text_mode: TextMode,
 }
 
 impl RenderState {
@@ -128,6 +148,7 @@ impl RenderState {
             transform: TransformStack::new(),
             rect_mode: ShapeMode::Corner,
             ellipse_mode: ShapeMode::Center,
+            shape_mode: ShapeMode::Corner,
             shape_builder: None,
             text_font_family: None,
             text_style: TextStyle::Normal,
@@ -140,6 +161,7 @@ impl RenderState {
             text_leading: None,
             text_wrap: TextWrapMode::Word,
             text_glyph_colors: None,
+            text_mode: TextMode::Vector,
         }
     }
 
@@ -161,6 +183,7 @@ impl RenderState {
         self.transform = TransformStack::new();
         self.rect_mode = ShapeMode::Corner;
         self.ellipse_mode = ShapeMode::Center;
+        self.shape_mode = ShapeMode::Corner;
         self.shape_builder = None;
         self.text_font_family = None;
         self.text_style = TextStyle::Normal;
@@ -173,6 +196,7 @@ impl RenderState {
         self.text_leading = None;
         self.text_wrap = TextWrapMode::Word;
         self.text_glyph_colors = None;
+        self.text_mode = TextMode::Vector;
     }
 
     pub fn begin_frame(&mut self) {
@@ -205,26 +229,39 @@ pub fn flush_draw_commands(
             &RenderLayers,
             &Projection,
             &Transform,
+            &SurfaceSize,
+            Option<&mut FrameTimings>,
+            Option<&FpsOverlay>,
         ),
         With<Flush>,
     >,
     p_images: Query<&Image>,
-    p_geometries: Query<(&Geometry, Option<&GltfNodeTransform>)>,
+    p_geometries: GeometrySources,
     p_material_handles: Query<&UntypedMaterial>,
     mut p_particles: Query<&mut Particles>,
     p_fonts: Query<&crate::text::font::Font>,
     text_cx: Res<TextContext>,
 ) {
-    for (graphics_entity, mut cmd_buffer, mut state, render_layers, projection, camera_transform) in
-        graphics.iter_mut()
+    for (
+        graphics_entity,
+        mut cmd_buffer,
+        mut state,
+        render_layers,
+        projection,
+        camera_transform,
+        surface_size,
+        timings,
+        fps_overlay,
+    ) in graphics.iter_mut()
     {
+        let start = Instant::now();
         let clip_from_view = projection.get_clip_from_view();
         let view_from_world = camera_transform.to_matrix().inverse();
         let world_from_clip = (clip_from_view * view_from_world).inverse();
-        let draw_commands = std::mem::take(&mut cmd_buffer.commands);
+        let mut draw_commands = cmd_buffer.take();
         let mut batch = BatchState::new(graphics_entity, render_layers.clone());
 
-        for cmd in draw_commands {
+        for cmd in draw_commands.drain(..) {
             match cmd {
                 DrawCommand::Fill(color) => {
                     state.fill_color = Some(color);
@@ -253,6 +290,9 @@ pub fn flush_draw_commands(
                 DrawCommand::StrokeJoin(join) => {
                     state.stroke_config.line_join = join;
                 }
+                DrawCommand::StrokeAlign(align) => {
+                    state.stroke_config.align = align;
+                }
                 DrawCommand::Roughness(r) => {
                     let mut pbr = state.material_key.as_pbr();
                     pbr.roughness = (r * 255.0) as u8;
@@ -285,6 +325,9 @@ pub fn flush_draw_commands(
                 DrawCommand::EllipseMode(mode) => {
                     state.ellipse_mode = mode;
                 }
+                DrawCommand::ShapeMode(mode) => {
+                    state.shape_mode = mode;
+                }
                 DrawCommand::Rect { x, y, w, h, radii } => {
                     let (x, y, w, h) = apply_shape_mode(state.rect_mode, x, y, w, h);
                     let stroke_config = state.stroke_config;
@@ -329,11 +372,7 @@ pub fn flush_draw_commands(
                     );
                 }
                 DrawCommand::Ellipse { cx, cy, w, h } => {
-                    // apply_shape_mode converts to top-left corner form, then we
-                    // compute center from that
-                    let (x, y, w, h) = apply_shape_mode(state.ellipse_mode, cx, cy, w, h);
-                    let cx = x + w / 2.0;
-                    let cy = y + h / 2.0;
+                    let (cx, cy, w, h) = radial_bounds(state.ellipse_mode, cx, cy, w, h);
                     let stroke_config = state.stroke_config;
                     add_fill(
                         &mut res,
@@ -385,6 +424,32 @@ pub fn flush_draw_commands(
                         &p_material_handles,
                     );
                 }
+                DrawCommand::Line3d { start, end } => {
+                    let stroke_config = state.stroke_config;
+                    let toward_viewer = toward_viewer(
+                        camera_transform,
+                        projection,
+                        state.transform.current(),
+                        start.midpoint(end),
+                    );
+                    add_stroke(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        |mesh, color, weight| {
+                            line_3d(
+                                mesh,
+                                start,
+                                end,
+                                toward_viewer,
+                                color,
+                                weight,
+                                &stroke_config,
+                            )
+                        },
+                        &p_material_handles,
+                    );
+                }
                 DrawCommand::Triangle {
                     x1,
                     y1,
@@ -494,35 +559,46 @@ pub fn flush_draw_commands(
                     );
                 }
                 DrawCommand::Point { x, y } => {
-                    if let Some(color) = state.stroke_color {
-                        let d = state.stroke_weight;
-                        let material_key =
-                            material_key_with_color(&state.material_key, color, state.blend_state);
-
-                        if needs_batch(&batch, &state, &material_key) {
-                            start_batch(
-                                &mut res,
-                                &mut batch,
-                                &state,
-                                material_key,
-                                &p_material_handles,
-                            );
-                        }
-
-                        if let Some(ref mut mesh) = batch.current_mesh {
-                            let stroke_config = state.stroke_config;
-                            ellipse(
-                                mesh,
-                                x,
-                                y,
-                                d,
-                                d,
-                                color,
-                                TessellationMode::Fill,
-                                &stroke_config,
-                            );
-                        }
-                    }
+                    let stroke_config = state.stroke_config;
+                    add_stroke(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        |mesh, color, weight| point(mesh, x, y, color, weight, &stroke_config),
+                        &p_material_handles,
+                    );
+                }
+                DrawCommand::Point3d(position) => {
+                    let stroke_config = state.stroke_config;
+                    let toward_viewer = toward_viewer(
+                        camera_transform,
+                        projection,
+                        state.transform.current(),
+                        position,
+                    );
+                    add_stroke(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        |mesh, color, weight| {
+                            point_3d(mesh, position, toward_viewer, color, weight, &stroke_config)
+                        },
+                        &p_material_handles,
+                    );
+                }
+                DrawCommand::Points(points) => {
+                    let stroke_config = state.stroke_config;
+                    add_stroke(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        |mesh, color, weight| {
+                            for [x, y] in points {
+                                point(mesh, x, y, color, weight, &stroke_config);
+                            }
+                        },
+                        &p_material_handles,
+                    );
                 }
                 DrawCommand::Arc {
                     cx,
@@ -533,9 +609,7 @@ pub fn flush_draw_commands(
                     stop,
                     mode,
                 } => {
-                    let (x, y, w, h) = apply_shape_mode(state.ellipse_mode, cx, cy, w, h);
-                    let cx = x + w / 2.0;
-                    let cy = y + h / 2.0;
+                    let (cx, cy, w, h) = radial_bounds(state.ellipse_mode, cx, cy, w, h);
                     let stroke_config = state.stroke_config;
                     add_fill(
                         &mut res,
@@ -830,72 +904,87 @@ pub fn flush_draw_commands(
 
                     let uv_xform = match (sx, sy, s_width, s_height) {
                         (Some(sx), Some(sy), Some(sw), Some(sh)) => {
-                            Affine2::from_scale_angle_translation(
-                                Vec2::new(sw / img_w, sh / img_h),
-                                0.0,
-                                Vec2::new(sx / img_w, sy / img_h),
-                            )
+                            image_region_uv_transform(img_w, img_h, sx, sy, sw, sh)
                         }
                         _ => Affine2::IDENTITY,
                     };
 
                     let tint = state.tint_color.unwrap_or(Color::WHITE);
-                    let material_key = MaterialKey::Color {
-                        transparent: tint.alpha() < 1.0,
-                        background_image: Some(p_image.handle.clone()),
-                        uv_transform: uv_xform,
-                        blend_state: state.blend_state,
+                    draw_image_quad(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        p_image.handle.clone(),
+                        uv_xform,
+                        [x, y, w, h],
+                        tint,
+                        &p_material_handles,
+                    );
+                }
+                DrawCommand::DrawImageRegion {
+                    image,
+                    sx,
+                    sy,
+                    sw,
+                    sh,
+                    dx,
+                    dy,
+                    dw,
+                    dh,
+                } => {
+                    let Some(p_image) = p_images.get(image).ok() else {
+                        warn!("Could not find PImage for entity {:?}", image);
+                        continue;
                     };
-                    let stroke_config = state.stroke_config;
 
-                    flush_batch(&mut res, &mut batch, &p_material_handles);
-                    start_batch(
+                    let uv_xform = image_region_uv_transform(
+                        p_image.size.width as f32,
+                        p_image.size.height as f32,
+                        sx,
+                        sy,
+                        sw,
+                        sh,
+                    );
+                    draw_image_quad(
                         &mut res,
                         &mut batch,
                         &state,
-                        material_key,
+                        p_image.handle.clone(),
+                        uv_xform,
+                        [dx, dy, dw, dh],
+                        Color::WHITE,
                         &p_material_handles,
                     );
-
-                    if let Some(ref mut mesh) = batch.current_mesh {
-                        rect(
-                            mesh,
-                            x,
-                            y,
-                            w,
-                            h,
-                            [0.0; 4],
-                            tint,
-                            TessellationMode::Fill,
-                            &stroke_config,
-                        );
-                    }
-
-                    flush_batch(&mut res, &mut batch, &p_material_handles);
                 }
                 DrawCommand::BackgroundColor(color) => {
                     flush_batch(&mut res, &mut batch, &p_material_handles);
 
-                    let mesh = create_ndc_background_quad(world_from_clip, color, false);
-                    let mesh_handle = res.meshes.add(mesh);
-
+                    let mesh = create_ndc_background_quad(world_from_clip, [color; 4], false);
                     let material_key = MaterialKey::Color {
                         transparent: color.alpha() < 1.0,
                         background_image: None,
                         uv_transform: Affine2::IDENTITY,
                         blend_state: Some(BlendState::REPLACE),
                     };
-                    let material_handle = material_key.to_material(&mut res.materials);
-
-                    res.commands.spawn((
-                        Mesh3d(mesh_handle),
-                        UntypedMaterial(material_handle),
-                        BelongsToGraphics(batch.graphics_entity),
-                        Transform::IDENTITY,
-                        batch.render_layers.clone(),
-                    ));
+                    spawn_background(&mut res, &mut batch, mesh, material_key);
+                }
+                DrawCommand::BackgroundGradient {
+                    from,
+                    to,
+                    direction,
+                } => {
+                    flush_batch(&mut res, &mut batch, &p_material_handles);
 
-                    batch.draw_index += 1;
+                    // the vertex colors are interpolated across the quad, so no texture is needed
+                    let corners = gradient_corner_colors(from, to, direction);
+                    let mesh = create_ndc_background_quad(world_from_clip, corners, false);
+                    let material_key = MaterialKey::Color {
+                        transparent: from.alpha() < 1.0 || to.alpha() < 1.0,
+                        background_image: None,
+                        uv_transform: Affine2::IDENTITY,
+                        blend_state: Some(BlendState::REPLACE),
+                    };
+                    spawn_background(&mut res, &mut batch, mesh, material_key);
                 }
                 DrawCommand::BackgroundImage(entity) => {
                     let Some(p_image) = p_images.get(entity).ok() else {
@@ -905,92 +994,115 @@ pub fn flush_draw_commands(
 
                     flush_batch(&mut res, &mut batch, &p_material_handles);
 
-                    let mesh = create_ndc_background_quad(world_from_clip, Color::WHITE, true);
-                    let mesh_handle = res.meshes.add(mesh);
-
+                    let mesh = create_ndc_background_quad(world_from_clip, [Color::WHITE; 4], true);
                     let material_key = MaterialKey::Color {
                         transparent: false,
                         background_image: Some(p_image.handle.clone()),
                         uv_transform: Affine2::IDENTITY,
                         blend_state: Some(BlendState::REPLACE),
                     };
-                    let material_handle = material_key.to_material(&mut res.materials);
-
-                    res.commands.spawn((
-                        Mesh3d(mesh_handle),
-                        UntypedMaterial(material_handle),
-                        BelongsToGraphics(batch.graphics_entity),
-                        Transform::IDENTITY,
-                        batch.render_layers.clone(),
-                    ));
-
-                    batch.draw_index += 1;
+                    spawn_background(&mut res, &mut batch, mesh, material_key);
                 }
-                DrawCommand::PushMatrix => state.transform.push(),
-                DrawCommand::PopMatrix => state.transform.pop(),
-                DrawCommand::ResetMatrix => state.transform.reset(),
-                DrawCommand::Translate(v) => state.transform.translate(v.x, v.y),
-                DrawCommand::Rotate { angle } => state.transform.rotate(angle),
-                DrawCommand::RotateX { angle } => state.transform.rotate_x(angle),
-                DrawCommand::RotateY { angle } => state.transform.rotate_y(angle),
-                DrawCommand::RotateZ { angle } => state.transform.rotate_z(angle),
-                DrawCommand::Scale(v) => state.transform.scale(v.x, v.y),
-                DrawCommand::ShearX { angle } => state.transform.shear_x(angle),
-                DrawCommand::ShearY { angle } => state.transform.shear_y(angle),
-                DrawCommand::Geometry(entity) => {
-                    let Some((geometry, node_transform)) = p_geometries.get(entity).ok() else {
-                        warn!("Could not find Geometry for entity {:?}", entity);
+                DrawCommand::BackgroundTiled {
+                    image,
+                    tile_w,
+                    tile_h,
+                } => {
+                    let Some(p_image) = p_images.get(image).ok() else {
+                        warn!("Could not find PImage for entity {:?}", image);
                         continue;
                     };
 
-                    let material_key = material_key_with_fill(&state);
-                    let material_handle = match &material_key {
-                        MaterialKey::Custom {
-                            entity: mat_entity,
-                            blend_state,
-                        } => {
-                            let Some(untyped) = p_material_handles.get(*mat_entity).ok() else {
-                                warn!("Could not find material for entity {:?}", mat_entity);
-                                continue;
-                            };
-                            clone_custom_material_with_blend(
-                                &mut res.custom_materials,
-                                &untyped.0,
-                                *blend_state,
-                            )
-                        }
-                        _ => material_key.to_material(&mut res.materials),
-                    };
-
                     flush_batch(&mut res, &mut batch, &p_material_handles);
 
-                    let z_offset = -(batch.draw_index as f32 * BATCH_INDEX_STEP);
-                    let mut transform = state.transform.to_bevy_transform();
-
-                    // if the "source" geometry was parented in a gltf scene, we need to make sure that
-                    // we apply the parent transform here to ensure the correct final transform
-                    // TODO: think about how hierarchies should work, especially for retained
-                    if let Some(nt) = node_transform {
-                        transform =
-                            Transform::from_matrix(transform.to_matrix() * nt.0.to_matrix());
-                    }
-                    transform.translation.z += z_offset;
-
-                    res.commands.spawn((
-                        Mesh3d(geometry.handle.clone()),
-                        UntypedMaterial(material_handle),
-                        BelongsToGraphics(batch.graphics_entity),
-                        transform,
-                        batch.render_layers.clone(),
-                    ));
-
-                    batch.draw_index += 1;
+                    let surface = Vec2::new(surface_size.0 as f32, surface_size.1 as f32);
+                    let mesh = create_ndc_tiled_background(
+                        world_from_clip,
+                        surface,
+                        Vec2::new(tile_w, tile_h),
+                    );
+                    let material_key = MaterialKey::Color {
+                        transparent: false,
+                        background_image: Some(p_image.handle.clone()),
+                        uv_transform: Affine2::IDENTITY,
+                        blend_state: Some(BlendState::REPLACE),
+                    };
+                    spawn_background(&mut res, &mut batch, mesh, material_key);
+                }
+                DrawCommand::PushMatrix
+                | DrawCommand::PopMatrix
+                | DrawCommand::ResetMatrix
+                | DrawCommand::ApplyMatrix(_)
+                | DrawCommand::Translate(_)
+                | DrawCommand::Rotate { .. }
+                | DrawCommand::RotateX { .. }
+                | DrawCommand::RotateY { .. }
+                | DrawCommand::RotateZ { .. }
+                | DrawCommand::Scale(_)
+                | DrawCommand::ShearX { .. }
+                | DrawCommand::ShearY { .. } => {
+                    state.transform.apply_command(&cmd);
+                }
+                DrawCommand::Geometry(entity) => {
+                    let material_key = material_key_with_fill(&state);
+                    draw_geometry(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        camera_transform,
+                        projection,
+                        &p_geometries,
+                        &p_material_handles,
+                        entity,
+                        material_key,
+                        None,
+                    );
+                }
+                // a per-draw material only stands in for the current one while drawing that geometry
+                DrawCommand::GeometryWithMaterial { geometry, material } => {
+                    let material_key = material_key_with_override(&state, Some(material));
+                    draw_geometry(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        camera_transform,
+                        projection,
+                        &p_geometries,
+                        &p_material_handles,
+                        geometry,
+                        material_key,
+                        None,
+                    );
+                }
+                DrawCommand::Shape {
+                    geometry,
+                    x,
+                    y,
+                    w,
+                    h,
+                    material,
+                } => {
+                    let material_key = material_key_with_override(&state, material);
+                    let placement = shape_placement(state.shape_mode, x, y, w, h);
+                    draw_geometry(
+                        &mut res,
+                        &mut batch,
+                        &state,
+                        camera_transform,
+                        projection,
+                        &p_geometries,
+                        &p_material_handles,
+                        geometry,
+                        material_key,
+                        Some(placement),
+                    );
                 }
                 DrawCommand::Particles {
                     particles,
                     geometry,
                 } => {
-                    let Some((geometry_data, _)) = p_geometries.get(geometry).ok() else {
+                    let Some((geometry_data, _, _)) = p_geometries.geometries.get(geometry).ok()
+                    else {
                         warn!("Could not find Geometry for entity {:?}", geometry);
                         continue;
                     };
@@ -1242,9 +1354,75 @@ pub fn flush_draw_commands(
                 DrawCommand::TextWrap(mode) => {
                     state.text_wrap = mode;
                 }
+                DrawCommand::TextMode(mode) => {
+                    state.text_mode = mode;
+                }
                 DrawCommand::TextGlyphColors(colors) => {
                     state.text_glyph_colors = Some(colors);
                 }
+                DrawCommand::DebugText(content) => {
+                    let Some(fps_overlay) = fps_overlay else {
+                        warn!("No overlay camera for graphics {:?}", graphics_entity);
+                        continue;
+                    };
+                    flush_batch(&mut res, &mut batch, &p_material_handles);
+                    let sketch_layers = std::mem::replace(
+                        &mut batch.render_layers,
+                        fps_overlay.render_layers.clone(),
+                    );
+                    // a fresh state keeps the sketch's transform and styles out of the overlay
+                    let mut overlay = RenderState::default();
+                    let text_params =
+                        primitive::text::OwnedTextParams::from_render_state(&overlay, None, None);
+                    let text_cx = text_cx.clone();
+                    let line_height = overlay.text_size * 1.5;
+                    let lines = content.lines().count().max(1) as f32;
+
+                    overlay.fill_color = Some(Color::srgba(0.0, 0.0, 0.0, 0.6));
+                    add_fill(
+                        &mut res,
+                        &mut batch,
+                        &overlay,
+                        |mesh, color| {
+                            rect(
+                                mesh,
+                                0.0,
+                                0.0,
+                                160.0,
+                                lines * line_height + 8.0,
+                                [0.0; 4],
+                                color,
+                                TessellationMode::Fill,
+                                &overlay.stroke_config,
+                            )
+                        },
+                        &p_material_handles,
+                    );
+
+                    overlay.fill_color = Some(Color::WHITE);
+                    for (i, text_line) in content.lines().enumerate() {
+                        let y = 4.0 + overlay.text_size + i as f32 * line_height;
+                        add_fill(
+                            &mut res,
+                            &mut batch,
+                            &overlay,
+                            |mesh, color| {
+                                primitive::text::text(
+                                    mesh,
+                                    text_line,
+                                    4.0,
+                                    y,
+                                    color,
+                                    &text_params.as_params(),
+                                    &text_cx,
+                                );
+                            },
+                            &p_material_handles,
+                        );
+                    }
+                    flush_batch(&mut res, &mut batch, &p_material_handles);
+                    batch.render_layers = sketch_layers;
+                }
                 DrawCommand::Text {
                     content,
                     x,
@@ -1271,23 +1449,45 @@ pub fn flush_draw_commands(
                         state.transform.translate_3d(0.0, 0.0, z);
                     }
 
-                    add_fill(
-                        &mut res,
-                        &mut batch,
-                        &state,
-                        |mesh, color| {
-                            primitive::text::text(
-                                mesh,
-                                &content,
-                                x,
-                                y,
-                                color,
-                                &text_params.as_params(),
-                                &text_cx,
-                            );
-                        },
-                        &p_material_handles,
-                    );
+                    if state.text_mode == TextMode::Sdf {
+                        add_sdf_text_fill(
+                            &mut res,
+                            &mut batch,
+                            &state,
+                            |mesh, color, atlas, images| {
+                                primitive::text::text_sdf(
+                                    mesh,
+                                    &content,
+                                    x,
+                                    y,
+                                    color,
+                                    &text_params.as_params(),
+                                    &text_cx,
+                                    atlas,
+                                    images,
+                                );
+                            },
+                            &p_material_handles,
+                        );
+                    } else {
+                        add_fill(
+                            &mut res,
+                            &mut batch,
+                            &state,
+                            |mesh, color| {
+                                primitive::text::text(
+                                    mesh,
+                                    &content,
+                                    x,
+                                    y,
+                                    color,
+                                    &text_params.as_params(),
+                                    &text_cx,
+                                );
+                            },
+                            &p_material_handles,
+                        );
+                    }
 
                     add_stroke(
                         &mut res,
@@ -1312,12 +1512,21 @@ pub fn flush_draw_commands(
         }
 
         flush_batch(&mut res, &mut batch, &p_material_handles);
+        cmd_buffer.recycle(draw_commands);
+
+        if let Some(mut timings) = timings {
+            timings.command_flush = start.elapsed();
+            timings.tessellation = batch.tessellation;
+        }
     }
 }
 
-pub fn activate_cameras(mut cameras: Query<(&mut Camera, Option<&Flush>)>) {
-    for (mut camera, flush) in cameras.iter_mut() {
-        let active = flush.is_some();
+pub fn activate_cameras(
+    mut cameras: Query<(&mut Camera, Option<&Flush>, Option<&FpsOverlayCamera>)>,
+    flushing: Query<(), With<Flush>>,
+) {
+    for (mut camera, flush, overlay) in cameras.iter_mut() {
+        let active = flush.is_some() || overlay.is_some_and(|overlay| flushing.contains(overlay.0));
         camera.is_active = active;
     }
 }
@@ -1364,16 +1573,34 @@ fn spawn_mesh(
             };
             clone_custom_material_with_blend(&mut res.custom_materials, &untyped.0, *blend_state)
         }
+        MaterialKey::SdfText { atlas } => res
+            .sdf_materials
+            .add(SdfTextMaterial {
+                base: StandardMaterial {
+                    unlit: true,
+                    cull_mode: None,
+                    alpha_mode: AlphaMode::Blend,
+                    ..default()
+                },
+                extension: SdfTextExtension {
+                    atlas: atlas.clone(),
+                },
+            })
+            .untyped(),
         _ => key.to_material(&mut res.materials),
     };
 
-    res.commands.spawn((
-        Mesh3d(mesh_handle),
-        UntypedMaterial(material_handle),
-        BelongsToGraphics(batch.graphics_entity),
-        transform,
-        batch.render_layers.clone(),
-    ));
+    let spawned = res
+        .commands
+        .spawn((
+            Mesh3d(mesh_handle),
+            UntypedMaterial(material_handle),
+            BelongsToGraphics(batch.graphics_entity),
+            transform,
+            batch.render_layers.clone(),
+        ))
+        .id();
+    batch.spawned.push(spawned);
 }
 
 fn needs_batch(batch: &BatchState, state: &RenderState, material_key: &MaterialKey) -> bool {
@@ -1396,13 +1623,59 @@ fn start_batch(
     batch.current_mesh = Some(empty_mesh());
 }
 
-fn apply_shape_mode(mode: ShapeMode, a: f32, b: f32, c: f32, d: f32) -> (f32, f32, f32, f32) {
-    match mode {
-        ShapeMode::Corner => (a, b, c, d),
-        ShapeMode::Corners => (a, b, c - a, d - b),
-        ShapeMode::Center => (a - c / 2.0, b - d / 2.0, c, d),
-        ShapeMode::Radius => (a - c, b - d, c * 2.0, d * 2.0),
+/// UV transform selecting the `sw` x `sh` pixel region at `sx`, `sy` of an `img_w` x `img_h` image.
+fn image_region_uv_transform(
+    img_w: f32,
+    img_h: f32,
+    sx: f32,
+    sy: f32,
+    sw: f32,
+    sh: f32,
+) -> Affine2 {
+    Affine2::from_scale_angle_translation(
+        Vec2::new(sw / img_w, sh / img_h),
+        0.0,
+        Vec2::new(sx / img_w, sy / img_h),
+    )
+}
+
+/// Draw `image` as a textured quad over `rect` (`[x, y, w, h]`) in its own batch.
+#[allow(clippy::too_many_arguments)]
+fn draw_image_quad(
+    res: &mut RenderResources,
+    batch: &mut BatchState,
+    state: &RenderState,
+    image: Handle<bevy::image::Image>,
+    uv_transform: Affine2,
+    [x, y, w, h]: [f32; 4],
+    tint: Color,
+    material_handles: &Query<&UntypedMaterial>,
+) {
+    let material_key = MaterialKey::Color {
+        transparent: tint.alpha() < 1.0,
+        background_image: Some(image),
+        uv_transform,
+        blend_state: state.blend_state,
+    };
+
+    flush_batch(res, batch, material_handles);
+    start_batch(res, batch, state, material_key, material_handles);
+
+    if let Some(ref mut mesh) = batch.current_mesh {
+        rect(
+            mesh,
+            x,
+            y,
+            w,
+            h,
+            [0.0; 4],
+            tint,
+            TessellationMode::Fill,
+            &state.stroke_config,
+        );
     }
+
+    flush_batch(res, batch, material_handles);
 }
 
 fn clone_custom_material_with_blend(
@@ -1452,6 +1725,9 @@ fn material_key_with_color(
             entity: *entity,
             blend_state,
         },
+        MaterialKey::SdfText { atlas } => MaterialKey::SdfText {
+            atlas: atlas.clone(),
+        },
     }
 }
 
@@ -1478,6 +1754,18 @@ fn particles_fill_material(
     Some(handle.untyped())
 }
 
+/// The key for a geometry drawn with `material` in place of the current one, or with the current
+/// fill when there's no override.
+fn material_key_with_override(state: &RenderState, material: Option<Entity>) -> MaterialKey {
+    match material {
+        Some(entity) => MaterialKey::Custom {
+            entity,
+            blend_state: state.blend_state,
+        },
+        None => material_key_with_fill(state),
+    }
+}
+
 fn material_key_with_fill(state: &RenderState) -> MaterialKey {
     let color = state.fill_color.unwrap_or(Color::WHITE);
     material_key_with_color(&state.material_key, color, state.blend_state)
@@ -1500,7 +1788,34 @@ fn add_fill(
     }
 
     if let Some(ref mut mesh) = batch.current_mesh {
-        tessellate(mesh, color);
+        timed(&mut batch.tessellation, || tessellate(mesh, color));
+    }
+}
+
+/// Like [`add_fill`], but batched under the SDF text material, which samples the glyph atlas.
+/// Strokes aren't affected by the text mode and are always tessellated.
+fn add_sdf_text_fill(
+    res: &mut RenderResources,
+    batch: &mut BatchState,
+    state: &RenderState,
+    build: impl FnOnce(&mut Mesh, Color, &mut SdfAtlas, &mut Assets<bevy::image::Image>),
+    material_handles: &Query<&UntypedMaterial>,
+) {
+    let Some(color) = state.fill_color else {
+        return;
+    };
+    let material_key = MaterialKey::SdfText {
+        atlas: res.sdf_atlas.image.clone(),
+    };
+
+    if needs_batch(batch, state, &material_key) {
+        start_batch(res, batch, state, material_key, material_handles);
+    }
+
+    if let Some(ref mut mesh) = batch.current_mesh {
+        timed(&mut batch.tessellation, || {
+            build(mesh, color, &mut res.sdf_atlas, &mut res.images)
+        });
     }
 }
 
@@ -1522,8 +1837,71 @@ fn add_stroke(
     }
 
     if let Some(ref mut mesh) = batch.current_mesh {
-        tessellate(mesh, color, stroke_weight);
+        timed(&mut batch.tessellation, || {
+            tessellate(mesh, color, stroke_weight)
+        });
+    }
+}
+
+/// Direction from `point`, in the model space of `model`, toward the camera. Orthographic cameras
+/// look the same way everywhere, so only perspective ones depend on where the point is.
+fn toward_viewer(
+    camera: &Transform,
+    projection: &Projection,
+    model: Affine3A,
+    point: Vec3,
+) -> Vec3 {
+    let direction = match projection {
+        Projection::Orthographic(_) => camera.back().as_vec3(),
+        _ => camera.translation - model.transform_point3(point),
+    };
+    model.inverse().transform_vector3(direction)
+}
+
+/// Run `f`, adding the time it took to `elapsed`.
+fn timed<T>(elapsed: &mut Duration, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    *elapsed += start.elapsed();
+    result
+}
+
+/// Draw a full screen background quad. Like Processing's `background()`, an opaque background
+/// also resets depth: the quad sits on the far plane, so meshes spawned earlier in this flush would
+/// otherwise show through it, and since it covers them they're discarded instead. Translucent
+/// backgrounds blend over what's already drawn and leave it in place.
+fn spawn_background(
+    res: &mut RenderResources,
+    batch: &mut BatchState,
+    mesh: Mesh,
+    material_key: MaterialKey,
+) {
+    if matches!(
+        material_key,
+        MaterialKey::Color {
+            transparent: false,
+            ..
+        }
+    ) {
+        for entity in batch.spawned.drain(..) {
+            res.commands.entity(entity).despawn();
+        }
     }
+
+    let mesh_handle = res.meshes.add(mesh);
+    let material_handle = material_key.to_material(&mut res.materials);
+    let spawned = res
+        .commands
+        .spawn((
+            Mesh3d(mesh_handle),
+            UntypedMaterial(material_handle),
+            BelongsToGraphics(batch.graphics_entity),
+            Transform::IDENTITY,
+            batch.render_layers.clone(),
+        ))
+        .id();
+    batch.spawned.push(spawned);
+    batch.draw_index += 1;
 }
 
 fn flush_batch(
@@ -1539,6 +1917,132 @@ fn flush_batch(
     batch.material_key = None;
 }
 
+/// Queries and settings used to draw a geometry.
+#[derive(SystemParam)]
+pub struct GeometrySources<'w, 's> {
+    geometries: Query<
+        'w,
+        's,
+        (
+            &'static Geometry,
+            Option<&'static GltfNodeTransform>,
+            Option<&'static GeometryLod>,
+        ),
+    >,
+    line_widths: Query<'w, 's, &'static GeometryLineWidth>,
+    require_material: Res<'w, RequireMaterial>,
+    missing_material: Res<'w, MissingMaterial>,
+}
+
+/// Spawn `entity`'s geometry with `material_key`, at the current transform followed by
+/// `placement` when the draw stretches it over a rect.
+#[allow(clippy::too_many_arguments)]
+fn draw_geometry(
+    res: &mut RenderResources,
+    batch: &mut BatchState,
+    state: &RenderState,
+    camera_transform: &Transform,
+    projection: &Projection,
+    p_geometries: &GeometrySources,
+    p_material_handles: &Query<&UntypedMaterial>,
+    entity: Entity,
+    material_key: MaterialKey,
+    placement: Option<Mat4>,
+) {
+    let Some((geometry, node_transform, lod)) = p_geometries.geometries.get(entity).ok() else {
+        warn!("Could not find Geometry for entity {:?}", entity);
+        return;
+    };
+
+    let material_key = if res
+        .meshes
+        .get(&geometry.handle)
+        .is_some_and(|mesh| geometry.has_translucent_colors(mesh))
+    {
+        material_key.translucent()
+    } else {
+        material_key
+    };
+    let material_handle = match &material_key {
+        MaterialKey::Custom {
+            entity: mat_entity,
+            blend_state,
+        } => {
+            let Some(untyped) = p_material_handles.get(*mat_entity).ok() else {
+                warn!("Could not find material for entity {:?}", mat_entity);
+                return;
+            };
+            clone_custom_material_with_blend(&mut res.custom_materials, &untyped.0, *blend_state)
+        }
+        MaterialKey::Color { .. } if p_geometries.require_material.0 => {
+            warn_once!("Geometry drawn without a material is shown in magenta");
+            p_geometries.missing_material.0.clone()
+        }
+        _ => material_key.to_material(&mut res.materials),
+    };
+
+    flush_batch(res, batch, p_material_handles);
+
+    let z_offset = -(batch.draw_index as f32 * BATCH_INDEX_STEP);
+    let mut transform = state.transform.to_bevy_transform();
+    if let Some(placement) = placement {
+        transform = Transform::from_matrix(transform.to_matrix() * placement);
+    }
+
+    // if the "source" geometry was parented in a gltf scene, we need to make sure that
+    // we apply the parent transform here to ensure the correct final transform
+    // TODO: think about how hierarchies should work, especially for retained
+    if let Some(nt) = node_transform {
+        transform = Transform::from_matrix(transform.to_matrix() * nt.0.to_matrix());
+    }
+
+    // swap in a lower detail geometry based on the camera's distance to its center
+    let geometry = match lod {
+        Some(lod) => {
+            let level = lod.select_for_draw(
+                entity,
+                geometry,
+                res.meshes.get(&geometry.handle),
+                &transform,
+                camera_transform.translation,
+            );
+            p_geometries
+                .geometries
+                .get(level)
+                .map_or(geometry, |(level, _, _)| level)
+        }
+        None => geometry,
+    };
+    transform.translation.z += z_offset;
+
+    // wide lines are rebuilt as quads facing the camera, found in the
+    // geometry's own space
+    let mut mesh_handle = geometry.handle.clone();
+    if let Ok(GeometryLineWidth(width)) = p_geometries.line_widths.get(entity)
+        && let Some(mesh) = res.meshes.get(&geometry.handle)
+    {
+        let model = transform.compute_affine();
+        let facing = |midpoint| toward_viewer(camera_transform, projection, model, midpoint);
+        if let Some(quads) = expand_lines(mesh, *width, facing) {
+            mesh_handle = res.meshes.add(quads);
+        }
+    }
+
+    let spawned = res
+        .commands
+        .spawn((
+            Mesh3d(mesh_handle),
+            UntypedMaterial(material_handle),
+            BelongsToGraphics(batch.graphics_entity),
+            transform,
+            batch.render_layers.clone(),
+        ))
+        .id();
+    batch.spawned.push(spawned);
+
+    batch.draw_index += 1;
+}
+
 fn add_shape3d(
     res: &mut RenderResources,
     batch: &mut BatchState,
@@ -1583,6 +2087,7 @@ fn add_shape3d(
                 base,
                 extension: ProcessingMaterial {
                     blend_state: state.blend_state,
+                    ..default()
                 },
             };
             res.materials.add(extended).untyped()
@@ -1618,12 +2123,27 @@ fn add_shape3d(
         ));
     }
 
+    batch.spawned.push(entity.id());
     batch.draw_index += 1;
 }
 
+/// Corner colors of a background gradient, ordered bottom left, bottom right, top right, top left
+/// like the corners of [`create_ndc_background_quad`].
+fn gradient_corner_colors(from: Color, to: Color, direction: GradientDirection) -> [Color; 4] {
+    match direction {
+        GradientDirection::Vertical => [to, to, from, from],
+        GradientDirection::Horizontal => [from, to, to, from],
+        GradientDirection::Diagonal => {
+            let mid = Color::from((from.to_linear() + to.to_linear()) * 0.5);
+            [mid, to, mid, from]
+        }
+    }
+}
+
 /// fullscreen quad built by transforming NDC corners by the inverse clip-from-world matrix,
-/// so the vertex shader's `clip_from_world` brings them back to NDC.
-fn create_ndc_background_quad(world_from_clip: Mat4, color: Color, with_uvs: bool) -> Mesh {
+/// so the vertex shader's `clip_from_world` brings them back to NDC. `colors` are per corner in
+/// bl, br, tr, tl order.
+fn create_ndc_background_quad(world_from_clip: Mat4, colors: [Color; 4], with_uvs: bool) -> Mesh {
     use bevy::asset::RenderAssetUsages;
     use bevy::mesh::{Indices, PrimitiveTopology};
 
@@ -1650,8 +2170,10 @@ fn create_ndc_background_quad(world_from_clip: Mat4, color: Color, with_uvs: boo
         [0.0, 0.0], // tl
     ];
 
-    let color_array: [f32; 4] = color.to_linear().to_f32_array();
-    let colors: Vec<[f32; 4]> = vec![color_array; 4];
+    let colors: Vec<[f32; 4]> = colors
+        .iter()
+        .map(|c| c.to_linear().to_f32_array())
+        .collect();
 
     let indices: Vec<u32> = vec![0, 1, 2, 0, 2, 3];
 
@@ -1669,3 +2191,141 @@ fn create_ndc_background_quad(world_from_clip: Mat4, color: Color, with_uvs: boo
 
     mesh
 }
+
+/// A background covering `surface` pixels with one quad per `tile`, each showing the whole image
+/// (or the part of it that fits on the last row and column). Drawing the tiles as separate quads
+/// means the image's own sampler never has to wrap.
+fn create_ndc_tiled_background(world_from_clip: Mat4, surface: Vec2, tile: Vec2) -> Mesh {
+    use bevy::asset::RenderAssetUsages;
+    use bevy::mesh::{Indices, PrimitiveTopology};
+
+    let to_world = |px: Vec2| {
+        let ndc = Vec4::new(
+            px.x / surface.x * 2.0 - 1.0,
+            1.0 - px.y / surface.y * 2.0,
+            f32::EPSILON,
+            1.0,
+        );
+        let world = world_from_clip * ndc;
+        [world.x / world.w, world.y / world.w, world.z / world.w]
+    };
+
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+    let mut y = 0.0;
+    while y < surface.y {
+        let bottom = (y + tile.y).min(surface.y);
+        let mut x = 0.0;
+        while x < surface.x {
+            let right = (x + tile.x).min(surface.x);
+            let uv = Vec2::new((right - x) / tile.x, (bottom - y) / tile.y);
+            let base = positions.len() as u32;
+            positions.extend([
+                to_world(Vec2::new(x, bottom)),
+                to_world(Vec2::new(right, bottom)),
+                to_world(Vec2::new(right, y)),
+                to_world(Vec2::new(x, y)),
+            ]);
+            uvs.extend([[0.0, uv.y], [uv.x, uv.y], [uv.x, 0.0], [0.0, 0.0]]);
+            indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+            x = right;
+        }
+        y = bottom;
+    }
+
+    let colors = vec![Color::WHITE.to_linear().to_f32_array(); positions.len()];
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_lines_face_an_orthographic_camera_from_anywhere() {
+        use bevy::render::render_resource::PrimitiveTopology;
+        use bevy::{asset::RenderAssetUsages, mesh::VertexAttributeValues};
+
+        let mesh = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![[0.0, 0.0, 0.0], [0.0, 10.0, 0.0]],
+            );
+        // off to the side of the line, but an orthographic camera looks straight down -z everywhere
+        let camera = Transform::from_xyz(50.0, 0.0, 100.0);
+        let projection = Projection::Orthographic(OrthographicProjection::default_3d());
+        let quads = expand_lines(&mesh, 4.0, |midpoint| {
+            toward_viewer(&camera, &projection, Affine3A::IDENTITY, midpoint)
+        })
+        .unwrap();
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            quads.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("expanded lines have no positions");
+        };
+        assert!(positions.iter().all(|p| p[2].abs() < 1e-5));
+        assert!(positions.iter().any(|p| (p[0] - 2.0).abs() < 1e-5));
+    }
+
+    #[test]
+    fn left_half_region_samples_left_half() {
+        let xform = image_region_uv_transform(64.0, 32.0, 0.0, 0.0, 32.0, 32.0);
+
+        // the quad's uv corners land on the left half of the image
+        assert_eq!(xform.transform_point2(Vec2::ZERO), Vec2::ZERO);
+        assert_eq!(xform.transform_point2(Vec2::ONE), Vec2::new(0.5, 1.0));
+        for u in [0.0, 0.25, 0.75, 1.0] {
+            assert!(xform.transform_point2(Vec2::new(u, 0.5)).x <= 0.5);
+        }
+    }
+
+    #[test]
+    fn vertical_gradient_runs_top_to_bottom() {
+        let red = Color::srgb(1.0, 0.0, 0.0);
+        let blue = Color::srgb(0.0, 0.0, 1.0);
+        let [bl, br, tr, tl] = gradient_corner_colors(red, blue, GradientDirection::Vertical);
+
+        assert_eq!([tl, tr], [red, red]);
+        assert_eq!([bl, br], [blue, blue]);
+
+        let [bl, br, tr, tl] = gradient_corner_colors(red, blue, GradientDirection::Diagonal);
+        assert_eq!((tl, br), (red, blue));
+        assert_eq!(bl, tr);
+    }
+
+    #[test]
+    fn timed_tessellation_accumulates() {
+        let mut mesh = empty_mesh();
+        let mut elapsed = Duration::ZERO;
+        for _ in 0..2 {
+            timed(&mut elapsed, || {
+                ellipse(
+                    &mut mesh,
+                    50.0,
+                    50.0,
+                    80.0,
+                    80.0,
+                    Color::WHITE,
+                    TessellationMode::Fill,
+                    &StrokeConfig::default(),
+                )
+            });
+        }
+        let first = elapsed;
+        timed(&mut elapsed, || ());
+
+        assert!(first > Duration::ZERO);
+        assert!(elapsed >= first);
+        assert!(mesh.count_vertices() > 0);
+    }
+}
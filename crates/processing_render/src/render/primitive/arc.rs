@@ -14,22 +14,22 @@ fn arc_path(
     mode: ArcMode,
     for_fill: bool,
 ) -> Path {
+    // reversed angles describe the same arc
+    let (start, stop) = if start > stop {
+        (stop, start)
+    } else {
+        (start, stop)
+    };
     let rx = w / 2.0;
     let ry = h / 2.0;
-    let angle_range = stop - start;
-
-    // adaptive segment count based on arc size and angle
-    let circumference =
-        std::f32::consts::PI * (3.0 * (rx + ry) - ((3.0 * rx + ry) * (rx + 3.0 * ry)).sqrt());
-    let arc_length = circumference * (angle_range.abs() / (2.0 * std::f32::consts::PI));
-    let num_segments = (arc_length / 2.0).clamp(16.0, 256.0) as u32;
 
     let mut builder = Path::builder();
 
     let first_point = Point::new(cx + rx * start.cos(), cy + ry * start.sin());
 
+    // fill and stroke share this outline; only open arcs leave the stroke unclosed
     match mode {
-        ArcMode::Pie if for_fill => {
+        ArcMode::Pie => {
             builder.begin(Point::new(cx, cy));
             builder.line_to(first_point);
         }
@@ -38,10 +38,8 @@ fn arc_path(
         }
     }
 
-    for i in 1..=num_segments {
-        let t = i as f32 / num_segments as f32;
-        let angle = start + angle_range * t;
-        builder.line_to(Point::new(cx + rx * angle.cos(), cy + ry * angle.sin()));
+    for point in arc_points(cx, cy, rx, ry, start, stop).skip(1) {
+        builder.line_to(Point::new(point.x, point.y));
     }
 
     let should_close = match mode {
@@ -54,6 +52,30 @@ fn arc_path(
     builder.build()
 }
 
+/// Points along the ellipse around (`cx`, `cy`) with radii `rx` and `ry`, from angle `start` to
+/// `stop` inclusive, in as many segments as the arc's length needs to look smooth.
+pub fn arc_points(
+    cx: f32,
+    cy: f32,
+    rx: f32,
+    ry: f32,
+    start: f32,
+    stop: f32,
+) -> impl Iterator<Item = Vec2> {
+    let angle_range = stop - start;
+
+    // adaptive segment count based on arc size and angle
+    let circumference =
+        std::f32::consts::PI * (3.0 * (rx + ry) - ((3.0 * rx + ry) * (rx + 3.0 * ry)).sqrt());
+    let arc_length = circumference * (angle_range.abs() / (2.0 * std::f32::consts::PI));
+    let num_segments = (arc_length / 2.0).clamp(16.0, 256.0) as u32;
+
+    (0..=num_segments).map(move |i| {
+        let angle = start + angle_range * (i as f32 / num_segments as f32);
+        Vec2::new(cx + rx * angle.cos(), cy + ry * angle.sin())
+    })
+}
+
 pub fn arc_fill(
     mesh: &mut Mesh,
     cx: f32,
@@ -70,3 +70,42 @@ pub fn curve(
         stroke_config,
     );
 }
+
+/// Evaluate one coordinate of a cubic bezier with anchors `a`, `d` and controls `b`, `c` at `t`.
+pub fn bezier_point(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+    let mt = 1.0 - t;
+    mt * mt * mt * a + 3.0 * mt * mt * t * b + 3.0 * mt * t * t * c + t * t * t * d
+}
+
+/// The derivative of [`bezier_point`] at `t`.
+pub fn bezier_tangent(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+    let mt = 1.0 - t;
+    3.0 * mt * mt * (b - a) + 6.0 * mt * t * (c - b) + 3.0 * t * t * (d - c)
+}
+
+/// Evaluate one coordinate of the Catmull-Rom segment between `b` and `c` at `t`, with `a` and
+/// `d` as the outer control points like [`curve`].
+pub fn curve_point(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+    bezier_point(b, b + (c - a) / 6.0, c - (d - b) / 6.0, c, t)
+}
+
+/// The derivative of [`curve_point`] at `t`.
+pub fn curve_tangent(a: f32, b: f32, c: f32, d: f32, t: f32) -> f32 {
+    bezier_tangent(b, b + (c - a) / 6.0, c - (d - b) / 6.0, c, t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bezier_point_hits_anchors_and_midpoint() {
+        assert_eq!(bezier_point(0.0, 10.0, 20.0, 30.0, 0.0), 0.0);
+        assert_eq!(bezier_point(0.0, 10.0, 20.0, 30.0, 1.0), 30.0);
+        // evenly spaced controls make a straight line at constant speed
+        assert!((bezier_point(0.0, 10.0, 20.0, 30.0, 0.5) - 15.0).abs() < 1e-5);
+        assert!((bezier_tangent(0.0, 10.0, 20.0, 30.0, 0.5) - 30.0).abs() < 1e-5);
+        assert_eq!(curve_point(0.0, 10.0, 20.0, 30.0, 0.0), 10.0);
+        assert_eq!(curve_point(0.0, 10.0, 20.0, 30.0, 1.0), 20.0);
+    }
+}
@@ -1,7 +1,13 @@
-use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use bevy::{
+    mesh::{Indices, VertexAttributeValues},
+    prelude::*,
+};
 use lyon::{geom::Point, path::Path};
 
-use crate::render::primitive::{StrokeConfig, TessellationMode, tessellate_path};
+use crate::render::command::StrokeCapMode;
+use crate::render::primitive::{StrokeConfig, TessellationMode, ellipse, tessellate_path};
 
 pub fn line(
     mesh: &mut Mesh,
@@ -13,6 +19,12 @@ pub fn line(
     weight: f32,
     stroke_config: &StrokeConfig,
 ) {
+    // a zero-length line has no direction to stroke along, so draw its cap as a dot
+    if x1 == x2 && y1 == y2 {
+        point(mesh, x1, y1, color, weight, stroke_config);
+        return;
+    }
+
     let mut builder = Path::builder();
     builder.begin(Point::new(x1, y1));
     builder.line_to(Point::new(x2, y2));
@@ -26,3 +38,138 @@ pub fn line(
         stroke_config,
     );
 }
+
+/// A dot `weight` across, shaped by the stroke cap: round caps draw a circle, others a square.
+pub fn point(
+    mesh: &mut Mesh,
+    x: f32,
+    y: f32,
+    color: Color,
+    weight: f32,
+    stroke_config: &StrokeConfig,
+) {
+    if stroke_config.line_cap == StrokeCapMode::Round {
+        ellipse(
+            mesh,
+            x,
+            y,
+            weight,
+            weight,
+            color,
+            TessellationMode::Fill,
+            stroke_config,
+        );
+        return;
+    }
+
+    let r = weight / 2.0;
+    let mut builder = Path::builder();
+    builder.begin(Point::new(x - r, y - r));
+    builder.line_to(Point::new(x + r, y - r));
+    builder.line_to(Point::new(x + r, y + r));
+    builder.line_to(Point::new(x - r, y + r));
+    builder.end(true);
+    let path = builder.build();
+    tessellate_path(mesh, &path, color, TessellationMode::Fill, stroke_config);
+}
+
+/// A line between two points in 3d, stroked as a ribbon `weight` wide. The ribbon is built in
+/// world space and turned around the line to face `toward_viewer`, the direction from the line
+/// to the camera.
+pub fn line_3d(
+    mesh: &mut Mesh,
+    start: Vec3,
+    end: Vec3,
+    toward_viewer: Vec3,
+    color: Color,
+    weight: f32,
+    stroke_config: &StrokeConfig,
+) {
+    let direction = (end - start).normalize_or_zero();
+    if direction == Vec3::ZERO {
+        point_3d(mesh, start, toward_viewer, color, weight, stroke_config);
+        return;
+    }
+
+    // looking straight down the line leaves no side to widen it along, so pick any
+    let side = direction
+        .cross(toward_viewer)
+        .try_normalize()
+        .unwrap_or_else(|| direction.any_orthonormal_vector())
+        * (weight / 2.0);
+    // project and round caps reach past the ends by half the weight; round ones are squared off
+    let reach = match stroke_config.line_cap {
+        StrokeCapMode::Square => Vec3::ZERO,
+        _ => direction * (weight / 2.0),
+    };
+    let (start, end) = (start - reach, end + reach);
+    let normal = side.cross(direction).normalize();
+    push_facing_polygon(
+        mesh,
+        &[start + side, end + side, end - side, start - side],
+        normal,
+        color,
+    );
+}
+
+/// A dot `weight` across at `position`, facing along `toward_viewer`. Round caps draw a circle,
+/// others a square.
+pub fn point_3d(
+    mesh: &mut Mesh,
+    position: Vec3,
+    toward_viewer: Vec3,
+    color: Color,
+    weight: f32,
+    stroke_config: &StrokeConfig,
+) {
+    let normal = toward_viewer.try_normalize().unwrap_or(Vec3::Z);
+    let right = normal.any_orthonormal_vector();
+    let up = normal.cross(right);
+    let r = weight / 2.0;
+
+    let corners: Vec<Vec3> = if stroke_config.line_cap == StrokeCapMode::Round {
+        const SEGMENTS: usize = 16;
+        (0..SEGMENTS)
+            .map(|i| {
+                let (sin, cos) = (i as f32 / SEGMENTS as f32 * TAU).sin_cos();
+                position + (right * cos + up * sin) * r
+            })
+            .collect()
+    } else {
+        [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .iter()
+            .map(|(x, y)| position + (right * *x + up * *y) * r)
+            .collect()
+    };
+    push_facing_polygon(mesh, &corners, normal, color);
+}
+
+/// Fan-triangulate the convex polygon `corners`, given counter-clockwise around `normal`.
+fn push_facing_polygon(mesh: &mut Mesh, corners: &[Vec3], normal: Vec3, color: Color) {
+    let base = mesh.count_vertices() as u32;
+
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        positions.extend(corners.iter().map(|c| c.to_array()));
+    }
+    if let Some(VertexAttributeValues::Float32x4(colors)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR)
+    {
+        let color = color.to_linear().to_f32_array();
+        colors.extend(corners.iter().map(|_| color));
+    }
+    if let Some(VertexAttributeValues::Float32x3(normals)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+    {
+        normals.extend(corners.iter().map(|_| normal.to_array()));
+    }
+    if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0) {
+        uvs.extend(corners.iter().map(|_| [0.0, 0.0]));
+    }
+    if let Some(Indices::U32(indices)) = mesh.indices_mut() {
+        for i in 1..corners.len().saturating_sub(1) as u32 {
+            indices.extend([base, base + i, base + i + 1]);
+        }
+    }
+}
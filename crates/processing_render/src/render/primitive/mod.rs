@@ -9,17 +9,17 @@ mod shape3d;
 pub mod text;
 mod triangle;
 
-pub use arc::{arc_fill, arc_stroke};
+pub use arc::{arc_fill, arc_points, arc_stroke};
 use bevy::{
     asset::RenderAssetUsages,
     mesh::{Indices, PrimitiveTopology},
     prelude::*,
 };
-pub use curves::{bezier, curve};
+pub use curves::{bezier, bezier_point, bezier_tangent, curve, curve_point, curve_tangent};
 pub use ellipse::ellipse;
-pub use line::line;
+pub use line::{line, line_3d, point, point_3d};
 use lyon::{
-    path::Path,
+    path::{Path, PathEvent, iterator::PathIterator, math::Point},
     tessellation::{
         FillOptions, FillTessellator, LineCap, LineJoin, StrokeOptions, StrokeTessellator,
     },
@@ -36,7 +36,7 @@ pub use shape3d::{
 };
 pub use triangle::triangle;
 
-use super::command::{StrokeCapMode, StrokeJoinMode};
+use super::command::{ShapeMode, StrokeAlignMode, StrokeCapMode, StrokeJoinMode};
 use super::mesh_builder::MeshBuilder;
 
 pub enum TessellationMode {
@@ -48,6 +48,7 @@ pub enum TessellationMode {
 pub struct StrokeConfig {
     pub line_cap: StrokeCapMode,
     pub line_join: StrokeJoinMode,
+    pub align: StrokeAlignMode,
 }
 
 impl Default for StrokeConfig {
@@ -55,6 +56,7 @@ impl Default for StrokeConfig {
         Self {
             line_cap: StrokeCapMode::Round,
             line_join: StrokeJoinMode::Round,
+            align: StrokeAlignMode::Center,
         }
     }
 }
@@ -82,6 +84,34 @@ impl StrokeJoinMode {
     }
 }
 
+/// Convert `rectMode`/`ellipseMode` style arguments to the top left corner and size.
+pub fn apply_shape_mode(mode: ShapeMode, a: f32, b: f32, c: f32, d: f32) -> (f32, f32, f32, f32) {
+    let (x, y, w, h) = match mode {
+        ShapeMode::Corner => (a, b, c, d),
+        ShapeMode::Corners => (a, b, c - a, d - b),
+        ShapeMode::Center => (a - c / 2.0, b - d / 2.0, c, d),
+        ShapeMode::Radius => (a - c, b - d, c * 2.0, d * 2.0),
+    };
+    // like Processing, a negative size (or swapped corners) covers the same area flipped over
+    let (x, w) = if w < 0.0 { (x + w, -w) } else { (x, w) };
+    let (y, h) = if h < 0.0 { (y + h, -h) } else { (y, h) };
+    (x, y, w, h)
+}
+
+/// The transform that stretches a retained shape modeled in the unit square, `(0, 0)` to `(1, 1)`,
+/// over the rect given by `shapeMode` style arguments.
+pub fn shape_placement(mode: ShapeMode, a: f32, b: f32, c: f32, d: f32) -> Mat4 {
+    let (x, y, w, h) = apply_shape_mode(mode, a, b, c, d);
+    Mat4::from_translation(Vec3::new(x, y, 0.0)) * Mat4::from_scale(Vec3::new(w, h, 1.0))
+}
+
+/// Center and size of an ellipse or arc from its `ellipseMode` arguments. Every radial primitive
+/// goes through this so they all read the mode the same way.
+pub fn radial_bounds(mode: ShapeMode, a: f32, b: f32, c: f32, d: f32) -> (f32, f32, f32, f32) {
+    let (x, y, w, h) = apply_shape_mode(mode, a, b, c, d);
+    (x + w / 2.0, y + h / 2.0, w, h)
+}
+
 pub fn tessellate_path(
     mesh: &mut Mesh,
     path: &Path,
@@ -98,6 +128,14 @@ pub fn tessellate_path(
                 .expect("Failed to tessellate fill");
         }
         TessellationMode::Stroke(weight) => {
+            let offset = match stroke_config.align {
+                StrokeAlignMode::Center => None,
+                StrokeAlignMode::Inner => Some(-weight / 2.0),
+                StrokeAlignMode::Outer => Some(weight / 2.0),
+            };
+            let offset_path = offset.map(|distance| offset_closed_path(path, distance));
+            let path = offset_path.as_ref().unwrap_or(path);
+
             let mut tessellator = StrokeTessellator::new();
             let options = StrokeOptions::default()
                 .with_line_width(weight)
@@ -111,6 +149,69 @@ pub fn tessellate_path(
     }
 }
 
+/// Move every closed subpath of `path` outward by `distance` (inward when negative), so a centered
+/// stroke along the result lines up with one side of the original edge. Curves are flattened
+/// first and open subpaths are copied unchanged.
+fn offset_closed_path(path: &Path, distance: f32) -> Path {
+    let mut builder = Path::builder();
+    let mut points: Vec<Point> = Vec::new();
+
+    for event in path.iter().flattened(StrokeOptions::DEFAULT_TOLERANCE) {
+        match event {
+            PathEvent::Begin { at } => {
+                points.clear();
+                points.push(at);
+            }
+            PathEvent::Line { to, .. } => points.push(to),
+            PathEvent::End { close, .. } => {
+                points.dedup();
+                if points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+                if close && points.len() >= 3 {
+                    points = offset_polygon(&points, distance);
+                }
+                if let Some((first, rest)) = points.split_first() {
+                    builder.begin(*first);
+                    for &p in rest {
+                        builder.line_to(p);
+                    }
+                    builder.end(close);
+                }
+            }
+            _ => {}
+        }
+    }
+    builder.build()
+}
+
+fn offset_polygon(points: &[Point], distance: f32) -> Vec<Point> {
+    let n = points.len();
+    let area: f32 = (0..n)
+        .map(|i| points[i].to_vector().cross(points[(i + 1) % n].to_vector()))
+        .sum();
+    // the outward side of an edge depends on the winding
+    let outward = if area > 0.0 { 1.0 } else { -1.0 };
+    let normal = |a: Point, b: Point| {
+        let d = (b - a).normalize();
+        Vec2::new(d.y, -d.x) * outward
+    };
+
+    (0..n)
+        .map(|i| {
+            let prev = points[(i + n - 1) % n];
+            let curr = points[i];
+            let next = points[(i + 1) % n];
+            let n1 = normal(prev, curr);
+            let n2 = normal(curr, next);
+            let miter = (n1 + n2).normalize_or(n1);
+            // limit the miter so spikes stay bounded at very sharp corners
+            let scale = distance / miter.dot(n1).max(0.25);
+            Point::new(curr.x + miter.x * scale, curr.y + miter.y * scale)
+        })
+        .collect()
+}
+
 pub fn empty_mesh() -> Mesh {
     let mut mesh = Mesh::new(
         PrimitiveTopology::TriangleList,
@@ -125,3 +226,444 @@ pub fn empty_mesh() -> Mesh {
 
     mesh
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::command::ArcMode;
+    use bevy::camera::CameraProjection;
+    use std::f32::consts::{FRAC_PI_2, TAU};
+
+    fn bounds(mesh: &Mesh) -> (Vec2, Vec2) {
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh has no positions");
+        };
+        positions.iter().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), p| {
+                (
+                    min.min(Vec2::new(p[0], p[1])),
+                    max.max(Vec2::new(p[0], p[1])),
+                )
+            },
+        )
+    }
+
+    fn stroke_bounds(align: StrokeAlignMode) -> (Vec2, Vec2) {
+        let mut mesh = empty_mesh();
+        let config = StrokeConfig { align, ..default() };
+        rect(
+            &mut mesh,
+            0.0,
+            0.0,
+            100.0,
+            50.0,
+            [0.0; 4],
+            Color::WHITE,
+            TessellationMode::Stroke(10.0),
+            &config,
+        );
+        bounds(&mesh)
+    }
+
+    #[test]
+    fn ellipse_segments_scale_with_radius() {
+        let vertices = |d: f32| {
+            let mut mesh = empty_mesh();
+            ellipse(
+                &mut mesh,
+                0.0,
+                0.0,
+                d,
+                d,
+                Color::WHITE,
+                TessellationMode::Fill,
+                &StrokeConfig::default(),
+            );
+            mesh.count_vertices()
+        };
+
+        assert!(vertices(4.0) < vertices(40.0));
+        assert!(vertices(40.0) < vertices(400.0));
+    }
+
+    #[test]
+    fn shape_modes_match_processing() {
+        for (mode, args, expected) in [
+            (
+                ShapeMode::Corner,
+                (10.0, 20.0, 30.0, 40.0),
+                (10.0, 20.0, 30.0, 40.0),
+            ),
+            (
+                ShapeMode::Corners,
+                (10.0, 20.0, 40.0, 60.0),
+                (10.0, 20.0, 30.0, 40.0),
+            ),
+            (
+                ShapeMode::Center,
+                (25.0, 40.0, 30.0, 40.0),
+                (10.0, 20.0, 30.0, 40.0),
+            ),
+            (
+                ShapeMode::Radius,
+                (25.0, 40.0, 15.0, 20.0),
+                (10.0, 20.0, 30.0, 40.0),
+            ),
+            // swapped corners and negative sizes are flipped back
+            (
+                ShapeMode::Corners,
+                (40.0, 60.0, 10.0, 20.0),
+                (10.0, 20.0, 30.0, 40.0),
+            ),
+            (
+                ShapeMode::Corner,
+                (40.0, 60.0, -30.0, -40.0),
+                (10.0, 20.0, 30.0, 40.0),
+            ),
+        ] {
+            let (a, b, c, d) = args;
+            assert_eq!(apply_shape_mode(mode, a, b, c, d), expected, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn unit_shape_centers_in_center_mode() {
+        let placement = shape_placement(ShapeMode::Center, 100.0, 50.0, 40.0, 20.0);
+        let center = placement.transform_point3(Vec3::new(0.5, 0.5, 0.0));
+        assert!(center.abs_diff_eq(Vec3::new(100.0, 50.0, 0.0), 1e-5));
+        let corner = placement.transform_point3(Vec3::ZERO);
+        assert!(corner.abs_diff_eq(Vec3::new(80.0, 40.0, 0.0), 1e-5));
+        let far = placement.transform_point3(Vec3::new(1.0, 1.0, 0.0));
+        assert!(far.abs_diff_eq(Vec3::new(120.0, 60.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn ellipse_and_arc_read_ellipse_mode_alike() {
+        let config = StrokeConfig::default();
+        for (mode, expected) in [
+            (
+                ShapeMode::Center,
+                (Vec2::new(30.0, 35.0), Vec2::new(70.0, 65.0)),
+            ),
+            (
+                ShapeMode::Radius,
+                (Vec2::new(10.0, 20.0), Vec2::new(90.0, 80.0)),
+            ),
+        ] {
+            let (cx, cy, w, h) = radial_bounds(mode, 50.0, 50.0, 40.0, 30.0);
+
+            let mut ellipse_fill = empty_mesh();
+            ellipse(
+                &mut ellipse_fill,
+                cx,
+                cy,
+                w,
+                h,
+                Color::WHITE,
+                TessellationMode::Fill,
+                &config,
+            );
+            let mut arc = empty_mesh();
+            arc_fill(
+                &mut arc,
+                cx,
+                cy,
+                w,
+                h,
+                0.0,
+                TAU,
+                ArcMode::Chord,
+                Color::WHITE,
+                &config,
+            );
+            let mut ellipse_stroke = empty_mesh();
+            ellipse(
+                &mut ellipse_stroke,
+                cx,
+                cy,
+                w,
+                h,
+                Color::WHITE,
+                TessellationMode::Stroke(2.0),
+                &config,
+            );
+            let mut arc_outline = empty_mesh();
+            arc_stroke(
+                &mut arc_outline,
+                cx,
+                cy,
+                w,
+                h,
+                0.0,
+                TAU,
+                ArcMode::Chord,
+                Color::WHITE,
+                2.0,
+                &config,
+            );
+
+            for (min, max) in [bounds(&ellipse_fill), bounds(&arc)] {
+                assert!(min.abs_diff_eq(expected.0, 0.5), "{mode:?} {min}");
+                assert!(max.abs_diff_eq(expected.1, 0.5), "{mode:?} {max}");
+            }
+            let (stroke_min, stroke_max) = bounds(&ellipse_stroke);
+            let (arc_min, arc_max) = bounds(&arc_outline);
+            assert!(stroke_min.abs_diff_eq(arc_min, 0.5), "{mode:?}");
+            assert!(stroke_max.abs_diff_eq(arc_max, 0.5), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn pie_stroke_outlines_the_fill() {
+        let config = StrokeConfig::default();
+        let mut fill = empty_mesh();
+        arc_fill(
+            &mut fill,
+            0.0,
+            0.0,
+            100.0,
+            100.0,
+            0.0,
+            FRAC_PI_2,
+            ArcMode::Pie,
+            Color::WHITE,
+            &config,
+        );
+        let mut stroke = empty_mesh();
+        arc_stroke(
+            &mut stroke,
+            0.0,
+            0.0,
+            100.0,
+            100.0,
+            0.0,
+            FRAC_PI_2,
+            ArcMode::Pie,
+            Color::WHITE,
+            2.0,
+            &config,
+        );
+
+        // both reach the center of the pie
+        let reaches_center = |mesh: &Mesh| {
+            let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                return false;
+            };
+            positions
+                .iter()
+                .any(|p| Vec2::new(p[0], p[1]).length() < 1.5)
+        };
+        assert!(reaches_center(&fill));
+        assert!(reaches_center(&stroke));
+    }
+
+    #[test]
+    fn curve_vertex_ends_are_control_points() {
+        let mut shape = ShapeBuilder::new(crate::render::command::ShapeKind::Polygon);
+        for (x, y) in [(0.0, 0.0), (10.0, 5.0), (20.0, 5.0), (30.0, 0.0)] {
+            shape.push_vertex(VertexType::CurveVertex(x, y));
+        }
+        let mut mesh = empty_mesh();
+        build_polygon_stroke(
+            &mut mesh,
+            &shape,
+            false,
+            Color::WHITE,
+            1.0,
+            &StrokeConfig::default(),
+        );
+
+        // only the span between the second and third points is drawn
+        let (min, max) = bounds(&mesh);
+        assert!(min.x > 9.0 && max.x < 21.0, "{min} {max}");
+    }
+
+    #[test]
+    fn concave_polygon_fill_keeps_its_area() {
+        // an L shape, which a triangle fan from the first vertex would overfill
+        let mut shape = ShapeBuilder::new(crate::render::command::ShapeKind::Polygon);
+        for (x, y) in [
+            (0.0, 0.0),
+            (20.0, 0.0),
+            (20.0, 10.0),
+            (10.0, 10.0),
+            (10.0, 20.0),
+            (0.0, 20.0),
+        ] {
+            shape.push_vertex(VertexType::Normal(x, y));
+        }
+        let mut mesh = empty_mesh();
+        build_polygon_fill(
+            &mut mesh,
+            &shape,
+            true,
+            Color::WHITE,
+            &StrokeConfig::default(),
+        );
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("fill has no positions");
+        };
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        let area: f32 = indices
+            .chunks_exact(3)
+            .map(|t| {
+                let [a, b, c] =
+                    [t[0], t[1], t[2]].map(|i| Vec2::new(positions[i][0], positions[i][1]));
+                (b - a).perp_dot(c - a).abs() / 2.0
+            })
+            .sum();
+        assert!((area - 300.0).abs() < 0.01, "{area}");
+    }
+
+    #[test]
+    fn closed_pentagon_strokes_every_edge_and_faces_forward() {
+        let mut shape = ShapeBuilder::new(crate::render::command::ShapeKind::Polygon);
+        // clockwise on screen, where y points down, so the fill has to be rewound
+        for i in 0..5 {
+            let angle = i as f32 * TAU / 5.0;
+            shape.push_vertex(VertexType::Normal(angle.cos() * 10.0, angle.sin() * 10.0));
+        }
+        let segments = |close| {
+            shape::build_polygon_path(&shape, close)
+                .iter()
+                .filter(|event| {
+                    matches!(
+                        event,
+                        PathEvent::Line { .. } | PathEvent::End { close: true, .. }
+                    )
+                })
+                .count()
+        };
+        assert_eq!(segments(true), 5);
+        assert_eq!(segments(false), 4);
+
+        let mut mesh = empty_mesh();
+        build_polygon_fill(
+            &mut mesh,
+            &shape,
+            true,
+            Color::WHITE,
+            &StrokeConfig::default(),
+        );
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("fill has no positions");
+        };
+        // front faces wind counter-clockwise once the 2D projection puts them in clip space
+        let clip_from_view =
+            crate::graphics::ProcessingProjection::new(100.0, 100.0).get_clip_from_view();
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert!(!indices.is_empty());
+        for t in indices.chunks_exact(3) {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| {
+                clip_from_view
+                    .project_point3(Vec3::from(positions[i]))
+                    .truncate()
+            });
+            assert!((b - a).perp_dot(c - a) > 0.0);
+        }
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("fill has no normals");
+        };
+        assert!(normals.iter().all(|n| *n == [0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn zero_length_line_draws_square_dot() {
+        let mut mesh = empty_mesh();
+        let config = StrokeConfig {
+            line_cap: StrokeCapMode::Square,
+            ..default()
+        };
+        line(
+            &mut mesh,
+            10.0,
+            10.0,
+            10.0,
+            10.0,
+            Color::WHITE,
+            4.0,
+            &config,
+        );
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("dot has no positions");
+        };
+        assert_eq!(positions.len(), 4);
+        for p in positions {
+            assert_eq!((p[0] - 10.0).abs(), 2.0);
+            assert_eq!((p[1] - 10.0).abs(), 2.0);
+        }
+    }
+
+    #[test]
+    fn stroke_align_offsets_rect_outline() {
+        let (min, max) = stroke_bounds(StrokeAlignMode::Outer);
+        assert!(min.abs_diff_eq(Vec2::new(-10.0, -10.0), 0.01), "{min}");
+        assert!(max.abs_diff_eq(Vec2::new(110.0, 60.0), 0.01), "{max}");
+
+        let (min, max) = stroke_bounds(StrokeAlignMode::Inner);
+        assert!(min.cmpge(Vec2::splat(-0.01)).all(), "{min}");
+        assert!(max.cmple(Vec2::new(100.01, 50.01)).all(), "{max}");
+
+        let (min, max) = stroke_bounds(StrokeAlignMode::Center);
+        assert!(min.abs_diff_eq(Vec2::new(-5.0, -5.0), 0.01), "{min}");
+        assert!(max.abs_diff_eq(Vec2::new(105.0, 55.0), 0.01), "{max}");
+    }
+
+    #[test]
+    fn line_3d_projects_to_its_endpoints_and_faces_the_camera() {
+        let (start, end) = (Vec3::new(-1.0, 0.0, -5.0), Vec3::new(1.0, 1.0, -10.0));
+        let eye = Vec3::ZERO;
+        let mut mesh = empty_mesh();
+        let stroke_config = StrokeConfig {
+            line_cap: StrokeCapMode::Square,
+            ..default()
+        };
+        line_3d(
+            &mut mesh,
+            start,
+            end,
+            eye - start.midpoint(end),
+            Color::WHITE,
+            0.5,
+            &stroke_config,
+        );
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("line has no positions");
+        };
+        let p: Vec<Vec3> = positions.iter().map(|p| Vec3::from(*p)).collect();
+
+        // a 100x100 view with a 90 degree field of view, looking down -z from the origin
+        let clip_from_view = Mat4::perspective_rh(FRAC_PI_2, 1.0, 0.1, 100.0);
+        let screen = |v: Vec3| {
+            let ndc = clip_from_view.project_point3(v);
+            Vec2::new((ndc.x + 1.0) * 50.0, (1.0 - ndc.y) * 50.0)
+        };
+        assert!(screen(p[0].midpoint(p[3])).abs_diff_eq(Vec2::new(40.0, 50.0), 1e-3));
+        assert!(screen(p[1].midpoint(p[2])).abs_diff_eq(Vec2::new(55.0, 45.0), 1e-3));
+
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert_eq!(indices.len(), 6);
+        for t in indices.chunks_exact(3) {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| p[i]);
+            assert!((b - a).cross(c - a).dot(eye - a) > 0.0);
+        }
+    }
+}
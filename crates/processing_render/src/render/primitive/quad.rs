@@ -92,12 +92,55 @@ fn simple_quad(
     }
 
     if let Some(Indices::U32(indices)) = mesh.indices_mut() {
-        indices.push(base_idx);
-        indices.push(base_idx + 1);
-        indices.push(base_idx + 2);
+        let split = quad_split([
+            Vec2::new(x1, y1),
+            Vec2::new(x2, y2),
+            Vec2::new(x3, y3),
+            Vec2::new(x4, y4),
+        ]);
+        indices.extend(split.map(|i| base_idx + i));
+    }
+}
+
+/// Triangle indices for a quad. Either diagonal works for a convex quad, so the shorter one is
+/// used to avoid slivers; a concave quad is split through its reflex corner so that neither
+/// triangle covers area outside the outline.
+fn quad_split(p: [Vec2; 4]) -> [u32; 6] {
+    let area = |a: usize, b: usize, c: usize| (p[b] - p[a]).perp_dot(p[c] - p[a]);
+    // a diagonal is usable when the two triangles it makes wind the same way
+    let through_02 = area(0, 1, 2) * area(0, 2, 3) > 0.0;
+    let through_13 = area(0, 1, 3) * area(1, 2, 3) > 0.0;
+    let shorter_02 = p[0].distance_squared(p[2]) <= p[1].distance_squared(p[3]);
+
+    if through_13 && (!through_02 || !shorter_02) {
+        [0, 1, 3, 1, 2, 3]
+    } else {
+        [0, 1, 2, 0, 2, 3]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concave_quad_splits_through_reflex_corner() {
+        // a dart whose reflex corner is the last vertex, so only the 1-3 diagonal is inside
+        let dart = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(7.0, 3.0),
+        ];
+        assert_eq!(quad_split(dart), [0, 1, 3, 1, 2, 3]);
 
-        indices.push(base_idx);
-        indices.push(base_idx + 2);
-        indices.push(base_idx + 3);
+        // a long thin rectangle keeps its first diagonal when both are the same length
+        let rect = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        assert_eq!(quad_split(rect), [0, 1, 2, 0, 2, 3]);
     }
 }
@@ -100,7 +100,7 @@ pub fn build_polygon_stroke(
     );
 }
 
-fn build_polygon_path(builder: &ShapeBuilder, close: bool) -> Path {
+pub(super) fn build_polygon_path(builder: &ShapeBuilder, close: bool) -> Path {
     let mut pb = Path::builder();
 
     for (contour_idx, contour) in builder.contours.iter().enumerate() {
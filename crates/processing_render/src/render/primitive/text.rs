@@ -30,6 +30,7 @@ use crate::render::{
     mesh_builder::MeshBuilder,
 };
 use crate::text::font::{DEFAULT_FONT_FAMILY, TextContext};
+use crate::text::sdf::{SDF_GLYPH_SIZE, SdfAtlas, SdfGlyphKey};
 
 /// A path command for text outline data.
 #[derive(Debug, Clone)]
@@ -186,6 +187,39 @@ pub fn text_stroke(
     });
 }
 
+/// Lay out text as one quad per glyph, sampling the glyphs' distance fields from `atlas` and
+/// adding any glyphs it doesn't have yet.
+#[allow(clippy::too_many_arguments)]
+pub fn text_sdf(
+    mesh: &mut Mesh,
+    content: &str,
+    x: f32,
+    y: f32,
+    color: Color,
+    params: &TextParams,
+    text_cx: &TextContext,
+    atlas: &mut SdfAtlas,
+    images: &mut Assets<Image>,
+) {
+    if content.is_empty() {
+        return;
+    }
+
+    text_cx.with(|font_cx, layout_cx| {
+        let layout = build_layout(font_cx, layout_cx, content, color, params);
+        let (base_x, base_y) = compute_text_origin(&layout, x, y, params.align_v);
+        sdf_layout(
+            mesh,
+            &layout,
+            (base_x, base_y),
+            params.max_h,
+            params.glyph_colors,
+            atlas,
+            images,
+        );
+    });
+}
+
 /// Measure the width of text.
 pub fn text_width(content: &str, params: &TextParams, text_cx: &TextContext) -> f32 {
     if content.is_empty() {
@@ -1074,6 +1108,126 @@ fn tessellate_layout(
     }
 }
 
+fn sdf_layout(
+    mesh: &mut Mesh,
+    layout: &Layout<Color>,
+    (base_x, base_y): (f32, f32),
+    max_h: Option<f32>,
+    glyph_colors: Option<&[Color]>,
+    atlas: &mut SdfAtlas,
+    images: &mut Assets<Image>,
+) {
+    let mut glyph_index: usize = 0;
+
+    for line_idx in 0..layout.len() {
+        let Some(line) = layout.get(line_idx) else {
+            continue;
+        };
+
+        if let Some(h) = max_h {
+            let metrics = line.metrics();
+            if metrics.baseline + metrics.descent > h {
+                break;
+            }
+        }
+
+        for item in line.items() {
+            let PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                continue;
+            };
+
+            let run = glyph_run.run();
+            let font_data = run.font();
+            let normalized_coords = run.normalized_coords();
+            let color = glyph_run.style().brush;
+            // fields are rendered once at SDF_GLYPH_SIZE and scaled to the run's size
+            let scale = run.font_size() / SDF_GLYPH_SIZE;
+
+            let Ok(font_ref) = FontRef::from_index(font_data.data.as_ref(), font_data.index) else {
+                continue;
+            };
+
+            let outlines = font_ref.outline_glyphs();
+            let coords: Vec<NormalizedCoord> = normalized_coords
+                .iter()
+                .map(|&c| NormalizedCoord::from_bits(c))
+                .collect();
+            let location = LocationRef::new(&coords);
+
+            for glyph in glyph_run.positioned_glyphs() {
+                let glyph_color = glyph_colors
+                    .filter(|colors| !colors.is_empty())
+                    .map(|colors| colors[glyph_index % colors.len()])
+                    .unwrap_or(color);
+                glyph_index += 1;
+
+                let key = SdfGlyphKey {
+                    font: font_data.data.id(),
+                    index: font_data.index,
+                    glyph: glyph.id,
+                    coords: normalized_coords.to_vec(),
+                };
+                let sdf_glyph = atlas.glyph(key, images, || {
+                    let outline_glyph = outlines.get(skrifa::GlyphId::new(glyph.id))?;
+                    let mut pen = LyonOutlinePen::new();
+                    let settings = DrawSettings::unhinted(Size::new(SDF_GLYPH_SIZE), location);
+                    let _ = outline_glyph.draw(settings, &mut pen);
+                    pen.build()
+                });
+                let Some(sdf_glyph) = sdf_glyph else {
+                    continue;
+                };
+
+                // font outlines are Y-up, flip to Y-down like translate_path_flip_y
+                let tx = base_x + glyph.x;
+                let ty = base_y + glyph.y;
+                let left = tx + sdf_glyph.min.x * scale;
+                let right = tx + sdf_glyph.max.x * scale;
+                let top = ty - sdf_glyph.max.y * scale;
+                let bottom = ty - sdf_glyph.min.y * scale;
+
+                let base = vertex_count(mesh) as u32;
+                let (uv_min, uv_max) = (sdf_glyph.uv_min, sdf_glyph.uv_max);
+                push_quad_vertex(mesh, [left, top], [uv_min.x, uv_min.y], glyph_color);
+                push_quad_vertex(mesh, [right, top], [uv_max.x, uv_min.y], glyph_color);
+                push_quad_vertex(mesh, [right, bottom], [uv_max.x, uv_max.y], glyph_color);
+                push_quad_vertex(mesh, [left, bottom], [uv_min.x, uv_max.y], glyph_color);
+                if let Some(Indices::U32(indices)) = mesh.indices_mut() {
+                    indices.extend_from_slice(&[
+                        base,
+                        base + 1,
+                        base + 2,
+                        base,
+                        base + 2,
+                        base + 3,
+                    ]);
+                }
+            }
+        }
+    }
+}
+
+fn push_quad_vertex(mesh: &mut Mesh, position: [f32; 2], uv: [f32; 2], color: Color) {
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        positions.push([position[0], position[1], 0.0]);
+    }
+    if let Some(VertexAttributeValues::Float32x3(normals)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+    {
+        normals.push([0.0, 0.0, 1.0]);
+    }
+    if let Some(VertexAttributeValues::Float32x4(colors)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR)
+    {
+        colors.push(color.to_linear().to_f32_array());
+    }
+    if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0) {
+        uvs.push(uv);
+    }
+}
+
 fn stroke_layout(
     mesh: &mut Mesh,
     layout: &Layout<Color>,
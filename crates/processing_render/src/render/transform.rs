@@ -1,4 +1,6 @@
-use bevy::math::{Affine3A, Mat3, Quat, Vec3};
+use bevy::math::{Affine3A, Mat3, Mat4, Quat, Vec3};
+
+use super::command::DrawCommand;
 
 #[derive(Debug, Clone, Default)]
 pub struct TransformStack {
@@ -102,6 +104,28 @@ impl TransformStack {
         self.current *= transform;
     }
 
+    /// Apply `command` if it's one of the matrix commands, returning whether it was.
+    pub fn apply_command(&mut self, command: &DrawCommand) -> bool {
+        match *command {
+            DrawCommand::PushMatrix => self.push(),
+            DrawCommand::PopMatrix => self.pop(),
+            DrawCommand::ResetMatrix => self.reset(),
+            DrawCommand::ApplyMatrix(m) => {
+                self.apply(Affine3A::from_mat4(Mat4::from_cols_array(&m)))
+            }
+            DrawCommand::Translate(v) => self.translate(v.x, v.y),
+            DrawCommand::Rotate { angle } => self.rotate(angle),
+            DrawCommand::RotateX { angle } => self.rotate_x(angle),
+            DrawCommand::RotateY { angle } => self.rotate_y(angle),
+            DrawCommand::RotateZ { angle } => self.rotate_z(angle),
+            DrawCommand::Scale(v) => self.scale(v.x, v.y),
+            DrawCommand::ShearX { angle } => self.shear_x(angle),
+            DrawCommand::ShearY { angle } => self.shear_y(angle),
+            _ => return false,
+        }
+        true
+    }
+
     pub fn to_bevy_transform(&self) -> bevy::prelude::Transform {
         let (scale, rotation, translation) = self.current.to_scale_rotation_translation();
         bevy::prelude::Transform {
@@ -186,6 +210,18 @@ mod tests {
         assert!(approx_eq(y, 100.0));
     }
 
+    #[test]
+    fn test_apply_matrix_command() {
+        let mut stack = TransformStack::new();
+        stack.translate(10.0, 0.0);
+        let scale = Mat4::from_scale(Vec3::new(2.0, 2.0, 1.0)).to_cols_array();
+        assert!(stack.apply_command(&DrawCommand::ApplyMatrix(scale)));
+        assert!(!stack.apply_command(&DrawCommand::NoFill));
+        let (x, y) = stack.transform_point_2d(5.0, 5.0);
+        assert!(approx_eq(x, 20.0));
+        assert!(approx_eq(y, 10.0));
+    }
+
     #[test]
     fn test_pop_empty_is_noop() {
         let mut stack = TransformStack::new();
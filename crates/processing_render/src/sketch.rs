@@ -9,6 +9,7 @@ use bevy::{
 };
 use std::path::Path;
 
+use crate::{noise::Noise, random::Rng};
 use processing_core::config::{Config, ConfigKey};
 
 /// Plugin that registers the Sketch asset type and its loader.
@@ -26,12 +27,18 @@ impl Plugin for LivecodePlugin {
 pub fn sketch_update_handler(
     mut events: MessageReader<AssetEvent<Sketch>>,
     sketches: Res<Assets<Sketch>>,
+    mut rng: ResMut<Rng>,
+    mut noise: ResMut<Noise>,
 ) -> Option<Sketch> {
     for event in events.read() {
         if let AssetEvent::Modified { id } = event {
             info!("Modified: {id}");
             if let Some(sketch) = sketches.get(*id) {
                 let sketch = sketch.clone();
+                // a reloaded sketch starts from fresh generators, the same as a restarted one, so
+                // a `random_seed` at the top of it repeats what it did the first time
+                *rng = Rng::default();
+                *noise = Noise::default();
                 return Some(sketch);
             }
         }
@@ -372,6 +372,35 @@ pub fn prepare_offscreen(
     Ok((size, data, texture_format))
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    // on linux the clipboard only serves its contents while the owning `Clipboard` is alive, so
+    // keep it around after a copy rather than dropping it straight away
+    static CLIPBOARD: std::cell::RefCell<Option<arboard::Clipboard>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Put tightly packed 8-bit RGBA pixels on the system clipboard as an image.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn copy_to_clipboard(width: u32, height: u32, rgba: Vec<u8>) -> Result<()> {
+    let clipboard_error = |e: arboard::Error| ProcessingError::ClipboardError(e.to_string());
+    CLIPBOARD.with_borrow_mut(|clipboard| {
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().map_err(clipboard_error)?);
+        }
+        let image = arboard::ImageData {
+            width: width as usize,
+            height: height as usize,
+            bytes: rgba.into(),
+        };
+        clipboard
+            .as_mut()
+            .expect("clipboard was just created")
+            .set_image(image)
+            .map_err(clipboard_error)
+    })
+}
+
 pub fn destroy(
     In(surface_entity): In<Entity>,
     mut commands: Commands,
@@ -0,0 +1,192 @@
+//! Processing's `Table`: rows of text cells under a header row, read from and written to CSV or
+//! TSV files. Cells are kept as the text in the file, so numbers round-trip exactly.
+use std::path::Path;
+
+use processing_core::error::{ProcessingError, Result};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+/// Tab separated for `.tsv` files, comma separated for anything else.
+pub fn delimiter_for(path: &Path) -> char {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("tsv") => '\t',
+        _ => ',',
+    }
+}
+
+/// Split delimited text into records, following RFC 4180 quoting: quoted cells may hold the
+/// delimiter, newlines and `""` for a quote.
+fn records(text: &str, delimiter: char) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.is_empty() => quoted = true,
+            c if quoted => cell.push(c),
+            c if c == delimiter => record.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut cell));
+                records.push(std::mem::take(&mut record));
+            }
+            c => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err(ProcessingError::TableError(
+            "Unterminated quoted cell".into(),
+        ));
+    }
+    if !cell.is_empty() || !record.is_empty() {
+        record.push(cell);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn write_cell(out: &mut String, cell: &str, delimiter: char) {
+    if cell.contains([delimiter, '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&cell.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(cell);
+    }
+}
+
+impl Table {
+    pub fn new(columns: Vec<String>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Read a table from delimited text whose first line is the header.
+    pub fn parse(text: &str, delimiter: char) -> Result<Self> {
+        let mut records = records(text, delimiter)?.into_iter();
+        let columns = records.next().unwrap_or_default();
+        let mut rows: Vec<Vec<String>> = records.collect();
+        // short rows are padded so every row has a cell for every column
+        for row in &mut rows {
+            if row.len() < columns.len() {
+                row.resize(columns.len(), String::new());
+            }
+        }
+        Ok(Self { columns, rows })
+    }
+
+    /// The table as delimited text with a header line, quoting cells where needed.
+    pub fn to_delimited(&self, delimiter: char) -> String {
+        let mut out = String::new();
+        for record in std::iter::once(&self.columns).chain(&self.rows) {
+            for (i, cell) in record.iter().enumerate() {
+                if i > 0 {
+                    out.push(delimiter);
+                }
+                write_cell(&mut out, cell, delimiter);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Read a `.csv` or `.tsv` file, picking the delimiter from its extension.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ProcessingError::TableError(format!("{}: {e}", path.display())))?;
+        Self::parse(&text, delimiter_for(path))
+    }
+
+    /// Write a `.csv` or `.tsv` file, picking the delimiter from its extension.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_delimited(delimiter_for(path)))
+            .map_err(|e| ProcessingError::TableError(format!("{}: {e}", path.display())))
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == name)
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn add_row(&mut self, mut cells: Vec<String>) {
+        cells.resize(self.columns.len().max(cells.len()), String::new());
+        self.rows.push(cells);
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&str> {
+        self.rows.get(row)?.get(column).map(String::as_str)
+    }
+
+    /// A cell read as a number. `None` if it's missing or not a number.
+    pub fn get_float(&self, row: usize, column: usize) -> Option<f32> {
+        self.get(row, column)?.trim().parse().ok()
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: String) -> Result<()> {
+        let cell = self
+            .rows
+            .get_mut(row)
+            .and_then(|r| r.get_mut(column))
+            .ok_or_else(|| {
+                ProcessingError::InvalidArgument(format!("No cell at row {row}, column {column}"))
+            })?;
+        *cell = value;
+        Ok(())
+    }
+
+    /// Store `value` as the shortest text that reads back as the same `f32`.
+    pub fn set_float(&mut self, row: usize, column: usize, value: f32) -> Result<()> {
+        self.set(row, column, value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_table_loads_back_unchanged() {
+        let mut table = Table::new(vec!["name".into(), "value".into()]);
+        table.add_row(vec!["plain".into(), String::new()]);
+        table.add_row(vec!["with, comma".into(), String::new()]);
+        table.add_row(vec!["\"quoted\"\nand split".into(), String::new()]);
+        for (row, value) in [0.1_f32, -3.75e-8, 123456.79].into_iter().enumerate() {
+            table.set_float(row, 1, value).unwrap();
+        }
+
+        for name in [
+            "processing_table_round_trip.csv",
+            "processing_table_round_trip.tsv",
+        ] {
+            let path = std::env::temp_dir().join(name);
+            table.save(&path).unwrap();
+            let loaded = Table::load(&path).unwrap();
+            let _ = std::fs::remove_file(&path);
+
+            assert_eq!(loaded, table, "{name}");
+            assert_eq!(loaded.get(1, 0), Some("with, comma"));
+            assert_eq!(loaded.get_float(1, 1), Some(-3.75e-8));
+            assert_eq!(loaded.get_float(2, 1), Some(123456.79));
+        }
+    }
+}
@@ -1,14 +1,50 @@
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+use bevy::asset::io::{AssetSourceId, Reader};
 use bevy::prelude::*;
 use parley::{FontContext, LayoutContext};
 
+use processing_core::config::{Config, ConfigKey};
+use processing_core::error::{ProcessingError, Result};
+
 /// Font component: the resolved family name.
 #[derive(Component)]
 pub struct Font {
     pub family_name: String,
 }
 
+/// Font bytes read through the configured [`ConfigKey::AssetSource`], or `None` when no asset
+/// source is configured and fonts come from the filesystem.
+pub fn read_from_asset_source(world: &World, path: &str) -> Option<Result<Vec<u8>>> {
+    let source = world
+        .resource::<Config>()
+        .get(ConfigKey::AssetSource)?
+        .clone();
+    let load_error =
+        |e: &dyn std::fmt::Display| ProcessingError::FontLoadError(format!("{path}: {e}"));
+    let source = match world
+        .resource::<AssetServer>()
+        .get_source(AssetSourceId::from(source))
+    {
+        Ok(source) => source,
+        Err(e) => return Some(Err(load_error(&e))),
+    };
+    Some(bevy::tasks::block_on(async {
+        let mut reader = source
+            .reader()
+            .read(Path::new(path))
+            .await
+            .map_err(|e| load_error(&e))?;
+        let mut data = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .await
+            .map_err(|e| load_error(&e))?;
+        Ok(data)
+    }))
+}
+
 /// Shared parley font and layout contexts.
 #[derive(Resource, Clone)]
 pub struct TextContext {
@@ -167,6 +203,7 @@ pub struct TextPlugin;
 
 impl Plugin for TextPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(TextContext::new());
+        app.insert_resource(TextContext::new())
+            .add_plugins(super::sdf::SdfTextPlugin);
     }
 }
@@ -1 +1,2 @@
 pub mod font;
+pub mod sdf;
@@ -0,0 +1,327 @@
+//! Signed distance field text, drawn when the text mode is [`TextMode::Sdf`]. Glyph outlines are
+//! rendered once into a shared atlas as distance fields at a fixed size, and text is drawn as
+//! quads that the SDF shader thresholds per pixel, so edges stay crisp at any scale or transform.
+//!
+//! [`TextMode::Sdf`]: crate::render::command::TextMode::Sdf
+
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use bevy::asset::{RenderAssetUsages, embedded_asset};
+use bevy::image::ImageSampler;
+use bevy::pbr::{ExtendedMaterial, MaterialExtension, MaterialPlugin};
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, Extent3d, TextureDimension, TextureFormat};
+use bevy::shader::ShaderRef;
+use lyon::path::{Event, Path, iterator::PathIterator};
+
+use crate::render::material::UntypedMaterial;
+
+/// Em size glyphs are rendered into the atlas at.
+pub const SDF_GLYPH_SIZE: f32 = 48.0;
+/// Distance from the outline, in atlas texels, at which the field saturates.
+pub const SDF_SPREAD: f32 = 6.0;
+
+const ATLAS_SIZE: u32 = 1024;
+const FLATTEN_TOLERANCE: f32 = 0.05;
+
+pub struct SdfTextPlugin;
+
+impl Plugin for SdfTextPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "sdf.wgsl");
+        app.add_plugins(MaterialPlugin::<SdfTextMaterial>::default())
+            .init_resource::<SdfAtlas>();
+    }
+}
+
+pub type SdfTextMaterial = ExtendedMaterial<StandardMaterial, SdfTextExtension>;
+
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+pub struct SdfTextExtension {
+    #[texture(100)]
+    #[sampler(101)]
+    pub atlas: Handle<Image>,
+}
+
+impl MaterialExtension for SdfTextExtension {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://processing_render/text/sdf.wgsl".into()
+    }
+}
+
+/// promote `UntypedMaterial(handle)` to `MeshMaterial3d<SdfTextMaterial>`
+/// where the handle's type matches.
+pub fn add_sdf_text_materials(mut commands: Commands, meshes: Query<(Entity, &UntypedMaterial)>) {
+    for (entity, handle) in meshes.iter() {
+        let handle = handle.deref().clone();
+        if let Ok(handle) = handle.try_typed::<SdfTextMaterial>() {
+            commands
+                .entity(entity)
+                .insert(MeshMaterial3d::<SdfTextMaterial>(handle));
+        }
+    }
+}
+
+/// A glyph of a particular font instance: the font blob, its index in a collection, the glyph id
+/// and the normalized variation coordinates.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SdfGlyphKey {
+    pub font: u64,
+    pub index: u32,
+    pub glyph: u32,
+    pub coords: Vec<i16>,
+}
+
+/// A glyph's distance field in the atlas.
+#[derive(Clone, Copy, Debug)]
+pub struct SdfGlyph {
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+    /// Bounds of the field in Y-up outline units at [`SDF_GLYPH_SIZE`], spread included.
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+/// Distance fields of every glyph drawn so far, packed in rows into a single `R8Unorm` image.
+#[derive(Resource)]
+pub struct SdfAtlas {
+    pub image: Handle<Image>,
+    // `None` for glyphs without an outline, like spaces
+    glyphs: HashMap<SdfGlyphKey, Option<SdfGlyph>>,
+    cursor: UVec2,
+    row_height: u32,
+}
+
+impl FromWorld for SdfAtlas {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(&mut world.resource_mut::<Assets<Image>>())
+    }
+}
+
+impl SdfAtlas {
+    pub fn new(images: &mut Assets<Image>) -> Self {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0],
+            TextureFormat::R8Unorm,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::linear();
+
+        Self {
+            image: images.add(image),
+            glyphs: HashMap::new(),
+            cursor: UVec2::ZERO,
+            row_height: 0,
+        }
+    }
+
+    /// Look up a glyph, rendering its field into the atlas the first time it's seen. `outline`
+    /// gives the glyph's outline at [`SDF_GLYPH_SIZE`], or `None` if it has none.
+    pub fn glyph(
+        &mut self,
+        key: SdfGlyphKey,
+        images: &mut Assets<Image>,
+        outline: impl FnOnce() -> Option<Path>,
+    ) -> Option<SdfGlyph> {
+        if let Some(glyph) = self.glyphs.get(&key) {
+            return *glyph;
+        }
+        let glyph = outline().and_then(|path| self.insert(&path, images));
+        self.glyphs.insert(key, glyph);
+        glyph
+    }
+
+    fn insert(&mut self, path: &Path, images: &mut Assets<Image>) -> Option<SdfGlyph> {
+        let segments = flatten(path);
+        let (min, max) = segments.iter().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), &(a, b)| (min.min(a).min(b), max.max(a).max(b)),
+        );
+        if segments.is_empty() {
+            return None;
+        }
+        let min = (min - SDF_SPREAD).floor();
+        let max = (max + SDF_SPREAD).ceil();
+        let size = (max - min).as_uvec2();
+
+        // shelf packing, leaving a texel between glyphs so they don't bleed into each other
+        if self.cursor.x + size.x + 1 > ATLAS_SIZE {
+            self.cursor = UVec2::new(0, self.cursor.y + self.row_height);
+            self.row_height = 0;
+        }
+        if self.cursor.x + size.x > ATLAS_SIZE || self.cursor.y + size.y > ATLAS_SIZE {
+            warn!("SDF text atlas is full, skipping glyph");
+            return None;
+        }
+        let origin = self.cursor;
+        self.cursor.x += size.x + 1;
+        self.row_height = self.row_height.max(size.y + 1);
+
+        let field = distance_field(&segments, min, max, size);
+        let mut image = images.get_mut(&self.image)?;
+        let data = image.data.as_mut()?;
+        for row in 0..size.y {
+            let dst = ((origin.y + row) * ATLAS_SIZE + origin.x) as usize;
+            let src = (row * size.x) as usize;
+            data[dst..dst + size.x as usize].copy_from_slice(&field[src..src + size.x as usize]);
+        }
+
+        Some(SdfGlyph {
+            uv_min: origin.as_vec2() / ATLAS_SIZE as f32,
+            uv_max: (origin + size).as_vec2() / ATLAS_SIZE as f32,
+            min,
+            max,
+        })
+    }
+}
+
+/// Signed distance field of the outline made of `segments` over the texels between `min` and
+/// `max`, one byte per texel with rows top to bottom. The outline is at 128, the inside above it.
+pub fn distance_field(segments: &[(Vec2, Vec2)], min: Vec2, max: Vec2, size: UVec2) -> Vec<u8> {
+    let mut field = Vec::with_capacity((size.x * size.y) as usize);
+    for row in 0..size.y {
+        for col in 0..size.x {
+            let p = Vec2::new(min.x + col as f32 + 0.5, max.y - row as f32 - 0.5);
+            let mut distance = f32::MAX;
+            let mut winding = 0;
+            for &(a, b) in segments {
+                distance = distance.min(segment_distance(p, a, b));
+                winding += crossing(p, a, b);
+            }
+            // font outlines use the nonzero fill rule
+            let signed = if winding != 0 { distance } else { -distance };
+            let value = 0.5 + signed / (2.0 * SDF_SPREAD);
+            field.push((value.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+    field
+}
+
+fn flatten(path: &Path) -> Vec<(Vec2, Vec2)> {
+    path.iter()
+        .flattened(FLATTEN_TOLERANCE)
+        .filter_map(|event| match event {
+            Event::Line { from, to } => Some((from, to)),
+            // contours are always filled closed
+            Event::End { last, first, .. } => Some((last, first)),
+            _ => None,
+        })
+        .map(|(a, b)| (Vec2::new(a.x, a.y), Vec2::new(b.x, b.y)))
+        .collect()
+}
+
+fn segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((p - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+/// Winding number contribution of the edge `a`-`b` for a ray from `p` towards +x.
+fn crossing(p: Vec2, a: Vec2, b: Vec2) -> i32 {
+    let side = (b - a).perp_dot(p - a);
+    if a.y <= p.y {
+        if b.y > p.y && side > 0.0 { 1 } else { 0 }
+    } else if b.y <= p.y && side < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::mesh::VertexAttributeValues;
+
+    use super::*;
+    use crate::render::RenderState;
+    use crate::render::primitive::empty_mesh;
+    use crate::render::primitive::text::{OwnedTextParams, text_sdf};
+    use crate::text::font::TextContext;
+
+    /// Bilinear sample of the atlas at `uv`, like the shader's sampler.
+    fn sample(atlas: &SdfAtlas, images: &Assets<Image>, uv: Vec2) -> f32 {
+        let data = images.get(&atlas.image).unwrap().data.as_ref().unwrap();
+        let texel = |x: i32, y: i32| {
+            let x = x.clamp(0, ATLAS_SIZE as i32 - 1) as u32;
+            let y = y.clamp(0, ATLAS_SIZE as i32 - 1) as u32;
+            data[(y * ATLAS_SIZE + x) as usize] as f32 / 255.0
+        };
+        let p = uv * ATLAS_SIZE as f32 - 0.5;
+        let (x, y) = (p.x.floor() as i32, p.y.floor() as i32);
+        let f = p - p.floor();
+        let top = texel(x, y) * (1.0 - f.x) + texel(x + 1, y) * f.x;
+        let bottom = texel(x, y + 1) * (1.0 - f.x) + texel(x + 1, y + 1) * f.x;
+        top * (1.0 - f.y) + bottom * f.y
+    }
+
+    #[test]
+    fn scaled_text_has_thin_antialiased_edges() {
+        let mut images = Assets::<Image>::default();
+        let mut atlas = SdfAtlas::new(&mut images);
+        let text_cx = TextContext::new();
+        let mut params = OwnedTextParams::from_render_state(&RenderState::new(), None, None);
+        // about 8x the size the field was rendered at
+        params.text_size = 400.0;
+
+        let mut mesh = empty_mesh();
+        text_sdf(
+            &mut mesh,
+            "O",
+            0.0,
+            0.0,
+            Color::WHITE,
+            &params.as_params(),
+            &text_cx,
+            &mut atlas,
+            &mut images,
+        );
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("missing uvs");
+        };
+        assert_eq!(positions.len(), 4);
+
+        // walk a pixel row through the middle of the O, shading it like the SDF shader does
+        let (left, right) = (positions[0][0], positions[2][0]);
+        let (uv_min, uv_max) = (Vec2::from(uvs[0]), Vec2::from(uvs[2]));
+        let v = (uv_min.y + uv_max.y) / 2.0;
+        let distances: Vec<f32> = (left.floor() as i32..right.ceil() as i32)
+            .map(|x| {
+                let t = (x as f32 + 0.5 - left) / (right - left);
+                sample(
+                    &atlas,
+                    &images,
+                    Vec2::new(uv_min.x + (uv_max.x - uv_min.x) * t, v),
+                )
+            })
+            .collect();
+        let partial = distances
+            .windows(2)
+            .filter(|d| {
+                let width = (d[1] - d[0]).abs().max(1e-4);
+                let t = ((d[0] - (0.5 - width)) / (2.0 * width)).clamp(0.0, 1.0);
+                let coverage = t * t * (3.0 - 2.0 * t);
+                coverage > 0.0 && coverage < 1.0
+            })
+            .count();
+
+        // four edges crossed, each blended over a pixel or two rather than the ~8 pixels a
+        // magnified bitmap would smear across
+        assert!(
+            (4..=12).contains(&partial),
+            "{partial} partially covered pixels"
+        );
+    }
+}
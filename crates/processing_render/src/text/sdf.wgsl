@@ -0,0 +1,30 @@
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::main_pass_post_lighting_processing,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var sdf_atlas: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var sdf_sampler: sampler;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    // unlit, so this is just the vertex color
+    let pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    // the outline is at 0.5; scaling the ramp by the screen space rate of change keeps edges
+    // about a pixel wide no matter how far the glyph is magnified
+    let distance = textureSample(sdf_atlas, sdf_sampler, in.uv).r;
+    let width = max(fwidth(distance), 0.0001);
+    let coverage = smoothstep(0.5 - width, 0.5 + width, distance);
+
+    var color = pbr_input.material.base_color;
+    color.a = color.a * coverage;
+
+    var out: FragmentOutput;
+    out.color = main_pass_post_lighting_processing(pbr_input, color);
+    return out;
+}
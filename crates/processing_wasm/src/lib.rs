@@ -170,6 +170,46 @@ pub fn js_color_mode(
     })())
 }
 
+/// Resolve a color under the graphics' current color mode and pack it as `0xAARRGGBB`, so it
+/// can be stored and passed to `setFillArgb` or `setStrokeArgb` later.
+#[wasm_bindgen(js_name = "color")]
+pub fn js_color(
+    graphics_id: u64,
+    c1: f32,
+    c2: f32,
+    c3: f32,
+    a: f32,
+    space: u8,
+) -> Result<u32, JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let color = Color {
+        c1,
+        c2,
+        c3,
+        a,
+        space,
+    };
+    check((|| {
+        let mode = graphics_get_color_mode(graphics_entity)?;
+        Ok::<_, ProcessingError>(processing::prelude::color::to_argb(color.resolve(&mode)))
+    })())
+}
+
+/// Blend between two packed colors in the graphics' current color mode, returning a packed color.
+#[wasm_bindgen(js_name = "lerpColor")]
+pub fn js_lerp_color(graphics_id: u64, a: u32, b: u32, t: f32) -> Result<u32, JsValue> {
+    use processing::prelude::color::{from_argb, to_argb};
+    check(
+        graphics_lerp_color(
+            Entity::from_bits(graphics_id),
+            from_argb(a),
+            from_argb(b),
+            t,
+        )
+        .map(to_argb),
+    )
+}
+
 #[wasm_bindgen(js_name = "setFill")]
 pub fn js_set_fill(
     graphics_id: u64,
@@ -193,6 +233,46 @@ pub fn js_set_fill(
     })())
 }
 
+#[wasm_bindgen(js_name = "setTint")]
+pub fn js_set_tint(
+    graphics_id: u64,
+    c1: f32,
+    c2: f32,
+    c3: f32,
+    a: f32,
+    space: u8,
+) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let color = Color {
+        c1,
+        c2,
+        c3,
+        a,
+        space,
+    };
+    check((|| {
+        let mode = graphics_get_color_mode(graphics_entity)?;
+        graphics_record_command(graphics_entity, DrawCommand::Tint(color.resolve(&mode)))
+    })())
+}
+
+#[wasm_bindgen(js_name = "noTint")]
+pub fn js_no_tint(graphics_id: u64) -> Result<(), JsValue> {
+    check(graphics_record_command(
+        Entity::from_bits(graphics_id),
+        DrawCommand::NoTint,
+    ))
+}
+
+#[wasm_bindgen(js_name = "setFillArgb")]
+pub fn js_set_fill_argb(graphics_id: u64, argb: u32) -> Result<(), JsValue> {
+    let color = processing::prelude::color::from_argb(argb);
+    check(graphics_record_command(
+        Entity::from_bits(graphics_id),
+        DrawCommand::Fill(color),
+    ))
+}
+
 #[wasm_bindgen(js_name = "setStrokeColor")]
 pub fn js_set_stroke_color(
     graphics_id: u64,
@@ -219,6 +299,15 @@ pub fn js_set_stroke_color(
     })())
 }
 
+#[wasm_bindgen(js_name = "setStrokeArgb")]
+pub fn js_set_stroke_argb(graphics_id: u64, argb: u32) -> Result<(), JsValue> {
+    let color = processing::prelude::color::from_argb(argb);
+    check(graphics_record_command(
+        Entity::from_bits(graphics_id),
+        DrawCommand::StrokeColor(color),
+    ))
+}
+
 #[wasm_bindgen(js_name = "setStrokeWeight")]
 pub fn js_set_stroke_weight(graphics_id: u64, weight: f32) -> Result<(), JsValue> {
     let graphics_entity = Entity::from_bits(graphics_id);
@@ -246,6 +335,15 @@ pub fn js_set_stroke_join(graphics_id: u64, join: u8) -> Result<(), JsValue> {
     ))
 }
 
+#[wasm_bindgen(js_name = "setStrokeAlign")]
+pub fn js_set_stroke_align(graphics_id: u64, align: u8) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::StrokeAlign(processing::prelude::StrokeAlignMode::from(align)),
+    ))
+}
+
 #[wasm_bindgen(js_name = "rectMode")]
 pub fn js_rect_mode(graphics_id: u64, mode: u8) -> Result<(), JsValue> {
     let graphics_entity = Entity::from_bits(graphics_id);
@@ -264,6 +362,51 @@ pub fn js_ellipse_mode(graphics_id: u64, mode: u8) -> Result<(), JsValue> {
     ))
 }
 
+#[wasm_bindgen(js_name = "shapeMode")]
+pub fn js_shape_mode(graphics_id: u64, mode: u8) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::ShapeMode(processing::prelude::ShapeMode::from(mode)),
+    ))
+}
+
+#[wasm_bindgen(js_name = "imageMode")]
+pub fn js_image_mode(graphics_id: u64, mode: u8) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::ImageMode(processing::prelude::ShapeMode::from(mode)),
+    ))
+}
+
+/// Draws an image at `x`, `y`, at its own size unless `w` and `h` are given.
+#[wasm_bindgen(js_name = "image")]
+pub fn js_image(
+    graphics_id: u64,
+    image_id: u64,
+    x: f32,
+    y: f32,
+    w: Option<f32>,
+    h: Option<f32>,
+) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::Image {
+            entity: Entity::from_bits(image_id),
+            dx: x,
+            dy: y,
+            d_width: w,
+            d_height: h,
+            sx: None,
+            sy: None,
+            s_width: None,
+            s_height: None,
+        },
+    ))
+}
+
 #[wasm_bindgen(js_name = "noFill")]
 pub fn js_no_fill(graphics_id: u64) -> Result<(), JsValue> {
     let graphics_entity = Entity::from_bits(graphics_id);
@@ -309,6 +452,30 @@ pub fn js_reset_matrix(graphics_id: u64) -> Result<(), JsValue> {
     ))
 }
 
+/// Multiply the current transform by a column-major 4x4 matrix of 16 floats.
+#[wasm_bindgen(js_name = "applyMatrix")]
+pub fn js_apply_matrix(graphics_id: u64, matrix: &[f32]) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let matrix: [f32; 16] = matrix
+        .try_into()
+        .map_err(|_| JsValue::from_str("applyMatrix expects 16 floats"))?;
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::ApplyMatrix(matrix),
+    ))
+}
+
+#[wasm_bindgen(js_name = "getMatrix")]
+pub fn js_get_matrix(graphics_id: u64) -> Result<Vec<f32>, JsValue> {
+    check(graphics_get_matrix(Entity::from_bits(graphics_id))).map(|m| m.to_vec())
+}
+
+/// Where a point drawn at the current transform lands on screen, as `[x, y]` in pixels.
+#[wasm_bindgen(js_name = "screenXY")]
+pub fn js_screen_xy(graphics_id: u64, x: f32, y: f32, z: f32) -> Result<Vec<f32>, JsValue> {
+    check(graphics_screen_xy(Entity::from_bits(graphics_id), x, y, z)).map(|(sx, sy)| vec![sx, sy])
+}
+
 #[wasm_bindgen(js_name = "translate")]
 pub fn js_translate(graphics_id: u64, x: f32, y: f32) -> Result<(), JsValue> {
     let graphics_entity = Entity::from_bits(graphics_id);
@@ -434,6 +601,26 @@ pub fn js_line(graphics_id: u64, x1: f32, y1: f32, x2: f32, y2: f32) -> Result<(
     ))
 }
 
+#[wasm_bindgen(js_name = "line3d")]
+pub fn js_line_3d(
+    graphics_id: u64,
+    x1: f32,
+    y1: f32,
+    z1: f32,
+    x2: f32,
+    y2: f32,
+    z2: f32,
+) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::Line3d {
+            start: Vec3::new(x1, y1, z1),
+            end: Vec3::new(x2, y2, z2),
+        },
+    ))
+}
+
 #[wasm_bindgen(js_name = "triangle")]
 pub fn js_triangle(
     graphics_id: u64,
@@ -495,6 +682,26 @@ pub fn js_point(graphics_id: u64, x: f32, y: f32) -> Result<(), JsValue> {
     ))
 }
 
+#[wasm_bindgen(js_name = "point3d")]
+pub fn js_point_3d(graphics_id: u64, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::Point3d(Vec3::new(x, y, z)),
+    ))
+}
+
+/// Draw many points from a flat `[x0, y0, x1, y1, ...]` array.
+#[wasm_bindgen(js_name = "points")]
+pub fn js_points(graphics_id: u64, points: &[f32]) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let points = points.chunks_exact(2).map(|p| [p[0], p[1]]).collect();
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::Points(points),
+    ))
+}
+
 #[wasm_bindgen(js_name = "square")]
 pub fn js_square(graphics_id: u64, x: f32, y: f32, s: f32) -> Result<(), JsValue> {
     let graphics_entity = Entity::from_bits(graphics_id);
@@ -1032,6 +1239,12 @@ pub fn js_text_wrap(graphics_id: u64, mode: u8) -> Result<(), JsValue> {
     check(graphics_text_wrap(graphics_entity, mode))
 }
 
+#[wasm_bindgen(js_name = "textMode")]
+pub fn js_text_mode(graphics_id: u64, mode: u8) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_text_mode(graphics_entity, mode))
+}
+
 #[wasm_bindgen(js_name = "textWidth")]
 pub fn js_text_width(graphics_id: u64, content: &str) -> Result<f32, JsValue> {
     let graphics_entity = Entity::from_bits(graphics_id);
@@ -1103,6 +1316,29 @@ pub fn js_mode_2d(graphics_id: u64) -> Result<(), JsValue> {
     check(graphics_mode_2d(graphics_entity))
 }
 
+#[wasm_bindgen(js_name = "camera")]
+#[allow(clippy::too_many_arguments)]
+pub fn js_camera(
+    graphics_id: u64,
+    eye_x: f32,
+    eye_y: f32,
+    eye_z: f32,
+    center_x: f32,
+    center_y: f32,
+    center_z: f32,
+    up_x: f32,
+    up_y: f32,
+    up_z: f32,
+) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_camera(
+        graphics_entity,
+        Vec3::new(eye_x, eye_y, eye_z),
+        Vec3::new(center_x, center_y, center_z),
+        Vec3::new(up_x, up_y, up_z),
+    ))
+}
+
 #[wasm_bindgen(js_name = "perspective")]
 pub fn js_perspective(
     graphics_id: u64,
@@ -1247,6 +1483,18 @@ pub const PROCESSING_STROKE_JOIN_ROUND: u8 = 0;
 pub const PROCESSING_STROKE_JOIN_MITER: u8 = 1;
 pub const PROCESSING_STROKE_JOIN_BEVEL: u8 = 2;
 
+pub const PROCESSING_STROKE_ALIGN_INNER: u8 = 0;
+pub const PROCESSING_STROKE_ALIGN_CENTER: u8 = 1;
+pub const PROCESSING_STROKE_ALIGN_OUTER: u8 = 2;
+
+pub const PROCESSING_TEXT_ALIGN_LEFT: u8 = TEXT_ALIGN_LEFT;
+pub const PROCESSING_TEXT_ALIGN_CENTER: u8 = TEXT_ALIGN_CENTER;
+pub const PROCESSING_TEXT_ALIGN_RIGHT: u8 = TEXT_ALIGN_RIGHT;
+pub const PROCESSING_TEXT_ALIGN_BASELINE: u8 = TEXT_ALIGN_BASELINE;
+pub const PROCESSING_TEXT_ALIGN_TOP: u8 = TEXT_ALIGN_TOP;
+pub const PROCESSING_TEXT_ALIGN_MIDDLE: u8 = TEXT_ALIGN_MIDDLE;
+pub const PROCESSING_TEXT_ALIGN_BOTTOM: u8 = TEXT_ALIGN_BOTTOM;
+
 pub const PROCESSING_BLEND_MODE_BLEND: u8 = 0;
 pub const PROCESSING_BLEND_MODE_ADD: u8 = 1;
 pub const PROCESSING_BLEND_MODE_SUBTRACT: u8 = 2;
@@ -1369,23 +1617,23 @@ pub fn js_geometry_attribute_destroy(attr_id: u64) -> Result<(), JsValue> {
 }
 
 #[wasm_bindgen(js_name = "geometryAttributePosition")]
-pub fn js_geometry_attribute_position() -> u64 {
-    geometry_attribute_position().to_bits()
+pub fn js_geometry_attribute_position() -> Result<u64, JsValue> {
+    check(geometry_attribute_position().map(|e| e.to_bits()))
 }
 
 #[wasm_bindgen(js_name = "geometryAttributeNormal")]
-pub fn js_geometry_attribute_normal() -> u64 {
-    geometry_attribute_normal().to_bits()
+pub fn js_geometry_attribute_normal() -> Result<u64, JsValue> {
+    check(geometry_attribute_normal().map(|e| e.to_bits()))
 }
 
 #[wasm_bindgen(js_name = "geometryAttributeColor")]
-pub fn js_geometry_attribute_color() -> u64 {
-    geometry_attribute_color().to_bits()
+pub fn js_geometry_attribute_color() -> Result<u64, JsValue> {
+    check(geometry_attribute_color().map(|e| e.to_bits()))
 }
 
 #[wasm_bindgen(js_name = "geometryAttributeUv")]
-pub fn js_geometry_attribute_uv() -> u64 {
-    geometry_attribute_uv().to_bits()
+pub fn js_geometry_attribute_uv() -> Result<u64, JsValue> {
+    check(geometry_attribute_uv().map(|e| e.to_bits()))
 }
 
 #[wasm_bindgen(js_name = "geometryAttributeFloat")]
@@ -1558,6 +1806,42 @@ pub fn js_model(graphics_id: u64, geo_id: u64) -> Result<(), JsValue> {
     ))
 }
 
+/// Draws a geometry modeled in the unit square stretched over the rect at `x`, `y`, `w`, `h`,
+/// placed by the shape mode.
+#[wasm_bindgen(js_name = "shape")]
+pub fn js_shape(
+    graphics_id: u64,
+    geo_id: u64,
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    check(graphics_record_command(
+        graphics_entity,
+        DrawCommand::Shape {
+            geometry: Entity::from_bits(geo_id),
+            x,
+            y,
+            w,
+            h,
+            material: None,
+        },
+    ))
+}
+
+#[wasm_bindgen(js_name = "modelWithMaterial")]
+pub fn js_model_with_material(graphics_id: u64, geo_id: u64, mat_id: u64) -> Result<(), JsValue> {
+    check(graphics_record_command(
+        Entity::from_bits(graphics_id),
+        DrawCommand::GeometryWithMaterial {
+            geometry: Entity::from_bits(geo_id),
+            material: Entity::from_bits(mat_id),
+        },
+    ))
+}
+
 #[wasm_bindgen(js_name = "geometryBox")]
 pub fn js_geometry_box(width: f32, height: f32, depth: f32) -> Result<u64, JsValue> {
     check(geometry_box(width, height, depth)).map(|e| e.to_bits())
@@ -1568,6 +1852,16 @@ pub fn js_geometry_sphere(radius: f32, sectors: u32, stacks: u32) -> Result<u64,
     check(geometry_sphere(radius, sectors, stacks)).map(|e| e.to_bits())
 }
 
+#[wasm_bindgen(js_name = "geometryBegin")]
+pub fn js_geometry_begin(graphics_id: u64) -> Result<(), JsValue> {
+    check(geometry_begin(Entity::from_bits(graphics_id)))
+}
+
+#[wasm_bindgen(js_name = "geometryEnd")]
+pub fn js_geometry_end(graphics_id: u64) -> Result<u64, JsValue> {
+    check(geometry_end(Entity::from_bits(graphics_id))).map(|e| e.to_bits())
+}
+
 #[wasm_bindgen(js_name = "lightCreateDirectional")]
 pub fn js_light_create_directional(
     graphics_id: u64,
@@ -1593,6 +1887,40 @@ pub fn js_light_create_directional(
     .map(|e| e.to_bits())
 }
 
+#[wasm_bindgen(js_name = "lightSetEnabled")]
+pub fn js_light_set_enabled(light_id: u64, enabled: bool) -> Result<(), JsValue> {
+    check(light_set_enabled(Entity::from_bits(light_id), enabled))
+}
+
+#[wasm_bindgen(js_name = "lightDestroy")]
+pub fn js_light_destroy(light_id: u64) -> Result<(), JsValue> {
+    check(light_destroy(Entity::from_bits(light_id)))
+}
+
+#[wasm_bindgen(js_name = "lightCreateAmbient")]
+pub fn js_light_create_ambient(
+    graphics_id: u64,
+    c1: f32,
+    c2: f32,
+    c3: f32,
+    a: f32,
+    space: u8,
+    brightness: f32,
+) -> Result<(), JsValue> {
+    let graphics_entity = Entity::from_bits(graphics_id);
+    let color = Color {
+        c1,
+        c2,
+        c3,
+        a,
+        space,
+    };
+    check((|| {
+        let mode = graphics_get_color_mode(graphics_entity)?;
+        light_create_ambient(graphics_entity, color.resolve(&mode), brightness)
+    })())
+}
+
 #[wasm_bindgen(js_name = "lightCreatePoint")]
 pub fn js_light_create_point(
     graphics_id: u64,
@@ -1693,6 +2021,15 @@ pub fn js_material_set_float4(
     ))
 }
 
+#[wasm_bindgen(js_name = "materialSetTexture")]
+pub fn js_material_set_texture(mat_id: u64, name: &str, image_id: u64) -> Result<(), JsValue> {
+    check(material_set_texture(
+        Entity::from_bits(mat_id),
+        name,
+        Entity::from_bits(image_id),
+    ))
+}
+
 #[wasm_bindgen(js_name = "materialDestroy")]
 pub fn js_material_destroy(mat_id: u64) -> Result<(), JsValue> {
     check(material_destroy(Entity::from_bits(mat_id)))
@@ -2268,18 +2605,18 @@ fn key_code_to_u32(kc: KeyCode) -> u32 {
 }
 
 #[wasm_bindgen(js_name = "geometryAttributeRotation")]
-pub fn js_geometry_attribute_rotation() -> u64 {
-    geometry_attribute_rotation().to_bits()
+pub fn js_geometry_attribute_rotation() -> Result<u64, JsValue> {
+    check(geometry_attribute_rotation().map(|e| e.to_bits()))
 }
 
 #[wasm_bindgen(js_name = "geometryAttributeScale")]
-pub fn js_geometry_attribute_scale() -> u64 {
-    geometry_attribute_scale().to_bits()
+pub fn js_geometry_attribute_scale() -> Result<u64, JsValue> {
+    check(geometry_attribute_scale().map(|e| e.to_bits()))
 }
 
 #[wasm_bindgen(js_name = "geometryAttributeDead")]
-pub fn js_geometry_attribute_dead() -> u64 {
-    geometry_attribute_dead().to_bits()
+pub fn js_geometry_attribute_dead() -> Result<u64, JsValue> {
+    check(geometry_attribute_dead().map(|e| e.to_bits()))
 }
 
 #[wasm_bindgen(js_name = "particlesCreate")]
@@ -2386,3 +2723,36 @@ pub fn js_material_set_albedo_buffer(entity: u64, buffer: u64) -> Result<(), JsV
         Entity::from_bits(buffer),
     ))
 }
+
+#[wasm_bindgen(js_name = "noise")]
+pub fn js_noise(x: f32, y: f32, z: f32) -> Result<f32, JsValue> {
+    check(noise_3d(x, y, z))
+}
+
+#[wasm_bindgen(js_name = "noiseSeed")]
+pub fn js_noise_seed(seed: u64) -> Result<(), JsValue> {
+    check(noise_seed(seed))
+}
+
+#[wasm_bindgen(js_name = "noiseDetail")]
+pub fn js_noise_detail(octaves: i32, falloff: f32) -> Result<(), JsValue> {
+    check(noise_detail(octaves, falloff))
+}
+
+#[wasm_bindgen(js_name = "random")]
+pub fn js_random(low_or_high: f32, high: Option<f32>) -> Result<f32, JsValue> {
+    match high {
+        Some(high) => check(random_range(low_or_high, high)),
+        None => check(random(low_or_high)),
+    }
+}
+
+#[wasm_bindgen(js_name = "randomSeed")]
+pub fn js_random_seed(seed: u64) -> Result<(), JsValue> {
+    check(random_seed(seed))
+}
+
+#[wasm_bindgen(js_name = "randomGaussian")]
+pub fn js_random_gaussian() -> Result<f32, JsValue> {
+    check(random_gaussian())
+}
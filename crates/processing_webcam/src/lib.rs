@@ -60,46 +60,35 @@ fn destroy(In(entity): In<Entity>, mut commands: Commands) -> Result<()> {
 }
 
 pub fn webcam_create() -> Result<Entity> {
-    app_mut(|app| Ok(app.world_mut().run_system_cached_with(create, ()).unwrap()))
+    app_mut(|app| Ok(app.world_mut().run_system_cached_with(create, ())?))
 }
 
 pub fn webcam_create_with_format(format: WebcamFormat) -> Result<Entity> {
     app_mut(|app| {
         Ok(app
             .world_mut()
-            .run_system_cached_with(create_with_format, format)
-            .unwrap())
+            .run_system_cached_with(create_with_format, format)?)
     })
 }
 
 pub fn webcam_is_connected(entity: Entity) -> Result<bool> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(is_connected, entity)
-            .unwrap()
+            .run_system_cached_with(is_connected, entity)?
     })
 }
 
 pub fn webcam_image(entity: Entity) -> Result<Entity> {
     app_mut(|app| {
         app.world_mut()
-            .run_system_cached_with(create_image, entity)
-            .unwrap()
+            .run_system_cached_with(create_image, entity)?
     })
 }
 
 pub fn webcam_resolution(entity: Entity) -> Result<(u32, u32)> {
-    app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(resolution, entity)
-            .unwrap()
-    })
+    app_mut(|app| app.world_mut().run_system_cached_with(resolution, entity)?)
 }
 
 pub fn webcam_destroy(entity: Entity) -> Result<()> {
-    app_mut(|app| {
-        app.world_mut()
-            .run_system_cached_with(destroy, entity)
-            .unwrap()
-    })
+    app_mut(|app| app.world_mut().run_system_cached_with(destroy, entity)?)
 }
@@ -62,7 +62,7 @@ fn sketch() -> error::Result<()> {
     }
     let bytes: Vec<u8> = floats.iter().flat_map(|f| f.to_le_bytes()).collect();
 
-    let position_attr = geometry_attribute_position();
+    let position_attr = geometry_attribute_position()?;
     let p = particles_create(capacity, vec![position_attr])?;
     let position_buf =
         particles_buffer(p, position_attr)?.ok_or(error::ProcessingError::ParticlesNotFound)?;
@@ -42,8 +42,8 @@ fn sketch() -> error::Result<()> {
         }
     }
 
-    let position_attr = geometry_attribute_position();
-    let color_attr = geometry_attribute_color();
+    let position_attr = geometry_attribute_position()?;
+    let color_attr = geometry_attribute_color()?;
     let p = particles_create(capacity, vec![position_attr, color_attr])?;
     let position_buf =
         particles_buffer(p, position_attr)?.ok_or(error::ProcessingError::ParticlesNotFound)?;
@@ -23,8 +23,8 @@ fn sketch() -> error::Result<()> {
     let sphere = geometry_sphere(0.08, 8, 6)?;
 
     let capacity: u32 = 2000;
-    let position_attr = geometry_attribute_position();
-    let color_attr = geometry_attribute_color();
+    let position_attr = geometry_attribute_position()?;
+    let color_attr = geometry_attribute_color()?;
     let p = particles_create(capacity, vec![position_attr, color_attr])?;
     let position_buf =
         particles_buffer(p, position_attr)?.ok_or(error::ProcessingError::ParticlesNotFound)?;
@@ -136,10 +136,10 @@ fn sketch() -> error::Result<()> {
     let particle = geometry_sphere(0.12, 8, 6)?;
 
     let capacity: u32 = 40000;
-    let position_attr = geometry_attribute_position();
-    let color_attr = geometry_attribute_color();
-    let scale_attr = geometry_attribute_scale();
-    let dead_attr = geometry_attribute_dead();
+    let position_attr = geometry_attribute_position()?;
+    let color_attr = geometry_attribute_color()?;
+    let scale_attr = geometry_attribute_scale()?;
+    let dead_attr = geometry_attribute_dead()?;
     let velocity_attr = geometry_attribute_create("velocity", AttributeFormat::Float3)?;
     let age_attr = geometry_attribute_create("age", AttributeFormat::Float)?;
 
@@ -61,10 +61,10 @@ fn sketch() -> error::Result<()> {
     let sphere = geometry_sphere(0.1, 8, 6)?;
 
     let capacity: u32 = 800;
-    let position_attr = geometry_attribute_position();
-    let color_attr = geometry_attribute_color();
-    let scale_attr = geometry_attribute_scale();
-    let dead_attr = geometry_attribute_dead();
+    let position_attr = geometry_attribute_position()?;
+    let color_attr = geometry_attribute_color()?;
+    let scale_attr = geometry_attribute_scale()?;
+    let dead_attr = geometry_attribute_dead()?;
     let age_attr = geometry_attribute_create("age", AttributeFormat::Float)?;
 
     let p = particles_create(
@@ -25,9 +25,9 @@ fn sketch() -> error::Result<()> {
         light_create_directional(graphics, bevy::color::Color::srgb(0.95, 0.9, 0.85), 200.0)?;
 
     let source = geometry_sphere(5.0, 32, 24)?;
-    let position_attr = geometry_attribute_position();
-    let uv_attr = geometry_attribute_uv();
-    let color_attr = geometry_attribute_color();
+    let position_attr = geometry_attribute_position()?;
+    let uv_attr = geometry_attribute_uv()?;
+    let color_attr = geometry_attribute_color()?;
     let p = particles_create_from_geometry(source, vec![position_attr, uv_attr, color_attr])?;
 
     let uv_buf = particles_buffer(p, uv_attr)?.ok_or(error::ProcessingError::ParticlesNotFound)?;
@@ -83,9 +83,9 @@ fn sketch() -> error::Result<()> {
         }
     }
 
-    let position_attr = geometry_attribute_position();
-    let rotation_attr = geometry_attribute_rotation();
-    let scale_attr = geometry_attribute_scale();
+    let position_attr = geometry_attribute_position()?;
+    let rotation_attr = geometry_attribute_rotation()?;
+    let scale_attr = geometry_attribute_scale()?;
     let p = particles_create(capacity, vec![position_attr, rotation_attr, scale_attr])?;
     let position_buf =
         particles_buffer(p, position_attr)?.ok_or(error::ProcessingError::ParticlesNotFound)?;
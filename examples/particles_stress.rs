@@ -78,8 +78,8 @@ fn sketch() -> error::Result<()> {
     let cube = geometry_box(0.35, 0.35, 0.35)?;
 
     let capacity = GRID * GRID * GRID;
-    let position_attr = geometry_attribute_position();
-    let color_attr = geometry_attribute_color();
+    let position_attr = geometry_attribute_position()?;
+    let color_attr = geometry_attribute_color()?;
     let p = particles_create(capacity, vec![position_attr, color_attr])?;
 
     let mut positions: Vec<f32> = Vec::with_capacity(capacity as usize * 3);
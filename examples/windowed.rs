@@ -0,0 +1,36 @@
+use processing::prelude::*;
+use processing_render::render::command::DrawCommand;
+
+fn main() {
+    if let Err(e) = sketch() {
+        eprintln!("Sketch error: {:?}", e);
+    }
+}
+
+// no GLFW here, bevy creates the window and drives the loop
+fn sketch() -> error::Result<()> {
+    let mut x = 0.0;
+
+    run_windowed(
+        Config::default(),
+        |graphics| {
+            graphics_record_command(
+                graphics,
+                DrawCommand::BackgroundColor(bevy::color::Color::srgb(0.1, 0.1, 0.1)),
+            )
+        },
+        move |graphics| {
+            x = (x + 2.0) % 400.0;
+            graphics_record_command(
+                graphics,
+                DrawCommand::Rect {
+                    x,
+                    y: 10.0,
+                    w: 100.0,
+                    h: 100.0,
+                    radii: [0.0, 0.0, 0.0, 0.0],
+                },
+            )
+        },
+    )
+}
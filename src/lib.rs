@@ -1,4 +1,6 @@
 pub mod prelude;
+#[cfg(not(target_arch = "wasm32"))]
+mod windowed;
 
 use std::num::NonZero;
 
@@ -6,6 +8,7 @@ use bevy::app::{App, AppExit};
 use bevy::asset::{AssetApp, io::AssetSourceBuilder};
 use bevy::prelude::*;
 use bevy::render::RenderPlugin;
+use bevy::render::settings::{Backends, WgpuSettings};
 
 use processing_core::config::{Config, ConfigKey};
 use processing_core::error;
@@ -33,20 +36,42 @@ fn create_app(config: Config) -> App {
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    let plugins = DefaultPlugins
-        .build()
-        .set(RenderPlugin {
-            synchronous_pipeline_compilation: true,
-            ..default()
-        })
-        .disable::<bevy::winit::WinitPlugin>()
-        .disable::<bevy::log::LogPlugin>()
-        .disable::<bevy::render::pipelined_rendering::PipelinedRenderingPlugin>()
-        .set(WindowPlugin {
-            primary_window: None,
-            exit_condition: bevy::window::ExitCondition::DontExit,
-            ..default()
-        });
+    let plugins = {
+        let plugins = DefaultPlugins
+            .build()
+            .set(RenderPlugin {
+                render_creation: wgpu_settings(&config).into(),
+                synchronous_pipeline_compilation: true,
+                ..default()
+            })
+            .disable::<bevy::log::LogPlugin>()
+            .disable::<bevy::render::pipelined_rendering::PipelinedRenderingPlugin>();
+
+        if config
+            .get(ConfigKey::Windowing)
+            .is_some_and(|w| w == processing_core::config::WINDOWING_WINIT)
+        {
+            // bevy owns the window and the event loop, see `run_windowed`
+            plugins
+                .set(bevy::winit::WinitPlugin {
+                    run_on_any_thread: true,
+                    ..default()
+                })
+                .set(WindowPlugin {
+                    primary_window: Some(Window::default()),
+                    exit_condition: bevy::window::ExitCondition::OnPrimaryClosed,
+                    ..default()
+                })
+        } else {
+            plugins
+                .disable::<bevy::winit::WinitPlugin>()
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: bevy::window::ExitCondition::DontExit,
+                    ..default()
+                })
+        }
+    };
 
     #[cfg(target_arch = "wasm32")]
     let plugins = DefaultPlugins
@@ -73,6 +98,47 @@ fn create_app(config: Config) -> App {
     app
 }
 
+/// wgpu settings honouring the [`ConfigKey::Backends`] and [`ConfigKey::AdapterName`] keys. Unset
+/// keys keep wgpu's defaults, which read the `WGPU_BACKEND` and `WGPU_ADAPTER_NAME` variables.
+fn wgpu_settings(config: &Config) -> WgpuSettings {
+    let mut settings = WgpuSettings::default();
+    if let Some(backends) = config.get(ConfigKey::Backends) {
+        settings.backends = Some(Backends::from_comma_list(backends));
+    }
+    if let Some(name) = config.get(ConfigKey::AdapterName) {
+        settings.adapter_name = Some(name.clone());
+    }
+    settings
+}
+
+/// A GPU adapter wgpu can render with.
+#[derive(Debug, Clone)]
+pub struct Adapter {
+    /// The name to pass as [`ConfigKey::AdapterName`] to select this adapter.
+    pub name: String,
+    pub backend: String,
+}
+
+/// List the adapters available on every backend, for choosing one with
+/// [`ConfigKey::AdapterName`]. Can be called before [`init`].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn list_adapters() -> Vec<Adapter> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::all(),
+        ..default()
+    });
+    bevy::tasks::block_on(instance.enumerate_adapters(wgpu::Backends::all()))
+        .iter()
+        .map(|adapter| {
+            let info = adapter.get_info();
+            Adapter {
+                name: info.name,
+                backend: info.backend.to_string(),
+            }
+        })
+        .collect()
+}
+
 /// Initialize the app, if not already initialized. Must be called from the main thread and cannot
 /// be called concurrently from multiple threads.
 #[cfg(not(target_arch = "wasm32"))]
@@ -80,7 +146,7 @@ pub fn init(config: Config) -> error::Result<()> {
     if processing_core::is_already_init()? {
         return Ok(());
     }
-    setup_tracing(config.get(ConfigKey::LogLevel).map(|s| s.as_str()))?;
+    setup_tracing(&config)?;
 
     let mut app = create_app(config);
     // contrary to what the following methods might imply, this is just finishing plugin setup
@@ -97,6 +163,17 @@ pub fn init(config: Config) -> error::Result<()> {
     Ok(())
 }
 
+/// Initialize the app on a dedicated render thread instead of the calling one. Draw commands can
+/// then be recorded from any thread, and [`run_on_render_thread`] runs the rest of the API on the
+/// render thread and waits for the result. Other calls made from outside the render thread fail
+/// with a [`error::ProcessingError::RenderThread`] error.
+///
+/// [`run_on_render_thread`]: processing_core::run_on_render_thread
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn_render_thread(config: Config) -> error::Result<()> {
+    processing_core::spawn_render_thread(move || init(config))
+}
+
 /// Initialize the app asynchronously
 #[cfg(target_arch = "wasm32")]
 pub async fn init(config: Config) -> error::Result<()> {
@@ -105,7 +182,7 @@ pub async fn init(config: Config) -> error::Result<()> {
     if processing_core::is_already_init()? {
         return Ok(());
     }
-    setup_tracing(config.get(ConfigKey::LogLevel).map(|s| s.as_str()))?;
+    setup_tracing(&config)?;
 
     let mut app = create_app(config);
 
@@ -131,6 +208,9 @@ pub async fn init(config: Config) -> error::Result<()> {
 }
 
 pub fn exit(exit_code: u8) -> error::Result<()> {
+    if processing_core::is_off_render_thread() {
+        return processing_core::run_on_render_thread(move || exit(exit_code));
+    }
     processing_core::app_mut(|app| {
         app.world_mut().write_message(match exit_code {
             0 => AppExit::Success,
@@ -149,21 +229,115 @@ pub fn exit(exit_code: u8) -> error::Result<()> {
     Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use windowed::run_windowed;
+
 #[cfg(feature = "cuda")]
 pub use processing_cuda;
 
-fn setup_tracing(log_level: Option<&str>) -> error::Result<()> {
-    // TODO: figure out wasm compatible tracing subscriber
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        use tracing_subscriber::EnvFilter;
-
-        let filter = EnvFilter::try_new(log_level.unwrap_or("info"))
-            .unwrap_or_else(|_| EnvFilter::new("info"));
-        let subscriber = tracing_subscriber::FmtSubscriber::builder()
-            .with_env_filter(filter)
-            .finish();
-        tracing::subscriber::set_global_default(subscriber)?;
+/// Directives applied before the configured ones, keeping wgpu's chatter out of the log.
+const DEFAULT_LOG_TARGETS: &str = "wgpu=error,naga=warn";
+
+fn log_filter(config: &Config) -> tracing_subscriber::EnvFilter {
+    use tracing_subscriber::EnvFilter;
+
+    let level = config
+        .get(ConfigKey::LogLevel)
+        .map_or("info", |s| s.as_str());
+    let mut directives = format!("{DEFAULT_LOG_TARGETS},{level}");
+    if let Some(targets) = config.get(ConfigKey::LogTargets) {
+        directives.push(',');
+        directives.push_str(targets);
     }
+    EnvFilter::try_new(directives)
+        .unwrap_or_else(|_| EnvFilter::new(format!("{DEFAULT_LOG_TARGETS},info")))
+}
+
+fn setup_tracing(config: &Config) -> error::Result<()> {
+    let filter = log_filter(config);
+    #[cfg(not(target_arch = "wasm32"))]
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_env_filter(filter)
+        .finish();
+    // the fmt subscriber writes to stdout, which goes nowhere in a browser
+    #[cfg(target_arch = "wasm32")]
+    let subscriber = {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(tracing_wasm::WASMLayer::new(
+                tracing_wasm::WASMLayerConfig::default(),
+            ))
+    };
+    tracing::subscriber::set_global_default(subscriber)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "needs a display"]
+    fn winit_windowing_initializes() {
+        let mut config = Config::new();
+        config.set(
+            ConfigKey::Windowing,
+            processing_core::config::WINDOWING_WINIT.to_string(),
+        );
+        let app = create_app(config);
+        assert!(app.is_plugin_added::<bevy::winit::WinitPlugin>());
+    }
+
+    #[test]
+    fn backend_config_limits_backends() {
+        let mut config = Config::new();
+        assert_eq!(
+            wgpu_settings(&config).backends,
+            WgpuSettings::default().backends
+        );
+        config.set(ConfigKey::Backends, "vulkan,gl".to_string());
+        config.set(ConfigKey::AdapterName, "llvmpipe".to_string());
+        let settings = wgpu_settings(&config);
+        assert_eq!(settings.backends, Some(Backends::VULKAN | Backends::GL));
+        assert_eq!(settings.adapter_name.as_deref(), Some("llvmpipe"));
+    }
+
+    #[test]
+    fn error_level_suppresses_info() {
+        use tracing::Level;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let mut config = Config::new();
+        config.log_level(Level::ERROR);
+        config.log_target("processing_render", Level::DEBUG);
+        let subscriber = tracing_subscriber::registry().with(log_filter(&config));
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(!tracing::enabled!(target: "sketch", Level::INFO));
+            assert!(tracing::enabled!(target: "sketch", Level::ERROR));
+            assert!(tracing::enabled!(target: "processing_render::image", Level::DEBUG));
+        });
+
+        // wgpu stays quiet at the default level
+        let subscriber = tracing_subscriber::registry().with(log_filter(&Config::new()));
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(tracing::enabled!(target: "sketch", Level::INFO));
+            assert!(!tracing::enabled!(target: "wgpu_core::device", Level::WARN));
+        });
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn adapters_are_listed() {
+        let adapters = list_adapters();
+        assert!(!adapters.is_empty());
+        assert!(adapters.iter().all(|a| !a.name.is_empty()));
+    }
+
+    #[test]
+    fn exit_before_init_is_not_initialized() {
+        let res = exit(0);
+        assert!(matches!(res, Err(error::ProcessingError::NotInitialized)));
+    }
+}
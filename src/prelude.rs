@@ -2,7 +2,7 @@ pub use bevy::input::keyboard::KeyCode;
 pub use bevy::input::mouse::MouseButton;
 pub use bevy::prelude::default;
 pub use bevy::render::render_resource::TextureFormat;
-pub use processing_core::{config::*, error};
+pub use processing_core::{config::*, error, run_on_render_thread};
 pub use processing_input::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use processing_midi::{
@@ -11,10 +11,18 @@ pub use processing_midi::{
 };
 pub use processing_render::{
     render::command::{
-        ArcMode, BlendMode, DrawCommand, ShapeKind, ShapeMode, StrokeCapMode, StrokeJoinMode,
-        TextAlignH, TextAlignV, TextStyle, TextWrapMode, custom_blend_state,
+        ArcMode, BlendMode, DrawCommand, GradientDirection, ShapeKind, ShapeMode, StrokeAlignMode,
+        StrokeCapMode, StrokeJoinMode, TEXT_ALIGN_BASELINE, TEXT_ALIGN_BOTTOM, TEXT_ALIGN_CENTER,
+        TEXT_ALIGN_LEFT, TEXT_ALIGN_MIDDLE, TEXT_ALIGN_RIGHT, TEXT_ALIGN_TOP, TextAlignH,
+        TextAlignV, TextStyle, TextWrapMode, custom_blend_state,
     },
     *,
 };
 
-pub use crate::{exit, init};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::list_adapters;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::run_windowed;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::spawn_render_thread;
+pub use crate::{Adapter, exit, init};
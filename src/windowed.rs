@@ -0,0 +1,118 @@
+//! Bevy-managed windowing, where winit owns the window and the event loop instead of an external
+//! library like GLFW. See [`ConfigKey::Windowing`].
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::window::{PrimaryWindow, RawHandleWrapper};
+
+use processing_core::config::{Config, ConfigKey, WINDOWING_WINIT};
+use processing_core::error::{self, ProcessingError};
+use processing_render::{graphics, surface, time};
+
+type SetupFn = Box<dyn FnOnce(Entity) -> error::Result<()>>;
+type DrawFn = Box<dyn FnMut(Entity) -> error::Result<()>>;
+
+/// Run a sketch in a window that Bevy creates and drives via winit, rather than one handed to us
+/// by an external windowing library like GLFW. `setup` is called once with the graphics entity of
+/// the window and `draw` every frame after that, both between the usual begin/end of a draw, so
+/// they should only record commands. The frame count advances before each `draw`, so `setup` sees
+/// 0 and the first `draw` sees 1. Blocks until the window is closed.
+///
+/// This is an alternative to [`crate::init`], and can't be combined with it.
+pub fn run_windowed(
+    mut config: Config,
+    setup: impl FnOnce(Entity) -> error::Result<()> + 'static,
+    draw: impl FnMut(Entity) -> error::Result<()> + 'static,
+) -> error::Result<()> {
+    if processing_core::is_already_init()? {
+        return Err(ProcessingError::InvalidArgument(
+            "run_windowed can't be called after init".to_string(),
+        ));
+    }
+    config.set(ConfigKey::Windowing, WINDOWING_WINIT.to_string());
+    crate::setup_tracing(&config)?;
+
+    let error = Rc::new(RefCell::new(None));
+    let mut app = crate::create_app(config);
+    app.insert_non_send_resource(WindowedSketch {
+        graphics: None,
+        setup: Some(Box::new(setup)),
+        draw: Box::new(draw),
+        error: error.clone(),
+    });
+    app.add_systems(
+        Update,
+        run_windowed_frame.before(processing_render::render::flush_draw_commands),
+    );
+
+    // the winit runner takes ownership of the app until the window is closed
+    app.run();
+
+    match error.take() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+struct WindowedSketch {
+    graphics: Option<Entity>,
+    setup: Option<SetupFn>,
+    draw: DrawFn,
+    error: Rc<RefCell<Option<ProcessingError>>>,
+}
+
+impl WindowedSketch {
+    fn frame(&mut self, world: &mut World) -> error::Result<()> {
+        let graphics = match self.graphics {
+            Some(graphics) => graphics,
+            None => {
+                let mut windows = world
+                    .query_filtered::<(Entity, &Window), (With<PrimaryWindow>, With<RawHandleWrapper>)>();
+                // winit creates the window on its first resume, which may be a few frames in
+                let Ok((window, size)) = windows.single(world).map(|(e, w)| {
+                    (
+                        e,
+                        (w.resolution.width() as u32, w.resolution.height() as u32),
+                    )
+                }) else {
+                    return Ok(());
+                };
+                world
+                    .entity_mut(window)
+                    .insert((surface::Surface, surface::WindowControls::default()));
+
+                let graphics = world.run_system_cached_with(
+                    graphics::create,
+                    (size.0, size.1, window, TextureFormat::Rgba16Float),
+                )??;
+                world.run_system_cached_with(graphics::present_continuously, graphics)??;
+                self.graphics = Some(graphics);
+                graphics
+            }
+        };
+
+        world.run_system_cached_with(graphics::begin_draw, graphics)??;
+        if let Some(setup) = self.setup.take() {
+            processing_core::with_lent_world(world, || setup(graphics))?;
+        }
+        world.run_system_cached(time::advance_frame_count)?;
+        processing_core::with_lent_world(world, || (self.draw)(graphics))
+    }
+}
+
+fn run_windowed_frame(world: &mut World) {
+    let Some(mut sketch) = world.remove_non_send_resource::<WindowedSketch>() else {
+        return;
+    };
+    if sketch.error.borrow().is_none()
+        && let Err(e) = sketch.frame(world)
+    {
+        *sketch.error.borrow_mut() = Some(e);
+        world.write_message(AppExit::error());
+    }
+    world.insert_non_send_resource(sketch);
+}
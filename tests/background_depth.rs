@@ -0,0 +1,44 @@
+use bevy::color::LinearRgba;
+use bevy::math::{Vec2, Vec3};
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn background_hides_geometry_drawn_before_it() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (64, 64);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let cube = geometry_box(20.0, 20.0, 20.0)?;
+    let center = (height / 2 * width + width / 2) as usize;
+
+    graphics_mode_3d(graphics)?;
+    transform_set_position(graphics, Vec3::new(0.0, 0.0, 100.0))?;
+    transform_look_at(graphics, Vec3::ZERO)?;
+
+    // the cube sits in the middle of the first frame
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::RED.into()),
+    )?;
+    graphics_record_command(graphics, DrawCommand::Geometry(cube))?;
+    graphics_end_draw(graphics)?;
+
+    // then moves aside, and the background drawn over it in the same flush must win
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(graphics, DrawCommand::Geometry(cube))?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::BLUE.into()),
+    )?;
+    graphics_record_command(graphics, DrawCommand::PushMatrix)?;
+    graphics_record_command(graphics, DrawCommand::Translate(Vec2::new(30.0, 0.0)))?;
+    graphics_record_command(graphics, DrawCommand::Geometry(cube))?;
+    graphics_record_command(graphics, DrawCommand::PopMatrix)?;
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    assert_eq!(pixels[center], LinearRgba::BLUE);
+    Ok(())
+}
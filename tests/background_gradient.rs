@@ -0,0 +1,38 @@
+use bevy::color::LinearRgba;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn gradient_blends_through_the_middle() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (64, 64);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+
+    let close =
+        |a: LinearRgba, b: LinearRgba| (a.to_vec4() - b.to_vec4()).abs().max_element() < 0.03;
+    let purple = LinearRgba::new(0.5, 0.0, 0.5, 1.0);
+    for (direction, start, end) in [
+        (GradientDirection::Horizontal, (0, 32), (63, 32)),
+        (GradientDirection::Vertical, (32, 0), (32, 63)),
+    ] {
+        graphics_begin_draw(graphics)?;
+        graphics_record_command(
+            graphics,
+            DrawCommand::BackgroundGradient {
+                from: LinearRgba::RED.into(),
+                to: LinearRgba::BLUE.into(),
+                direction,
+            },
+        )?;
+        let pixels = graphics_readback(graphics)?;
+        graphics_end_draw(graphics)?;
+
+        let at = |(x, y): (u32, u32)| pixels[(y * width + x) as usize];
+        assert!(close(at(start), LinearRgba::RED), "{direction:?} start");
+        assert!(close(at(end), LinearRgba::BLUE), "{direction:?} end");
+        // the colors are interpolated in linear space, so the middle is an even mix
+        assert!(close(at((32, 32)), purple), "{direction:?} middle");
+    }
+    Ok(())
+}
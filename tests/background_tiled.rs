@@ -0,0 +1,65 @@
+use bevy::asset::Assets;
+use bevy::image::ImageSampler;
+use bevy::render::render_resource::Extent3d;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn tiled_background_repeats_without_touching_the_image() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (32, 16);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+
+    // red on the left, green on the right
+    let size = Extent3d {
+        width: 2,
+        height: 1,
+        depth_or_array_layers: 1,
+    };
+    let data = vec![255, 0, 0, 255, 0, 255, 0, 255];
+    let image = image_create(size, data, TextureFormat::Rgba8Unorm)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_background_tiled(graphics, image, 8.0, 16.0)?;
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    let at = |x: u32| pixels[(8 * width + x) as usize];
+    let close = |a: bevy::color::LinearRgba, b: bevy::color::LinearRgba| {
+        (a.red - b.red).abs() < 1e-3 && (a.green - b.green).abs() < 1e-3
+    };
+    assert!(
+        at(1).red > at(1).green,
+        "left of a tile is red: {:?}",
+        at(1)
+    );
+    assert!(
+        at(6).green > at(6).red,
+        "right of a tile is green: {:?}",
+        at(6)
+    );
+    for tile in 1..4 {
+        assert!(
+            close(at(1), at(1 + tile * 8)),
+            "tile {tile} starts like the first"
+        );
+        assert!(
+            close(at(6), at(6 + tile * 8)),
+            "tile {tile} ends like the first"
+        );
+    }
+
+    let sampler = processing_core::app_mut(|app| {
+        let world = app.world();
+        let handle = world
+            .get::<processing_render::image::Image>(image)
+            .unwrap()
+            .handle
+            .clone();
+        let images = world.resource::<Assets<bevy::image::Image>>();
+        Ok(images.get(&handle).unwrap().sampler.clone())
+    })?;
+    assert!(matches!(sampler, ImageSampler::Default));
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use bevy::math::Vec3;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn box_is_nearer_than_the_background() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (64, 64);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let cube = geometry_box(20.0, 20.0, 20.0)?;
+
+    graphics_mode_3d(graphics)?;
+    transform_set_position(graphics, Vec3::new(0.0, 0.0, 100.0))?;
+    transform_look_at(graphics, Vec3::ZERO)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(graphics, DrawCommand::Geometry(cube))?;
+    let depth = graphics_readback_depth(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    assert_eq!(depth.len(), (width * height) as usize);
+    let center = depth[(height / 2 * width + width / 2) as usize];
+    let corner = depth[0];
+    // depth is reversed, so nearer is larger
+    assert!(
+        center > corner,
+        "center {center} should be nearer than corner {corner}"
+    );
+    Ok(())
+}
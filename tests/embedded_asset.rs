@@ -0,0 +1,40 @@
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn image_loads_from_embedded_source() -> error::Result<()> {
+    let mut config = Config::default();
+    config.asset_source("embedded");
+    init(config)?;
+    // no file by this name exists, so it can only come from the binary
+    asset_embed(
+        "bundled/logo.png",
+        include_bytes!("../assets/images/logo.png"),
+    )?;
+
+    let image = image_load("bundled/logo.png")?;
+    assert_eq!(image_readback(image)?.len(), 2048 * 2048);
+    assert!(image_load("bundled/missing.png").is_err());
+    Ok(())
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn obj_and_table_load_from_embedded_source() -> error::Result<()> {
+    let mut config = Config::default();
+    config.asset_source("embedded");
+    init(config)?;
+    asset_embed(
+        "bundled/triangle.obj",
+        b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n",
+    )?;
+    asset_embed("bundled/scores.csv", b"name,value\na,1.5\n")?;
+
+    let geometry = obj_load("bundled/triangle.obj")?;
+    assert_eq!(geometry_vertex_count(geometry)?, 3);
+    let table = table_load("bundled/scores.csv")?;
+    assert_eq!(table.get(0, 0), Some("a"));
+    assert_eq!(table.get_float(0, 1), Some(1.5));
+    assert!(obj_load("bundled/missing.obj").is_err());
+    Ok(())
+}
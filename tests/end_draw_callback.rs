@@ -0,0 +1,29 @@
+use std::sync::{Arc, Mutex};
+
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn end_draw_callback_fires_once_per_frame() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (16, 16);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let seen = frames.clone();
+    graphics_on_end_draw(graphics, move |frame| seen.lock().unwrap().push(frame))?;
+
+    for _ in 0..3 {
+        advance_frame_count()?;
+        graphics_begin_draw(graphics)?;
+        graphics_end_draw(graphics)?;
+    }
+    assert_eq!(*frames.lock().unwrap(), vec![1, 2, 3]);
+
+    graphics_clear_end_draw_callback(graphics)?;
+    graphics_begin_draw(graphics)?;
+    graphics_end_draw(graphics)?;
+    assert_eq!(frames.lock().unwrap().len(), 3);
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use bevy::color::LinearRgba;
+use processing::prelude::*;
+use std::time::Duration;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn timings_cover_the_last_flush_and_readback() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (32, 32);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::BLACK.into()),
+    )?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::Ellipse {
+            cx: 16.0,
+            cy: 16.0,
+            w: 20.0,
+            h: 20.0,
+        },
+    )?;
+    graphics_readback(graphics)?;
+
+    let timings = graphics_frame_timings(graphics)?;
+    assert!(timings.tessellation > Duration::ZERO);
+    assert!(timings.command_flush >= timings.tessellation);
+    assert!(timings.gpu_submit > Duration::ZERO);
+    assert!(timings.readback > Duration::ZERO);
+
+    // a flush without a readback doesn't keep the previous readback's time
+    graphics_flush(graphics)?;
+    assert_eq!(graphics_frame_timings(graphics)?.readback, Duration::ZERO);
+    graphics_end_draw(graphics)?;
+    Ok(())
+}
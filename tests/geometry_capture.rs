@@ -0,0 +1,53 @@
+use bevy::ecs::entity::Entity;
+use bevy::math::Vec2;
+use processing::prelude::*;
+
+fn capture(graphics: Entity, commands: Vec<DrawCommand>) -> error::Result<Entity> {
+    geometry_begin(graphics)?;
+    for command in commands {
+        graphics_record_command(graphics, command)?;
+    }
+    geometry_end(graphics)
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn two_translated_spheres_become_one_geometry() -> error::Result<()> {
+    init(Config::default())?;
+    let surface = surface_create_offscreen(32, 32, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, 32, 32, TextureFormat::Rgba16Float)?;
+    graphics_mode_3d(graphics)?;
+    let sphere = DrawCommand::Sphere {
+        radius: 1.0,
+        sectors: 8,
+        stacks: 6,
+    };
+
+    graphics_begin_draw(graphics)?;
+    let single = capture(graphics, vec![sphere.clone()])?;
+    let merged = capture(
+        graphics,
+        vec![
+            DrawCommand::Fill(bevy::color::Color::WHITE),
+            DrawCommand::PushMatrix,
+            DrawCommand::Translate(Vec2::new(-5.0, 0.0)),
+            sphere.clone(),
+            DrawCommand::PopMatrix,
+            DrawCommand::Translate(Vec2::new(5.0, 0.0)),
+            sphere,
+        ],
+    )?;
+    graphics_end_draw(graphics)?;
+
+    let count = geometry_vertex_count(merged)?;
+    assert_eq!(count, 2 * geometry_vertex_count(single)?);
+    let positions = geometry_get_positions(merged, 0, count as usize)?;
+    let min_x = positions.iter().map(|p| p[0]).fold(f32::INFINITY, f32::min);
+    let max_x = positions
+        .iter()
+        .map(|p| p[0])
+        .fold(f32::NEG_INFINITY, f32::max);
+    assert!((min_x + 6.0).abs() < 1e-4, "min x {min_x}");
+    assert!((max_x - 6.0).abs() < 1e-4, "max x {max_x}");
+    Ok(())
+}
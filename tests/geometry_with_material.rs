@@ -0,0 +1,94 @@
+use bevy::color::LinearRgba;
+use bevy::math::{Vec2, Vec3};
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn one_geometry_draws_with_two_materials() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (64, 32);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let cube = geometry_box(10.0, 10.0, 10.0)?;
+    let red = material_create_unlit()?;
+    material_set_albedo_color(red, [1.0, 0.0, 0.0, 1.0])?;
+    let green = material_create_unlit()?;
+    material_set_albedo_color(green, [0.0, 1.0, 0.0, 1.0])?;
+
+    graphics_mode_3d(graphics)?;
+    transform_set_position(graphics, Vec3::new(0.0, 0.0, 100.0))?;
+    transform_look_at(graphics, Vec3::ZERO)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::BLACK.into()),
+    )?;
+    for (x, material) in [(-15.0, red), (15.0, green)] {
+        graphics_record_command(graphics, DrawCommand::PushMatrix)?;
+        graphics_record_command(graphics, DrawCommand::Translate(Vec2::new(x, 0.0)))?;
+        graphics_record_command(
+            graphics,
+            DrawCommand::GeometryWithMaterial {
+                geometry: cube,
+                material,
+            },
+        )?;
+        graphics_record_command(graphics, DrawCommand::PopMatrix)?;
+    }
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    let has = |color: LinearRgba| {
+        pixels.iter().any(|p| {
+            (p.red - color.red).abs() < 1e-2
+                && (p.green - color.green).abs() < 1e-2
+                && (p.blue - color.blue).abs() < 1e-2
+        })
+    };
+    assert!(has(LinearRgba::RED), "no pixel drawn with the red material");
+    assert!(
+        has(LinearRgba::GREEN),
+        "no pixel drawn with the green material"
+    );
+    Ok(())
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn shape_draws_with_a_material() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (64, 32);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let square = geometry_box(1.0, 1.0, 1.0)?;
+    let green = material_create_unlit()?;
+    material_set_albedo_color(green, [0.0, 1.0, 0.0, 1.0])?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::BLACK.into()),
+    )?;
+    graphics_record_command(graphics, DrawCommand::Fill(LinearRgba::RED.into()))?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::Shape {
+            geometry: square,
+            x: 32.0,
+            y: 16.0,
+            w: 16.0,
+            h: 16.0,
+            material: Some(green),
+        },
+    )?;
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    let center = pixels[(16 * width + 32) as usize];
+    assert!(
+        center.green > 0.9 && center.red < 0.1,
+        "shape drawn with the current fill instead of its material: {center:?}"
+    );
+    Ok(())
+}
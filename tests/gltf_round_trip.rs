@@ -0,0 +1,26 @@
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn exported_geometry_loads_back_with_its_material() -> error::Result<()> {
+    let dir = std::env::temp_dir().join("processing_gltf_round_trip");
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut config = Config::new();
+    config.set(ConfigKey::AssetRootPath, dir.to_string_lossy().into_owned());
+    init(config)?;
+
+    let surface = surface_create_offscreen(32, 32, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, 32, 32, TextureFormat::Rgba16Float)?;
+    let cube = geometry_box(10.0, 10.0, 10.0)?;
+    let red = material_create_pbr()?;
+    material_set_albedo_color(red, [1.0, 0.0, 0.0, 1.0])?;
+
+    geometry_save_gltf(cube, dir.join("cube.glb").to_str().unwrap(), Some(red))?;
+    let gltf = gltf_load(graphics, "cube.glb")?;
+
+    let loaded = gltf_geometry(gltf, "cube")?;
+    assert_eq!(geometry_vertex_count(loaded)?, geometry_vertex_count(cube)?);
+    assert!(gltf_material_names(gltf)?.contains(&"cube".to_string()));
+    gltf_material(gltf, "cube")?;
+    Ok(())
+}
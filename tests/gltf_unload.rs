@@ -0,0 +1,32 @@
+//! Loads glTF scenes through the full app, so this runs as its own test binary rather than next
+//! to tests that expect the app to be uninitialized.
+use bevy::prelude::{Assets, Mesh};
+use processing::prelude::*;
+
+fn mesh_count() -> error::Result<usize> {
+    processing_core::app_mut(|app| {
+        // dropped handles are only collected when the app updates
+        app.update();
+        Ok(app.world().resource::<Assets<Mesh>>().len())
+    })
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn repeated_gltf_loads_are_freed() -> error::Result<()> {
+    init(Config::default())?;
+    let surface = surface_create_offscreen(4, 4, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, 4, 4, TextureFormat::Rgba16Float)?;
+    let baseline = mesh_count()?;
+
+    for _ in 0..5 {
+        let gltf = gltf_load(graphics, "gltf/Duck.glb")?;
+        let duck = gltf_geometry(gltf, "LOD3spShape")?;
+        gltf_unload(gltf)?;
+        // the geometry keeps the mesh it was taken from
+        assert!(mesh_count()? > baseline);
+        geometry_destroy(duck)?;
+        assert_eq!(mesh_count()?, baseline);
+    }
+    Ok(())
+}
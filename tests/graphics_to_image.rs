@@ -0,0 +1,90 @@
+use bevy::color::LinearRgba;
+use bevy::render::render_resource::Extent3d;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn copy_from_graphics_into_image() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (16, 8);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let size = Extent3d {
+        width: 8,
+        height: 8,
+        depth_or_array_layers: 1,
+    };
+    let image = image_create(size, vec![0; 8 * 8 * 4], TextureFormat::Rgba8Unorm)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::RED.into()),
+    )?;
+    image_copy(graphics, 0, 0, 4, 4, image, 0, 0, 4, 4)?;
+    graphics_end_draw(graphics)?;
+
+    let pixels = image_readback(image)?;
+    let is_red = |p: &LinearRgba| (p.red - 1.0).abs() < 1e-2 && p.green < 1e-2 && p.blue < 1e-2;
+    assert!(is_red(&pixels[0]), "copied pixel is {:?}", pixels[0]);
+    assert!(is_red(&pixels[3 * 8 + 3]));
+    assert_eq!(pixels[7 * 8 + 7], LinearRgba::NONE);
+    Ok(())
+}
+
+#[test]
+#[ignore = "needs a GPU and a display"]
+fn copy_from_on_screen_graphics_into_image() -> error::Result<()> {
+    let (width, height) = (16, 8);
+    let mut glfw_ctx = processing_glfw::GlfwContext::new(width, height)?;
+    init(Config::default())?;
+    let surface = glfw_ctx.create_surface(width, height)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let size = Extent3d {
+        width: 8,
+        height: 8,
+        depth_or_array_layers: 1,
+    };
+    let image = image_create(size, vec![0; 8 * 8 * 4], TextureFormat::Rgba8Unorm)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::GREEN.into()),
+    )?;
+    image_copy(graphics, 0, 0, 4, 4, image, 0, 0, 4, 4)?;
+    graphics_end_draw(graphics)?;
+
+    let pixels = image_readback(image)?;
+    let is_green = |p: &LinearRgba| p.red < 1e-2 && (p.green - 1.0).abs() < 1e-2 && p.blue < 1e-2;
+    assert!(is_green(&pixels[0]), "copied pixel is {:?}", pixels[0]);
+    assert!(is_green(&pixels[3 * 8 + 3]));
+    assert_eq!(pixels[7 * 8 + 7], LinearRgba::NONE);
+    Ok(())
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn copy_past_the_edges_is_clipped() -> error::Result<()> {
+    init(Config::default())?;
+    let size = |side| Extent3d {
+        width: side,
+        height: side,
+        depth_or_array_layers: 1,
+    };
+    let red = image_create(
+        size(2),
+        [255, 0, 0, 255].repeat(4),
+        TextureFormat::Rgba8Unorm,
+    )?;
+    let image = image_create(size(4), vec![0; 4 * 4 * 4], TextureFormat::Rgba8Unorm)?;
+
+    // a 4x4 source region on a 2x2 image, into a 4x4 region hanging off the bottom right
+    image_copy(red, 0, 0, 4, 4, image, 2, 2, 4, 4)?;
+
+    let pixels = image_readback(image)?;
+    assert_eq!(pixels[2 * 4 + 2], LinearRgba::RED);
+    assert_eq!(pixels[3 * 4 + 3], LinearRgba::RED);
+    assert_eq!(pixels[4 + 1], LinearRgba::NONE);
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use bevy::color::{LinearRgba, Srgba};
+use processing::prelude::*;
+use processing_render::color::{ColorMode, ColorSpace};
+
+#[test]
+#[ignore = "needs a GPU"]
+fn hsb_fill_renders_the_matching_rgb() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (16, 16);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+
+    // colorMode(HSB, 360, 100, 100) then fill(30, 100, 100) is orange
+    graphics_set_color_mode(
+        graphics,
+        ColorMode::new(ColorSpace::Hsv, 360.0, 100.0, 100.0, 1.0),
+    )?;
+    let mode = graphics_get_color_mode(graphics)?;
+    let orange = mode.space.color(
+        mode.scale(30.0, 0),
+        mode.scale(100.0, 1),
+        mode.scale(100.0, 2),
+        mode.scale(1.0, 3),
+    );
+
+    graphics_begin_draw(graphics)?;
+    for command in [
+        DrawCommand::BackgroundColor(LinearRgba::BLACK.into()),
+        DrawCommand::NoStroke,
+        DrawCommand::Fill(orange),
+        DrawCommand::Rect {
+            x: 0.0,
+            y: 0.0,
+            w: width as f32,
+            h: height as f32,
+            radii: [0.0; 4],
+        },
+    ] {
+        graphics_record_command(graphics, command)?;
+    }
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    let center = Srgba::from(pixels[(height / 2 * width + width / 2) as usize]);
+    assert!((center.red - 1.0).abs() < 0.02, "{center:?}");
+    assert!((center.green - 0.5).abs() < 0.02, "{center:?}");
+    assert!(center.blue < 0.02, "{center:?}");
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use bevy::color::LinearRgba;
+use bevy::render::render_resource::Extent3d;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn images_ignore_stroke_and_fill() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (32, 32);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let size = Extent3d {
+        width: 4,
+        height: 4,
+        depth_or_array_layers: 1,
+    };
+    let green: Vec<u8> = [0, 255, 0, 255].repeat(16);
+    let image = image_create(size, green, TextureFormat::Rgba8UnormSrgb)?;
+
+    graphics_begin_draw(graphics)?;
+    for command in [
+        DrawCommand::BackgroundColor(LinearRgba::BLACK.into()),
+        DrawCommand::StrokeColor(LinearRgba::RED.into()),
+        DrawCommand::StrokeWeight(4.0),
+        DrawCommand::Fill(LinearRgba::BLUE.into()),
+        DrawCommand::Image {
+            entity: image,
+            dx: 8.0,
+            dy: 8.0,
+            d_width: Some(16.0),
+            d_height: Some(16.0),
+            sx: None,
+            sy: None,
+            s_width: None,
+            s_height: None,
+        },
+    ] {
+        graphics_record_command(graphics, command)?;
+    }
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    let at = |x: u32, y: u32| pixels[(y * width + x) as usize];
+    let close =
+        |a: LinearRgba, b: LinearRgba| (a.to_vec4() - b.to_vec4()).abs().max_element() < 1e-2;
+    // a centered 4px stroke would cover 2px either side of the image's edge
+    assert!(
+        close(at(6, 16), LinearRgba::BLACK),
+        "outline outside the image"
+    );
+    assert!(
+        close(at(9, 16), LinearRgba::GREEN),
+        "outline inside the image"
+    );
+    assert!(
+        close(at(16, 16), LinearRgba::GREEN),
+        "fill tinted the image"
+    );
+    Ok(())
+}
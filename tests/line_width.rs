@@ -0,0 +1,31 @@
+use bevy::color::Color;
+use bevy::math::Vec3;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn wide_line_geometry_covers_more_than_a_pixel() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (64, 64);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba8UnormSrgb)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba8UnormSrgb)?;
+
+    let line = geometry_create(geometry::Topology::LineList)?;
+    geometry_vertex(line, Vec3::new(8.0, 32.0, 0.0))?;
+    geometry_vertex(line, Vec3::new(56.0, 32.0, 0.0))?;
+    geometry_line_width(line, 5.0)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(graphics, DrawCommand::BackgroundColor(Color::BLACK))?;
+    graphics_record_command(graphics, DrawCommand::Fill(Color::WHITE))?;
+    graphics_record_command(graphics, DrawCommand::Geometry(line))?;
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    // count lit pixels down the middle column
+    let lit = (0..height)
+        .filter(|y| pixels[(y * width + width / 2) as usize].red > 0.5)
+        .count();
+    assert!(lit >= 4, "line is {lit}px wide, expected about 5");
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use bevy::color::LinearRgba;
+use bevy::render::render_resource::Extent3d;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn failed_map_batch_leaves_pixels_alone() -> error::Result<()> {
+    init(Config::default())?;
+    let size = Extent3d {
+        width: 4,
+        height: 3,
+        depth_or_array_layers: 1,
+    };
+    let data: Vec<u8> = (0..4 * 3 * 4).map(|i| (i * 20) as u8).collect();
+    let image = image_create(size, data, TextureFormat::Rgba8UnormSrgb)?;
+    let before = image_readback(image)?;
+
+    let result = image_map_batch(image, |pixels, _| {
+        pixels.fill(LinearRgba::RED);
+        Err("callback failed")
+    })?;
+    assert_eq!(result, Err("callback failed"));
+    assert_eq!(image_readback(image)?, before);
+
+    image_map_batch(image, |pixels, _| {
+        pixels.fill(LinearRgba::RED);
+        Ok::<_, ()>(())
+    })?
+    .unwrap();
+    assert!(image_readback(image)?.iter().all(|p| *p == LinearRgba::RED));
+    Ok(())
+}
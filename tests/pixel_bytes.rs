@@ -0,0 +1,23 @@
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn pixel_bytes_round_trip() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (20, 10);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba8UnormSrgb)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba8UnormSrgb)?;
+
+    let pattern: Vec<u8> = (0..width * height * 4)
+        .map(|i| (i * 7 % 251) as u8)
+        .collect();
+    graphics_begin_draw(graphics)?;
+    graphics_update_pixels_bytes(graphics, &pattern)?;
+    let bytes = graphics_load_pixels_bytes(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    assert_eq!(bytes.len(), (width * height * 4) as usize);
+    assert_eq!(bytes, pattern);
+    assert!(graphics_update_pixels_bytes(graphics, &bytes[1..]).is_err());
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use bevy::color::LinearRgba;
+use bevy::render::render_resource::Extent3d;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn readback_into_matches_readback() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (32, 16);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::RED.into()),
+    )?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::Rect {
+            x: 4.0,
+            y: 4.0,
+            w: 8.0,
+            h: 8.0,
+            radii: [0.0; 4],
+        },
+    )?;
+    let pixels = graphics_readback(graphics)?;
+    let mut into = vec![LinearRgba::NONE; (width * height) as usize];
+    graphics_readback_into(graphics, &mut into)?;
+    graphics_end_draw(graphics)?;
+    assert_eq!(into, pixels);
+
+    let mut short = vec![LinearRgba::NONE; 10];
+    assert!(matches!(
+        graphics_readback_into(graphics, &mut short),
+        Err(error::ProcessingError::InvalidArgument(_))
+    ));
+
+    let size = Extent3d {
+        width: 4,
+        height: 3,
+        depth_or_array_layers: 1,
+    };
+    let data: Vec<u8> = (0..4 * 3 * 4).map(|i| (i * 20) as u8).collect();
+    let image = image_create(size, data, TextureFormat::Rgba8UnormSrgb)?;
+    let mut into = vec![LinearRgba::NONE; 12];
+    image_readback_into(image, &mut into)?;
+    assert_eq!(into, image_readback(image)?);
+    assert!(matches!(
+        image_readback_into(image, &mut short),
+        Err(error::ProcessingError::InvalidArgument(_))
+    ));
+    Ok(())
+}
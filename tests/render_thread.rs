@@ -0,0 +1,59 @@
+//! The render thread keeps the app in its own thread-local, so this runs as its own test binary
+//! rather than next to tests that initialize the app on the test thread.
+use bevy::color::Color;
+use bevy::ecs::entity::Entity;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn commands_from_a_worker_thread_render() -> error::Result<()> {
+    spawn_render_thread(Config::default())?;
+
+    let graphics = run_on_render_thread(|| {
+        let surface = surface_create_offscreen(4, 4, 1.0, TextureFormat::Rgba16Float)?;
+        graphics_create(surface, 4, 4, TextureFormat::Rgba16Float)
+    })?;
+    run_on_render_thread(move || graphics_begin_draw(graphics))?;
+
+    std::thread::spawn(move || {
+        graphics_record_command(
+            graphics,
+            DrawCommand::BackgroundColor(Color::srgb(1.0, 0.0, 0.0)),
+        )
+    })
+    .join()
+    .unwrap()?;
+
+    // errors come back to the recording thread, and the rest of the API has to be sent over
+    std::thread::spawn(move || {
+        let missing = graphics_record_command(Entity::PLACEHOLDER, DrawCommand::PushMatrix);
+        assert!(missing.is_err());
+        let direct = graphics_readback(graphics);
+        assert!(matches!(
+            direct,
+            Err(error::ProcessingError::RenderThread(_))
+        ));
+    })
+    .join()
+    .unwrap();
+
+    // a panicking call comes back as an error and leaves the thread serving later calls
+    let panicked = run_on_render_thread(|| -> error::Result<()> { panic!("job failed") });
+    assert!(matches!(
+        panicked,
+        Err(error::ProcessingError::RenderThread(msg)) if msg.contains("job failed")
+    ));
+
+    // the readback is queued behind the worker's command
+    let pixels = run_on_render_thread(move || {
+        graphics_end_draw(graphics)?;
+        graphics_readback(graphics)
+    })?;
+    assert_eq!(pixels.len(), 16);
+    assert!(
+        pixels
+            .iter()
+            .all(|p| (p.red - 1.0).abs() < 1e-3 && p.green < 1e-3 && p.blue < 1e-3)
+    );
+    Ok(())
+}
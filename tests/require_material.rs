@@ -0,0 +1,37 @@
+use bevy::color::LinearRgba;
+use bevy::math::Vec3;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn geometry_without_material_is_magenta() -> error::Result<()> {
+    let mut config = Config::default();
+    config.require_material(true);
+    init(config)?;
+    let (width, height) = (32, 32);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let cube = geometry_box(20.0, 20.0, 20.0)?;
+
+    graphics_mode_3d(graphics)?;
+    transform_set_position(graphics, Vec3::new(0.0, 0.0, 100.0))?;
+    transform_look_at(graphics, Vec3::ZERO)?;
+
+    let mut center_after_drawing = || -> error::Result<LinearRgba> {
+        graphics_begin_draw(graphics)?;
+        graphics_record_command(
+            graphics,
+            DrawCommand::BackgroundColor(LinearRgba::BLACK.into()),
+        )?;
+        graphics_record_command(graphics, DrawCommand::Fill(LinearRgba::WHITE.into()))?;
+        graphics_record_command(graphics, DrawCommand::Geometry(cube))?;
+        let pixels = graphics_readback(graphics)?;
+        graphics_end_draw(graphics)?;
+        Ok(pixels[(height / 2 * width + width / 2) as usize])
+    };
+
+    assert_eq!(center_after_drawing()?, LinearRgba::new(1.0, 0.0, 1.0, 1.0));
+    set_require_material(false)?;
+    assert_eq!(center_after_drawing()?, LinearRgba::WHITE);
+    Ok(())
+}
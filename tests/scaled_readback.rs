@@ -0,0 +1,30 @@
+use bevy::color::LinearRgba;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn scaled_surface_reads_back_physical_pixels() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (24, 10);
+    let surface = surface_create_offscreen(width, height, 2.0, TextureFormat::Rgba16Float)?;
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+    let (physical_width, physical_height) = (width * 2, height * 2);
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::RED.into()),
+    )?;
+    let raw = graphics_readback_raw(graphics)?;
+    let pixels = graphics_readback(graphics)?;
+    let mut into = vec![LinearRgba::NONE; (physical_width * physical_height) as usize];
+    graphics_readback_into(graphics, &mut into)?;
+    graphics_end_draw(graphics)?;
+
+    assert_eq!((raw.width, raw.height), (physical_width, physical_height));
+    assert_eq!(pixels.len(), (physical_width * physical_height) as usize);
+    // the last row is there too, not cut off at the logical size
+    assert!(pixels.iter().all(|p| *p == LinearRgba::RED));
+    assert_eq!(into, pixels);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn saved_table_loads_back_by_the_same_name() -> error::Result<()> {
+    let dir = std::env::temp_dir().join("processing_table_round_trip");
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut config = Config::new();
+    config.set(ConfigKey::AssetRootPath, dir.to_string_lossy().into_owned());
+    init(config)?;
+
+    let mut table = table::Table::new(vec!["name".into(), "value".into()]);
+    table.add_row(vec!["a".into(), "1.5".into()]);
+    table.add_row(vec!["b, c".into(), "-2".into()]);
+
+    for name in ["scores.csv", "scores.tsv"] {
+        table_save(&table, name)?;
+        // relative names land in the asset directory, where table_load reads them from
+        assert!(dir.join(name).exists());
+        let loaded = table_load(name)?;
+        assert_eq!(loaded.columns(), table.columns());
+        assert_eq!(loaded.row_count(), 2);
+        assert_eq!(loaded.get(1, 0), Some("b, c"));
+        assert_eq!(loaded.get_float(0, 1), Some(1.5));
+    }
+    Ok(())
+}
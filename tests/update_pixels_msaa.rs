@@ -0,0 +1,33 @@
+use bevy::color::LinearRgba;
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn written_pixels_survive_the_next_frame() -> error::Result<()> {
+    init(Config::default())?;
+    let (width, height) = (32, 32);
+    let surface = surface_create_offscreen(width, height, 1.0, TextureFormat::Rgba16Float)?;
+    // graphics render with msaa by default
+    let graphics = graphics_create(surface, width, height, TextureFormat::Rgba16Float)?;
+
+    graphics_begin_draw(graphics)?;
+    graphics_record_command(
+        graphics,
+        DrawCommand::BackgroundColor(LinearRgba::BLACK.into()),
+    )?;
+    graphics_end_draw(graphics)?;
+
+    graphics_update_region(graphics, 4, 4, 8, 8, &[LinearRgba::RED; 64])?;
+
+    // a frame that draws nothing must keep the written pixels
+    graphics_begin_draw(graphics)?;
+    graphics_end_draw(graphics)?;
+    graphics_begin_draw(graphics)?;
+    let pixels = graphics_readback(graphics)?;
+    graphics_end_draw(graphics)?;
+
+    let at = |x: u32, y: u32| pixels[(y * width + x) as usize];
+    assert_eq!(at(6, 6), LinearRgba::RED);
+    assert_eq!(at(20, 20), LinearRgba::BLACK);
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::{Window, default};
+use bevy::window::WindowResolution;
+use processing::prelude::*;
+use processing_render::graphics::{self, SurfaceSize};
+use processing_render::surface::Surface;
+
+#[test]
+#[ignore = "needs a GPU"]
+fn window_resize_resizes_graphics() -> error::Result<()> {
+    init(Config::default())?;
+    let (window, graphics) = processing_core::app_mut(|app| {
+        let world = app.world_mut();
+        let window = world
+            .spawn((
+                Window {
+                    resolution: WindowResolution::new(32, 32),
+                    ..default()
+                },
+                Surface,
+            ))
+            .id();
+        let graphics = world.run_system_cached_with(
+            graphics::create,
+            (32, 32, window, TextureFormat::Rgba16Float),
+        )??;
+        Ok((window, graphics))
+    })?;
+
+    input_window_resize(window, 64.0, 48.0)?;
+    input_flush()?;
+
+    let size = processing_core::app_mut(|app| {
+        let size = app.world().get::<SurfaceSize>(graphics).unwrap();
+        Ok((size.0, size.1))
+    })?;
+    assert_eq!(size, (64, 48));
+    Ok(())
+}
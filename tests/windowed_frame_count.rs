@@ -0,0 +1,36 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use processing::prelude::*;
+
+#[test]
+#[ignore = "needs a GPU and a display"]
+fn windowed_draws_advance_the_frame_count() {
+    let frames = Rc::new(RefCell::new(Vec::new()));
+    let seen = frames.clone();
+    let setup_frame = Rc::new(RefCell::new(None));
+    let setup_seen = setup_frame.clone();
+
+    // run_windowed only returns once the sketch stops, so the third draw stops it with an error
+    let result = run_windowed(
+        Config::default(),
+        move |_| {
+            *setup_seen.borrow_mut() = Some(frame_count()?);
+            Ok(())
+        },
+        move |_| {
+            seen.borrow_mut().push(frame_count()?);
+            if seen.borrow().len() == 3 {
+                return Err(error::ProcessingError::InvalidArgument("done".to_string()));
+            }
+            Ok(())
+        },
+    );
+
+    assert!(matches!(
+        result,
+        Err(error::ProcessingError::InvalidArgument(_))
+    ));
+    assert_eq!(*setup_frame.borrow(), Some(0));
+    assert_eq!(*frames.borrow(), vec![1, 2, 3]);
+}